@@ -1,12 +1,123 @@
 #[macro_use]
 extern crate criterion;
 
+use boa::compile;
+use boa::exec::{Executor, Interpreter};
 use boa::realm::Realm;
+use criterion::black_box;
 use criterion::Criterion;
 
+static VARIABLE_LOOKUP_SRC: &str = r#"
+let total = 0;
+let step = 1;
+
+function inner(count) {
+  let sum = 0;
+  let i = 0;
+  while (i < count) {
+    sum = sum + step + total;
+    i++;
+  }
+  return sum;
+}
+
+function variableLookup() {
+  let round = 0;
+  while (round < 20) {
+    total = inner(50);
+    round++;
+  }
+  return total;
+}
+"#;
+
+static CLOSURE_LOOP_SRC: &str = r#"
+function closureLoop() {
+  let limit = 200;
+  let step = 2;
+  function loop() {
+    let total = 0;
+    let i = 0;
+    while (i < limit) {
+      total = total + step;
+      i++;
+    }
+    return total;
+  }
+  return loop();
+}
+"#;
+
+static CLOSURE_CREATION_SRC: &str = r#"
+function adder(n) {
+  return (x) => {
+    let total = x + n;
+    return total * 2;
+  };
+}
+
+function closureCreation() {
+  let i = 0;
+  while (i < 100) {
+    adder(i);
+    i++;
+  }
+}
+"#;
+
 fn create_realm(c: &mut Criterion) {
-    c.bench_function("Create Realm", move |b| b.iter(|| Realm::create()));
+    c.bench_function("Create Realm", move |b| b.iter(Realm::create));
+}
+
+/// Run `declarations` once, then measure running the compiled `call`, so declaring the functions
+/// is left out of the measurement
+fn bench_compiled(c: &mut Criterion, name: &str, declarations: &str, call: &str) {
+    let mut engine: Interpreter = Executor::new(Realm::create());
+    let declarations = compile(declarations).expect("failed to compile");
+    engine
+        .run_compiled(&declarations)
+        .expect("failed to declare");
+    let script = compile(call).expect("failed to compile");
+    c.bench_function(name, move |b| {
+        b.iter(|| engine.run_compiled(black_box(&script)))
+    });
+}
+
+fn variable_lookup(c: &mut Criterion) {
+    // Most of the time goes on resolving `sum`, `step`, `total` and `i` through the scopes
+    bench_compiled(
+        c,
+        "Variable lookup (Compiled)",
+        VARIABLE_LOOKUP_SRC,
+        "variableLookup();",
+    );
+}
+
+fn closure_loop(c: &mut Criterion) {
+    // `limit` and `step` are read from the enclosing function on every iteration
+    bench_compiled(
+        c,
+        "Closure loop (Compiled)",
+        CLOSURE_LOOP_SRC,
+        "closureLoop();",
+    );
+}
+
+fn closure_creation(c: &mut Criterion) {
+    // Making a closure shares the body of its declaration rather than copying it
+    bench_compiled(
+        c,
+        "Closure creation (Compiled)",
+        CLOSURE_CREATION_SRC,
+        "closureCreation();",
+    );
 }
 
-criterion_group!(benches, create_realm);
+criterion_group!(
+    benches,
+    create_realm,
+    variable_lookup,
+    closure_loop,
+    closure_creation
+);
 criterion_main!(benches);
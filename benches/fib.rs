@@ -1,7 +1,9 @@
 #[macro_use]
 extern crate criterion;
 
-use boa::exec;
+use boa::exec::{Executor, Interpreter};
+use boa::realm::Realm;
+use boa::{compile, exec};
 use criterion::black_box;
 use criterion::Criterion;
 
@@ -24,5 +26,26 @@ fn fibonacci(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, fibonacci);
+static FIB_SRC: &str = r#"
+function fib(n) {
+  if (n <= 1) return 1;
+  return fib(n - 1) + fib(n - 2);
+}
+"#;
+
+fn fibonacci_compiled(c: &mut Criterion) {
+    // Only the execution is measured, the script is lexed and parsed once. `fib` is declared
+    // beforehand, so only the call is measured
+    let mut engine: Interpreter = Executor::new(Realm::create());
+    let declaration = compile(FIB_SRC).expect("failed to compile");
+    engine
+        .run_compiled(&declaration)
+        .expect("failed to declare");
+    let script = compile("fib(12);").expect("failed to compile");
+    c.bench_function("fibonacci (Compiled)", move |b| {
+        b.iter(|| engine.run_compiled(black_box(&script)))
+    });
+}
+
+criterion_group!(benches, fibonacci, fibonacci_compiled);
 criterion_main!(benches);
@@ -22,7 +22,10 @@
 )]
 
 use boa::realm::Realm;
-use boa::{exec::Executor, forward_val};
+use boa::{
+    exec::{Executor, Interpreter},
+    forward_val,
+};
 use std::{fs::read_to_string, path::PathBuf};
 use structopt::StructOpt;
 
@@ -37,11 +40,27 @@ pub fn main() -> Result<(), std::io::Error> {
 
     let buffer = read_to_string(args.file)?;
     let realm = Realm::create();
-    let mut engine = Executor::new(realm);
+    let mut engine: Interpreter = Executor::new(realm);
 
     match forward_val(&mut engine, &buffer) {
-        Ok(v) => print!("{}", v.to_string()),
-        Err(v) => eprint!("{}", v.to_string()),
+        Ok(v) => print!("{}", v),
+        Err(v) => {
+            // Error objects are shown by their name and message
+            let message = if v.is_error() {
+                engine
+                    .value_to_rust_string(&v)
+                    .unwrap_or_else(|thrown| thrown.to_string())
+            } else {
+                v.to_string()
+            };
+            match engine.error_position() {
+                Some(pos) => eprint!(
+                    "{} (line {}, column {})",
+                    message, pos.line_number, pos.column_number
+                ),
+                None => eprint!("{}", message),
+            }
+        }
     }
 
     Ok(())
@@ -11,6 +11,7 @@ use crate::{
         lexical_environment::{Environment, EnvironmentType},
     },
     js::value::{Value, ValueData},
+    syntax::ast::sym::Sym,
 };
 use gc::Gc;
 use gc_derive::{Finalize, Trace};
@@ -29,21 +30,81 @@ pub struct DeclarativeEnvironmentRecordBinding {
     pub strict: bool,
 }
 
+/// The bindings of a declarative record, kept in numbered slots so that a binding which has been
+/// found once can be read again without looking its name up.
+///
+/// Removing a binding moves the last binding into its slot.
+#[derive(Debug, Trace, Finalize, Clone, Default)]
+pub struct DeclarativeBindings {
+    slots: HashMap<Sym, usize>,
+    names: Vec<Sym>,
+    bindings: Vec<DeclarativeEnvironmentRecordBinding>,
+}
+
+impl DeclarativeBindings {
+    pub fn contains_key(&self, name: &Sym) -> bool {
+        self.slots.contains_key(name)
+    }
+
+    pub fn get(&self, name: &Sym) -> Option<&DeclarativeEnvironmentRecordBinding> {
+        let slot = *self.slots.get(name)?;
+        self.bindings.get(slot)
+    }
+
+    pub fn get_mut(&mut self, name: &Sym) -> Option<&mut DeclarativeEnvironmentRecordBinding> {
+        let slot = *self.slots.get(name)?;
+        self.bindings.get_mut(slot)
+    }
+
+    pub fn insert(&mut self, name: &Sym, binding: DeclarativeEnvironmentRecordBinding) {
+        match self.slots.get(name) {
+            Some(&slot) => self.bindings[slot] = binding,
+            None => {
+                self.slots.insert(name.clone(), self.bindings.len());
+                self.names.push(name.clone());
+                self.bindings.push(binding);
+            }
+        }
+    }
+
+    pub fn remove(&mut self, name: &Sym) -> Option<DeclarativeEnvironmentRecordBinding> {
+        let slot = self.slots.remove(name)?;
+        self.names.swap_remove(slot);
+        if let Some(moved) = self.names.get(slot) {
+            self.slots.insert(moved.clone(), slot);
+        }
+        Some(self.bindings.swap_remove(slot))
+    }
+
+    /// The slot which holds the binding for `name`
+    pub fn slot(&self, name: &Sym) -> Option<usize> {
+        self.slots.get(name).cloned()
+    }
+
+    /// The value in `slot`, if it holds the binding for `name` and the binding has been initialized
+    pub fn value_at(&self, slot: usize, name: &Sym) -> Option<Value> {
+        if self.names.get(slot) != Some(name) {
+            return None;
+        }
+        self.bindings[slot].value.clone()
+    }
+}
+
 /// A declarative Environment Record binds the set of identifiers defined by the
 /// declarations contained within its scope.
 #[derive(Debug, Trace, Finalize, Clone)]
 pub struct DeclarativeEnvironmentRecord {
-    pub env_rec: HashMap<String, DeclarativeEnvironmentRecordBinding>,
+    pub env_rec: DeclarativeBindings,
     pub outer_env: Option<Environment>,
 }
 
 impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
-    fn has_binding(&self, name: &str) -> bool {
+    fn has_binding(&self, name: &Sym) -> bool {
         self.env_rec.contains_key(name)
     }
 
-    fn create_mutable_binding(&mut self, name: String, deletion: bool) {
-        if self.env_rec.contains_key(&name) {
+    fn create_mutable_binding(&mut self, name: &Sym, deletion: bool) {
+        if self.env_rec.contains_key(name) {
             // TODO: change this when error handling comes into play
             panic!("Identifier {} has already been declared", name);
         }
@@ -59,8 +120,8 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
         );
     }
 
-    fn create_immutable_binding(&mut self, name: String, strict: bool) -> bool {
-        if self.env_rec.contains_key(&name) {
+    fn create_immutable_binding(&mut self, name: &Sym, strict: bool) -> bool {
+        if self.env_rec.contains_key(name) {
             // TODO: change this when error handling comes into play
             panic!("Identifier {} has already been declared", name);
         }
@@ -78,7 +139,7 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
         true
     }
 
-    fn initialize_binding(&mut self, name: &str, value: Value) {
+    fn initialize_binding(&mut self, name: &Sym, value: Value) {
         if let Some(ref mut record) = self.env_rec.get_mut(name) {
             match record.value {
                 Some(_) => {
@@ -90,14 +151,14 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
         }
     }
 
-    fn set_mutable_binding(&mut self, name: &str, value: Value, mut strict: bool) {
+    fn set_mutable_binding(&mut self, name: &Sym, value: Value, mut strict: bool) {
         if self.env_rec.get(name).is_none() {
             if strict {
                 // TODO: change this when error handling comes into play
                 panic!("Reference Error: Cannot set mutable binding for {}", name);
             }
 
-            self.create_mutable_binding(name.to_owned(), true);
+            self.create_mutable_binding(name, true);
             self.initialize_binding(name, value);
             return;
         }
@@ -119,7 +180,7 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
         }
     }
 
-    fn get_binding_value(&self, name: &str, _strict: bool) -> Value {
+    fn get_binding_value(&self, name: &Sym, _strict: bool) -> Value {
         if self.env_rec.get(name).is_some() && self.env_rec.get(name).unwrap().value.is_some() {
             let record: &DeclarativeEnvironmentRecordBinding = self.env_rec.get(name).unwrap();
             record.value.as_ref().unwrap().clone()
@@ -129,7 +190,7 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
         }
     }
 
-    fn delete_binding(&mut self, name: &str) -> bool {
+    fn delete_binding(&mut self, name: &Sym) -> bool {
         if self.env_rec.get(name).is_some() {
             if self.env_rec.get(name).unwrap().can_delete {
                 self.env_rec.remove(name);
@@ -142,6 +203,20 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
         }
     }
 
+    fn is_immutable_binding(&self, name: &Sym) -> bool {
+        self.env_rec
+            .get(name)
+            .is_some_and(|binding| !binding.mutable)
+    }
+
+    fn binding_slot(&self, name: &Sym) -> Option<usize> {
+        self.env_rec.slot(name)
+    }
+
+    fn binding_value_at(&self, slot: usize, name: &Sym) -> Option<Value> {
+        self.env_rec.value_at(slot, name)
+    }
+
     fn has_this_binding(&self) -> bool {
         false
     }
@@ -155,7 +230,7 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
     }
 
     fn get_outer_environment(&self) -> Option<Environment> {
-        None
+        self.outer_env.clone()
     }
 
     fn set_outer_environment(&mut self, env: Environment) {
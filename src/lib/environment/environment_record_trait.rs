@@ -10,9 +10,10 @@
 //!
 use crate::{
     environment::lexical_environment::{Environment, EnvironmentType},
-    js::value::Value,
+    js::value::{Value, ValueData},
+    syntax::ast::sym::Sym,
 };
-use gc::{Finalize, Trace};
+use gc::{Finalize, Gc, Trace};
 use std::fmt::Debug;
 
 /// <https://tc39.github.io/ecma262/#sec-environment-records>
@@ -21,40 +22,77 @@ use std::fmt::Debug;
 /// In this implementation we have a trait which represents the behaviour of all `EnvironmentRecord` types.
 pub trait EnvironmentRecordTrait: Debug + Trace + Finalize {
     /// Determine if an Environment Record has a binding for the String value N. Return true if it does and false if it does not.
-    fn has_binding(&self, name: &str) -> bool;
+    fn has_binding(&self, name: &Sym) -> bool;
 
     /// Create a new but uninitialized mutable binding in an Environment Record. The String value N is the text of the bound name.
     /// If the Boolean argument deletion is true the binding may be subsequently deleted.
-    fn create_mutable_binding(&mut self, name: String, deletion: bool);
+    fn create_mutable_binding(&mut self, name: &Sym, deletion: bool);
 
     /// Create a new but uninitialized immutable binding in an Environment Record.
     /// The String value N is the text of the bound name.
     /// If strict is true then attempts to set it after it has been initialized will always throw an exception,
     /// regardless of the strict mode setting of operations that reference that binding.
-    fn create_immutable_binding(&mut self, name: String, strict: bool) -> bool;
+    fn create_immutable_binding(&mut self, name: &Sym, strict: bool) -> bool;
 
     /// Set the value of an already existing but uninitialized binding in an Environment Record.
     /// The String value N is the text of the bound name.
     /// V is the value for the binding and is a value of any ECMAScript language type.
-    fn initialize_binding(&mut self, name: &str, value: Value);
+    fn initialize_binding(&mut self, name: &Sym, value: Value);
+
+    /// Bind `name` for a `var` declaration, setting it to `value` if there is one.
+    /// Records other than the global one create and initialize a binding of their own.
+    fn create_var_binding(&mut self, name: &Sym, value: Option<Value>) {
+        self.create_mutable_binding(name, false);
+        self.initialize_binding(name, value.unwrap_or_else(|| Gc::new(ValueData::Undefined)));
+    }
 
     /// Set the value of an already existing mutable binding in an Environment Record.
     /// The String value `name` is the text of the bound name.
     /// value is the `value` for the binding and may be a value of any ECMAScript language type. S is a Boolean flag.
     /// If `strict` is true and the binding cannot be set throw a TypeError exception.
-    fn set_mutable_binding(&mut self, name: &str, value: Value, strict: bool);
+    fn set_mutable_binding(&mut self, name: &Sym, value: Value, strict: bool);
 
     /// Returns the value of an already existing binding from an Environment Record.
     /// The String value N is the text of the bound name.
     /// S is used to identify references originating in strict mode code or that
     /// otherwise require strict mode reference semantics.
-    fn get_binding_value(&self, name: &str, strict: bool) -> Value;
+    fn get_binding_value(&self, name: &Sym, strict: bool) -> Value;
 
     /// Delete a binding from an Environment Record.
     /// The String value name is the text of the bound name.
     /// If a binding for name exists, remove the binding and return true.
     /// If the binding exists but cannot be removed return false. If the binding does not exist return true.
-    fn delete_binding(&mut self, name: &str) -> bool;
+    fn delete_binding(&mut self, name: &Sym) -> bool;
+
+    /// The slot which holds the binding for `name`, for records which number their bindings.
+    /// Other records return `None`.
+    fn binding_slot(&self, _name: &Sym) -> Option<usize> {
+        None
+    }
+
+    /// Returns true if the record holds an immutable binding for `name`, such as one made by
+    /// `const`. Records which only hold mutable bindings return `false`.
+    fn is_immutable_binding(&self, _name: &Sym) -> bool {
+        false
+    }
+
+    /// Returns true if the record binds `name` to a property of its binding object which is not
+    /// writable, such as the global `NaN`. Records without a binding object return `false`.
+    fn is_read_only_binding(&self, _name: &Sym) -> bool {
+        false
+    }
+
+    /// Returns the value in `slot` if it still holds the initialized binding for `name`.
+    /// Records which do not number their bindings return `None`.
+    fn binding_value_at(&self, _slot: usize, _name: &Sym) -> Option<Value> {
+        None
+    }
+
+    /// The new target of the function call the record was created for, which is `undefined`
+    /// unless the function was called with `new`. Records for other code return `None`.
+    fn get_new_target(&self) -> Option<Value> {
+        None
+    }
 
     /// Determine if an Environment Record establishes a this binding.
     /// Return true if it does and false if it does not.
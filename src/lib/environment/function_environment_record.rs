@@ -10,15 +10,17 @@
 
 use crate::{
     environment::{
-        declarative_environment_record::DeclarativeEnvironmentRecordBinding,
+        declarative_environment_record::{
+            DeclarativeBindings, DeclarativeEnvironmentRecordBinding,
+        },
         environment_record_trait::EnvironmentRecordTrait,
         lexical_environment::{Environment, EnvironmentType},
     },
     js::value::{Value, ValueData},
+    syntax::ast::sym::Sym,
 };
 use gc::Gc;
 use gc_derive::{Finalize, Trace};
-use std::collections::hash_map::HashMap;
 
 /// Different binding status for `this`.
 /// Usually set on a function environment record
@@ -35,7 +37,7 @@ pub enum BindingStatus {
 /// <https://tc39.github.io/ecma262/#table-16>
 #[derive(Debug, Trace, Finalize, Clone)]
 pub struct FunctionEnvironmentRecord {
-    pub env_rec: HashMap<String, DeclarativeEnvironmentRecordBinding>,
+    pub env_rec: DeclarativeBindings,
     /// This is the this value used for this invocation of the function.
     pub this_value: Value,
     /// If the value is "lexical", this is an ArrowFunction and does not have a local this value.
@@ -95,12 +97,12 @@ impl FunctionEnvironmentRecord {
 impl EnvironmentRecordTrait for FunctionEnvironmentRecord {
     // TODO: get_super_base can't implement until GetPrototypeof is implemented on object
 
-    fn has_binding(&self, name: &str) -> bool {
+    fn has_binding(&self, name: &Sym) -> bool {
         self.env_rec.contains_key(name)
     }
 
-    fn create_mutable_binding(&mut self, name: String, deletion: bool) {
-        if self.env_rec.contains_key(&name) {
+    fn create_mutable_binding(&mut self, name: &Sym, deletion: bool) {
+        if self.env_rec.contains_key(name) {
             // TODO: change this when error handling comes into play
             panic!("Identifier {} has already been declared", name);
         }
@@ -116,8 +118,8 @@ impl EnvironmentRecordTrait for FunctionEnvironmentRecord {
         );
     }
 
-    fn create_immutable_binding(&mut self, name: String, strict: bool) -> bool {
-        if self.env_rec.contains_key(&name) {
+    fn create_immutable_binding(&mut self, name: &Sym, strict: bool) -> bool {
+        if self.env_rec.contains_key(name) {
             // TODO: change this when error handling comes into play
             panic!("Identifier {} has already been declared", name);
         }
@@ -135,7 +137,7 @@ impl EnvironmentRecordTrait for FunctionEnvironmentRecord {
         true
     }
 
-    fn initialize_binding(&mut self, name: &str, value: Value) {
+    fn initialize_binding(&mut self, name: &Sym, value: Value) {
         if let Some(ref mut record) = self.env_rec.get_mut(name) {
             match record.value {
                 Some(_) => {
@@ -147,14 +149,14 @@ impl EnvironmentRecordTrait for FunctionEnvironmentRecord {
         }
     }
 
-    fn set_mutable_binding(&mut self, name: &str, value: Value, mut strict: bool) {
+    fn set_mutable_binding(&mut self, name: &Sym, value: Value, mut strict: bool) {
         if self.env_rec.get(name).is_none() {
             if strict {
                 // TODO: change this when error handling comes into play
                 panic!("Reference Error: Cannot set mutable binding for {}", name);
             }
 
-            self.create_mutable_binding(name.to_owned(), true);
+            self.create_mutable_binding(name, true);
             self.initialize_binding(name, value);
             return;
         }
@@ -177,7 +179,7 @@ impl EnvironmentRecordTrait for FunctionEnvironmentRecord {
         }
     }
 
-    fn get_binding_value(&self, name: &str, _strict: bool) -> Value {
+    fn get_binding_value(&self, name: &Sym, _strict: bool) -> Value {
         if self.env_rec.get(name).is_some() && self.env_rec.get(name).unwrap().value.is_some() {
             let record: &DeclarativeEnvironmentRecordBinding = self.env_rec.get(name).unwrap();
             record.value.as_ref().unwrap().clone()
@@ -187,7 +189,7 @@ impl EnvironmentRecordTrait for FunctionEnvironmentRecord {
         }
     }
 
-    fn delete_binding(&mut self, name: &str) -> bool {
+    fn delete_binding(&mut self, name: &Sym) -> bool {
         if self.env_rec.get(name).is_some() {
             if self.env_rec.get(name).unwrap().can_delete {
                 self.env_rec.remove(name);
@@ -208,6 +210,24 @@ impl EnvironmentRecordTrait for FunctionEnvironmentRecord {
         }
     }
 
+    fn is_immutable_binding(&self, name: &Sym) -> bool {
+        self.env_rec
+            .get(name)
+            .is_some_and(|binding| !binding.mutable)
+    }
+
+    fn binding_slot(&self, name: &Sym) -> Option<usize> {
+        self.env_rec.slot(name)
+    }
+
+    fn binding_value_at(&self, slot: usize, name: &Sym) -> Option<Value> {
+        self.env_rec.value_at(slot, name)
+    }
+
+    fn get_new_target(&self) -> Option<Value> {
+        Some(self.new_target.clone())
+    }
+
     fn has_this_binding(&self) -> bool {
         match self.this_binding_status {
             BindingStatus::Lexical => false,
@@ -220,10 +240,7 @@ impl EnvironmentRecordTrait for FunctionEnvironmentRecord {
     }
 
     fn get_outer_environment(&self) -> Option<Environment> {
-        match &self.outer_env {
-            Some(outer) => Some(outer.clone()),
-            None => None,
-        }
+        self.outer_env.clone()
     }
 
     fn set_outer_environment(&mut self, env: Environment) {
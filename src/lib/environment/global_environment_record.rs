@@ -15,6 +15,7 @@ use crate::{
         object_environment_record::ObjectEnvironmentRecord,
     },
     js::value::{Value, ValueData},
+    syntax::ast::sym::Sym,
 };
 use gc::Gc;
 use gc_derive::{Finalize, Trace};
@@ -25,7 +26,7 @@ pub struct GlobalEnvironmentRecord {
     pub object_record: Box<ObjectEnvironmentRecord>,
     pub global_this_binding: Value,
     pub declarative_record: Box<DeclarativeEnvironmentRecord>,
-    pub var_names: HashSet<String>,
+    pub var_names: HashSet<Sym>,
 }
 
 impl GlobalEnvironmentRecord {
@@ -33,17 +34,17 @@ impl GlobalEnvironmentRecord {
         self.global_this_binding.clone()
     }
 
-    pub fn has_var_declaration(&self, name: &str) -> bool {
+    pub fn has_var_declaration(&self, name: &Sym) -> bool {
         self.var_names.contains(name)
     }
 
-    pub fn has_lexical_declaration(&self, name: &str) -> bool {
+    pub fn has_lexical_declaration(&self, name: &Sym) -> bool {
         self.declarative_record.has_binding(name)
     }
 
-    pub fn has_restricted_global_property(&self, name: &str) -> bool {
+    pub fn has_restricted_global_property(&self, name: &Sym) -> bool {
         let global_object = &self.object_record.bindings;
-        let existing_prop = global_object.get_prop(name);
+        let existing_prop = global_object.get_prop(name.as_str());
         match existing_prop {
             Some(prop) => {
                 if prop.value.is_none() || prop.configurable.unwrap_or(false) {
@@ -55,25 +56,26 @@ impl GlobalEnvironmentRecord {
         }
     }
 
-    pub fn create_global_var_binding(&mut self, name: String, deletion: bool) {
+    pub fn create_global_var_binding(&mut self, name: &Sym, deletion: bool) {
         let obj_rec = &mut self.object_record;
         let global_object = &obj_rec.bindings;
-        let has_property = global_object.has_field(&name);
+        let has_property = global_object.has_field(name.as_str());
         let extensible = global_object.is_extensible();
         if !has_property && extensible {
-            obj_rec.create_mutable_binding(name.clone(), deletion);
-            obj_rec.initialize_binding(&name, Gc::new(ValueData::Undefined));
+            obj_rec.create_mutable_binding(name, deletion);
+            obj_rec.initialize_binding(name, Gc::new(ValueData::Undefined));
         }
 
         let var_declared_names = &mut self.var_names;
-        if !var_declared_names.contains(&name) {
-            var_declared_names.insert(name);
+        if !var_declared_names.contains(name) {
+            var_declared_names.insert(name.clone());
         }
     }
 
-    pub fn create_global_function_binding(&mut self, name: &str, value: Value, deletion: bool) {
+    pub fn create_global_function_binding(&mut self, name: &Sym, value: Value, deletion: bool) {
+        let name = name.as_str();
         let global_object = &mut self.object_record.bindings;
-        let existing_prop = global_object.get_prop(&name);
+        let existing_prop = global_object.get_prop(name);
         if let Some(prop) = existing_prop {
             if prop.value.is_none() || prop.configurable.unwrap_or(false) {
                 global_object.update_prop(
@@ -91,35 +93,35 @@ impl GlobalEnvironmentRecord {
 }
 
 impl EnvironmentRecordTrait for GlobalEnvironmentRecord {
-    fn has_binding(&self, name: &str) -> bool {
+    fn has_binding(&self, name: &Sym) -> bool {
         if self.declarative_record.has_binding(name) {
             return true;
         }
         self.object_record.has_binding(name)
     }
 
-    fn create_mutable_binding(&mut self, name: String, deletion: bool) {
-        if self.declarative_record.has_binding(&name) {
+    fn create_mutable_binding(&mut self, name: &Sym, deletion: bool) {
+        if self.declarative_record.has_binding(name) {
             // TODO: change to exception
             panic!("Binding already exists!");
         }
 
         self.declarative_record
-            .create_mutable_binding(name.clone(), deletion)
+            .create_mutable_binding(name, deletion)
     }
 
-    fn create_immutable_binding(&mut self, name: String, strict: bool) -> bool {
-        if self.declarative_record.has_binding(&name) {
+    fn create_immutable_binding(&mut self, name: &Sym, strict: bool) -> bool {
+        if self.declarative_record.has_binding(name) {
             // TODO: change to exception
             panic!("Binding already exists!");
         }
 
         self.declarative_record
-            .create_immutable_binding(name.clone(), strict)
+            .create_immutable_binding(name, strict)
     }
 
-    fn initialize_binding(&mut self, name: &str, value: Value) {
-        if self.declarative_record.has_binding(&name) {
+    fn initialize_binding(&mut self, name: &Sym, value: Value) {
+        if self.declarative_record.has_binding(name) {
             // TODO: assert binding is in the object environment record
             return self.declarative_record.initialize_binding(name, value);
         }
@@ -127,8 +129,25 @@ impl EnvironmentRecordTrait for GlobalEnvironmentRecord {
         panic!("Should not initialized binding without creating first.");
     }
 
-    fn set_mutable_binding(&mut self, name: &str, value: Value, strict: bool) {
-        if self.declarative_record.has_binding(&name) {
+    /// Global `var` declarations are properties of the global object, unless a lexical
+    /// declaration of the same name already shadows them
+    /// <https://tc39.es/ecma262/#sec-createglobalvarbinding>
+    fn create_var_binding(&mut self, name: &Sym, value: Option<Value>) {
+        if self.declarative_record.has_binding(name) {
+            if let Some(value) = value {
+                self.declarative_record
+                    .set_mutable_binding(name, value, false);
+            }
+            return;
+        }
+        self.create_global_var_binding(name, false);
+        if let Some(value) = value {
+            self.object_record.set_mutable_binding(name, value, false);
+        }
+    }
+
+    fn set_mutable_binding(&mut self, name: &Sym, value: Value, strict: bool) {
+        if self.declarative_record.has_binding(name) {
             return self
                 .declarative_record
                 .set_mutable_binding(name, value, strict);
@@ -136,20 +155,36 @@ impl EnvironmentRecordTrait for GlobalEnvironmentRecord {
         self.object_record.set_mutable_binding(name, value, strict)
     }
 
-    fn get_binding_value(&self, name: &str, strict: bool) -> Value {
-        if self.declarative_record.has_binding(&name) {
+    fn get_binding_value(&self, name: &Sym, strict: bool) -> Value {
+        if self.declarative_record.has_binding(name) {
             return self.declarative_record.get_binding_value(name, strict);
         }
         self.object_record.get_binding_value(name, strict)
     }
 
-    fn delete_binding(&mut self, name: &str) -> bool {
-        if self.declarative_record.has_binding(&name) {
+    fn is_immutable_binding(&self, name: &Sym) -> bool {
+        self.declarative_record.is_immutable_binding(name)
+    }
+
+    fn is_read_only_binding(&self, name: &Sym) -> bool {
+        !self.declarative_record.has_binding(name) && self.object_record.is_read_only_binding(name)
+    }
+
+    fn binding_slot(&self, name: &Sym) -> Option<usize> {
+        self.declarative_record.binding_slot(name)
+    }
+
+    fn binding_value_at(&self, slot: usize, name: &Sym) -> Option<Value> {
+        self.declarative_record.binding_value_at(slot, name)
+    }
+
+    fn delete_binding(&mut self, name: &Sym) -> bool {
+        if self.declarative_record.has_binding(name) {
             return self.declarative_record.delete_binding(name);
         }
 
         let global: &Value = &self.object_record.bindings;
-        if global.has_field(name) {
+        if global.has_field(name.as_str()) {
             let status = self.object_record.delete_binding(name);
             if status {
                 let var_names = &mut self.var_names;
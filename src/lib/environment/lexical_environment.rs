@@ -6,18 +6,22 @@
 //! This is the entrypoint to lexical environments.
 //!
 
-use crate::environment::declarative_environment_record::DeclarativeEnvironmentRecord;
+use crate::environment::declarative_environment_record::{
+    DeclarativeBindings, DeclarativeEnvironmentRecord,
+};
 use crate::environment::environment_record_trait::EnvironmentRecordTrait;
 use crate::environment::function_environment_record::{BindingStatus, FunctionEnvironmentRecord};
 use crate::environment::global_environment_record::GlobalEnvironmentRecord;
 use crate::environment::object_environment_record::ObjectEnvironmentRecord;
 use crate::js::value::{Value, ValueData};
-use gc::{Gc, GcCell};
-use std::collections::hash_map::HashMap;
+use crate::syntax::ast::sym::Sym;
+use gc::{unsafe_empty_trace, Finalize, Gc, GcCell, Trace};
+use std::cell::Cell;
 use std::collections::{HashSet, VecDeque};
 use std::debug_assert;
 use std::error;
 use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 /// Environments are wrapped in a Box and then in a GC wrapper
 pub type Environment = Gc<GcCell<Box<dyn EnvironmentRecordTrait>>>;
@@ -35,6 +39,44 @@ pub enum EnvironmentType {
 #[derive(Debug)]
 pub struct LexicalEnvironment {
     environment_stack: VecDeque<Environment>,
+    /// Changes whenever an environment is entered or left or a binding is created, which is when
+    /// the bindings found through `BindingCache`s may stop being the ones a lookup would find
+    generation: u64,
+}
+
+/// Generations are unique across every `LexicalEnvironment`, so that a script shared between
+/// interpreters never finds a binding which was cached by another one
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+fn next_generation() -> u64 {
+    NEXT_GENERATION.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Where a binding was found, as the number of outer environments to go through and the slot of
+/// the binding in the environment reached
+#[derive(Debug, Clone, Copy)]
+struct ResolvedBinding {
+    generation: u64,
+    depth: usize,
+    slot: usize,
+}
+
+/// The binding an identifier expression found the last time it was run, so that running it again
+/// in a loop can skip searching the scope chain
+#[derive(Debug, Default)]
+pub struct BindingCache(Cell<Option<ResolvedBinding>>);
+
+/// A copied expression starts without a cached binding
+impl Clone for BindingCache {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl Finalize for BindingCache {}
+
+unsafe impl Trace for BindingCache {
+    unsafe_empty_trace!();
 }
 
 /// An error that occurred during lexing or compiling of the source input.
@@ -73,6 +115,7 @@ impl LexicalEnvironment {
         let global_env = new_global_environment(global.clone(), global);
         let mut lexical_env = Self {
             environment_stack: VecDeque::new(),
+            generation: next_generation(),
         };
 
         // lexical_env.push(global_env);
@@ -80,14 +123,20 @@ impl LexicalEnvironment {
         lexical_env
     }
 
+    /// Enter `env`, which is nested in the current environment unless it already has an outer
+    /// one, as the environment of a function call has the one the function was made in
     pub fn push(&mut self, env: Environment) {
-        let current_env: Environment = self.get_current_environment().clone();
-        env.borrow_mut().set_outer_environment(current_env);
+        if env.borrow().get_outer_environment().is_none() {
+            let current_env: Environment = self.get_current_environment().clone();
+            env.borrow_mut().set_outer_environment(current_env);
+        }
         self.environment_stack.push_back(env);
+        self.generation = next_generation();
     }
 
     pub fn pop(&mut self) {
         self.environment_stack.pop_back();
+        self.generation = next_generation();
     }
 
     pub fn get_global_object(&self) -> Option<Value> {
@@ -95,35 +144,126 @@ impl LexicalEnvironment {
         global.borrow().get_global_object()
     }
 
-    pub fn create_mutable_binding(&mut self, name: String, deletion: bool) {
+    pub fn create_mutable_binding<N: Into<Sym>>(&mut self, name: N, deletion: bool) {
+        self.generation = next_generation();
         self.get_current_environment()
             .borrow_mut()
-            .create_mutable_binding(name, deletion)
+            .create_mutable_binding(&name.into(), deletion)
     }
 
-    pub fn create_immutable_binding(&mut self, name: String, deletion: bool) -> bool {
+    pub fn create_immutable_binding<N: Into<Sym>>(&mut self, name: N, deletion: bool) -> bool {
+        self.generation = next_generation();
         self.get_current_environment()
             .borrow_mut()
-            .create_immutable_binding(name, deletion)
+            .create_immutable_binding(&name.into(), deletion)
     }
 
-    pub fn set_mutable_binding(&mut self, name: &str, value: Value, strict: bool) {
-        let env = self.get_current_environment();
-        env.borrow_mut().set_mutable_binding(name, value, strict);
+    /// Set the binding in the innermost environment which has it, such as an outer function
+    /// or the object of a `with` statement, or else make it a property of the global object
+    /// <https://tc39.es/ecma262/#sec-putvalue>
+    pub fn set_mutable_binding<N: Into<Sym>>(&mut self, name: N, value: Value, strict: bool) {
+        let name = name.into();
+        let mut env = Some(self.get_current_environment().clone());
+        while let Some(current) = env {
+            if current.borrow().has_binding(&name) {
+                current
+                    .borrow_mut()
+                    .set_mutable_binding(&name, value, strict);
+                return;
+            }
+            env = current.borrow().get_outer_environment();
+        }
+        // The binding is created by setting it
+        self.generation = next_generation();
+        let global = &self.environment_stack[0];
+        global
+            .borrow_mut()
+            .set_mutable_binding(&name, value, strict);
     }
 
-    pub fn initialize_binding(&mut self, name: &str, value: Value) {
+    /// Whether the current environment itself has a binding for `name`, without looking in the
+    /// environments around it
+    pub fn has_own_binding<N: Into<Sym>>(&self, name: N) -> bool {
+        self.get_current_environment_ref()
+            .borrow()
+            .has_binding(&name.into())
+    }
+
+    /// Whether `name` is bound in the current environment or any of the environments around it
+    pub fn has_binding<N: Into<Sym>>(&self, name: N) -> bool {
+        let name = name.into();
+        let mut env = Some(self.get_current_environment_ref().clone());
+        while let Some(current) = env {
+            let borrowed = current.borrow();
+            if borrowed.has_binding(&name) {
+                return true;
+            }
+            env = borrowed.get_outer_environment();
+        }
+        false
+    }
+
+    /// Whether the innermost binding of `name` is immutable, such as one made by `const`
+    pub fn is_immutable_binding<N: Into<Sym>>(&self, name: N) -> bool {
+        let name = name.into();
+        let mut env = Some(self.get_current_environment_ref().clone());
+        while let Some(current) = env {
+            let borrowed = current.borrow();
+            if borrowed.has_binding(&name) {
+                return borrowed.is_immutable_binding(&name);
+            }
+            env = borrowed.get_outer_environment();
+        }
+        false
+    }
+
+    /// Whether the innermost binding of `name` is a property which is not writable, such as the
+    /// global `NaN`
+    pub fn is_read_only_binding<N: Into<Sym>>(&self, name: N) -> bool {
+        let name = name.into();
+        let mut env = Some(self.get_current_environment_ref().clone());
+        while let Some(current) = env {
+            let borrowed = current.borrow();
+            if borrowed.has_binding(&name) {
+                return borrowed.is_read_only_binding(&name);
+            }
+            env = borrowed.get_outer_environment();
+        }
+        false
+    }
+
+    /// The new target of the innermost function call, or `undefined` outside of functions
+    /// <https://tc39.es/ecma262/#sec-getnewtarget>
+    pub fn get_new_target(&self) -> Value {
+        let mut env = Some(self.get_current_environment_ref().clone());
+        while let Some(current) = env {
+            let borrowed = current.borrow();
+            if let Some(new_target) = borrowed.get_new_target() {
+                return new_target;
+            }
+            env = borrowed.get_outer_environment();
+        }
+        Gc::new(ValueData::Undefined)
+    }
+
+    pub fn initialize_binding<N: Into<Sym>>(&mut self, name: N, value: Value) {
         let env = self.get_current_environment();
-        env.borrow_mut().initialize_binding(name, value);
+        env.borrow_mut().initialize_binding(&name.into(), value);
+    }
+
+    /// Bind `name` in the current environment for a `var` declaration, which at the top level
+    /// makes it a property of the global object
+    pub fn create_var_binding<N: Into<Sym>>(&mut self, name: N, value: Option<Value>) {
+        self.generation = next_generation();
+        self.get_current_environment()
+            .borrow_mut()
+            .create_var_binding(&name.into(), value)
     }
 
     /// get_current_environment_ref is used when you only need to borrow the environment
     /// (you only need to add a new variable binding, or you want to fetch a value)
     pub fn get_current_environment_ref(&self) -> &Environment {
-        &self
-            .environment_stack
-            .get(self.environment_stack.len() - 1)
-            .unwrap()
+        self.environment_stack.back().unwrap()
     }
 
     /// When neededing to clone an environment (linking it with another environnment)
@@ -132,12 +272,13 @@ impl LexicalEnvironment {
         self.environment_stack.back_mut().unwrap()
     }
 
-    pub fn get_binding_value(&mut self, name: &str) -> Value {
+    pub fn get_binding_value<N: Into<Sym>>(&mut self, name: N) -> Value {
+        let name = name.into();
         let env: Environment = self.get_current_environment().clone();
         let borrowed_env = env.borrow();
-        let result = borrowed_env.has_binding(name);
+        let result = borrowed_env.has_binding(&name);
         if result {
-            return borrowed_env.get_binding_value(name, false);
+            return borrowed_env.get_binding_value(&name, false);
         }
 
         // Check outer scope
@@ -145,7 +286,7 @@ impl LexicalEnvironment {
             let mut outer: Option<Environment> = borrowed_env.get_outer_environment();
             while outer.is_some() {
                 if outer.as_ref().unwrap().borrow().has_binding(&name) {
-                    return outer.unwrap().borrow().get_binding_value(name, false);
+                    return outer.unwrap().borrow().get_binding_value(&name, false);
                 }
                 outer = outer.unwrap().borrow().get_outer_environment();
             }
@@ -153,11 +294,68 @@ impl LexicalEnvironment {
 
         Gc::new(ValueData::Undefined)
     }
+
+    /// Get the value of a binding like `get_binding_value`, going straight to the binding which
+    /// `cache` recorded if nothing has changed which could make the lookup find another one
+    pub fn get_binding_value_cached(&mut self, name: &Sym, cache: &BindingCache) -> Value {
+        if let Some(resolved) = cache.0.get() {
+            if resolved.generation == self.generation {
+                if let Some(env) = self.get_outer_environment_at(resolved.depth) {
+                    let value = env.borrow().binding_value_at(resolved.slot, name);
+                    if let Some(value) = value {
+                        return value;
+                    }
+                }
+            }
+        }
+
+        cache.0.set(
+            self.resolve_binding(name)
+                .map(|(depth, slot)| ResolvedBinding {
+                    generation: self.generation,
+                    depth,
+                    slot,
+                }),
+        );
+        self.get_binding_value(name)
+    }
+
+    /// Find the environment which has a binding for `name`, as the number of outer environments
+    /// above the current one and the slot of the binding there
+    ///
+    /// Bindings which are not kept in slots, and bindings beyond the object of a `with` statement
+    /// which could gain a property of the same name at any time, are not given a position.
+    fn resolve_binding(&self, name: &Sym) -> Option<(usize, usize)> {
+        let mut env = Some(self.get_current_environment_ref().clone());
+        let mut depth = 0;
+        while let Some(current) = env {
+            let borrowed = current.borrow();
+            if borrowed.has_binding(name) {
+                return borrowed.binding_slot(name).map(|slot| (depth, slot));
+            }
+            if let EnvironmentType::Object = borrowed.get_environment_type() {
+                return None;
+            }
+            env = borrowed.get_outer_environment();
+            depth += 1;
+        }
+        None
+    }
+
+    /// The environment `depth` steps out from the current one
+    fn get_outer_environment_at(&self, depth: usize) -> Option<Environment> {
+        let mut env = self.get_current_environment_ref().clone();
+        for _ in 0..depth {
+            let outer = env.borrow().get_outer_environment()?;
+            env = outer;
+        }
+        Some(env)
+    }
 }
 
 pub fn new_declarative_environment(env: Option<Environment>) -> Environment {
     let boxed_env = Box::new(DeclarativeEnvironmentRecord {
-        env_rec: HashMap::new(),
+        env_rec: DeclarativeBindings::default(),
         outer_env: env,
     });
 
@@ -170,9 +368,9 @@ pub fn new_function_environment(
     outer: Option<Environment>,
 ) -> Environment {
     debug_assert!(f.is_function());
-    debug_assert!(new_target.is_object() || new_target.is_undefined());
+    debug_assert!(new_target.is_function() || new_target.is_object() || new_target.is_undefined());
     Gc::new(GcCell::new(Box::new(FunctionEnvironmentRecord {
-        env_rec: HashMap::new(),
+        env_rec: DeclarativeBindings::default(),
         function_object: f,
         this_binding_status: BindingStatus::Uninitialized, // hardcoding to unitialized for now until short functions are properly supported
         home_object: Gc::new(ValueData::Undefined),
@@ -182,7 +380,11 @@ pub fn new_function_environment(
     })))
 }
 
-pub fn new_object_environment(object: Value, environment: Option<Environment>) -> Environment {
+pub fn new_object_environment(
+    object: Value,
+    with_environment: bool,
+    environment: Option<Environment>,
+) -> Environment {
     Gc::new(GcCell::new(Box::new(ObjectEnvironmentRecord {
         bindings: object,
         outer_env: environment,
@@ -191,7 +393,7 @@ pub fn new_object_environment(object: Value, environment: Option<Environment>) -
         /// The capability is controlled by a withEnvironment Boolean value that is associated
         /// with each object Environment Record. By default, the value of withEnvironment is false
         /// for any object Environment Record.
-        with_environment: false,
+        with_environment,
     })))
 }
 
@@ -208,7 +410,7 @@ pub fn new_global_environment(global: Value, this_value: Value) -> Environment {
     });
 
     let dcl_rec = Box::new(DeclarativeEnvironmentRecord {
-        env_rec: HashMap::new(),
+        env_rec: DeclarativeBindings::default(),
         outer_env: None,
     });
 
@@ -15,6 +15,7 @@ use crate::{
         property::Property,
         value::{Value, ValueData},
     },
+    syntax::ast::sym::Sym,
 };
 use gc::Gc;
 use gc_derive::{Finalize, Trace};
@@ -27,8 +28,8 @@ pub struct ObjectEnvironmentRecord {
 }
 
 impl EnvironmentRecordTrait for ObjectEnvironmentRecord {
-    fn has_binding(&self, name: &str) -> bool {
-        if self.bindings.has_field(name) {
+    fn has_binding(&self, name: &Sym) -> bool {
+        if self.bindings.has_field(name.as_str()) {
             if self.with_environment {
                 // TODO: implement unscopables
             }
@@ -38,7 +39,7 @@ impl EnvironmentRecordTrait for ObjectEnvironmentRecord {
         }
     }
 
-    fn create_mutable_binding(&mut self, name: String, deletion: bool) {
+    fn create_mutable_binding(&mut self, name: &Sym, deletion: bool) {
         // TODO: could save time here and not bother generating a new undefined object,
         // only for it to be replace with the real value later. We could just add the name to a Vector instead
         let bindings = &mut self.bindings;
@@ -48,31 +49,38 @@ impl EnvironmentRecordTrait for ObjectEnvironmentRecord {
             .enumerable(true)
             .configurable(deletion);
 
-        bindings.set_prop(name, prop);
+        bindings.set_prop(name.to_string(), prop);
     }
 
-    fn create_immutable_binding(&mut self, _name: String, _strict: bool) -> bool {
+    fn create_immutable_binding(&mut self, _name: &Sym, _strict: bool) -> bool {
         true
     }
 
-    fn initialize_binding(&mut self, name: &str, value: Value) {
+    fn initialize_binding(&mut self, name: &Sym, value: Value) {
         // We should never need to check if a binding has been created,
         // As all calls to create_mutable_binding are followed by initialized binding
         // The below is just a check.
-        debug_assert!(self.has_binding(&name));
+        debug_assert!(self.has_binding(name));
         self.set_mutable_binding(name, value, false)
     }
 
-    fn set_mutable_binding(&mut self, name: &str, value: Value, strict: bool) {
-        debug_assert!(value.is_object() || value.is_function());
+    fn set_mutable_binding(&mut self, name: &Sym, value: Value, _strict: bool) {
+        self.bindings.set_field_slice(name.as_str(), value);
+    }
 
-        let bindings = &mut self.bindings;
-        bindings.update_prop(name, Some(value.clone()), None, None, Some(strict));
+    fn is_read_only_binding(&self, name: &Sym) -> bool {
+        matches!(
+            self.bindings.get_prop(name.as_str()),
+            Some(Property {
+                writable: Some(false),
+                ..
+            })
+        )
     }
 
-    fn get_binding_value(&self, name: &str, strict: bool) -> Value {
-        if self.bindings.has_field(name) {
-            self.bindings.get_field(name)
+    fn get_binding_value(&self, name: &Sym, strict: bool) -> Value {
+        if self.bindings.has_field(name.as_str()) {
+            self.bindings.get_field(name.as_str())
         } else {
             if strict {
                 // TODO: throw error here
@@ -82,8 +90,8 @@ impl EnvironmentRecordTrait for ObjectEnvironmentRecord {
         }
     }
 
-    fn delete_binding(&mut self, name: &str) -> bool {
-        self.bindings.remove_prop(name);
+    fn delete_binding(&mut self, name: &Sym) -> bool {
+        self.bindings.remove_prop(name.as_str());
         true
     }
 
@@ -106,10 +114,7 @@ impl EnvironmentRecordTrait for ObjectEnvironmentRecord {
     }
 
     fn get_outer_environment(&self) -> Option<Environment> {
-        match &self.outer_env {
-            Some(outer) => Some(outer.clone()),
-            None => None,
-        }
+        self.outer_env.clone()
     }
 
     fn set_outer_environment(&mut self, env: Environment) {
@@ -117,7 +122,7 @@ impl EnvironmentRecordTrait for ObjectEnvironmentRecord {
     }
 
     fn get_environment_type(&self) -> EnvironmentType {
-        EnvironmentType::Function
+        EnvironmentType::Object
     }
 
     fn get_global_object(&self) -> Option<Value> {
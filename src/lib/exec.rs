@@ -1,23 +1,55 @@
 use crate::{
-    environment::lexical_environment::new_function_environment,
+    environment::lexical_environment::{
+        new_declarative_environment, new_function_environment, new_object_environment,
+    },
     js::{
+        array, date, display, error,
         function::{create_unmapped_arguments_object, Function, RegularFunction},
-        object::{ObjectKind, INSTANCE_PROTOTYPE, PROTOTYPE},
-        value::{from_value, to_value, ResultValue, Value, ValueData},
+        object::{enumerable_keys, own_property_keys, ObjectKind, INSTANCE_PROTOTYPE, PROTOTYPE},
+        property::Property,
+        typed_array,
+        value::{
+            abstract_equals, from_value, strict_equals, to_value, ResultValue, Value, ValueData,
+        },
     },
     realm::Realm,
     syntax::ast::{
         constant::Const,
-        expr::{Expr, ExprDef},
+        expr::{
+            DeclarationKind, Expr, ExprDef, MethodDefinitionKind, PropertyDefinition,
+            PropertyName,
+        },
         op::{AssignOp, BinOp, BitOp, CompOp, LogOp, NumOp, UnaryOp},
+        pos::Position,
+        sym::Sym,
     },
+    Script,
 };
 use gc::{Gc, GcCell};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use std::{
     borrow::Borrow,
+    cmp::Ordering,
+    collections::VecDeque,
+    mem,
     ops::{Deref, DerefMut},
+    ptr,
 };
 
+/// How deeply functions may call each other by default before a `RangeError` is thrown.
+/// Optimised builds use about 5 KiB of stack for each call, so this many fit in the 8 MiB
+/// stack a main thread usually has.
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 1_000;
+
+/// How much of the native stack is kept back from calls, so a `RangeError` is thrown while
+/// there is still room for the frames around them and for the work done between calls.
+/// Unoptimised builds use about 80 KiB of stack for each call, so on smaller stacks it is this
+/// guard rather than `DEFAULT_MAX_CALL_DEPTH` which stops deep recursion.
+pub const STACK_GUARD_SIZE: usize = 1024 * 1024;
+
+/// The error thrown once a script has run for more steps than its limit allows
+pub const STEP_LIMIT_EXCEEDED: &str = "StepLimitExceeded: the script ran for too many steps";
+
 /// An execution engine
 pub trait Executor {
     /// Make a new execution engine
@@ -26,27 +58,51 @@ pub trait Executor {
     fn run(&mut self, expr: &Expr) -> ResultValue;
 }
 
+/// A statement which leaves the code around it for the loop or `switch` it is in
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Jump {
+    /// `break`, which leaves the innermost loop or `switch`
+    Break,
+    /// `continue`, which goes on to the next iteration of the innermost loop
+    Continue,
+}
+
 /// A Javascript intepreter
 #[derive(Debug)]
 pub struct Interpreter {
     is_return: bool,
+    /// A `break` or `continue` on its way out to the loop or `switch` it applies to
+    jump: Option<Jump>,
     /// realm holds both the global object and the environment
     realm: Realm,
-}
-
-fn exec_assign_op(op: &AssignOp, v_a: ValueData, v_b: ValueData) -> Value {
-    Gc::new(match *op {
-        AssignOp::Add => v_a + v_b,
-        AssignOp::Sub => v_a - v_b,
-        AssignOp::Mul => v_a * v_b,
-        AssignOp::Div => v_a / v_b,
-        AssignOp::Mod => v_a % v_b,
-        AssignOp::And => v_a & v_b,
-        AssignOp::Or => v_a | v_b,
-        AssignOp::Xor => v_a ^ v_b,
-        AssignOp::Shl => v_a << v_b,
-        AssignOp::Shr => v_a << v_b,
-    })
+    /// The source of the current time for `Date`, in milliseconds since the epoch
+    clock: fn() -> f64,
+    /// The source of `Math.random`
+    rng: StdRng,
+    /// Whether a `return` would return from the body of a function being called,
+    /// making a call it returns a tail call
+    tail_position: bool,
+    /// A tail call to make once the function which returned it has finished, as the
+    /// callee, `this` and arguments
+    tail_call: Option<(Value, Value, Vec<Value>)>,
+    /// The function object being called, whose internal slots hold the state of built-in
+    /// functions such as the resolving functions of a `Promise`
+    active_function: Option<Value>,
+    /// Jobs waiting to run once the script has finished, as the function, `this` and arguments
+    microtasks: VecDeque<(Value, Value, Vec<Value>)>,
+    /// The number of function bodies being run
+    call_depth: usize,
+    /// The number of function bodies which may be run at once
+    max_call_depth: usize,
+    /// The number of expressions run since the step limit was set
+    steps: u64,
+    /// The number of expressions which may be run, if limited
+    step_limit: Option<u64>,
+    /// The last value thrown and where in the source it was first thrown from
+    error: Option<(Value, Position)>,
+    /// Whether the code being run is strict mode code
+    /// <https://tc39.es/ecma262/#sec-strict-mode-code>
+    strict: bool,
 }
 
 impl Executor for Interpreter {
@@ -54,11 +110,41 @@ impl Executor for Interpreter {
         Interpreter {
             realm,
             is_return: false,
+            jump: None,
+            clock: date::system_clock,
+            rng: StdRng::from_entropy(),
+            tail_position: false,
+            tail_call: None,
+            active_function: None,
+            microtasks: VecDeque::new(),
+            call_depth: 0,
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            steps: 0,
+            step_limit: None,
+            error: None,
+            strict: false,
         }
     }
 
-    #[allow(clippy::match_same_arms)]
     fn run(&mut self, expr: &Expr) -> ResultValue {
+        let result = self.run_expr(expr);
+        if let (Err(ref thrown), Some(pos)) = (&result, expr.pos) {
+            self.locate_error(thrown, pos);
+        }
+        result
+    }
+}
+
+impl Interpreter {
+    /// Run an expression, leaving it to `run` to note where an error it throws came from
+    #[allow(clippy::match_same_arms)]
+    fn run_expr(&mut self, expr: &Expr) -> ResultValue {
+        if let Some(step_limit) = self.step_limit {
+            if self.steps >= step_limit {
+                return Err(to_value(STEP_LIMIT_EXCEEDED));
+            }
+            self.steps += 1;
+        }
         match expr.def {
             ExprDef::Const(Const::Null) => Ok(to_value(None::<()>)),
             ExprDef::Const(Const::Undefined) => Ok(Gc::new(ValueData::Undefined)),
@@ -73,10 +159,10 @@ impl Executor for Interpreter {
                 let mut obj = to_value(None::<()>);
                 for e in es.iter() {
                     let val = self.run(e)?;
-                    // early return
-                    if self.is_return {
+                    // early return, the flag is cleared once the function call finishes, or the
+                    // loop or switch a `break` or `continue` applies to
+                    if self.is_return || self.jump.is_some() {
                         obj = val;
-                        self.is_return = false;
                         break;
                     }
                     if e == es.last().expect("unable to get last value") {
@@ -85,49 +171,115 @@ impl Executor for Interpreter {
                 }
                 Ok(obj)
             }
-            ExprDef::Local(ref name) => {
-                let val = self.realm.environment.get_binding_value(name);
-                Ok(val)
+            ExprDef::Local(ref name) => Ok(self
+                .realm
+                .environment
+                .get_binding_value_cached(name, &expr.binding)),
+            ExprDef::NewTargetExpr => Ok(self.realm.environment.get_new_target()),
+            // Functions other than arrow functions bind `this` when they are called, so it is
+            // only missing at the top level
+            // <https://tc39.es/ecma262/#sec-this-keyword>
+            ExprDef::ThisExpr => {
+                let this_sym = self.realm.interner.intern("this");
+                if self.realm.environment.has_binding(&this_sym) {
+                    Ok(self.realm.environment.get_binding_value(&this_sym))
+                } else {
+                    Ok(self.realm.global_obj.clone())
+                }
             }
             ExprDef::GetConstField(ref obj, ref field) => {
                 let val_obj = self.run(obj)?;
-                Ok(val_obj.borrow().get_field(field))
+                self.require_object_coercible(&val_obj, field)?;
+                self.get_property_value(&val_obj, field)
             }
             ExprDef::GetField(ref obj, ref field) => {
                 let val_obj = self.run(obj)?;
-                let val_field = self.run(field)?;
-                Ok(val_obj.borrow().get_field(&val_field.borrow().to_string()))
+                let val_field = self.run(field)?.borrow().to_string();
+                self.require_object_coercible(&val_obj, &val_field)?;
+                self.get_property_value(&val_obj, &val_field)
             }
             ExprDef::Call(ref callee, ref args) => {
-                let (this, func) = match callee.def {
-                    ExprDef::GetConstField(ref obj, ref field) => {
-                        let mut obj = self.run(obj)?;
-                        if obj.get_type() != "object" {
-                            obj = self.to_object(&obj).expect("failed to convert to object");
-                        }
-                        (obj.clone(), obj.borrow().get_field(field))
-                    }
-                    ExprDef::GetField(ref obj, ref field) => {
-                        let obj = self.run(obj)?;
-                        let field = self.run(field)?;
-                        (
-                            obj.clone(),
-                            obj.borrow().get_field(&field.borrow().to_string()),
-                        )
-                    }
-                    _ => (self.realm.global_obj.clone(), self.run(&callee.clone())?), // 'this' binding should come from the function's self-contained environment
-                };
-                let mut v_args = Vec::with_capacity(args.len());
-                for arg in args.iter() {
-                    v_args.push(self.run(arg)?);
-                }
-
+                let (func, this, v_args) = self.prepare_call(callee, args)?;
                 self.call(&func, &this, v_args)
             }
             ExprDef::WhileLoop(ref cond, ref expr) => {
                 let mut result = Gc::new(ValueData::Undefined);
                 while self.run(cond)?.borrow().is_true() {
                     result = self.run(expr)?;
+                    if self.loop_should_stop() {
+                        break;
+                    }
+                }
+                Ok(result)
+            }
+            ExprDef::WithExpr(ref object, ref expr) => {
+                let object = self.run(object)?;
+                if object.is_null_or_undefined() {
+                    return Err(error::new_error(
+                        "TypeError",
+                        "Cannot convert undefined or null to object",
+                        self,
+                    ));
+                }
+                let object = if object.is_object() || object.is_function() {
+                    object
+                } else {
+                    self.to_object(&object)?
+                };
+                // Names are looked up on the object first, then in the enclosing scopes
+                let env = &mut self.realm.environment;
+                env.push(new_object_environment(
+                    object,
+                    true,
+                    Some(env.get_current_environment_ref().clone()),
+                ));
+                let result = self.run(expr);
+                self.realm.environment.pop();
+                result
+            }
+            ExprDef::ForInLoop(ref kind, ref name, ref object, ref expr) => {
+                let object = self.run(object)?;
+                let name = self.realm.interner.intern(name);
+                let mut result = Gc::new(ValueData::Undefined);
+                for key in enumerable_keys(&object) {
+                    let key = to_value(key);
+                    let env = &mut self.realm.environment;
+                    match *kind {
+                        // Each iteration gets its own binding, so closures keep their key
+                        Some(DeclarationKind::Let) | Some(DeclarationKind::Const) => {
+                            env.push(new_declarative_environment(Some(
+                                env.get_current_environment_ref().clone(),
+                            )));
+                            if let Some(DeclarationKind::Const) = *kind {
+                                env.create_immutable_binding(&name, false);
+                            } else {
+                                env.create_mutable_binding(&name, false);
+                            }
+                            env.initialize_binding(&name, key);
+                        }
+                        Some(DeclarationKind::Var) => env.create_var_binding(&name, Some(key)),
+                        None => {
+                            self.check_mutable(&name)?;
+                            self.realm
+                                .environment
+                                .set_mutable_binding(&name, key, true);
+                        }
+                    }
+                    let iteration = self.run(expr);
+                    if let Some(DeclarationKind::Let) | Some(DeclarationKind::Const) = *kind {
+                        self.realm.environment.pop();
+                    }
+                    result = iteration?;
+                    if self.loop_should_stop() {
+                        break;
+                    }
+                }
+                Ok(result)
+            }
+            ExprDef::DoWhileLoop(ref expr, ref cond) => {
+                let mut result = self.run(expr)?;
+                while !self.loop_should_stop() && self.run(cond)?.borrow().is_true() {
+                    result = self.run(expr)?;
                 }
                 Ok(result)
             }
@@ -153,25 +305,91 @@ impl Executor for Interpreter {
                     let block = &tup.1;
                     if val == self.run(cond)? {
                         matched = true;
-                        let last_expr = block.last().unwrap();
                         for expr in block.iter() {
                             let e_result = self.run(expr)?;
-                            if expr == last_expr {
-                                result = e_result;
+                            if self.is_return {
+                                return Ok(e_result);
+                            }
+                            // `break` ends the switch, and `continue` goes on to the loop around it
+                            if self.jump.is_some() {
+                                if self.jump == Some(Jump::Break) {
+                                    self.jump = None;
+                                }
+                                return Ok(result);
                             }
+                            result = e_result;
                         }
                     }
                 }
                 if !matched && default.is_some() {
                     result = self.run(default.as_ref().unwrap())?;
+                    if self.jump == Some(Jump::Break) {
+                        self.jump = None;
+                    }
                 }
                 Ok(result)
             }
-            ExprDef::ObjectDecl(ref map) => {
+            ExprDef::ObjectDecl(ref properties) => {
                 let global_val = &self.realm.environment.get_global_object().unwrap();
                 let obj = ValueData::new_obj(Some(global_val));
-                for (key, val) in map.iter() {
-                    obj.borrow().set_field(key.clone(), self.run(val)?);
+                for property in properties.iter() {
+                    match *property {
+                        PropertyDefinition::Property(ref name, ref val) => {
+                            let key = self.property_key(name)?;
+                            let val = self.run_named(val, &key)?;
+                            obj.borrow().set_field(key, val);
+                        }
+                        PropertyDefinition::MethodDefinition(
+                            MethodDefinitionKind::Ordinary,
+                            ref name,
+                            ref method,
+                        ) => {
+                            let key = self.property_key(name)?;
+                            let func = self.run(method)?;
+                            func.set_non_enumerable_field("name", to_value(key.clone()));
+                            obj.borrow().set_field(key, func);
+                        }
+                        PropertyDefinition::MethodDefinition(ref kind, ref name, ref method) => {
+                            let key = self.property_key(name)?;
+                            let func = self.run(method)?;
+                            func.set_non_enumerable_field(
+                                "name",
+                                to_value(format!("{}{}", kind, key)),
+                            );
+                            // Keep the other half of an accessor pair declared earlier on
+                            let existing = match *obj {
+                                ValueData::Object(ref o) => {
+                                    o.borrow().properties.get(&key).cloned()
+                                }
+                                _ => None,
+                            };
+                            let mut accessor = match existing {
+                                Some(ref prop) if prop.value.is_none() => prop.clone(),
+                                _ => Property::new()
+                                    .get(Gc::new(ValueData::Undefined))
+                                    .set(Gc::new(ValueData::Undefined))
+                                    .enumerable(true)
+                                    .configurable(true),
+                            };
+                            if let MethodDefinitionKind::Get = *kind {
+                                accessor.get = Some(func);
+                            } else {
+                                accessor.set = Some(func);
+                            }
+                            obj.set_prop(key, accessor);
+                        }
+                        PropertyDefinition::SpreadObject(ref source) => {
+                            let source = self.run(source)?;
+                            // Only own enumerable properties are copied, prototypes are left behind
+                            let keys = own_property_keys(&source, |prop| {
+                                prop.enumerable != Some(false)
+                            });
+                            for key in keys {
+                                let value = self.get_property_value(&source, &key)?;
+                                obj.borrow().set_field(key, value);
+                            }
+                        }
+                    }
                 }
                 Ok(obj)
             }
@@ -194,35 +412,65 @@ impl Executor for Interpreter {
                         .borrow()
                         .get_field_slice(PROTOTYPE),
                 );
-                arr_map.borrow().set_field_slice("length", to_value(index));
+                arr_map
+                    .borrow()
+                    .set_non_enumerable_field("length", to_value(index));
                 Ok(arr_map)
             }
             ExprDef::FunctionDecl(ref name, ref args, ref expr) => {
-                let function =
-                    Function::RegularFunc(RegularFunction::new(*expr.clone(), args.clone()));
+                let function = Function::RegularFunc(RegularFunction::new(
+                    expr.clone(),
+                    args.clone(),
+                    self.realm.environment.get_current_environment_ref().clone(),
+                    self.strict || has_use_strict_directive(expr),
+                ));
                 let val = Gc::new(ValueData::Function(Box::new(GcCell::new(function))));
-                if name.is_some() {
-                    self.realm
-                        .environment
-                        .create_mutable_binding(name.clone().unwrap(), false);
-                    self.realm
-                        .environment
-                        .initialize_binding(name.as_ref().unwrap(), val.clone())
+                self.set_function_prototype(&val);
+                // Objects constructed by this function inherit from its prototype object
+                let global_val = &self.realm.environment.get_global_object().unwrap();
+                let proto = ValueData::new_obj(Some(global_val));
+                proto.set_non_enumerable_field("constructor", val.clone());
+                val.set_non_enumerable_field(PROTOTYPE, proto);
+                val.set_non_enumerable_field("name", to_value(name.clone().unwrap_or_default()));
+                val.set_non_enumerable_field("length", to_value(args.len() as i32));
+                if let Some(name) = name {
+                    let env = &mut self.realm.environment;
+                    // Running a declaration again, as a compiled script does each time it is
+                    // run, replaces the function
+                    if env.has_own_binding(name) {
+                        env.set_mutable_binding(name, val.clone(), false);
+                    } else {
+                        env.create_mutable_binding(name, false);
+                        env.initialize_binding(name, val.clone());
+                    }
                 }
                 Ok(val)
             }
             ExprDef::ArrowFunctionDecl(ref args, ref expr) => {
-                let function =
-                    Function::RegularFunc(RegularFunction::new(*expr.clone(), args.clone()));
-                Ok(Gc::new(ValueData::Function(Box::new(GcCell::new(
-                    function,
-                )))))
+                let mut function = RegularFunction::new(
+                    expr.clone(),
+                    args.clone(),
+                    self.realm.environment.get_current_environment_ref().clone(),
+                    self.strict || has_use_strict_directive(expr),
+                );
+                function.lexical_this = true;
+                let function = Function::RegularFunc(function);
+                let val = Gc::new(ValueData::Function(Box::new(GcCell::new(function))));
+                self.set_function_prototype(&val);
+                val.set_non_enumerable_field("name", to_value(String::new()));
+                val.set_non_enumerable_field("length", to_value(args.len() as i32));
+                Ok(val)
             }
             ExprDef::BinOp(BinOp::Num(ref op), ref a, ref b) => {
                 let v_r_a = self.run(a)?;
                 let v_r_b = self.run(b)?;
-                let v_a = (*v_r_a).clone();
-                let v_b = (*v_r_b).clone();
+                // `+` concatenates if either primitive is a string, so it has no hint
+                let hint = match *op {
+                    NumOp::Add => None,
+                    _ => Some("number"),
+                };
+                let v_a = self.primitive_operand(&v_r_a, hint)?;
+                let v_b = self.primitive_operand(&v_r_b, hint)?;
                 Ok(Gc::new(match *op {
                     NumOp::Add => v_a + v_b,
                     NumOp::Sub => v_a - v_b,
@@ -235,43 +483,70 @@ impl Executor for Interpreter {
                 let v_r_a = self.run(a)?;
                 let v_a = (*v_r_a).clone();
                 Ok(match *op {
-                    UnaryOp::Minus => to_value(-v_a.to_num()),
-                    UnaryOp::Plus => to_value(v_a.to_num()),
+                    UnaryOp::Minus => to_value(-self.to_number(&v_r_a)?),
+                    UnaryOp::Plus => to_value(self.to_number(&v_r_a)?),
                     UnaryOp::Not => Gc::new(!v_a),
-                    _ => unreachable!(),
+                    UnaryOp::BitNot => {
+                        let operand = self.primitive_operand(&v_r_a, Some("number"))?;
+                        to_value(!operand.to_int32())
+                    }
+                    // The operand has been evaluated for its side effects
+                    UnaryOp::Void => Gc::new(ValueData::Undefined),
+                    UnaryOp::IncrementPost
+                    | UnaryOp::IncrementPre
+                    | UnaryOp::DecrementPost
+                    | UnaryOp::DecrementPre => {
+                        let old_value = self.to_number(&v_r_a)?;
+                        let new_value = match *op {
+                            UnaryOp::IncrementPost | UnaryOp::IncrementPre => old_value + 1.0,
+                            _ => old_value - 1.0,
+                        };
+                        self.assign(a, to_value(new_value))?;
+                        match *op {
+                            UnaryOp::IncrementPost | UnaryOp::DecrementPost => to_value(old_value),
+                            _ => to_value(new_value),
+                        }
+                    }
                 })
             }
             ExprDef::BinOp(BinOp::Bit(ref op), ref a, ref b) => {
                 let v_r_a = self.run(a)?;
                 let v_r_b = self.run(b)?;
-                let v_a = (*v_r_a).clone();
-                let v_b = (*v_r_b).clone();
+                let v_a = self.primitive_operand(&v_r_a, Some("number"))?;
+                let v_b = self.primitive_operand(&v_r_b, Some("number"))?;
                 Ok(Gc::new(match *op {
                     BitOp::And => v_a & v_b,
                     BitOp::Or => v_a | v_b,
                     BitOp::Xor => v_a ^ v_b,
                     BitOp::Shl => v_a << v_b,
                     BitOp::Shr => v_a >> v_b,
+                    BitOp::UShr => v_a.unsigned_shr(&v_b),
                 }))
             }
             ExprDef::BinOp(BinOp::Comp(ref op), ref a, ref b) => {
                 let v_r_a = self.run(a)?;
                 let v_r_b = self.run(b)?;
-                let v_a = v_r_a.borrow();
-                let v_b = v_r_b.borrow();
                 Ok(to_value(match *op {
-                    CompOp::Equal if v_a.is_object() => v_r_a == v_r_b,
-                    CompOp::Equal => v_a == v_b,
-                    CompOp::NotEqual if v_a.is_object() => v_r_a != v_r_b,
-                    CompOp::NotEqual => v_a != v_b,
-                    CompOp::StrictEqual if v_a.is_object() => v_r_a == v_r_b,
-                    CompOp::StrictEqual => v_a == v_b,
-                    CompOp::StrictNotEqual if v_a.is_object() => v_r_a != v_r_b,
-                    CompOp::StrictNotEqual => v_a != v_b,
-                    CompOp::GreaterThan => v_a.to_num() > v_b.to_num(),
-                    CompOp::GreaterThanOrEqual => v_a.to_num() >= v_b.to_num(),
-                    CompOp::LessThan => v_a.to_num() < v_b.to_num(),
-                    CompOp::LessThanOrEqual => v_a.to_num() <= v_b.to_num(),
+                    CompOp::Equal => abstract_equals(&v_r_a, &v_r_b, self)?,
+                    CompOp::NotEqual => !abstract_equals(&v_r_a, &v_r_b, self)?,
+                    CompOp::StrictEqual => strict_equals(&v_r_a, &v_r_b),
+                    CompOp::StrictNotEqual => !strict_equals(&v_r_a, &v_r_b),
+                    CompOp::GreaterThan => matches!(
+                        self.compare_values(&v_r_a, &v_r_b)?,
+                        Some(Ordering::Greater)
+                    ),
+                    CompOp::GreaterThanOrEqual => matches!(
+                        self.compare_values(&v_r_a, &v_r_b)?,
+                        Some(Ordering::Greater) | Some(Ordering::Equal)
+                    ),
+                    CompOp::LessThan => {
+                        matches!(self.compare_values(&v_r_a, &v_r_b)?, Some(Ordering::Less))
+                    }
+                    CompOp::LessThanOrEqual => matches!(
+                        self.compare_values(&v_r_a, &v_r_b)?,
+                        Some(Ordering::Less) | Some(Ordering::Equal)
+                    ),
+                    CompOp::InstanceOf => self.instance_of(&v_r_a, &v_r_b)?,
                 }))
             }
             ExprDef::BinOp(BinOp::Log(ref op), ref a, ref b) => {
@@ -284,20 +559,32 @@ impl Executor for Interpreter {
             }
             ExprDef::BinOp(BinOp::Assign(ref op), ref a, ref b) => match a.def {
                 ExprDef::Local(ref name) => {
-                    let v_a = (*self.realm.environment.get_binding_value(&name)).clone();
-                    let v_b = (*self.run(b)?).clone();
-                    let value = exec_assign_op(op, v_a, v_b);
+                    let v_r_a = self.realm.environment.get_binding_value(name);
+                    let v_r_b = self.run(b)?;
+                    let value = self.exec_assign_op(op, &v_r_a, &v_r_b)?;
+                    self.check_mutable(name)?;
                     self.realm
                         .environment
-                        .set_mutable_binding(&name, value.clone(), true);
+                        .set_mutable_binding(name, value.clone(), true);
                     Ok(value)
                 }
                 ExprDef::GetConstField(ref obj, ref field) => {
                     let v_r_a = self.run(obj)?;
-                    let v_a = (*v_r_a.borrow().get_field(field)).clone();
-                    let v_b = (*self.run(b)?).clone();
-                    let value = exec_assign_op(op, v_a, v_b.clone());
-                    v_r_a.borrow().set_field(field.clone(), value.clone());
+                    self.require_object_coercible(&v_r_a, field)?;
+                    let v_r_field = self.get_property_value(&v_r_a, field)?;
+                    let v_r_b = self.run(b)?;
+                    let value = self.exec_assign_op(op, &v_r_field, &v_r_b)?;
+                    self.set_property_value(&v_r_a, field, value.clone())?;
+                    Ok(value)
+                }
+                ExprDef::GetField(ref obj, ref field) => {
+                    let v_r_a = self.run(obj)?;
+                    let field = self.run(field)?.borrow().to_string();
+                    self.require_object_coercible(&v_r_a, &field)?;
+                    let v_r_field = self.get_property_value(&v_r_a, &field)?;
+                    let v_r_b = self.run(b)?;
+                    let value = self.exec_assign_op(op, &v_r_field, &v_r_b)?;
+                    self.set_property_value(&v_r_a, &field, value.clone())?;
                     Ok(value)
                 }
                 _ => Ok(Gc::new(ValueData::Undefined)),
@@ -315,7 +602,11 @@ impl Executor for Interpreter {
                     func_object.borrow().get_field_slice(PROTOTYPE),
                 );
 
-                let construct = func_object.get_internal_slot("construct");
+                // Ordinary functions are their own [[Construct]]
+                let mut construct = func_object.get_internal_slot("construct");
+                if construct.is_undefined() {
+                    construct = func_object.clone();
+                }
 
                 match *construct {
                     ValueData::Function(ref inner_func) => match inner_func.clone().into_inner() {
@@ -327,30 +618,69 @@ impl Executor for Interpreter {
                             }
                         }
                         Function::RegularFunc(ref data) => {
+                            self.enter_call()?;
+                            let this_sym = self.realm.interner.intern("this");
                             // Create new scope
                             let env = &mut self.realm.environment;
                             env.push(new_function_environment(
                                 construct.clone(),
-                                this.clone(),
-                                Some(env.get_current_environment_ref().clone()),
+                                func_object.clone(),
+                                Some(data.environment.clone()),
                             ));
+                            env.create_mutable_binding(&this_sym, false);
+                            env.initialize_binding(&this_sym, this.clone());
 
                             for i in 0..data.args.len() {
-                                let name = data.args.get(i).unwrap();
-                                let expr = v_args.get(i).unwrap();
-                                env.create_mutable_binding(name.clone(), false);
-                                env.initialize_binding(name, expr.to_owned());
+                                let name = &data.args[i];
+                                let expr = v_args
+                                    .get(i)
+                                    .cloned()
+                                    .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+                                env.create_mutable_binding(name, false);
+                                env.initialize_binding(name, expr);
                             }
+                            // The returned value decides the result, so a returned call is
+                            // made straight away rather than as a tail call
+                            let tail_position = self.tail_position;
+                            self.tail_position = false;
+                            let strict = mem::replace(&mut self.strict, data.strict);
                             let result = self.run(&data.expr);
+                            self.strict = strict;
+                            self.tail_position = tail_position;
+                            let returned = self.is_return || !is_block(&data.expr);
+                            self.is_return = false;
+                            self.jump = None;
+                            self.call_depth -= 1;
                             self.realm.environment.pop();
-                            result
+                            // The new object is the result unless the function returns an object
+                            match result {
+                                Ok(ref val)
+                                    if !returned || (!val.is_object() && !val.is_function()) =>
+                                {
+                                    Ok(this)
+                                }
+                                _ => result,
+                            }
                         }
                     },
-                    _ => Ok(Gc::new(ValueData::Undefined)),
+                    _ => Err(error::new_error(
+                        "TypeError",
+                        &format!("{} is not a constructor", callee),
+                        self,
+                    )),
                 }
             }
             ExprDef::Return(ref ret) => {
                 let result = match *ret {
+                    // A returned call is left for `call` to make once the frame of the
+                    // current function is gone, so tail recursion runs in constant stack
+                    Some(ref v) if self.tail_position => match v.def {
+                        ExprDef::Call(ref callee, ref args) => {
+                            self.tail_call = Some(self.prepare_call(callee, args)?);
+                            Ok(Gc::new(ValueData::Undefined))
+                        }
+                        _ => self.run(v),
+                    },
                     Some(ref v) => self.run(v),
                     None => Ok(Gc::new(ValueData::Undefined)),
                 };
@@ -358,43 +688,73 @@ impl Executor for Interpreter {
                 self.is_return = true;
                 result
             }
+            ExprDef::Break => {
+                self.jump = Some(Jump::Break);
+                Ok(Gc::new(ValueData::Undefined))
+            }
+            ExprDef::Continue => {
+                self.jump = Some(Jump::Continue);
+                Ok(Gc::new(ValueData::Undefined))
+            }
             ExprDef::Throw(ref ex) => Err(self.run(ex)?),
-            ExprDef::Assign(ref ref_e, ref val_e) => {
-                let val = self.run(val_e)?;
-                match ref_e.def {
-                    ExprDef::Local(ref name) => {
-                        if *self.realm.environment.get_binding_value(&name) != ValueData::Undefined
-                        {
-                            // Binding already exists
-                            self.realm
-                                .environment
-                                .set_mutable_binding(&name, val.clone(), true);
-                        } else {
-                            self.realm
-                                .environment
-                                .create_mutable_binding(name.clone(), true);
-                            self.realm.environment.initialize_binding(name, val.clone());
+            ExprDef::Try(ref block, ref param, ref catch, ref finally) => {
+                // A call returned from the try or catch block has to be made here, so that
+                // it is caught and followed by the finally block
+                let tail_position = self.tail_position;
+                self.tail_position = false;
+                let mut result = self.run(block);
+                if let Some(ref catch) = *catch {
+                    // Running out of steps cannot be caught
+                    if !self.step_limit_exceeded() {
+                        if let Err(thrown) = result {
+                            result = match *param {
+                                Some(ref name) => {
+                                    let env = &mut self.realm.environment;
+                                    env.push(new_declarative_environment(Some(
+                                        env.get_current_environment_ref().clone(),
+                                    )));
+                                    env.create_mutable_binding(name.clone(), false);
+                                    env.initialize_binding(name, thrown);
+                                    let result = self.run(catch);
+                                    self.realm.environment.pop();
+                                    result
+                                }
+                                // Without a binding, the thrown value is discarded
+                                None => self.run(catch),
+                            };
                         }
                     }
-                    ExprDef::GetConstField(ref obj, ref field) => {
-                        let val_obj = self.run(obj)?;
-                        val_obj.borrow().set_field(field.clone(), val.clone());
+                }
+                self.tail_position = tail_position;
+                if let Some(ref finally) = *finally {
+                    // The finally block runs after a return, break or continue too, but its own
+                    // ones or a throw take over from the result of the other blocks
+                    let is_return = self.is_return;
+                    let jump = self.jump.take();
+                    self.is_return = false;
+                    let finally_result = self.run(finally)?;
+                    if self.is_return || self.jump.is_some() {
+                        return Ok(finally_result);
                     }
-                    _ => (),
+                    self.is_return = is_return;
+                    self.jump = jump;
                 }
+                result
+            }
+            ExprDef::Assign(ref ref_e, ref val_e) => {
+                let val = self.run(val_e)?;
+                self.assign(ref_e, val.clone())?;
                 Ok(val)
             }
             ExprDef::VarDecl(ref vars) => {
                 for var in vars.iter() {
                     let (name, value) = var.clone();
                     let val = match value {
-                        Some(v) => self.run(&v)?,
-                        None => Gc::new(ValueData::Undefined),
+                        Some(v) => Some(self.run_named(&v, &name)?),
+                        None => None,
                     };
-                    self.realm
-                        .environment
-                        .create_mutable_binding(name.clone(), false);
-                    self.realm.environment.initialize_binding(&name, val);
+                    let name = self.realm.interner.intern(&name);
+                    self.realm.environment.create_var_binding(&name, val);
                 }
                 Ok(Gc::new(ValueData::Undefined))
             }
@@ -402,23 +762,21 @@ impl Executor for Interpreter {
                 for var in vars.iter() {
                     let (name, value) = var.clone();
                     let val = match value {
-                        Some(v) => self.run(&v)?,
+                        Some(v) => self.run_named(&v, &name)?,
                         None => Gc::new(ValueData::Undefined),
                     };
-                    self.realm
-                        .environment
-                        .create_mutable_binding(name.clone(), false);
+                    let name = self.realm.interner.intern(&name);
+                    self.realm.environment.create_mutable_binding(&name, false);
                     self.realm.environment.initialize_binding(&name, val);
                 }
                 Ok(Gc::new(ValueData::Undefined))
             }
             ExprDef::ConstDecl(ref vars) => {
                 for (name, value) in vars.iter() {
-                    self.realm
-                        .environment
-                        .create_immutable_binding(name.clone(), false);
-                    let val = self.run(&value)?;
-                    self.realm.environment.initialize_binding(&name, val);
+                    let sym = self.realm.interner.intern(name);
+                    self.realm.environment.create_immutable_binding(&sym, false);
+                    let val = self.run_named(value, name)?;
+                    self.realm.environment.initialize_binding(&sym, val);
                 }
                 Ok(Gc::new(ValueData::Undefined))
             }
@@ -426,30 +784,376 @@ impl Executor for Interpreter {
                 let val = self.run(val_e)?;
                 Ok(to_value(match *val {
                     ValueData::Undefined => "undefined",
-                    ValueData::Null | ValueData::Object(_) => "object",
+                    ValueData::Null | ValueData::Object(_) | ValueData::ArrayBuffer(_) => "object",
                     ValueData::Boolean(_) => "boolean",
                     ValueData::Number(_) | ValueData::Integer(_) => "number",
                     ValueData::String(_) => "string",
                     ValueData::Function(_) => "function",
                 }))
             }
+            ExprDef::TemplateExpr(ref quasis, ref exprs) => {
+                let mut string = quasis[0].clone();
+                for (expr, quasi) in exprs.iter().zip(quasis.iter().skip(1)) {
+                    let val = self.run(expr)?;
+                    string.push_str(&self.to_string(&val)?.to_string());
+                    string.push_str(quasi);
+                }
+                Ok(to_value(string))
+            }
         }
     }
-}
 
-impl Interpreter {
+    /// Limit how many function bodies may be run at once, which defaults to
+    /// `DEFAULT_MAX_CALL_DEPTH`
+    pub fn set_max_call_depth(&mut self, max_call_depth: usize) {
+        self.max_call_depth = max_call_depth;
+    }
+
+    /// Limit the number of expressions scripts may run from now on, after which they fail
+    /// with `STEP_LIMIT_EXCEEDED`. Setting the limit again starts a new count.
+    pub fn set_step_limit(&mut self, step_limit: u64) {
+        self.steps = 0;
+        self.step_limit = Some(step_limit);
+    }
+
+    /// Returns true if scripts have run out of steps
+    pub fn step_limit_exceeded(&self) -> bool {
+        self.step_limit
+            .is_some_and(|step_limit| self.steps >= step_limit)
+    }
+
+    /// Where in the source the last error thrown by a script was first thrown from, if known
+    pub fn error_position(&self) -> Option<Position> {
+        self.error.as_ref().map(|&(_, pos)| pos)
+    }
+
+    /// Note that `thrown` was thrown by an expression at `pos`, unless it was already noted by
+    /// an expression within that one, or is a caught value being thrown again. `Error` objects
+    /// are given a `stack` property saying where they were thrown from.
+    fn locate_error(&mut self, thrown: &Value, pos: Position) {
+        if let Some((ref located, _)) = self.error {
+            if ptr::eq::<ValueData>(&**located, &**thrown) {
+                return;
+            }
+        }
+        self.error = Some((thrown.clone(), pos));
+        if thrown.is_error() && thrown.get_field_slice("stack").is_undefined() {
+            let name = thrown.get_field_slice("name");
+            let message = thrown.get_field_slice("message").to_string();
+            let heading = if message.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}: {}", name, message)
+            };
+            let stack = format!(
+                "{}\n    at line {}, column {}",
+                heading, pos.line_number, pos.column_number
+            );
+            thrown.set_field_slice("stack", to_value(stack));
+        }
+    }
+
+    /// Throw a `TypeError` if the property `field` is being read from `undefined` or `null`
+    /// <https://tc39.es/ecma262/#sec-requireobjectcoercible>
+    fn require_object_coercible(&self, obj: &Value, field: &str) -> Result<(), Value> {
+        match **obj {
+            ValueData::Undefined | ValueData::Null => Err(error::new_error(
+                "TypeError",
+                &format!("Cannot read property '{}' of {}", field, obj),
+                self,
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Throw a `TypeError` if `name` is a constant, which cannot be assigned to
+    fn check_mutable(&self, name: &Sym) -> Result<(), Value> {
+        if self.realm.environment.is_immutable_binding(name) {
+            return Err(error::new_error(
+                "TypeError",
+                "Assignment to constant variable.",
+                self,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns true if the prototype of `constructor` is in the prototype chain of `object`
+    /// <https://tc39.es/ecma262/#sec-instanceofoperator>
+    fn instance_of(&mut self, object: &Value, constructor: &Value) -> Result<bool, Value> {
+        if !constructor.is_function() {
+            return Err(error::new_error(
+                "TypeError",
+                "Right-hand side of 'instanceof' is not callable",
+                self,
+            ));
+        }
+        let prototype = constructor.get_field_slice(PROTOTYPE);
+        let mut ancestor = if object.is_object() || object.is_function() {
+            object.get_internal_slot(INSTANCE_PROTOTYPE)
+        } else {
+            return Ok(false);
+        };
+        while ancestor.is_object() {
+            if strict_equals(&ancestor, &prototype) {
+                return Ok(true);
+            }
+            ancestor = ancestor.get_internal_slot(INSTANCE_PROTOTYPE);
+        }
+        Ok(false)
+    }
+
+    /// Count a function body about to be run, throwing a `RangeError` if there are too many
+    /// or less than `STACK_GUARD_SIZE` of the current thread's native stack is left
+    fn enter_call(&mut self) -> Result<(), Value> {
+        // Platforms where the stack left can't be found only limit the number of calls
+        let stack_exhausted =
+            matches!(stacker::remaining_stack(), Some(left) if left < STACK_GUARD_SIZE);
+        if self.call_depth >= self.max_call_depth || stack_exhausted {
+            return Err(error::new_error(
+                "RangeError",
+                "Maximum call stack size exceeded",
+                self,
+            ));
+        }
+        self.call_depth += 1;
+        Ok(())
+    }
+
+    /// Replace the clock `Date` reads the current time from, such as with a fixed time in tests
+    pub fn set_clock(&mut self, clock: fn() -> f64) {
+        self.clock = clock;
+    }
+
+    /// Seed the random number generator `Math.random` reads from, making its numbers the
+    /// same every time the seed is used
+    pub fn set_random_seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// A random number between 0 inclusive and 1 exclusive
+    pub fn random(&mut self) -> f64 {
+        self.rng.gen()
+    }
+
+    /// The current time in milliseconds since the epoch
+    pub fn now(&self) -> f64 {
+        (self.clock)()
+    }
+
+    /// Get the Interpreter's realm
+    pub fn get_realm(&self) -> &Realm {
+        &self.realm
+    }
+
+    /// The function object currently being called, or undefined at the top level
+    pub fn active_function(&self) -> Value {
+        self.active_function
+            .clone()
+            .unwrap_or_else(|| Gc::new(ValueData::Undefined))
+    }
+
+    /// Queue a call of `func` to run after the script, and after the jobs queued before it
+    /// <https://tc39.es/ecma262/#sec-hostenqueuepromisejob>
+    pub fn enqueue_microtask(&mut self, func: Value, this: Value, arguments: Vec<Value>) {
+        self.microtasks.push_back((func, this, arguments));
+    }
+
+    /// Run queued jobs until there are none left, including those queued by the jobs
+    /// themselves, stopping at the first one which throws
+    pub fn run_microtasks(&mut self) -> Result<(), Value> {
+        while let Some((func, this, arguments)) = self.microtasks.pop_front() {
+            self.call(&func, &this, arguments)?;
+        }
+        Ok(())
+    }
+
+    /// Run a compiled script, followed by the promise jobs it queued
+    pub fn run_compiled(&mut self, script: &Script) -> ResultValue {
+        let strict = mem::replace(&mut self.strict, has_use_strict_directive(script.expr()));
+        let result = self.run(script.expr());
+        self.strict = strict;
+        let microtasks = self.run_microtasks();
+        result.and_then(|value| microtasks.map(|_| value))
+    }
+
+    /// Format a value for people to read, showing the contents of objects and arrays
+    pub fn inspect_value(&self, value: &Value) -> String {
+        display::format_value(value)
+    }
+
+    /// Get the value of a property, invoking its getter if it is an accessor property
+    pub fn get_property_value(&mut self, obj: &Value, field: &str) -> ResultValue {
+        self.adopt_array_prototype(obj);
+        if let Some(element) = typed_array::get_element(obj, field) {
+            return Ok(element);
+        }
+        match obj.get_prop(field) {
+            Some(Property {
+                get: Some(ref getter),
+                ..
+            }) if getter.is_function() => self.call(getter, obj, vec![]),
+            _ => Ok(obj.get_field_slice(field)),
+        }
+    }
+
+    /// Set the value of a property, invoking its setter if it is an accessor property
+    pub fn set_property_value(&mut self, obj: &Value, field: &str, val: Value) -> ResultValue {
+        if typed_array::set_element(obj, field, &val)
+            || array::set_array_property(obj, field, &val, self)?
+        {
+            return Ok(val);
+        }
+        if let Some(prop) = obj.get_prop(field) {
+            if let Some(ref setter) = prop.set {
+                if setter.is_function() {
+                    self.call(setter, obj, vec![val.clone()])?;
+                    return Ok(val);
+                }
+            }
+            // Assigning to an accessor property without a setter or to a property which is not
+            // writable does nothing, or throws in strict mode code
+            if prop.value.is_none() || prop.writable == Some(false) {
+                if self.strict {
+                    return Err(error::new_error(
+                        "TypeError",
+                        &format!("Cannot assign to read only property '{}'", field),
+                        self,
+                    ));
+                }
+                return Ok(val);
+            }
+        }
+        Ok(obj.set_field_slice(field, val))
+    }
+
+    /// Arrays made from Rust values with `to_value` have no realm to take a prototype from, so
+    /// they are given this realm's `Array.prototype` when a property is first looked up on them
+    fn adopt_array_prototype(&self, obj: &Value) {
+        if obj.is_array() && obj.get_internal_slot(INSTANCE_PROTOTYPE).is_undefined() {
+            let array = self.realm.global_obj.get_field_slice("Array");
+            obj.set_internal_slot(INSTANCE_PROTOTYPE, array.get_field_slice(PROTOTYPE));
+        }
+    }
+
+    /// Evaluate the callee, `this` and the arguments of a call expression
+    fn prepare_call(
+        &mut self,
+        callee: &Expr,
+        args: &[Expr],
+    ) -> Result<(Value, Value, Vec<Value>), Value> {
+        let (this, func) = match callee.def {
+            ExprDef::GetConstField(ref obj, ref field) => {
+                let mut obj = self.run(obj)?;
+                self.require_object_coercible(&obj, field)?;
+                if obj.get_type() != "object" {
+                    obj = self.to_object(&obj).expect("failed to convert to object");
+                }
+                self.adopt_array_prototype(&obj);
+                (obj.clone(), obj.borrow().get_field(field))
+            }
+            ExprDef::GetField(ref obj, ref field) => {
+                let obj = self.run(obj)?;
+                let field = self.run(field)?.borrow().to_string();
+                self.require_object_coercible(&obj, &field)?;
+                self.adopt_array_prototype(&obj);
+                (obj.clone(), obj.borrow().get_field(&field))
+            }
+            // Plain calls have no receiver
+            _ => (Gc::new(ValueData::Undefined), self.run(&callee.clone())?),
+        };
+        if !func.is_function() {
+            return Err(error::new_error(
+                "TypeError",
+                &format!("{} is not a function", callee),
+                self,
+            ));
+        }
+        let mut v_args = Vec::with_capacity(args.len());
+        for arg in args.iter() {
+            v_args.push(self.run(arg)?);
+        }
+        Ok((func, this, v_args))
+    }
+
     /// https://tc39.es/ecma262/#sec-call
-    fn call(&mut self, f: &Value, v: &Value, arguments_list: Vec<Value>) -> ResultValue {
+    pub fn call(&mut self, f: &Value, v: &Value, arguments_list: Vec<Value>) -> ResultValue {
+        let mut result = self.call_once(f, v, arguments_list);
+        // Calls returned in tail position are made here, after the frame of the function
+        // which returned them is gone
+        while let Some((func, this, arguments)) = self.tail_call.take() {
+            result = self.call_once(&func, &this, arguments);
+        }
+        result
+    }
+
+    /// Store `val` in the variable or property which `ref_e` refers to
+    fn assign(&mut self, ref_e: &Expr, val: Value) -> Result<(), Value> {
+        match ref_e.def {
+            ExprDef::Local(ref name) => {
+                // The innermost binding is set even if it holds `undefined`, and one is made in
+                // the current environment if there is none
+                self.check_mutable(name)?;
+                if self.strict && self.realm.environment.is_read_only_binding(name) {
+                    return Err(error::new_error(
+                        "TypeError",
+                        &format!("Cannot assign to read only property '{}'", name),
+                        self,
+                    ));
+                }
+                self.realm.environment.set_mutable_binding(name, val, true);
+            }
+            ExprDef::GetConstField(ref obj, ref field) => {
+                let val_obj = self.run(obj)?;
+                self.set_property_value(&val_obj, field, val)?;
+            }
+            ExprDef::GetField(ref obj, ref field) => {
+                let val_obj = self.run(obj)?;
+                let val_field = self.run(field)?;
+                self.set_property_value(&val_obj, &val_field.borrow().to_string(), val)?;
+            }
+            _ => (),
+        }
+        Ok(())
+    }
+
+    /// Make a newly created function inherit from `Function.prototype`
+    fn set_function_prototype(&mut self, func: &Value) {
+        let prototype = self
+            .realm
+            .environment
+            .get_binding_value("Function")
+            .get_field_slice(PROTOTYPE);
+        func.set_internal_slot(INSTANCE_PROTOTYPE, prototype);
+    }
+
+    /// Settle a `break` or `continue` which left the body of a loop, returning true if the loop
+    /// has to stop, as it does for a `break` or a `return`
+    fn loop_should_stop(&mut self) -> bool {
+        match self.jump.take() {
+            Some(Jump::Break) => true,
+            Some(Jump::Continue) | None => self.is_return,
+        }
+    }
+
+    /// Call `f` without making the tail call it may leave behind
+    fn call_once(&mut self, f: &Value, v: &Value, arguments_list: Vec<Value>) -> ResultValue {
         // All functions should be objects, and eventually will be.
         // During this transition call will support both native functions and function objects
         match (*f).deref() {
             ValueData::Object(ref obj) => {
                 let func: Value = obj.borrow_mut().deref_mut().get_internal_slot("call");
                 if !func.is_undefined() {
-                    return self.call(&func, v, arguments_list);
+                    let active_function = self.active_function.replace(f.clone());
+                    let result = self.call(&func, v, arguments_list);
+                    self.active_function = active_function;
+                    return result;
                 }
-                // TODO: error object should be here
-                Err(Gc::new(ValueData::Undefined))
+                Err(error::new_error(
+                    "TypeError",
+                    "object is not a function",
+                    self,
+                ))
             }
             ValueData::Function(ref inner_func) => match *inner_func.deref().borrow() {
                 Function::NativeFunc(ref ntv) => {
@@ -457,45 +1161,126 @@ impl Interpreter {
                     func(v, &arguments_list, self)
                 }
                 Function::RegularFunc(ref data) => {
+                    self.enter_call()?;
                     let env = &mut self.realm.environment;
                     // New target (second argument) is only needed for constructors, just pass undefined
                     let undefined = Gc::new(ValueData::Undefined);
                     env.push(new_function_environment(
                         f.clone(),
                         undefined,
-                        Some(env.get_current_environment_ref().clone()),
+                        Some(data.environment.clone()),
                     ));
-                    for i in 0..data.args.len() {
-                        let name = data.args.get(i).unwrap();
-                        let expr: &Value = arguments_list.get(i).unwrap();
-                        self.realm
-                            .environment
-                            .create_mutable_binding(name.clone(), false);
+                    // Arrow functions see the `this` of the code around them. Plain calls have
+                    // no receiver, so `this` is the global object unless the function is strict.
+                    // <https://tc39.es/ecma262/#sec-ordinarycallbindthis>
+                    if !data.lexical_this {
+                        let this = if v.is_null_or_undefined() && !data.strict {
+                            self.realm.global_obj.clone()
+                        } else {
+                            v.clone()
+                        };
+                        let this_sym = self.realm.interner.intern("this");
                         self.realm
                             .environment
-                            .initialize_binding(name, expr.clone());
+                            .create_mutable_binding(&this_sym, false);
+                        self.realm.environment.initialize_binding(&this_sym, this);
+                    }
+                    for i in 0..data.args.len() {
+                        let name = &data.args[i];
+                        let expr = arguments_list
+                            .get(i)
+                            .cloned()
+                            .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+                        self.realm.environment.create_mutable_binding(name, false);
+                        self.realm.environment.initialize_binding(name, expr);
                     }
 
                     // Add arguments object
                     let arguments_obj = create_unmapped_arguments_object(arguments_list);
+                    let arguments_sym = self.realm.interner.intern("arguments");
                     self.realm
                         .environment
-                        .create_mutable_binding("arguments".to_string(), false);
+                        .create_mutable_binding(&arguments_sym, false);
                     self.realm
                         .environment
-                        .initialize_binding("arguments", arguments_obj);
+                        .initialize_binding(&arguments_sym, arguments_obj);
 
+                    let tail_position = self.tail_position;
+                    self.tail_position = true;
+                    let strict = mem::replace(&mut self.strict, data.strict);
                     let result = self.run(&data.expr);
+                    self.strict = strict;
+                    self.tail_position = tail_position;
+                    let returned = self.is_return || !is_block(&data.expr);
+                    self.is_return = false;
+                    self.jump = None;
+                    self.call_depth -= 1;
                     self.realm.environment.pop();
-                    result
+                    match result {
+                        Ok(_) if !returned => Ok(Gc::new(ValueData::Undefined)),
+                        _ => result,
+                    }
                 }
             },
-            _ => Err(Gc::new(ValueData::Undefined)),
+            _ => Err(error::new_error(
+                "TypeError",
+                &format!("{} is not a function", f),
+                self,
+            )),
         }
     }
 
+    /// Apply the operator of a compound assignment such as `+=`
+    fn exec_assign_op(&mut self, op: &AssignOp, a: &Value, b: &Value) -> ResultValue {
+        let hint = match *op {
+            AssignOp::Add => None,
+            _ => Some("number"),
+        };
+        let v_a = self.primitive_operand(a, hint)?;
+        let v_b = self.primitive_operand(b, hint)?;
+        Ok(Gc::new(match *op {
+            AssignOp::Add => v_a + v_b,
+            AssignOp::Sub => v_a - v_b,
+            AssignOp::Mul => v_a * v_b,
+            AssignOp::Div => v_a / v_b,
+            AssignOp::Mod => v_a % v_b,
+            AssignOp::And => v_a & v_b,
+            AssignOp::Or => v_a | v_b,
+            AssignOp::Xor => v_a ^ v_b,
+            AssignOp::Shl => v_a << v_b,
+            AssignOp::Shr => v_a >> v_b,
+            AssignOp::UShr => v_a.unsigned_shr(&v_b),
+        }))
+    }
+
+    /// Convert an operand to a primitive for an operator, leaving primitives as they are
+    fn primitive_operand(&mut self, value: &Value, hint: Option<&str>) -> Result<ValueData, Value> {
+        Ok((*self.to_primitive(value, hint)?).clone())
+    }
+
+    /// Convert an operand to a number for an operator, calling `valueOf` on objects
+    #[allow(clippy::wrong_self_convention)]
+    fn to_number(&mut self, value: &Value) -> Result<f64, Value> {
+        Ok(self.primitive_operand(value, Some("number"))?.to_num())
+    }
+
+    /// Compare the operands of a relational operator such as `<`, converting them to primitives
+    /// from left to right. Two strings are compared by their UTF-16 code units and anything else
+    /// as numbers, giving `None` if either is `NaN`.
+    /// <https://tc39.es/ecma262/#sec-abstract-relational-comparison>
+    fn compare_values(&mut self, a: &Value, b: &Value) -> Result<Option<Ordering>, Value> {
+        let v_a = self.to_primitive(a, Some("number"))?;
+        let v_b = self.to_primitive(b, Some("number"))?;
+        Ok(match (&*v_a, &*v_b) {
+            (ValueData::String(ref x), ValueData::String(ref y)) => {
+                Some(x.encode_utf16().cmp(y.encode_utf16()))
+            }
+            _ => v_a.to_num().partial_cmp(&v_b.to_num()),
+        })
+    }
+
     /// https://tc39.es/ecma262/#sec-ordinarytoprimitive
-    fn ordinary_to_primitive(&mut self, o: &Value, hint: &str) -> Value {
+    fn ordinary_to_primitive(&mut self, o: &Value, hint: &str) -> ResultValue {
         debug_assert!(o.get_type() == "object");
         debug_assert!(hint == "string" || hint == "number");
         let method_names: Vec<&str> = if hint == "string" {
@@ -506,31 +1291,27 @@ impl Interpreter {
         for name in method_names.iter() {
             let method: Value = o.get_field_slice(name);
             if method.is_function() {
-                let result = self.call(&method, &o, vec![]);
-                match result {
-                    Ok(val) => {
-                        if val.is_object() {
-                            // TODO: throw exception
-                            continue;
-                        } else {
-                            return val;
-                        }
-                    }
-                    Err(_) => continue,
+                let val = self.call(&method, o, vec![])?;
+                if !val.is_object() && !val.is_function() {
+                    return Ok(val);
                 }
             }
         }
 
-        Gc::new(ValueData::Undefined)
+        Err(error::new_error(
+            "TypeError",
+            "Cannot convert object to primitive value",
+            self,
+        ))
     }
 
     /// The abstract operation ToPrimitive takes an input argument and an optional argument PreferredType.
     /// https://tc39.es/ecma262/#sec-toprimitive
     #[allow(clippy::wrong_self_convention)]
-    pub fn to_primitive(&mut self, input: &Value, preferred_type: Option<&str>) -> Value {
+    pub fn to_primitive(&mut self, input: &Value, preferred_type: Option<&str>) -> ResultValue {
         let mut hint: &str;
         match (*input).deref() {
-            ValueData::Object(_) => {
+            ValueData::Object(ref obj) => {
                 hint = match preferred_type {
                     None => "default",
                     Some(pt) => match pt {
@@ -542,43 +1323,46 @@ impl Interpreter {
 
                 // Skip d, e we don't support Symbols yet
                 // TODO: add when symbols are supported
+                // Until then, this stands in for `Date.prototype[@@toPrimitive]`, which
+                // prefers strings
                 if hint == "default" {
-                    hint = "number";
+                    hint = match obj.borrow().kind {
+                        ObjectKind::Date => "string",
+                        _ => "number",
+                    };
                 };
 
-                self.ordinary_to_primitive(&input, hint)
+                self.ordinary_to_primitive(input, hint)
             }
-            _ => input.clone(),
+            _ => Ok(input.clone()),
         }
     }
     /// to_string() converts a value into a String
     /// https://tc39.es/ecma262/#sec-tostring
     #[allow(clippy::wrong_self_convention)]
-    pub fn to_string(&mut self, value: &Value) -> Value {
-        match *value.deref().borrow() {
+    pub fn to_string(&mut self, value: &Value) -> ResultValue {
+        Ok(match *value.deref() {
             ValueData::Undefined => to_value("undefined"),
             ValueData::Null => to_value("null"),
             ValueData::Boolean(ref boolean) => to_value(boolean.to_string()),
-            ValueData::Number(ref num) => to_value(num.to_string()),
-            ValueData::Integer(ref num) => to_value(num.to_string()),
+            ValueData::Number(_) | ValueData::Integer(_) => to_value(value.to_string()),
             ValueData::String(ref string) => to_value(string.clone()),
             ValueData::Object(_) => {
-                let prim_value = self.to_primitive(value, Some("string"));
-                self.to_string(&prim_value)
+                let prim_value = self.to_primitive(value, Some("string"))?;
+                return self.to_string(&prim_value);
             }
             _ => to_value("function(){...}"),
-        }
+        })
     }
 
     /// The abstract operation ToObject converts argument to a value of type Object
     /// https://tc39.es/ecma262/#sec-toobject
     #[allow(clippy::wrong_self_convention)]
     pub fn to_object(&mut self, value: &Value) -> ResultValue {
-        match *value.deref().borrow() {
-            ValueData::Undefined
-            | ValueData::Function(_)
-            | ValueData::Integer(_)
-            | ValueData::Null => Err(Gc::new(ValueData::Undefined)),
+        match *value.deref() {
+            ValueData::Undefined | ValueData::Null => Err(Gc::new(ValueData::Undefined)),
+            // Functions are objects already
+            ValueData::Function(_) | ValueData::ArrayBuffer(_) => Ok(value.clone()),
             ValueData::Boolean(_) => {
                 let proto = self
                     .realm
@@ -590,7 +1374,7 @@ impl Interpreter {
                 bool_obj.set_internal_slot("BooleanData", value.clone());
                 Ok(bool_obj)
             }
-            ValueData::Number(_) => {
+            ValueData::Number(_) | ValueData::Integer(_) => {
                 let proto = self
                     .realm
                     .environment
@@ -615,25 +1399,83 @@ impl Interpreter {
     }
 
     /// value_to_rust_string() converts a value into a rust heap allocated string
-    pub fn value_to_rust_string(&mut self, value: &Value) -> String {
-        match *value.deref().borrow() {
+    pub fn value_to_rust_string(&mut self, value: &Value) -> Result<String, Value> {
+        Ok(match *value.deref() {
             ValueData::Null => String::from("null"),
             ValueData::Boolean(ref boolean) => boolean.to_string(),
-            ValueData::Number(ref num) => num.to_string(),
-            ValueData::Integer(ref num) => num.to_string(),
+            // Numbers are shown the way Javascript does, through `number_to_string`
+            ValueData::Number(_) | ValueData::Integer(_) => value.to_string(),
             ValueData::String(ref string) => string.clone(),
             ValueData::Object(_) => {
-                let prim_value = self.to_primitive(value, Some("string"));
-                self.to_string(&prim_value).to_string()
+                let prim_value = self.to_primitive(value, Some("string"))?;
+                self.to_string(&prim_value)?.to_string()
             }
             _ => String::from("undefined"),
+        })
+    }
+
+    /// Run `expr`, naming the function it creates after `name` when it is anonymous
+    /// <https://tc39.es/ecma262/#sec-isanonymousfunctiondefinition>
+    fn run_named(&mut self, expr: &Expr, name: &str) -> ResultValue {
+        let val = self.run(expr)?;
+        match expr.def {
+            ExprDef::FunctionDecl(None, _, _) | ExprDef::ArrowFunctionDecl(_, _) => {
+                val.set_non_enumerable_field("name", to_value(name.to_string()));
+            }
+            _ => (),
+        }
+        Ok(val)
+    }
+
+    /// Resolve the name of an object literal member to the key it is stored under
+    fn property_key(&mut self, name: &PropertyName) -> Result<String, Value> {
+        match *name {
+            PropertyName::Literal(ref key) => Ok(key.clone()),
+            PropertyName::Computed(ref expr) => {
+                let key = self.run(expr)?;
+                self.value_to_rust_string(&key)
+            }
         }
     }
 }
 
+/// Whether a function body is a block of statements, which give the function no result unless
+/// one of them returns, rather than the expression body of an arrow function
+fn is_block(body: &Expr) -> bool {
+    matches!(body.def, ExprDef::Block(_))
+}
+
+/// Whether a script or function body starts with a `"use strict"` directive
+/// <https://tc39.es/ecma262/#sec-directive-prologues-and-the-use-strict-directive>
+fn has_use_strict_directive(body: &Expr) -> bool {
+    match body.def {
+        ExprDef::Block(ref exprs) => matches!(
+            exprs.first().map(|first| &first.def),
+            Some(ExprDef::Const(Const::String(ref directive))) if directive == "use strict"
+        ),
+        _ => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::exec;
+    use crate::exec::Executor;
+    use crate::forward;
+    use crate::realm::Realm;
+
+    /// The stack a main thread usually has, which is larger than the one test threads get
+    const MAIN_THREAD_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+    /// Run `test` on a new thread with `MAIN_THREAD_STACK_SIZE` of stack
+    fn with_main_thread_stack<F: FnOnce() + Send + 'static>(test: F) {
+        std::thread::Builder::new()
+            .stack_size(MAIN_THREAD_STACK_SIZE)
+            .spawn(test)
+            .unwrap()
+            .join()
+            .unwrap();
+    }
 
     #[test]
     fn empty_let_decl_undefined() {
@@ -658,4 +1500,1393 @@ mod tests {
 
         assert_eq!(exec(scenario), pass);
     }
+
+    #[test]
+    fn object_literal_accessors() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const store = { v: 1 };
+        const obj = {
+            get x() {
+                return store.v * 2;
+            },
+            set x(v) {
+                store.v = v;
+            }
+        };
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "obj.x"), "2");
+        forward(&mut engine, "obj.x = 5");
+        assert_eq!(forward(&mut engine, "store.v"), "5");
+        assert_eq!(forward(&mut engine, "obj.x"), "10");
+
+        // Compound assignment goes through the getter and the setter
+        let scenario = r#"
+        const o = {
+            x: 1,
+            get y() {
+                return this.x;
+            },
+            set y(v) {
+                this.x = v;
+            }
+        };
+        o.y += 10;
+        "#;
+        forward(&mut engine, scenario);
+        assert_eq!(forward(&mut engine, "o.x"), "11");
+        assert_eq!(forward(&mut engine, "o.y *= 2"), "22");
+        assert_eq!(forward(&mut engine, "o.x"), "22");
+    }
+
+    #[test]
+    fn do_while_loop() {
+        let body_runs_once = r#"
+        let a = 0;
+        do {
+            a = a + 1;
+        } while (false);
+        a;
+        "#;
+        assert_eq!(exec(body_runs_once), String::from("1"));
+
+        let repeats = r#"
+        let b = 0;
+        do {
+            b = b + 1;
+        } while (b < 3);
+        b;
+        "#;
+        assert_eq!(exec(repeats), String::from("3"));
+
+        let jumps = r#"
+        let i = 0;
+        let skipped = 0;
+        do {
+            i = i + 1;
+            if (i < 3) continue;
+            skipped = skipped + 1;
+            if (i === 5) break;
+        } while (true);
+        [i, skipped].join()
+        "#;
+        assert_eq!(exec(jumps), String::from("5,3"));
+
+        // `continue` goes on to the condition, which can end the loop
+        let continue_to_condition = r#"
+        let runs = 0;
+        do {
+            runs = runs + 1;
+            continue;
+        } while (false);
+        runs
+        "#;
+        assert_eq!(exec(continue_to_condition), String::from("1"));
+    }
+
+    #[test]
+    fn break_and_continue() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+
+        let while_loop = r#"
+        var n = 0;
+        var odd = 0;
+        while (true) {
+            n = n + 1;
+            if (n % 2 === 0) {
+                continue;
+            }
+            if (n > 7) break;
+            odd = odd + n;
+        }
+        n + ',' + odd
+        "#;
+        assert_eq!(forward(&mut engine, while_loop), "9,16");
+
+        let for_in = r#"
+        var keys = '';
+        for (let key in { a: 1, b: 2, c: 3, d: 4 }) {
+            if (key === 'b') continue;
+            if (key === 'd') break;
+            keys = keys + key;
+        }
+        keys
+        "#;
+        assert_eq!(forward(&mut engine, for_in), "ac");
+
+        // `break` leaves only the innermost loop or switch
+        let nested = r#"
+        var pairs = [];
+        var i = 0;
+        while (i < 3) {
+            i = i + 1;
+            var j = 0;
+            while (true) {
+                j = j + 1;
+                if (j > i) break;
+                switch (j) {
+                    case 2:
+                        continue;
+                    default:
+                        break;
+                }
+                pairs.push(i + '' + j);
+            }
+        }
+        pairs.join()
+        "#;
+        assert_eq!(forward(&mut engine, nested), "11,21,31,33");
+
+        let switch_break = r#"
+        var picked = 'none';
+        switch ('b') {
+            case 'b':
+                picked = 'b';
+                break;
+                picked = 'unreachable';
+        }
+        picked
+        "#;
+        assert_eq!(forward(&mut engine, switch_break), "b");
+
+        // The finally block runs when `break` leaves the try block
+        let finally_after_break = r#"
+        var log = [];
+        while (true) {
+            try {
+                break;
+            } finally {
+                log.push('finally');
+            }
+        }
+        log.join()
+        "#;
+        assert_eq!(forward(&mut engine, finally_after_break), "finally");
+    }
+
+    #[test]
+    fn computed_property_names() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const prefix = 'get';
+        const obj = {
+            [1 + 1]: 'two',
+            ['a' + 'b']: 3,
+            [prefix + 'Name']: 'computed',
+            get ['c' + 'd']() {
+                return 4;
+            },
+            5: 'five'
+        };
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "obj[2]"), "two");
+        assert_eq!(forward(&mut engine, "obj['2']"), "two");
+        assert_eq!(forward(&mut engine, "obj.ab"), "3");
+        assert_eq!(forward(&mut engine, "obj.getName"), "computed");
+        assert_eq!(forward(&mut engine, "obj.cd"), "4");
+        assert_eq!(forward(&mut engine, "obj[5]"), "five");
+    }
+
+    #[test]
+    fn object_literal_methods() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const obj = {
+            double(x) {
+                return x * 2;
+            },
+            ['tri' + 'ple'](x) {
+                return x * 3;
+            },
+            value: 1
+        };
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "obj.double(3)"), "6");
+        assert_eq!(forward(&mut engine, "obj.triple(3)"), "9");
+        assert_eq!(forward(&mut engine, "obj.double.name"), "double");
+        assert_eq!(forward(&mut engine, "obj.triple.name"), "triple");
+        assert_eq!(
+            forward(&mut engine, "({double(x) { return x*2; }}).double(3) === 6"),
+            "true"
+        );
+    }
+
+    #[test]
+    fn object_spread() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const source = { a: 1, b: 2, get c() { return 3; } };
+        function Base() {}
+        Base.prototype.inherited = 'inherited';
+        const inherits = new Base();
+        inherits.own = 'own';
+        const spread = { a: 0, ...source, b: 4 };
+        const fromProto = { ...inherits };
+        const fromArray = { ...['x', 'y'] };
+        const fromNull = { ...null, d: 5 };
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "spread.a"), "1");
+        assert_eq!(forward(&mut engine, "spread.b"), "4");
+        assert_eq!(forward(&mut engine, "spread.c"), "3");
+        assert_eq!(forward(&mut engine, "source.b"), "2");
+        assert_eq!(forward(&mut engine, "fromProto.own"), "own");
+        assert_eq!(forward(&mut engine, "inherits.inherited"), "inherited");
+        assert_eq!(forward(&mut engine, "fromProto.inherited"), "undefined");
+        assert_eq!(forward(&mut engine, "fromArray[1]"), "y");
+        assert_eq!(forward(&mut engine, "fromArray.length"), "undefined");
+        assert_eq!(forward(&mut engine, "fromNull.d"), "5");
+    }
+
+    #[test]
+    fn function_name_and_length() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        function add(a, b) {
+            return a + b;
+        }
+        const f = function() {};
+        let arrow = (x, y, z) => x;
+        var single = x => x;
+        const obj = { prop: function(a) {} };
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "add.name"), "add");
+        assert_eq!(forward(&mut engine, "add.length"), "2");
+        assert_eq!(
+            forward(&mut engine, "(function foo(a,b){}).length === 2"),
+            "true"
+        );
+        assert_eq!(
+            forward(&mut engine, "(function bar(){}).name === 'bar'"),
+            "true"
+        );
+        assert_eq!(forward(&mut engine, "f.name"), "f");
+        assert_eq!(forward(&mut engine, "f.length"), "0");
+        assert_eq!(forward(&mut engine, "arrow.name"), "arrow");
+        assert_eq!(forward(&mut engine, "arrow.length"), "3");
+        assert_eq!(forward(&mut engine, "single.name"), "single");
+        assert_eq!(forward(&mut engine, "obj.prop.name"), "prop");
+        assert_eq!(forward(&mut engine, "obj.prop.length"), "1");
+    }
+
+    #[test]
+    fn return_from_nested_block() {
+        let scenario = r#"
+        function sign(n) {
+            if (n < 0) {
+                return -1;
+            }
+            while (true) {
+                if (n > 0) {
+                    return 1;
+                }
+                return 0;
+            }
+        }
+        [sign(-5), sign(5), sign(0)].join();
+        "#;
+        assert_eq!(exec(scenario), String::from("-1,1,0"));
+    }
+
+    #[test]
+    fn objects_to_primitives() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const money = { amount: 5, valueOf() { return this.amount; } };
+        const name = { toString() { return "boa"; } };
+        const both = { valueOf() { return 1; }, toString() { return "two"; } };
+        let total = 10;
+        total += money;
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "money * 2"), "10");
+        assert_eq!(forward(&mut engine, "money + 1"), "6");
+        assert_eq!(forward(&mut engine, "-money"), "-5");
+        assert_eq!(forward(&mut engine, "money > 4"), "true");
+        assert_eq!(forward(&mut engine, "total"), "15");
+        assert_eq!(forward(&mut engine, "'hello ' + name"), "hello boa");
+        assert_eq!(forward(&mut engine, "`${name}!`"), "boa!");
+        // `+` prefers valueOf, while string conversion prefers toString
+        assert_eq!(forward(&mut engine, "both + ''"), "1");
+        assert_eq!(forward(&mut engine, "`${both}`"), "two");
+        assert_eq!(forward(&mut engine, "({}) + ''"), "[object Object]");
+    }
+
+    #[test]
+    fn to_primitive_errors() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const throws = { valueOf() { throw "from valueOf"; }, toString() { return "1"; } };
+        const objects = { valueOf() { return {}; }, toString() { return {}; } };
+        function caught(f) {
+            try {
+                return f();
+            } catch (e) {
+                return (e instanceof TypeError) ? "TypeError" : e;
+            }
+        }
+        "#;
+        forward(&mut engine, init);
+
+        // An exception from valueOf is thrown rather than falling back to toString
+        assert_eq!(
+            forward(&mut engine, "caught(() => throws * 2)"),
+            "from valueOf"
+        );
+        assert_eq!(
+            forward(&mut engine, "caught(() => throws < 2)"),
+            "from valueOf"
+        );
+        assert_eq!(forward(&mut engine, "caught(() => `${throws}`)"), "1");
+        assert_eq!(
+            forward(&mut engine, "caught(() => objects + 1)"),
+            "TypeError"
+        );
+        assert_eq!(
+            forward(&mut engine, "caught(() => String(objects))"),
+            "TypeError"
+        );
+        assert_eq!(
+            forward(&mut engine, "caught(() => [objects].join())"),
+            "TypeError"
+        );
+    }
+
+    #[test]
+    fn relational_comparison() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let cases = [
+            ("1 < 2", "true"),
+            ("2 <= 2", "true"),
+            ("3 > 2", "true"),
+            ("2 >= 3", "false"),
+            // Strings are compared by their code units rather than as numbers
+            ("'a' < 'b'", "true"),
+            ("'10' < '9'", "true"),
+            ("'b' > 'a'", "true"),
+            ("'abc' <= 'abd'", "true"),
+            ("'' < 'a'", "true"),
+            ("'｡' < '\u{1F600}'", "false"),
+            // A string is compared with a number as a number
+            ("'10' < 9", "false"),
+            ("'a' < 1", "false"),
+            ("'a' >= 1", "false"),
+            ("NaN <= NaN", "false"),
+            ("null >= 0", "true"),
+            ("undefined < 1", "false"),
+            ("[2] > 1", "true"),
+            ("({ toString() { return 'b'; } }) > 'a'", "true"),
+        ];
+        for (source, expected) in cases.iter() {
+            assert_eq!(forward(&mut engine, source), *expected, "{}", source);
+        }
+    }
+
+    #[test]
+    fn arithmetic_edge_cases() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+
+        let cases = [
+            // NaN propagates through every operator
+            ("NaN + 1", "NaN"),
+            ("NaN - NaN", "NaN"),
+            ("NaN * 0", "NaN"),
+            ("NaN / 1", "NaN"),
+            ("NaN % 1", "NaN"),
+            ("undefined + 1", "NaN"),
+            // Division by zero gives a signed infinity, or NaN for 0 / 0
+            ("1 / 0", "Infinity"),
+            ("-1 / 0", "-Infinity"),
+            ("1 / -0", "-Infinity"),
+            ("-1 / -0", "Infinity"),
+            ("0 / 0", "NaN"),
+            ("(5 | 0) / (0 | 0)", "Infinity"),
+            ("(0 | 0) / (0 | 0)", "NaN"),
+            // Infinities
+            ("Infinity + Infinity", "Infinity"),
+            ("Infinity + -Infinity", "NaN"),
+            ("Infinity * 0", "NaN"),
+            ("Infinity * -1", "-Infinity"),
+            ("Infinity / Infinity", "NaN"),
+            ("Infinity / 0", "Infinity"),
+            ("1 / Infinity", "0"),
+            // The remainder takes the sign of the dividend
+            ("5 % 0", "NaN"),
+            ("(5 | 0) % (0 | 0)", "NaN"),
+            ("Infinity % 2", "NaN"),
+            ("5 % Infinity", "5"),
+            ("-5 % 2", "-1"),
+            ("5.5 % 2", "1.5"),
+            // Negative zero equals zero, but has a sign
+            ("-0", "0"),
+            ("-0 === 0", "true"),
+            ("1 / (-0 + -0)", "-Infinity"),
+            ("1 / (-0 + 0)", "Infinity"),
+            ("1 / (0 * -1)", "-Infinity"),
+            ("1 / (-0 % 5)", "-Infinity"),
+            ("1 / (-1 / Infinity)", "-Infinity"),
+            ("1 / -(0 | 0)", "-Infinity"),
+        ];
+        for (source, expected) in cases.iter() {
+            assert_eq!(forward(&mut engine, source), *expected, "{}", source);
+        }
+    }
+
+    #[test]
+    fn conditional_operator() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        assert_eq!(forward(&mut engine, "1 < 2 ? 'y' : 'n'"), "y");
+        assert_eq!(forward(&mut engine, "1 + 1 === 3 ? 'y' : 'n'"), "n");
+        assert_eq!(forward(&mut engine, "false || 0 ? 't' : 'f'"), "f");
+        assert_eq!(forward(&mut engine, "2 * (0 ? 3 : 4)"), "8");
+        assert_eq!(
+            forward(
+                &mut engine,
+                "['b', 'a'].sort((a, b) => a < b ? -1 : 1).join()"
+            ),
+            "a,b"
+        );
+        assert_eq!(forward(&mut engine, "var x = 1; x += 0 ? 5 : 10; x"), "11");
+    }
+
+    #[test]
+    fn integer_arithmetic_overflow() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        // `| 0` makes the operands 32-bit integer values
+        forward(
+            &mut engine,
+            "var max = 2147483647 | 0; var min = -2147483648 | 0; var one = 1 | 0;",
+        );
+
+        let cases = [
+            // Results which do not fit in 32 bits carry on as doubles
+            ("2147483647 + 1", "2147483648"),
+            ("max + one", "2147483648"),
+            ("min - one", "-2147483649"),
+            ("max * max", "4611686014132420600"),
+            ("min * -1", "2147483648"),
+            ("-min", "2147483648"),
+            ("var counter = max; counter++; counter", "2147483648"),
+            ("counter = min; counter--; counter", "-2147483649"),
+            // Division never truncates
+            ("5 / 2", "2.5"),
+            ("(5 | 0) / (2 | 0)", "2.5"),
+            ("-7 / 2", "-3.5"),
+            ("one / 0", "Infinity"),
+            ("-one / 0", "-Infinity"),
+            ("(0 | 0) / 0", "NaN"),
+            // The remainder has the sign of the dividend and works on fractions
+            ("-7 % 3", "-1"),
+            ("-7 % 3 === -1", "true"),
+            ("7 % -3", "1"),
+            ("(-7 | 0) % (3 | 0)", "-1"),
+            ("5.5 % 2", "1.5"),
+            ("-5.5 % 2", "-1.5"),
+            ("min % -1", "0"),
+        ];
+        for (source, expected) in cases.iter() {
+            assert_eq!(forward(&mut engine, source), *expected, "{}", source);
+        }
+    }
+
+    #[test]
+    fn abstract_equality() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        forward(
+            &mut engine,
+            "var obj = {}; var num = { valueOf: function () { return 5; } };",
+        );
+
+        assert_eq!(forward(&mut engine, "null == undefined"), "true");
+        assert_eq!(forward(&mut engine, "null == 0"), "false");
+        assert_eq!(forward(&mut engine, "undefined == false"), "false");
+        assert_eq!(forward(&mut engine, "'5' == 5"), "true");
+        assert_eq!(forward(&mut engine, "5 == '5.0'"), "true");
+        assert_eq!(forward(&mut engine, "'abc' == 5"), "false");
+        assert_eq!(forward(&mut engine, "false == 0"), "true");
+        assert_eq!(forward(&mut engine, "true == '1'"), "true");
+        assert_eq!(forward(&mut engine, "NaN == NaN"), "false");
+        assert_eq!(forward(&mut engine, "({}) == ({})"), "false");
+        assert_eq!(forward(&mut engine, "obj == obj"), "true");
+        assert_eq!(forward(&mut engine, "num == 5"), "true");
+        assert_eq!(forward(&mut engine, "'5' != num"), "false");
+        assert_eq!(forward(&mut engine, "obj == '[object Object]'"), "true");
+    }
+
+    #[test]
+    fn strict_equality() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        forward(&mut engine, "var obj = {}; function f() {}");
+
+        assert_eq!(forward(&mut engine, "'5' === 5"), "false");
+        assert_eq!(forward(&mut engine, "null === undefined"), "false");
+        assert_eq!(forward(&mut engine, "1 === 1.0"), "true");
+        assert_eq!(forward(&mut engine, "obj === obj"), "true");
+        assert_eq!(forward(&mut engine, "({}) !== ({})"), "true");
+        assert_eq!(forward(&mut engine, "f === f"), "true");
+    }
+
+    #[test]
+    fn numeric_literals() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+
+        assert_eq!(forward(&mut engine, "0xff + 1 === 256"), "true");
+        assert_eq!(forward(&mut engine, "1e-3 === 0.001"), "true");
+        assert_eq!(forward(&mut engine, "0b101 === 5"), "true");
+        assert_eq!(forward(&mut engine, "0o17 === 15"), "true");
+        assert_eq!(forward(&mut engine, ".5 + .25"), "0.75");
+        assert_eq!(forward(&mut engine, "1_000_000 === 1000000"), "true");
+        assert_eq!(forward(&mut engine, "1+2"), "3");
+        assert_eq!(forward(&mut engine, "5-3"), "2");
+    }
+
+    #[test]
+    fn bitwise_operators() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+
+        assert_eq!(forward(&mut engine, "2147483648 | 0"), "-2147483648");
+        assert_eq!(forward(&mut engine, "4294967297 | 0"), "1");
+        assert_eq!(forward(&mut engine, "-1.9 | 0"), "-1");
+        assert_eq!(forward(&mut engine, "NaN | 0"), "0");
+        assert_eq!(forward(&mut engine, "Infinity & 1"), "0");
+        // Shift counts only use their lowest five bits
+        assert_eq!(forward(&mut engine, "1 << 33"), "2");
+        assert_eq!(forward(&mut engine, "1 << 31"), "-2147483648");
+        assert_eq!(forward(&mut engine, "-8 >> 1"), "-4");
+        assert_eq!(forward(&mut engine, "16 >> -30"), "4");
+        assert_eq!(forward(&mut engine, "-1 >>> 0 === 4294967295"), "true");
+        assert_eq!(forward(&mut engine, "-1 >>> 28"), "15");
+        assert_eq!(forward(&mut engine, "-8 >>> 33"), "2147483644");
+        assert_eq!(forward(&mut engine, "4294967296.5 >>> 0"), "0");
+        assert_eq!(forward(&mut engine, "\"8\" & 12"), "8");
+        assert_eq!(forward(&mut engine, "'0x0f' ^ true"), "14");
+        assert_eq!(forward(&mut engine, "var bits = 1; bits <<= 32; bits"), "1");
+        assert_eq!(forward(&mut engine, "bits = 64; bits >>= 2; bits"), "16");
+        assert_eq!(
+            forward(&mut engine, "bits = -2; bits >>>= 1; bits"),
+            "2147483647"
+        );
+        assert_eq!(forward(&mut engine, "~2147483648"), "2147483647");
+    }
+
+    #[test]
+    fn bitwise_not() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+
+        assert_eq!(forward(&mut engine, "~5 === -6"), "true");
+        assert_eq!(forward(&mut engine, "~-1 === 0"), "true");
+        assert_eq!(forward(&mut engine, "~0 === -1"), "true");
+        assert_eq!(forward(&mut engine, "~~'12'"), "12");
+        assert_eq!(forward(&mut engine, "typeof ~1"), "number");
+    }
+
+    #[test]
+    fn increment_and_decrement() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        forward(&mut engine, "var a = 1; var obj = { count: 5 };");
+
+        assert_eq!(forward(&mut engine, "a++"), "1");
+        assert_eq!(forward(&mut engine, "a"), "2");
+        assert_eq!(forward(&mut engine, "++a"), "3");
+        assert_eq!(forward(&mut engine, "a--"), "3");
+        assert_eq!(forward(&mut engine, "--a"), "1");
+        assert_eq!(forward(&mut engine, "obj.count++; obj.count"), "6");
+        assert_eq!(forward(&mut engine, "--obj['count']"), "5");
+    }
+
+    #[test]
+    fn shadowed_variables() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        var x = 'global';
+        let y = 'outer';
+        function shadow(x) {
+            var y = x + ' param';
+            function inner() {
+                let x = 'inner';
+                return x + ' ' + y;
+            }
+            return inner() + ', ' + x;
+        }
+        function reads() {
+            return x + ' ' + y;
+        }
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(
+            forward(&mut engine, "shadow('arg')"),
+            "inner arg param, arg"
+        );
+        // Nothing declared inside the functions leaks out to the outer scopes
+        assert_eq!(forward(&mut engine, "reads()"), "global outer");
+        assert_eq!(forward(&mut engine, "x"), "global");
+        assert_eq!(forward(&mut engine, "y"), "outer");
+    }
+
+    #[test]
+    fn cached_variable_lookups() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        function makeCounter(start) {
+            let count = start;
+            return () => {
+                count++;
+                return count;
+            };
+        }
+        let first = makeCounter(0);
+        let second = makeCounter(100);
+
+        function shadowedByWith() {
+            let y = 'outer';
+            let o = {};
+            let seen = '';
+            let i = 0;
+            with (o) {
+                while (i < 3) {
+                    seen = seen + y + ' ';
+                    if (i == 0) {
+                        o.y = 'object';
+                    }
+                    i++;
+                }
+            }
+            return seen;
+        }
+
+        function sum(n) {
+            if (n <= 0) return 0;
+            return n + sum(n - 1);
+        }
+        "#;
+        forward(&mut engine, init);
+
+        // The same expressions read a different closure's variable on each call
+        assert_eq!(forward(&mut engine, "first()"), "1");
+        assert_eq!(forward(&mut engine, "second()"), "101");
+        assert_eq!(forward(&mut engine, "first() + ' ' + second()"), "2 102");
+        // A with object which gains the name shadows the variable found before
+        assert_eq!(
+            forward(&mut engine, "shadowedByWith()"),
+            "outer object object "
+        );
+        assert_eq!(forward(&mut engine, "sum(10)"), "55");
+    }
+
+    #[test]
+    fn closures_capture_outer_variables() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        function makeAdder(x) {
+            return function (y) {
+                return x + y;
+            };
+        }
+        let addFive = makeAdder(5);
+
+        function makePair() {
+            let shared = 0;
+            return {
+                increment: function () { shared++; },
+                read: () => shared
+            };
+        }
+        let pair = makePair();
+
+        function outer() {
+            let a = 'a';
+            function middle() {
+                let b = 'b';
+                return function inner() {
+                    return a + b;
+                };
+            }
+            return middle();
+        }
+
+        let name = 'global';
+        function readName() {
+            return name;
+        }
+        function callWithLocalName() {
+            let name = 'caller';
+            return readName();
+        }
+        "#;
+        forward(&mut engine, init);
+
+        // The captured variable outlives the call which declared it
+        assert_eq!(forward(&mut engine, "addFive(1)"), "6");
+        assert_eq!(forward(&mut engine, "makeAdder(10)(1)"), "11");
+        assert_eq!(forward(&mut engine, "addFive(2)"), "7");
+        // Closures made by the same call share its variables
+        forward(&mut engine, "pair.increment(); pair.increment();");
+        assert_eq!(forward(&mut engine, "pair.read()"), "2");
+        assert_eq!(forward(&mut engine, "makePair().read()"), "0");
+        // Variables are found through every enclosing function
+        assert_eq!(forward(&mut engine, "outer()()"), "ab");
+        // Functions see the scope they were made in, not the scope of their caller
+        assert_eq!(forward(&mut engine, "callWithLocalName()"), "global");
+    }
+
+    #[test]
+    fn cached_variable_lookups_across_interpreters() {
+        let script = crate::compile("value").expect("failed to compile");
+        let mut first = Executor::new(Realm::create());
+        let mut second = Executor::new(Realm::create());
+        forward(&mut first, "let value = 'first';");
+        forward(&mut second, "let value = 'second';");
+
+        for _ in 0..2 {
+            assert_eq!(first.run_compiled(&script).unwrap().to_string(), "first");
+            assert_eq!(second.run_compiled(&script).unwrap().to_string(), "second");
+        }
+    }
+
+    #[test]
+    fn void_operator() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const counter = { calls: 0 };
+        function sideEffect() {
+            counter.calls = counter.calls + 1;
+            return 'value';
+        }
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "void 0"), "undefined");
+        assert_eq!(forward(&mut engine, "void sideEffect()"), "undefined");
+        assert_eq!(forward(&mut engine, "counter.calls"), "1");
+        assert_eq!(forward(&mut engine, "typeof void 0"), "undefined");
+        assert_eq!(forward(&mut engine, "void 1 === undefined"), "true");
+        assert_eq!(forward(&mut engine, "void 0 === undefined"), "true");
+        forward(&mut engine, "var x = 1;");
+        assert_eq!(forward(&mut engine, "void (x = 5)"), "undefined");
+        assert_eq!(forward(&mut engine, "x"), "5");
+    }
+
+    #[test]
+    fn template_literals() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const name = "world";
+        const point = { x: 1, y: 2 };
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "`1 + 1 = ${1 + 1}`"), "1 + 1 = 2");
+        assert_eq!(forward(&mut engine, "`hello ${name}!`"), "hello world!");
+        assert_eq!(forward(&mut engine, "`${point.x}${point.y}`"), "12");
+        assert_eq!(
+            forward(&mut engine, "`${ { a: 'nested' }.a } ${`inner ${name}`}`"),
+            "nested inner world"
+        );
+        assert_eq!(
+            forward(&mut engine, "`${undefined} ${null}`"),
+            "undefined null"
+        );
+        assert_eq!(forward(&mut engine, "`a\\`b\\${c}`"), "a`b${c}");
+        assert_eq!(forward(&mut engine, "`line 1\\nline 2`"), "line 1\nline 2");
+        // Line terminators in the source are kept
+        assert_eq!(forward(&mut engine, "`first\nsecond`"), "first\nsecond");
+    }
+
+    #[test]
+    fn tail_calls_do_not_grow_the_stack() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        function count(n, acc) {
+            if (n === 0) {
+                return acc;
+            }
+            return count(n - 1, acc + 1);
+        }
+        function factorial(n, acc) {
+            if (n <= 1) {
+                return acc;
+            }
+            return factorial(n - 1, acc * n);
+        }
+        function isEven(n) {
+            if (n === 0) {
+                return true;
+            }
+            return isOdd(n - 1);
+        }
+        function isOdd(n) {
+            if (n === 0) {
+                return false;
+            }
+            return isEven(n - 1);
+        }
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "count(100000, 0)"), "100000");
+        assert_eq!(forward(&mut engine, "factorial(100000, 1)"), "Infinity");
+        assert_eq!(forward(&mut engine, "factorial(5, 1)"), "120");
+        assert_eq!(forward(&mut engine, "isEven(100001)"), "false");
+    }
+
+    #[test]
+    fn automatic_semicolon_insertion() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        var a = 1
+        var b = a + 1
+        const c = { value: b }
+        function early() {
+            return
+            c.value
+        }
+        function late() {
+            return c.value
+        }
+        function empty() { return }
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "b"), "2");
+        assert_eq!(forward(&mut engine, "c.value"), "2");
+        assert_eq!(forward(&mut engine, "early()"), "undefined");
+        assert_eq!(forward(&mut engine, "late()"), "2");
+        assert_eq!(forward(&mut engine, "empty()"), "undefined");
+
+        // A semicolon ends an expression even when the next statement could continue it
+        assert_eq!(forward(&mut engine, "var x = 5; -1; x"), "5");
+        assert_eq!(forward(&mut engine, "var n = 0; [1, 2].length"), "2");
+        assert_eq!(
+            forward(&mut engine, "var f = function () { return 9 }; (2)"),
+            "2"
+        );
+        assert_eq!(
+            forward(&mut engine, "var y = 0; if (true) y = 1; [y].length"),
+            "1"
+        );
+    }
+
+    #[test]
+    fn call_depth_limit() {
+        // Unoptimised builds need more stack for 32 calls than test threads have
+        with_main_thread_stack(|| {
+            let realm = Realm::create();
+            let mut engine: exec::Interpreter = Executor::new(realm);
+            engine.set_max_call_depth(32);
+            let init = r#"
+            function forever(n) {
+                return 1 + forever(n + 1);
+            }
+            function count(n) {
+                if (n == 0) {
+                    return 0;
+                }
+                return 1 + count(n - 1);
+            }
+            function Deep() {
+                new Deep();
+            }
+            "#;
+            forward(&mut engine, init);
+
+            assert_eq!(
+                forward(&mut engine, "forever(0)"),
+                "Error: RangeError: Maximum call stack size exceeded"
+            );
+            assert_eq!(
+                forward(&mut engine, "new Deep()"),
+                "Error: RangeError: Maximum call stack size exceeded"
+            );
+            // The depth is unwound after the error, so calls within the limit still work
+            assert_eq!(forward(&mut engine, "count(30)"), "30");
+        });
+    }
+
+    #[test]
+    fn deep_recursion() {
+        with_main_thread_stack(|| {
+            let realm = Realm::create();
+            let mut engine: exec::Interpreter = Executor::new(realm);
+            let init = r#"
+            function count(n) {
+                if (n == 0) {
+                    return 0;
+                }
+                return 1 + count(n - 1);
+            }
+            "#;
+            forward(&mut engine, init);
+            assert_eq!(forward(&mut engine, "count(50)"), "50");
+            // Whichever of the call depth and the stack guard runs out first, too deep a
+            // recursion throws rather than overflowing the stack
+            let too_deep = format!("count({})", exec::DEFAULT_MAX_CALL_DEPTH);
+            assert_eq!(
+                forward(&mut engine, &too_deep),
+                "Error: RangeError: Maximum call stack size exceeded"
+            );
+        });
+    }
+
+    #[test]
+    fn native_stack_limit() {
+        // This runs on a test thread, whose stack is far smaller than the call depth needs
+        let realm = Realm::create();
+        let mut engine: exec::Interpreter = Executor::new(realm);
+        // Only the native stack used by the calls stops this recursion
+        engine.set_max_call_depth(usize::max_value());
+        let init = r#"
+        function forever(n) {
+            return 1 + forever(n + 1);
+        }
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(
+            forward(&mut engine, "forever(0)"),
+            "Error: RangeError: Maximum call stack size exceeded"
+        );
+    }
+
+    #[test]
+    fn try_catch_finally() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        var state = { log: '', seen: 'unset' };
+        try {
+            throw 'boom';
+        } catch {
+            state.log = state.log + 'caught;';
+            state.seen = typeof e;
+        }
+        try {
+            throw 'boom';
+        } catch (error) {
+            state.log = state.log + error + ';';
+        }
+        try {
+            state.log = state.log + 'try;';
+        } finally {
+            state.log = state.log + 'finally;';
+        }
+        function early() {
+            try {
+                return 1;
+            } finally {
+                state.log = state.log + 'cleanup;';
+            }
+            return 2;
+        }
+        function fail() {
+            throw 'failed';
+        }
+        function caught() {
+            try {
+                return fail();
+            } catch (error) {
+                return 'caught ' + error;
+            }
+        }
+        function overridden() {
+            try {
+                throw 'lost';
+            } finally {
+                return 'finally';
+            }
+        }
+        var returned = early();
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(
+            forward(&mut engine, "state.log"),
+            "caught;boom;try;finally;cleanup;"
+        );
+        // The catch body runs without any binding of the thrown value
+        assert_eq!(forward(&mut engine, "state.seen"), "undefined");
+        assert_eq!(forward(&mut engine, "typeof error"), "undefined");
+        assert_eq!(forward(&mut engine, "returned"), "1");
+        assert_eq!(forward(&mut engine, "caught()"), "caught failed");
+        assert_eq!(forward(&mut engine, "overridden()"), "finally");
+        assert_eq!(
+            forward(
+                &mut engine,
+                "try { throw 'out'; } finally { state.log = ''; }"
+            ),
+            "Error: out"
+        );
+    }
+
+    #[test]
+    fn step_limit() {
+        let realm = Realm::create();
+        let mut engine: exec::Interpreter = Executor::new(realm);
+        let exceeded = format!("Error: {}", exec::STEP_LIMIT_EXCEEDED);
+
+        engine.set_step_limit(1000);
+        assert_eq!(forward(&mut engine, "1 + 2"), "3");
+        assert_eq!(forward(&mut engine, "while (true) {}"), exceeded);
+        assert!(engine.step_limit_exceeded());
+        // Once exhausted, the budget stays exhausted
+        assert_eq!(forward(&mut engine, "1 + 2"), exceeded);
+
+        engine.set_step_limit(1000);
+        assert_eq!(
+            forward(&mut engine, "try { while (true) {} } catch { 'caught'; }"),
+            exceeded
+        );
+    }
+
+    #[test]
+    fn with_statement() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        var point = { x: 1, y: 2 };
+        var outer = 'outer';
+        with (point) {
+            x = x + y;
+            outer = outer + ' changed';
+        }
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "with({x:42}){x}"), "42");
+        // Assignments change the object's properties, and other names are left to the
+        // enclosing scopes
+        assert_eq!(forward(&mut engine, "point.x"), "3");
+        assert_eq!(forward(&mut engine, "outer"), "outer changed");
+        assert_eq!(forward(&mut engine, "point.outer"), "undefined");
+        assert_eq!(forward(&mut engine, "with ('abc') { length }"), "3");
+        assert_eq!(
+            forward(&mut engine, "with (null) { x }"),
+            "Error: TypeError: Cannot convert undefined or null to object"
+        );
+    }
+
+    #[test]
+    fn for_in_loop() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        function keysOf(obj) {
+            let keys = '';
+            for (let key in obj) {
+                keys = keys + key + ',';
+            }
+            return keys;
+        }
+        function Point() {
+            this.x = 1;
+        }
+        Point.prototype.describe = function () {};
+        function f(a) {}
+        f.extra = true;
+        var last;
+        for (last in { a: 1, b: 2 }) {}
+        const getters = [];
+        for (const key in { c: 1, d: 2 }) {
+            getters.push(() => key);
+        }
+        "#;
+        forward(&mut engine, init);
+
+        // Array lengths, function names and built-in methods are not enumerable
+        assert_eq!(forward(&mut engine, "keysOf([5, 6])"), "0,1,");
+        assert_eq!(forward(&mut engine, "keysOf(f)"), "extra,");
+        assert_eq!(forward(&mut engine, "keysOf({})"), "");
+        // Inherited keys follow the object's own keys
+        assert_eq!(forward(&mut engine, "keysOf(new Point())"), "x,describe,");
+        assert_eq!(forward(&mut engine, "keysOf(null)"), "");
+        assert_eq!(forward(&mut engine, "last"), "b");
+        assert_eq!(forward(&mut engine, "getters[0]() + getters[1]()"), "cd");
+    }
+
+    #[test]
+    fn errors_report_their_position() {
+        let mut engine = Executor::new(Realm::create());
+        let scenario = r#"
+        let config = { server: {} };
+        let port = config.server.port;
+        let user = config.client.user;
+        "#;
+        assert_eq!(
+            forward(&mut engine, scenario),
+            "Error: TypeError: Cannot read property 'user' of undefined"
+        );
+        assert_eq!(engine.error_position().map(|pos| pos.line_number), Some(4));
+
+        // A caught error thrown again keeps the position it was first thrown from
+        let scenario = r#"
+        try {
+            null.value;
+        } catch (e) {
+            throw e;
+        }
+        "#;
+        assert_eq!(
+            forward(&mut engine, scenario),
+            "Error: TypeError: Cannot read property 'value' of null"
+        );
+        assert_eq!(engine.error_position().map(|pos| pos.line_number), Some(3));
+
+        let scenario = r#"
+        try {
+            throw new Error('boom');
+        } catch (e) {
+            e.stack
+        }
+        "#;
+        let stack = forward(&mut engine, scenario);
+        assert!(stack.starts_with("Error: boom\n    at line 3, column "));
+    }
+
+    #[test]
+    fn global_this() {
+        let mut engine = Executor::new(Realm::create());
+        assert_eq!(forward(&mut engine, "typeof globalThis"), "object");
+        assert_eq!(
+            forward(&mut engine, "globalThis.globalThis === globalThis"),
+            "true"
+        );
+        assert_eq!(forward(&mut engine, "globalThis.Math === Math"), "true");
+
+        // Properties of the global object are global variables
+        forward(&mut engine, "globalThis.count = 1;");
+        assert_eq!(forward(&mut engine, "count"), "1");
+        forward(&mut engine, "count = count + 1;");
+        assert_eq!(forward(&mut engine, "globalThis.count"), "2");
+        assert_eq!(
+            forward(
+                &mut engine,
+                "function readCount() { return count; } readCount()"
+            ),
+            "2"
+        );
+        forward(&mut engine, "globalThis['label'] = 'set through an index';");
+        assert_eq!(forward(&mut engine, "label"), "set through an index");
+
+        // Global var declarations are properties of the global object
+        forward(&mut engine, "var y = 5;");
+        assert_eq!(forward(&mut engine, "globalThis.y"), "5");
+        forward(&mut engine, "globalThis.y = 6;");
+        assert_eq!(forward(&mut engine, "y"), "6");
+        assert_eq!(forward(&mut engine, "var y; y"), "6");
+        assert_eq!(
+            forward(
+                &mut engine,
+                "var z; Object.prototype.hasOwnProperty.call(globalThis, 'z')"
+            ),
+            "true"
+        );
+        assert_eq!(
+            forward(
+                &mut engine,
+                "function local() { var w = 1; return w; } local()"
+            ),
+            "1"
+        );
+        assert_eq!(forward(&mut engine, "typeof globalThis.w"), "undefined");
+    }
+
+    #[test]
+    fn this_binding() {
+        let mut engine = Executor::new(Realm::create());
+        assert_eq!(forward(&mut engine, "this === globalThis"), "true");
+        assert_eq!(forward(&mut engine, "this.Math === Math"), "true");
+        let init = r#"
+        function plain() {
+            return this;
+        }
+        var obj = {
+            name: 'obj',
+            getThis: function () {
+                return this;
+            },
+            getName: function () {
+                return this.name;
+            }
+        };
+        "#;
+        forward(&mut engine, init);
+        assert_eq!(forward(&mut engine, "plain() === globalThis"), "true");
+        assert_eq!(forward(&mut engine, "obj.getThis() === obj"), "true");
+        assert_eq!(forward(&mut engine, "obj['getThis']() === obj"), "true");
+        assert_eq!(forward(&mut engine, "obj.getName()"), "obj");
+        // A method taken off its object is a plain call
+        assert_eq!(
+            forward(
+                &mut engine,
+                "var detached = obj.getThis; detached() === globalThis"
+            ),
+            "true"
+        );
+        // Arrow functions see the `this` of the function they were made in
+        let scenario = r#"
+        var o = {
+            v: 7,
+            m: function () {
+                var f = () => this.v;
+                return f();
+            }
+        };
+        o.m()
+        "#;
+        assert_eq!(forward(&mut engine, scenario), "7");
+        assert_eq!(
+            forward(
+                &mut engine,
+                "var arrow = () => this; arrow() === globalThis"
+            ),
+            "true"
+        );
+        // Strict functions called without a receiver see `undefined`
+        assert_eq!(
+            forward(
+                &mut engine,
+                "function strictThis() { 'use strict'; return this; } typeof strictThis()"
+            ),
+            "undefined"
+        );
+        assert_eq!(
+            forward(&mut engine, "strictThis.call(null) === null"),
+            "true"
+        );
+        assert_eq!(
+            forward(&mut engine, "plain.call(null) === globalThis"),
+            "true"
+        );
+    }
+
+    #[test]
+    fn this_in_methods_and_constructors() {
+        let mut engine = Executor::new(Realm::create());
+        let init = r#"
+        function Counter(start) {
+            this.count = start;
+            this.increment = function () {
+                this.count = this.count + 1;
+                return this;
+            };
+        }
+        Counter.prototype.current = function () {
+            return this.count;
+        };
+        var counter = new Counter(5);
+        var point = {
+            x: 3,
+            y: 4,
+            sum: function () {
+                return this.x + this.y;
+            }
+        };
+        "#;
+        forward(&mut engine, init);
+        assert_eq!(forward(&mut engine, "point.sum()"), "7");
+        forward(&mut engine, "point.x = 10;");
+        assert_eq!(forward(&mut engine, "point.sum()"), "14");
+        assert_eq!(forward(&mut engine, "counter.count"), "5");
+        assert_eq!(forward(&mut engine, "counter.current()"), "5");
+        assert_eq!(
+            forward(&mut engine, "counter.increment().increment().current()"),
+            "7"
+        );
+        // Each object sees its own fields through `this`
+        assert_eq!(forward(&mut engine, "new Counter(1).current()"), "1");
+        assert_eq!(forward(&mut engine, "counter.current()"), "7");
+    }
+
+    #[test]
+    fn new_target() {
+        let mut engine = Executor::new(Realm::create());
+        let init = r#"
+        function Shape() {
+            return new.target;
+        }
+        function Plain() {
+            this.constructed = new.target !== undefined;
+        }
+        function inner() {
+            return new.target;
+        }
+        function Outer() {
+            this.inner = inner();
+        }
+        "#;
+        forward(&mut engine, init);
+        assert_eq!(forward(&mut engine, "Shape() === undefined"), "true");
+        assert_eq!(forward(&mut engine, "new Plain().constructed"), "true");
+        assert_eq!(forward(&mut engine, "Plain(); constructed"), "false");
+        // Functions return the new object unless they return an object of their own
+        assert_eq!(forward(&mut engine, "new Shape() === Shape"), "true");
+        // A plain call made by a constructor has no new target
+        assert_eq!(
+            forward(&mut engine, "new Outer().inner === undefined"),
+            "true"
+        );
+    }
+
+    #[test]
+    fn numbers_convert_to_strings_like_javascript() {
+        let mut engine = Executor::new(Realm::create());
+        assert_eq!(forward(&mut engine, "[1e21].join()"), "1e+21");
+        assert_eq!(forward(&mut engine, "[-0].join()"), "0");
+        assert_eq!(forward(&mut engine, "[0.5, 1e-7].join()"), "0.5,1e-7");
+        assert_eq!(
+            forward(&mut engine, "Object.keys({[1e21]: 1})[0]"),
+            "1e+21"
+        );
+        // The default sort order compares "1e-7" with "0.000001"
+        assert_eq!(
+            forward(&mut engine, "[1e-7, 0.000001].sort().join()"),
+            "0.000001,1e-7"
+        );
+        assert_eq!(
+            forward(&mut engine, "String.prototype.toUpperCase.call(1e21)"),
+            "1E+21"
+        );
+        assert_eq!(
+            forward(
+                &mut engine,
+                "JSON.stringify({'1e+21': 1, a: 2}, [new Number(1e21)])"
+            ),
+            r#"{"1e+21":1}"#
+        );
+    }
 }
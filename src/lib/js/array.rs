@@ -1,13 +1,17 @@
 use crate::{
     exec::Interpreter,
     js::{
-        function::NativeFunctionData,
-        object::{Object, ObjectKind, PROTOTYPE},
+        error,
+        function::make_builtin_fn,
+        object::{self, Object, ObjectKind, INSTANCE_PROTOTYPE, PROTOTYPE},
         property::Property,
-        value::{from_value, to_value, ResultValue, Value, ValueData},
+        value::{
+            from_value, same_value_zero, strict_equals, to_value, ResultValue, Value, ValueData,
+        },
     },
 };
 use gc::Gc;
+use std::{cmp::Ordering, iter};
 
 /// Utility function for creating array objects: `array_obj` can be any array with
 /// prototype already set (it will be wiped and recreated from `array_contents`)
@@ -28,9 +32,22 @@ fn construct_array(array_obj: &Value, array_contents: &[Value]) -> ResultValue {
     Ok(array_obj_ptr)
 }
 
+/// Create a new, empty array object with the `Array` prototype
+pub fn new_array(interpreter: &Interpreter) -> ResultValue {
+    let global = &interpreter.get_realm().global_obj;
+    let array = ValueData::new_obj(Some(global));
+    array.set_kind(ObjectKind::Array);
+    array.set_internal_slot(
+        INSTANCE_PROTOTYPE,
+        global.get_field_slice("Array").get_field_slice(PROTOTYPE),
+    );
+    array.set_non_enumerable_field("length", to_value(0_i32));
+    Ok(array)
+}
+
 /// Utility function which takes an existing array object and puts additional
 /// values on the end, correctly rewriting the length
-fn add_to_array_object(array_ptr: &Value, add_values: &[Value]) -> ResultValue {
+pub fn add_to_array_object(array_ptr: &Value, add_values: &[Value]) -> ResultValue {
     let orig_length: i32 =
         from_value(array_ptr.get_field_slice("length")).expect("failed to conveert lenth to i32");
 
@@ -45,10 +62,16 @@ fn add_to_array_object(array_ptr: &Value, add_values: &[Value]) -> ResultValue {
 }
 
 /// Create a new array
-pub fn make_array(this: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+pub fn make_array(this: &Value, args: &[Value], interpreter: &mut Interpreter) -> ResultValue {
+    // Called as a function rather than with `new`, it makes a new array all the same
+    // <https://tc39.es/ecma262/#sec-array-constructor>
+    if !this.is_object() {
+        let array = new_array(interpreter)?;
+        return make_array(&array, args, interpreter);
+    }
     // Make a new Object which will internally represent the Array (mapping
     // between indices and values): this creates an Object with no prototype
-    this.set_field_slice("length", to_value(0_i32));
+    this.set_non_enumerable_field("length", to_value(0_i32));
     // This value is used by console.log and other routines to match Object type
     // to its Javascript Identifier (global constructor method name)
     this.set_kind(ObjectKind::Array);
@@ -64,6 +87,129 @@ pub fn make_array(this: &Value, args: &[Value], _: &mut Interpreter) -> ResultVa
     }
 }
 
+/// Array.isArray ( arg )
+///
+/// Returns true if the argument is an array object.
+/// <https://tc39.es/ecma262/#sec-array.isarray>
+pub fn is_array(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    Ok(to_value(args.first().is_some_and(|arg| arg.is_array())))
+}
+
+/// Array.of ( ...items )
+///
+/// Returns a new array holding the arguments.
+/// <https://tc39.es/ecma262/#sec-array.of>
+pub fn of(_: &Value, args: &[Value], interpreter: &mut Interpreter) -> ResultValue {
+    let array = new_array(interpreter)?;
+    add_to_array_object(&array, args)
+}
+
+/// Array.from ( items [ , mapfn [ , thisArg ] ] )
+///
+/// Returns a new array holding the elements of an array-like object, the values of an
+/// iterator, or the characters of a string, optionally passing each one through `mapfn`.
+/// Without `Symbol.iterator`, an object with a `next` method is taken to be an iterator.
+/// <https://tc39.es/ecma262/#sec-array.from>
+pub fn from(_: &Value, args: &[Value], interpreter: &mut Interpreter) -> ResultValue {
+    let undefined = Gc::new(ValueData::Undefined);
+    let items = args.first().cloned().unwrap_or_else(|| undefined.clone());
+    let map_fn = match args.get(1) {
+        Some(map_fn) if map_fn.is_function() => Some(map_fn),
+        Some(map_fn) if !map_fn.is_undefined() => {
+            return Err(error::new_error(
+                "TypeError",
+                "Array.from: when provided, the second argument must be a function",
+                interpreter,
+            ))
+        }
+        _ => None,
+    };
+    let this_arg = args.get(2).cloned().unwrap_or(undefined);
+
+    let values: Vec<Value> = match *items {
+        ValueData::Null | ValueData::Undefined => {
+            return Err(error::new_error(
+                "TypeError",
+                "Array.from requires an array-like object",
+                interpreter,
+            ))
+        }
+        ValueData::String(ref string) => string.chars().map(|c| to_value(c.to_string())).collect(),
+        _ if items.get_field_slice("next").is_function() => {
+            let next = items.get_field_slice("next");
+            let mut values = Vec::new();
+            loop {
+                let result = interpreter.call(&next, &items, vec![])?;
+                if result.get_field_slice("done").is_true() {
+                    break values;
+                }
+                values.push(result.get_field_slice("value"));
+            }
+        }
+        _ => {
+            let length = items.get_field_slice("length").to_num();
+            let length = if length.is_nan() || length < 0.0 {
+                0
+            } else {
+                length as i32
+            };
+            (0..length)
+                .map(|n| items.get_field(&n.to_string()))
+                .collect()
+        }
+    };
+
+    let array = new_array(interpreter)?;
+    for (n, value) in values.into_iter().enumerate() {
+        let value = match map_fn {
+            Some(map_fn) => interpreter.call(map_fn, &this_arg, vec![value, to_value(n)])?,
+            None => value,
+        };
+        array.set_field(n.to_string(), value);
+        array.set_field_slice("length", to_value(n + 1));
+    }
+    Ok(array)
+}
+
+/// Performs the [[Set]] of an array exotic object when `field` is an index or `length`,
+/// keeping the two consistent. Returns false when the write should go through untouched.
+/// <https://tc39.es/ecma262/#sec-array-exotic-objects-defineownproperty-p-desc>
+pub fn set_array_property(
+    obj: &Value,
+    field: &str,
+    val: &Value,
+    ctx: &Interpreter,
+) -> Result<bool, Value> {
+    if !obj.is_array() {
+        return Ok(false);
+    }
+    let length: i32 = from_value(obj.get_field_slice("length")).unwrap_or(0);
+    if field == "length" {
+        let new_length = val.to_num();
+        if new_length < 0.0 || new_length.fract() != 0.0 || new_length > f64::from(i32::MAX) {
+            return Err(error::new_error("RangeError", "Invalid array length", ctx));
+        }
+        let new_length = new_length as i32;
+        // Shrinking the array deletes every index past the new end
+        for n in new_length..length {
+            obj.remove_prop(&n.to_string());
+        }
+        obj.set_field_slice("length", to_value(new_length));
+        return Ok(true);
+    }
+    match field.parse::<i32>() {
+        // Only canonical indices like "2" are elements, "02" and "-1" are plain properties
+        Ok(index) if index >= 0 && index.to_string() == field => {
+            obj.set_field_slice(field, val.clone());
+            if index >= length {
+                obj.set_field_slice("length", to_value(index + 1));
+            }
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
 /// Get an array's length
 pub fn get_array_length(this: &Value, _: &[Value], _: &mut Interpreter) -> ResultValue {
     // Access the inner hash map which represents the actual Array contents
@@ -77,29 +223,29 @@ pub fn get_array_length(this: &Value, _: &[Value], _: &mut Interpreter) -> Resul
 /// array containing the array elements of the object followed by the array
 /// elements of each argument in order.
 /// <https://tc39.es/ecma262/#sec-array.prototype.concat>
-pub fn concat(this: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
-    if args.is_empty() {
-        // If concat is called with no arguments, it returns the original array
-        return Ok(this.clone());
-    }
-
-    // Make a new array (using this object as the prototype basis for the new
-    // one)
-    let mut new_values: Vec<Value> = Vec::new();
+pub fn concat(this: &Value, args: &[Value], interpreter: &mut Interpreter) -> ResultValue {
+    let new_array = new_array(interpreter)?;
+    let mut new_length: i32 = 0;
 
-    let this_length: i32 = from_value(this.get_field_slice("length")).unwrap();
-    for n in 0..this_length {
-        new_values.push(this.get_field(&n.to_string()));
-    }
-
-    for concat_array in args {
-        let concat_length: i32 = from_value(concat_array.get_field_slice("length")).unwrap();
-        for n in 0..concat_length {
-            new_values.push(concat_array.get_field(&n.to_string()));
+    for item in iter::once(this).chain(args.iter()) {
+        if item.is_array() {
+            // Arrays are flattened one level, keeping any holes they contain
+            let length: i32 = from_value(item.get_field_slice("length")).unwrap();
+            for n in 0..length {
+                let index = n.to_string();
+                if item.has_field(&index) {
+                    new_array.set_field((new_length + n).to_string(), item.get_field(&index));
+                }
+            }
+            new_length += length;
+        } else {
+            new_array.set_field(new_length.to_string(), item.clone());
+            new_length += 1;
         }
     }
 
-    construct_array(this, &new_values)
+    new_array.set_field_slice("length", to_value(new_length));
+    Ok(new_array)
 }
 
 /// Array.prototype.push ( ...items )
@@ -115,14 +261,14 @@ pub fn push(this: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
 
 /// Array.prototype.pop ( )
 ///
-/// The last element of the array is removed from the array and returned.
+/// The last element of the array is removed from the array and returned, or `undefined` if
+/// the array is empty.
 /// <https://tc39.es/ecma262/#sec-array.prototype.pop>
 pub fn pop(this: &Value, _: &[Value], _: &mut Interpreter) -> ResultValue {
     let curr_length: i32 = from_value(this.get_field_slice("length")).unwrap();
     if curr_length < 1 {
-        return Err(to_value(
-            "Cannot pop() on an array with zero length".to_string(),
-        ));
+        this.set_field_slice("length", to_value(0_i32));
+        return Ok(Gc::new(ValueData::Undefined));
     }
     let pop_index = curr_length - 1;
     let pop_value: Value = this.get_field(&pop_index.to_string());
@@ -137,23 +283,43 @@ pub fn pop(this: &Value, _: &[Value], _: &mut Interpreter) -> ResultValue {
 /// then concatenated, separated by occurrences of the separator. If no
 /// separator is provided, a single comma is used as the separator.
 /// <https://tc39.es/ecma262/#sec-array.prototype.join>
-pub fn join(this: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
-    let separator = if args.is_empty() {
-        String::from(",")
-    } else {
-        args[0].to_string()
+pub fn join(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let separator = match args.first() {
+        Some(separator) if !separator.is_undefined() => ctx.value_to_rust_string(separator)?,
+        _ => String::from(","),
     };
 
     let mut elem_strs: Vec<String> = Vec::new();
     let length: i32 = from_value(this.get_field_slice("length")).unwrap();
     for n in 0..length {
-        let elem_str: String = this.get_field(&n.to_string()).to_string();
+        let elem = this.get_field(&n.to_string());
+        // Holes, `undefined` and `null` are joined as empty strings, and other elements
+        // through their `toString`, which joins nested arrays in turn
+        let elem_str = if elem.is_null_or_undefined() {
+            String::new()
+        } else {
+            ctx.value_to_rust_string(&elem)?
+        };
         elem_strs.push(elem_str);
     }
 
     Ok(to_value(elem_strs.join(&separator)))
 }
 
+/// Array.prototype.toString ( )
+///
+/// Joins the elements of the array with commas, falling back to
+/// `Object.prototype.toString` if the array has no `join` method.
+/// <https://tc39.es/ecma262/#sec-array.prototype.tostring>
+pub fn to_string(this: &Value, _: &[Value], interpreter: &mut Interpreter) -> ResultValue {
+    let join = this.get_field_slice("join");
+    if join.is_function() {
+        interpreter.call(&join, this, vec![])
+    } else {
+        object::to_string(this, &[], interpreter)
+    }
+}
+
 /// Array.prototype.reverse ( )
 ///
 /// The elements of the array are rearranged so as to reverse their order.
@@ -202,15 +368,15 @@ pub fn shift(this: &Value, _: &[Value], _: &mut Interpreter) -> ResultValue {
 
     let first: Value = this.get_field(&0.to_string());
 
+    // Holes move down with the elements, rather than being filled with `undefined`
     for k in 1..len {
         let from = k.to_string();
         let to = (k - 1).to_string();
 
-        let from_value = this.get_field(&from);
-        if from_value == Gc::new(ValueData::Undefined) {
-            this.remove_prop(&to);
+        if this.has_field(&from) {
+            this.set_field(to, this.get_field(&from));
         } else {
-            this.set_field(to, from_value);
+            this.remove_prop(&to);
         }
     }
 
@@ -235,11 +401,10 @@ pub fn unshift(this: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue
             let from = (k - 1).to_string();
             let to = (k + arg_c - 1).to_string();
 
-            let from_value = this.get_field(&from);
-            if from_value == Gc::new(ValueData::Undefined) {
-                this.remove_prop(&to);
+            if this.has_field(&from) {
+                this.set_field(to, this.get_field(&from));
             } else {
-                this.set_field(to, from_value);
+                this.remove_prop(&to);
             }
         }
         for j in 0..arg_c {
@@ -251,6 +416,671 @@ pub fn unshift(this: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue
     Ok(to_value(len + arg_c))
 }
 
+/// Utility function implementing the abstract operation ToInteger: `NaN` becomes 0, everything
+/// else is truncated towards zero (infinities are preserved)
+fn to_integer(value: &Value) -> f64 {
+    let number = value.to_num();
+    if number.is_nan() {
+        0.0
+    } else {
+        number.trunc()
+    }
+}
+
+/// Array.prototype.indexOf ( searchElement [ , fromIndex ] )
+///
+/// indexOf compares searchElement to the elements of the array, in ascending order,
+/// using the Strict Equality Comparison algorithm, and if found at one or more indices,
+/// returns the smallest such index; otherwise, -1 is returned. Holes are skipped.
+/// <https://tc39.es/ecma262/#sec-array.prototype.indexof>
+pub fn index_of(this: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    let len: i32 = from_value(this.get_field_slice("length")).unwrap();
+    if len == 0 {
+        return Ok(to_value(-1));
+    }
+
+    let search_element = args
+        .first()
+        .cloned()
+        .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+    let n = args.get(1).map_or(0.0, to_integer);
+    // A negative fromIndex counts back from the end of the array
+    let start = if n >= 0.0 {
+        n.min(f64::from(len))
+    } else {
+        (f64::from(len) + n).max(0.0)
+    };
+
+    for k in (start as i32)..len {
+        let key = k.to_string();
+        if this.has_field(&key) && strict_equals(&this.get_field(&key), &search_element) {
+            return Ok(to_value(k));
+        }
+    }
+
+    Ok(to_value(-1))
+}
+
+/// Array.prototype.lastIndexOf ( searchElement [ , fromIndex ] )
+///
+/// lastIndexOf compares searchElement to the elements of the array in descending order
+/// using the Strict Equality Comparison algorithm, and if found at one or more indices,
+/// returns the largest such index; otherwise, -1 is returned. Holes are skipped.
+/// <https://tc39.es/ecma262/#sec-array.prototype.lastindexof>
+pub fn last_index_of(this: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    let len: i32 = from_value(this.get_field_slice("length")).unwrap();
+    if len == 0 {
+        return Ok(to_value(-1));
+    }
+
+    let search_element = args
+        .first()
+        .cloned()
+        .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+    let n = args.get(1).map_or(f64::from(len - 1), to_integer);
+    // A negative fromIndex counts back from the end of the array
+    let start = if n >= 0.0 {
+        n.min(f64::from(len - 1))
+    } else {
+        f64::from(len) + n
+    };
+    if start < 0.0 {
+        return Ok(to_value(-1));
+    }
+
+    for k in (0..=(start as i32)).rev() {
+        let key = k.to_string();
+        if this.has_field(&key) && strict_equals(&this.get_field(&key), &search_element) {
+            return Ok(to_value(k));
+        }
+    }
+
+    Ok(to_value(-1))
+}
+
+/// Array.prototype.includes ( searchElement [ , fromIndex ] )
+///
+/// includes compares searchElement to the elements of the array, in ascending order,
+/// using the SameValueZero algorithm, and if found at any position, returns true;
+/// otherwise, false is returned. Unlike indexOf, holes are read as `undefined`.
+/// <https://tc39.es/ecma262/#sec-array.prototype.includes>
+pub fn includes(this: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    let len: i32 = from_value(this.get_field_slice("length")).unwrap();
+    if len == 0 {
+        return Ok(to_value(false));
+    }
+
+    let search_element = args
+        .first()
+        .cloned()
+        .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+    let n = args.get(1).map_or(0.0, to_integer);
+    // A negative fromIndex counts back from the end of the array
+    let start = if n >= 0.0 {
+        n.min(f64::from(len))
+    } else {
+        (f64::from(len) + n).max(0.0)
+    };
+
+    for k in (start as i32)..len {
+        if same_value_zero(&this.get_field(&k.to_string()), &search_element) {
+            return Ok(to_value(true));
+        }
+    }
+
+    Ok(to_value(false))
+}
+
+/// Get the callback argument of a predicate-driven method, which must be callable
+fn get_callback<'a>(
+    args: &'a [Value],
+    method: &str,
+    ctx: &Interpreter,
+) -> Result<&'a Value, Value> {
+    match args.first() {
+        Some(callback) if callback.is_function() => Ok(callback),
+        _ => Err(error::new_error(
+            "TypeError",
+            &format!("Array.prototype.{}: callback is not a function", method),
+            ctx,
+        )),
+    }
+}
+
+/// Call `callback` with the element of `this` at `index`, the index and the array,
+/// returning whether the result is truthy
+fn call_predicate(
+    this: &Value,
+    args: &[Value],
+    callback: &Value,
+    index: i32,
+    interpreter: &mut Interpreter,
+) -> Result<bool, Value> {
+    let this_arg = args
+        .get(1)
+        .cloned()
+        .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+    let element = this.get_field(&index.to_string());
+    let arguments = vec![element, to_value(index), this.clone()];
+    Ok(interpreter.call(callback, &this_arg, arguments)?.is_true())
+}
+
+/// Array.prototype.find ( predicate [ , thisArg ] )
+///
+/// Returns the first element for which the predicate returns a truthy value,
+/// or undefined if there is none.
+/// <https://tc39.es/ecma262/#sec-array.prototype.find>
+pub fn find(this: &Value, args: &[Value], interpreter: &mut Interpreter) -> ResultValue {
+    let callback = get_callback(args, "find", interpreter)?;
+    let length: i32 = from_value(this.get_field_slice("length")).unwrap();
+    for n in 0..length {
+        if call_predicate(this, args, callback, n, interpreter)? {
+            return Ok(this.get_field(&n.to_string()));
+        }
+    }
+    Ok(Gc::new(ValueData::Undefined))
+}
+
+/// Array.prototype.findIndex ( predicate [ , thisArg ] )
+///
+/// Returns the index of the first element for which the predicate returns a
+/// truthy value, or -1 if there is none.
+/// <https://tc39.es/ecma262/#sec-array.prototype.findindex>
+pub fn find_index(this: &Value, args: &[Value], interpreter: &mut Interpreter) -> ResultValue {
+    let callback = get_callback(args, "findIndex", interpreter)?;
+    let length: i32 = from_value(this.get_field_slice("length")).unwrap();
+    for n in 0..length {
+        if call_predicate(this, args, callback, n, interpreter)? {
+            return Ok(to_value(n));
+        }
+    }
+    Ok(to_value(-1))
+}
+
+/// Array.prototype.some ( callbackfn [ , thisArg ] )
+///
+/// Returns true as soon as the callback returns a truthy value for an element.
+/// Holes are skipped.
+/// <https://tc39.es/ecma262/#sec-array.prototype.some>
+pub fn some(this: &Value, args: &[Value], interpreter: &mut Interpreter) -> ResultValue {
+    let callback = get_callback(args, "some", interpreter)?;
+    let length: i32 = from_value(this.get_field_slice("length")).unwrap();
+    for n in 0..length {
+        if this.has_field(&n.to_string()) && call_predicate(this, args, callback, n, interpreter)? {
+            return Ok(to_value(true));
+        }
+    }
+    Ok(to_value(false))
+}
+
+/// Array.prototype.every ( callbackfn [ , thisArg ] )
+///
+/// Returns false as soon as the callback returns a falsy value for an element.
+/// Holes are skipped, so an empty array gives true.
+/// <https://tc39.es/ecma262/#sec-array.prototype.every>
+pub fn every(this: &Value, args: &[Value], interpreter: &mut Interpreter) -> ResultValue {
+    let callback = get_callback(args, "every", interpreter)?;
+    let length: i32 = from_value(this.get_field_slice("length")).unwrap();
+    for n in 0..length {
+        if this.has_field(&n.to_string()) && !call_predicate(this, args, callback, n, interpreter)?
+        {
+            return Ok(to_value(false));
+        }
+    }
+    Ok(to_value(true))
+}
+
+/// Fold the elements of `this` at `indices` with the callback of `reduce` or `reduceRight`.
+/// Holes are skipped, and without an initial value the first element visited is used.
+fn reduce_elements<I>(
+    this: &Value,
+    args: &[Value],
+    indices: I,
+    method: &str,
+    interpreter: &mut Interpreter,
+) -> ResultValue
+where
+    I: Iterator<Item = i32>,
+{
+    let callback = get_callback(args, method, interpreter)?;
+    let mut indices = indices.filter(|n| this.has_field(&n.to_string()));
+    let mut accumulator = match args.get(1) {
+        Some(initial_value) => initial_value.clone(),
+        None => match indices.next() {
+            Some(n) => this.get_field(&n.to_string()),
+            None => {
+                return Err(error::new_error(
+                    "TypeError",
+                    &format!(
+                        "Array.prototype.{}: reduce of empty array with no initial value",
+                        method
+                    ),
+                    interpreter,
+                ))
+            }
+        },
+    };
+    let undefined = Gc::new(ValueData::Undefined);
+    for n in indices {
+        let element = this.get_field(&n.to_string());
+        let arguments = vec![accumulator, element, to_value(n), this.clone()];
+        accumulator = interpreter.call(callback, &undefined, arguments)?;
+    }
+    Ok(accumulator)
+}
+
+/// Array.prototype.reduce ( callbackfn [ , initialValue ] )
+///
+/// Calls the callback with the accumulated value and each element, from left to right.
+/// <https://tc39.es/ecma262/#sec-array.prototype.reduce>
+pub fn reduce(this: &Value, args: &[Value], interpreter: &mut Interpreter) -> ResultValue {
+    let length: i32 = from_value(this.get_field_slice("length")).unwrap();
+    reduce_elements(this, args, 0..length, "reduce", interpreter)
+}
+
+/// Array.prototype.reduceRight ( callbackfn [ , initialValue ] )
+///
+/// Calls the callback with the accumulated value and each element, from right to left.
+/// <https://tc39.es/ecma262/#sec-array.prototype.reduceright>
+pub fn reduce_right(this: &Value, args: &[Value], interpreter: &mut Interpreter) -> ResultValue {
+    let length: i32 = from_value(this.get_field_slice("length")).unwrap();
+    reduce_elements(this, args, (0..length).rev(), "reduceRight", interpreter)
+}
+
+/// Create an iterator over `array`, giving its keys, values or `[key, value]` entries
+/// depending on `kind`, which is `"key"`, `"value"` or `"key+value"`.
+/// <https://tc39.es/ecma262/#sec-createarrayiterator>
+fn create_array_iterator(array: &Value, kind: &str, interpreter: &Interpreter) -> Value {
+    let iterator = ValueData::new_obj(Some(&interpreter.get_realm().global_obj));
+    iterator.set_internal_slot("IteratedObject", array.clone());
+    iterator.set_internal_slot("ArrayIteratorNextIndex", to_value(0_i32));
+    iterator.set_internal_slot("ArrayIterationKind", to_value(kind));
+    iterator.set_field_slice(
+        "next",
+        make_builtin_fn(iterator_next, &interpreter.get_realm().global_obj),
+    );
+    iterator
+}
+
+/// Create an iterator result object, as returned by the `next` method of iterators
+/// <https://tc39.es/ecma262/#sec-createiterresultobject>
+pub fn create_iter_result_object(value: Value, done: bool, interpreter: &Interpreter) -> Value {
+    let result = ValueData::new_obj(Some(&interpreter.get_realm().global_obj));
+    result.set_field_slice("value", value);
+    result.set_field_slice("done", to_value(done));
+    result
+}
+
+/// %ArrayIteratorPrototype%.next ( )
+///
+/// Gives the next key, value or entry of the array, reading its length at every step.
+/// Once the iterator is done, it stays done even if the array grows.
+/// <https://tc39.es/ecma262/#sec-%arrayiteratorprototype%.next>
+pub fn iterator_next(this: &Value, _: &[Value], interpreter: &mut Interpreter) -> ResultValue {
+    let kind = this.get_internal_slot("ArrayIterationKind");
+    if kind.is_undefined() {
+        return Err(error::new_error(
+            "TypeError",
+            "next method called on incompatible receiver",
+            interpreter,
+        ));
+    }
+    let undefined = Gc::new(ValueData::Undefined);
+    let array = this.get_internal_slot("IteratedObject");
+    if array.is_undefined() {
+        return Ok(create_iter_result_object(undefined, true, interpreter));
+    }
+
+    let index: i32 = from_value(this.get_internal_slot("ArrayIteratorNextIndex")).unwrap();
+    let length = array.get_field_slice("length").to_int();
+    if index >= length {
+        this.set_internal_slot("IteratedObject", undefined.clone());
+        return Ok(create_iter_result_object(undefined, true, interpreter));
+    }
+    this.set_internal_slot("ArrayIteratorNextIndex", to_value(index + 1));
+
+    let value = match kind.to_string().as_str() {
+        "key" => to_value(index),
+        "value" => array.get_field(&index.to_string()),
+        _ => {
+            let entry = new_array(interpreter)?;
+            add_to_array_object(
+                &entry,
+                &[to_value(index), array.get_field(&index.to_string())],
+            )?
+        }
+    };
+    Ok(create_iter_result_object(value, false, interpreter))
+}
+
+/// Array.prototype.keys ( )
+///
+/// Returns an iterator over the indices of the array.
+/// <https://tc39.es/ecma262/#sec-array.prototype.keys>
+pub fn keys(this: &Value, _: &[Value], interpreter: &mut Interpreter) -> ResultValue {
+    Ok(create_array_iterator(this, "key", interpreter))
+}
+
+/// Array.prototype.values ( )
+///
+/// Returns an iterator over the elements of the array.
+/// <https://tc39.es/ecma262/#sec-array.prototype.values>
+pub fn values(this: &Value, _: &[Value], interpreter: &mut Interpreter) -> ResultValue {
+    Ok(create_array_iterator(this, "value", interpreter))
+}
+
+/// Array.prototype.entries ( )
+///
+/// Returns an iterator over `[index, element]` pairs of the array.
+/// <https://tc39.es/ecma262/#sec-array.prototype.entries>
+pub fn entries(this: &Value, _: &[Value], interpreter: &mut Interpreter) -> ResultValue {
+    Ok(create_array_iterator(this, "key+value", interpreter))
+}
+
+/// The SortCompare abstract operation, for two values which are not undefined
+/// <https://tc39.es/ecma262/#sec-sortcompare>
+fn sort_compare(
+    x: &Value,
+    y: &Value,
+    compare_fn: Option<&Value>,
+    interpreter: &mut Interpreter,
+) -> Result<Ordering, Value> {
+    match compare_fn {
+        Some(compare_fn) => {
+            let undefined = Gc::new(ValueData::Undefined);
+            let v = interpreter
+                .call(compare_fn, &undefined, vec![x.clone(), y.clone()])?
+                .to_num();
+            Ok(if v < 0.0 {
+                Ordering::Less
+            } else if v > 0.0 {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            })
+        }
+        None => {
+            // Strings are ordered by their UTF-16 code units rather than their UTF-8 bytes
+            let x_string = interpreter.value_to_rust_string(x)?;
+            let y_string = interpreter.value_to_rust_string(y)?;
+            Ok(x_string.encode_utf16().cmp(y_string.encode_utf16()))
+        }
+    }
+}
+
+/// Sort `values` stably with a merge sort, stopping at the first error the comparator throws.
+///
+/// `slice::sort_by` may panic when its comparison is not a total order, which a script's
+/// comparator need not be, so the sort is done here.
+fn merge_sort(
+    mut values: Vec<Value>,
+    compare_fn: Option<&Value>,
+    interpreter: &mut Interpreter,
+) -> Result<Vec<Value>, Value> {
+    if values.len() < 2 {
+        return Ok(values);
+    }
+    let right = values.split_off(values.len() / 2);
+    let left = merge_sort(values, compare_fn, interpreter)?;
+    let right = merge_sort(right, compare_fn, interpreter)?;
+
+    let mut merged = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+    while let (Some(x), Some(y)) = (left.peek(), right.peek()) {
+        // Equal elements keep their order by taking from the left first
+        if sort_compare(x, y, compare_fn, interpreter)? == Ordering::Greater {
+            merged.extend(right.next());
+        } else {
+            merged.extend(left.next());
+        }
+    }
+    merged.extend(left);
+    merged.extend(right);
+    Ok(merged)
+}
+
+/// Array.prototype.sort ( comparefn )
+///
+/// The elements of this array are sorted in place and the array is returned.
+/// Without a comparator elements are compared as strings. Undefined elements
+/// are moved after all other elements, followed by any holes.
+/// <https://tc39.es/ecma262/#sec-array.prototype.sort>
+pub fn sort(this: &Value, args: &[Value], interpreter: &mut Interpreter) -> ResultValue {
+    let compare_fn = match args.first() {
+        Some(compare_fn) if compare_fn.is_function() => Some(compare_fn),
+        Some(compare_fn) if !compare_fn.is_undefined() => {
+            return Err(error::new_error(
+                "TypeError",
+                "The comparison function must be either a function or undefined",
+                interpreter,
+            ))
+        }
+        _ => None,
+    };
+
+    let length: i32 = from_value(this.get_field_slice("length")).unwrap();
+    let mut values: Vec<Value> = Vec::new();
+    let mut undefined_count = 0;
+    for n in 0..length {
+        let index = n.to_string();
+        if this.has_field(&index) {
+            let value = this.get_field(&index);
+            if value.is_undefined() {
+                undefined_count += 1;
+            } else {
+                values.push(value);
+            }
+        }
+    }
+
+    // A throwing comparator aborts the sort before the array is modified
+    let values = merge_sort(values, compare_fn, interpreter)?;
+
+    let sorted_count = values.len() as i32;
+    for (n, value) in values.into_iter().enumerate() {
+        this.set_field(n.to_string(), value);
+    }
+    for n in sorted_count..sorted_count + undefined_count {
+        this.set_field(n.to_string(), Gc::new(ValueData::Undefined));
+    }
+    for n in sorted_count + undefined_count..length {
+        this.remove_prop(&n.to_string());
+    }
+    Ok(this.clone())
+}
+
+/// Array.prototype.at ( index )
+///
+/// Returns the element at `index`, counting negative indices back from the end,
+/// or undefined if the index is out of range.
+/// <https://tc39.es/ecma262/#sec-array.prototype.at>
+pub fn at(this: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    let length: i32 = from_value(this.get_field_slice("length")).unwrap();
+    let relative = args.first().map_or(0.0, |arg| arg.to_num());
+    let relative = if relative.is_nan() {
+        0.0
+    } else {
+        relative.trunc()
+    };
+    let index = if relative < 0.0 {
+        f64::from(length) + relative
+    } else {
+        relative
+    };
+    if index < 0.0 || index >= f64::from(length) {
+        return Ok(Gc::new(ValueData::Undefined));
+    }
+    Ok(this.get_field(&(index as i32).to_string()))
+}
+
+/// Convert a relative index argument to an absolute index in `0..=length`,
+/// counting negative values back from `length` and using `default` when the
+/// argument is missing or undefined
+fn relative_index(arg: Option<&Value>, length: i32, default: i32) -> i32 {
+    match arg {
+        Some(value) if !value.is_undefined() => {
+            let relative = value.to_num();
+            let relative = if relative.is_nan() {
+                0.0
+            } else {
+                relative.trunc()
+            };
+            if relative < 0.0 {
+                (f64::from(length) + relative).max(0.0) as i32
+            } else {
+                relative.min(f64::from(length)) as i32
+            }
+        }
+        _ => default,
+    }
+}
+
+/// Array.prototype.fill ( value [ , start [ , end ] ] )
+///
+/// Sets every element from `start` up to but not including `end` to `value`,
+/// returning the array.
+/// <https://tc39.es/ecma262/#sec-array.prototype.fill>
+pub fn fill(this: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    let length: i32 = from_value(this.get_field_slice("length")).unwrap();
+    let value = args
+        .first()
+        .cloned()
+        .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+    let start = relative_index(args.get(1), length, 0);
+    let end = relative_index(args.get(2), length, length);
+    for n in start..end {
+        this.set_field(n.to_string(), value.clone());
+    }
+    Ok(this.clone())
+}
+
+/// Array.prototype.copyWithin ( target, start [ , end ] )
+///
+/// Copies the elements from `start` up to but not including `end` over the
+/// elements beginning at `target`, returning the array. When the ranges overlap
+/// with the target after the source, the copy runs from the last element back
+/// to the first, so every element is read before it is overwritten.
+/// <https://tc39.es/ecma262/#sec-array.prototype.copywithin>
+pub fn copy_within(this: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    let length: i32 = from_value(this.get_field_slice("length")).unwrap();
+    let target = relative_index(args.first(), length, 0);
+    let start = relative_index(args.get(1), length, 0);
+    let end = relative_index(args.get(2), length, length);
+    let count = (end - start).min(length - target);
+
+    let offsets: Box<dyn Iterator<Item = i32>> = if start < target && target < start + count {
+        Box::new((0..count).rev())
+    } else {
+        Box::new(0..count)
+    };
+    for offset in offsets {
+        let from = (start + offset).to_string();
+        let to = (target + offset).to_string();
+        // Holes in the source range are copied as holes
+        if this.has_field(&from) {
+            this.set_field(to, this.get_field(&from));
+        } else {
+            this.remove_prop(&to);
+        }
+    }
+    Ok(this.clone())
+}
+
+/// How many arrays deep `flat` and `flatMap` may go before giving up, so that flattening an
+/// array which contains itself throws instead of overflowing the native stack
+const MAX_FLATTEN_NESTING: usize = 1024;
+
+/// Abstract operation `FlattenIntoArray`
+///
+/// Appends the elements of `source` to `target`, first passing each through
+/// `mapper` if one is given, and flattening elements which are arrays while
+/// `remaining_depth` is greater than zero. An infinite depth flattens every level.
+/// Holes in `source` are skipped. `nesting` is how many arrays deep `source` is.
+/// <https://tc39.es/ecma262/#sec-flattenintoarray>
+fn flatten_into_array(
+    target: &Value,
+    source: &Value,
+    remaining_depth: f64,
+    nesting: usize,
+    mapper: Option<(&Value, &Value)>,
+    interpreter: &mut Interpreter,
+) -> Result<(), Value> {
+    if nesting > MAX_FLATTEN_NESTING {
+        return Err(error::new_error(
+            "RangeError",
+            "Maximum call stack size exceeded",
+            interpreter,
+        ));
+    }
+    let length: i32 = from_value(source.get_field_slice("length")).unwrap_or(0);
+    for n in 0..length {
+        let index = n.to_string();
+        if !source.has_field(&index) {
+            continue;
+        }
+        let mut element = source.get_field(&index);
+        if let Some((mapper, this_arg)) = mapper {
+            let arguments = vec![element, to_value(n), source.clone()];
+            element = interpreter.call(mapper, this_arg, arguments)?;
+        }
+        if remaining_depth > 0.0 && element.is_array() {
+            // Infinity stays infinite
+            flatten_into_array(
+                target,
+                &element,
+                remaining_depth - 1.0,
+                nesting + 1,
+                None,
+                interpreter,
+            )?;
+        } else {
+            add_to_array_object(target, &[element])?;
+        }
+    }
+    Ok(())
+}
+
+/// Array.prototype.flat ( [ depth ] )
+///
+/// Returns a new array with the elements of sub-arrays concatenated into it,
+/// up to `depth` levels deep (1 by default).
+/// <https://tc39.es/ecma262/#sec-array.prototype.flat>
+pub fn flat(this: &Value, args: &[Value], interpreter: &mut Interpreter) -> ResultValue {
+    let depth = match args.first() {
+        Some(depth) if !depth.is_undefined() => depth.to_num(),
+        _ => 1.0,
+    };
+    // ToIntegerOrInfinity, which leaves the infinities as they are
+    let depth = if depth.is_nan() { 0.0 } else { depth.trunc() };
+    let new_array = new_array(interpreter)?;
+    flatten_into_array(&new_array, this, depth, 0, None, interpreter)?;
+    Ok(new_array)
+}
+
+/// Array.prototype.flatMap ( mapperFunction [ , thisArg ] )
+///
+/// Maps each element using the mapper function, then flattens the result one
+/// level into a new array.
+/// <https://tc39.es/ecma262/#sec-array.prototype.flatmap>
+pub fn flat_map(this: &Value, args: &[Value], interpreter: &mut Interpreter) -> ResultValue {
+    let callback = get_callback(args, "flatMap", interpreter)?;
+    let this_arg = args
+        .get(1)
+        .cloned()
+        .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+    let new_array = new_array(interpreter)?;
+    flatten_into_array(
+        &new_array,
+        this,
+        1.0,
+        0,
+        Some((callback, &this_arg)),
+        interpreter,
+    )?;
+    Ok(new_array)
+}
+
 /// Create a new `Array` object
 pub fn create_constructor(global: &Value) -> Value {
     // Create Constructor
@@ -263,24 +1093,46 @@ pub fn create_constructor(global: &Value) -> Value {
     // Create prototype
     let array_prototype = ValueData::new_obj(Some(global));
 
-    let length = Property::default().get(to_value(get_array_length as NativeFunctionData));
+    let length = Property::default().get(make_builtin_fn(get_array_length, global));
 
     array_prototype.set_prop_slice("length", length);
-    let concat_func = to_value(concat as NativeFunctionData);
+    let concat_func = make_builtin_fn(concat, global);
     concat_func.set_field_slice("length", to_value(1_i32));
     array_prototype.set_field_slice("concat", concat_func);
-    let push_func = to_value(push as NativeFunctionData);
+    let push_func = make_builtin_fn(push, global);
     push_func.set_field_slice("length", to_value(1_i32));
 
     array_prototype.set_field_slice("push", push_func);
-    array_prototype.set_field_slice("pop", to_value(pop as NativeFunctionData));
-    array_prototype.set_field_slice("join", to_value(join as NativeFunctionData));
-    array_prototype.set_field_slice("reverse", to_value(reverse as NativeFunctionData));
-    array_prototype.set_field_slice("shift", to_value(shift as NativeFunctionData));
-    array_prototype.set_field_slice("unshift", to_value(unshift as NativeFunctionData));
+    array_prototype.set_field_slice("pop", make_builtin_fn(pop, global));
+    array_prototype.set_field_slice("join", make_builtin_fn(join, global));
+    array_prototype.set_field_slice("toString", make_builtin_fn(to_string, global));
+    array_prototype.set_field_slice("reverse", make_builtin_fn(reverse, global));
+    array_prototype.set_field_slice("shift", make_builtin_fn(shift, global));
+    array_prototype.set_field_slice("unshift", make_builtin_fn(unshift, global));
+    array_prototype.set_field_slice("indexOf", make_builtin_fn(index_of, global));
+    array_prototype.set_field_slice("lastIndexOf", make_builtin_fn(last_index_of, global));
+    array_prototype.set_field_slice("includes", make_builtin_fn(includes, global));
+    array_prototype.set_field_slice("at", make_builtin_fn(at, global));
+    array_prototype.set_field_slice("sort", make_builtin_fn(sort, global));
+    array_prototype.set_field_slice("find", make_builtin_fn(find, global));
+    array_prototype.set_field_slice("findIndex", make_builtin_fn(find_index, global));
+    array_prototype.set_field_slice("some", make_builtin_fn(some, global));
+    array_prototype.set_field_slice("every", make_builtin_fn(every, global));
+    array_prototype.set_field_slice("reduce", make_builtin_fn(reduce, global));
+    array_prototype.set_field_slice("reduceRight", make_builtin_fn(reduce_right, global));
+    array_prototype.set_field_slice("fill", make_builtin_fn(fill, global));
+    array_prototype.set_field_slice("copyWithin", make_builtin_fn(copy_within, global));
+    array_prototype.set_field_slice("flat", make_builtin_fn(flat, global));
+    array_prototype.set_field_slice("flatMap", make_builtin_fn(flat_map, global));
+    array_prototype.set_field_slice("keys", make_builtin_fn(keys, global));
+    array_prototype.set_field_slice("values", make_builtin_fn(values, global));
+    array_prototype.set_field_slice("entries", make_builtin_fn(entries, global));
 
     let array = to_value(array_constructor);
     array.set_field_slice(PROTOTYPE, to_value(array_prototype.clone()));
+    array.set_field_slice("isArray", make_builtin_fn(is_array, global));
+    array.set_field_slice("of", make_builtin_fn(of, global));
+    array.set_field_slice("from", make_builtin_fn(from, global));
 
     array_prototype.set_field_slice("constructor", array.clone());
     array
@@ -294,26 +1146,38 @@ mod tests {
 
     #[test]
     fn concat() {
-        //TODO: array display formatter
         let realm = Realm::create();
         let mut engine = Executor::new(realm);
         let init = r#"
-        let empty = new Array();
-        let one = new Array(1);
+        let empty = [];
+        let one = [1];
+        let many = [2, 3];
+        let sparse = new Array(2);
         "#;
         forward(&mut engine, init);
-        // Empty ++ Empty
-        let _ee = forward(&mut engine, "empty.concat(empty)");
-        //assert_eq!(ee, String::from(""));
-        // Empty ++ NonEmpty
-        let _en = forward(&mut engine, "empty.concat(one)");
-        //assert_eq!(en, String::from("a"));
-        // NonEmpty ++ Empty
-        let _ne = forward(&mut engine, "one.concat(empty)");
-        //assert_eq!(ne, String::from("a.b.c"));
-        // NonEmpty ++ NonEmpty
-        let _nn = forward(&mut engine, "one.concat(one)");
-        //assert_eq!(nn, String::from("a.b.c"));
+        // Arrays are flattened, scalars are appended
+        assert_eq!(
+            forward(&mut engine, "one.concat(many, 4).join(',')"),
+            "1,2,3,4"
+        );
+        assert_eq!(forward(&mut engine, "one.concat(many, 4).length"), "4");
+        // Empty receiver
+        assert_eq!(forward(&mut engine, "empty.concat(many).join(',')"), "2,3");
+        assert_eq!(forward(&mut engine, "empty.concat().length"), "0");
+        // Nested arrays are only flattened one level
+        assert_eq!(forward(&mut engine, "one.concat([[5]]).length"), "2");
+        // Holes are preserved
+        assert_eq!(forward(&mut engine, "one.concat(sparse).length"), "3");
+        assert_eq!(
+            forward(&mut engine, "one.concat(sparse).hasOwnProperty('1')"),
+            "false"
+        );
+        // The result is a new array
+        assert_eq!(forward(&mut engine, "one.concat(many).push(9)"), "4");
+        // The originals are untouched
+        assert_eq!(forward(&mut engine, "one.join(',')"), "1");
+        assert_eq!(forward(&mut engine, "many.join(',')"), "2,3");
+        assert_eq!(forward(&mut engine, "empty.length"), "0");
     }
 
     #[test]
@@ -335,5 +1199,757 @@ mod tests {
         // Many
         let many = forward(&mut engine, "many.join('.')");
         assert_eq!(many, String::from("a.b.c"));
+        // Separators
+        assert_eq!(forward(&mut engine, "[1, 2, 3].join(' - ')"), "1 - 2 - 3");
+        assert_eq!(forward(&mut engine, "many.join()"), "a,b,c");
+        assert_eq!(forward(&mut engine, "many.join(undefined)"), "a,b,c");
+        assert_eq!(forward(&mut engine, "many.join('')"), "abc");
+        // Missing elements
+        assert_eq!(forward(&mut engine, "[1, null, 3].join()"), "1,,3");
+        assert_eq!(forward(&mut engine, "[undefined, 2].join()"), ",2");
+        // Nested arrays
+        assert_eq!(
+            forward(&mut engine, "[1, [2, [3, 4]], 5].join(';')"),
+            "1;2,3,4;5"
+        );
+    }
+
+    #[test]
+    fn push_and_pop() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        forward(&mut engine, "let stack = [];");
+        assert_eq!(forward(&mut engine, "stack.push('a')"), "1");
+        assert_eq!(forward(&mut engine, "stack.push('b', 'c')"), "3");
+        assert_eq!(forward(&mut engine, "stack.length"), "3");
+        assert_eq!(forward(&mut engine, "stack.pop()"), "c");
+        assert_eq!(forward(&mut engine, "stack.length"), "2");
+        assert_eq!(forward(&mut engine, "stack.push(undefined)"), "3");
+        assert_eq!(forward(&mut engine, "stack.pop()"), "undefined");
+        assert_eq!(forward(&mut engine, "stack.pop()"), "b");
+        assert_eq!(forward(&mut engine, "stack.pop()"), "a");
+        assert_eq!(forward(&mut engine, "stack.length"), "0");
+        // Popping an empty array gives undefined rather than failing
+        assert_eq!(forward(&mut engine, "stack.pop()"), "undefined");
+        assert_eq!(forward(&mut engine, "stack.length"), "0");
+        assert_eq!(forward(&mut engine, "stack.push()"), "0");
+    }
+
+    #[test]
+    fn shift_and_unshift() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        forward(&mut engine, "let queue = [];");
+        assert_eq!(forward(&mut engine, "queue.unshift('c')"), "1");
+        assert_eq!(forward(&mut engine, "queue.unshift('a', 'b')"), "3");
+        assert_eq!(forward(&mut engine, "queue.join()"), "a,b,c");
+        assert_eq!(forward(&mut engine, "queue.push('d')"), "4");
+        assert_eq!(forward(&mut engine, "queue.shift()"), "a");
+        assert_eq!(forward(&mut engine, "queue.length"), "3");
+        assert_eq!(forward(&mut engine, "queue.join()"), "b,c,d");
+        assert_eq!(forward(&mut engine, "queue.shift()"), "b");
+        assert_eq!(forward(&mut engine, "queue.shift()"), "c");
+        assert_eq!(forward(&mut engine, "queue.shift()"), "d");
+        assert_eq!(forward(&mut engine, "queue.length"), "0");
+        assert_eq!(forward(&mut engine, "queue.shift()"), "undefined");
+        assert_eq!(forward(&mut engine, "queue.length"), "0");
+
+        // Elements which are undefined are moved like any other, and holes stay holes
+        forward(
+            &mut engine,
+            "let gaps = new Array(3); gaps[0] = undefined; gaps[2] = 'z';",
+        );
+        assert_eq!(forward(&mut engine, "gaps.unshift('y')"), "4");
+        assert_eq!(forward(&mut engine, "gaps.hasOwnProperty('1')"), "true");
+        assert_eq!(forward(&mut engine, "gaps.hasOwnProperty('2')"), "false");
+        assert_eq!(forward(&mut engine, "gaps[3]"), "z");
+        assert_eq!(forward(&mut engine, "gaps.shift()"), "y");
+        assert_eq!(forward(&mut engine, "gaps.hasOwnProperty('0')"), "true");
+        assert_eq!(forward(&mut engine, "gaps.hasOwnProperty('1')"), "false");
+        assert_eq!(forward(&mut engine, "gaps[2]"), "z");
+        assert_eq!(forward(&mut engine, "gaps.length"), "3");
+    }
+
+    #[test]
+    fn reverse() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        let empty = [];
+        let odd = [1, 2, 3];
+        let even = ['a', 'b', 'c', 'd'];
+        let sparse = new Array(5);
+        sparse[0] = 'first';
+        sparse[1] = 'second';
+        sparse[4] = 'last';
+        "#;
+        forward(&mut engine, init);
+        assert_eq!(forward(&mut engine, "empty.reverse().length"), "0");
+        assert_eq!(
+            forward(&mut engine, "[1, 2, 3].reverse().join(',')"),
+            "3,2,1"
+        );
+        // The array is reversed in place and returned
+        assert_eq!(forward(&mut engine, "odd.reverse() === odd"), "true");
+        assert_eq!(forward(&mut engine, "odd.join(',')"), "3,2,1");
+        forward(&mut engine, "even.reverse();");
+        assert_eq!(forward(&mut engine, "even.join(',')"), "d,c,b,a");
+        // Holes move to the mirrored position
+        forward(&mut engine, "sparse.reverse();");
+        assert_eq!(forward(&mut engine, "sparse.length"), "5");
+        assert_eq!(forward(&mut engine, "sparse[0]"), "last");
+        assert_eq!(forward(&mut engine, "sparse[3]"), "second");
+        assert_eq!(forward(&mut engine, "sparse[4]"), "first");
+        assert_eq!(forward(&mut engine, "sparse.hasOwnProperty('1')"), "false");
+        assert_eq!(forward(&mut engine, "sparse.hasOwnProperty('2')"), "false");
+    }
+
+    #[test]
+    fn to_string() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const arr = [1, 2];
+        const joinless = [1, 2];
+        joinless.join = undefined;
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "arr.toString()"), "1,2");
+        assert_eq!(forward(&mut engine, "[].toString()"), "");
+        assert_eq!(forward(&mut engine, "'[' + arr + ']'"), "[1,2]");
+        assert_eq!(forward(&mut engine, "String(arr)"), "1,2");
+        assert_eq!(
+            forward(&mut engine, "joinless.toString()"),
+            "[object Array]"
+        );
+    }
+
+    #[test]
+    fn reduce_and_reduce_right() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const letters = ['a', 'b', 'c'];
+        const indices = { text: '' };
+        const join = (acc, x, i) => {
+            indices.text = indices.text + i;
+            return acc + x;
+        };
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "letters.reduce(join)"), "abc");
+        assert_eq!(forward(&mut engine, "indices.text"), "12");
+        assert_eq!(forward(&mut engine, "letters.reduceRight(join)"), "cba");
+        assert_eq!(forward(&mut engine, "indices.text"), "1210");
+        assert_eq!(
+            forward(&mut engine, "letters.reduceRight(join, '>')"),
+            ">cba"
+        );
+        assert_eq!(forward(&mut engine, "indices.text"), "1210210");
+        assert_eq!(
+            forward(&mut engine, "[1, 2, 3].reduceRight((acc, x) => acc - x)"),
+            "0"
+        );
+        assert_eq!(forward(&mut engine, "[].reduceRight(join, 'init')"), "init");
+        assert_eq!(
+            forward(&mut engine, "[].reduceRight(join)"),
+            "Error: TypeError: Array.prototype.reduceRight: reduce of empty array with no initial value"
+        );
+    }
+
+    #[test]
+    fn iterators() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const arr = ['a', 'b', 'c'];
+        const seen = { text: '' };
+        const it = arr.values();
+        let step = it.next();
+        while (!step.done) {
+            seen.text = seen.text + step.value;
+            step = it.next();
+        }
+        const entries = Array.from(arr.entries());
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "seen.text"), "abc");
+        // A finished iterator stays finished
+        forward(&mut engine, "arr.push('d')");
+        assert_eq!(forward(&mut engine, "it.next().done"), "true");
+        assert_eq!(forward(&mut engine, "it.next().value"), "undefined");
+
+        assert_eq!(
+            forward(&mut engine, "Array.from(arr.keys()).join()"),
+            "0,1,2,3"
+        );
+        assert_eq!(forward(&mut engine, "entries.length"), "3");
+        assert_eq!(forward(&mut engine, "entries[1][0]"), "1");
+        assert_eq!(forward(&mut engine, "entries[1][1]"), "b");
+        assert_eq!(
+            forward(&mut engine, "arr.entries().next().value.join()"),
+            "0,a"
+        );
+        assert_eq!(forward(&mut engine, "arr.keys().next().done"), "false");
+    }
+
+    #[test]
+    fn index_of() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const nan = 0 / 0;
+        const obj = {};
+        const arr = [1, 2, nan, obj, 2];
+        "#;
+        forward(&mut engine, init);
+        // NaN is never strictly equal to itself
+        assert_eq!(forward(&mut engine, "arr.indexOf(nan)"), "-1");
+        // Missing element
+        assert_eq!(forward(&mut engine, "arr.indexOf(3)"), "-1");
+        assert_eq!(forward(&mut engine, "arr.indexOf('2')"), "-1");
+        // fromIndex
+        assert_eq!(forward(&mut engine, "arr.indexOf(2)"), "1");
+        assert_eq!(forward(&mut engine, "arr.indexOf(2, 2)"), "4");
+        assert_eq!(forward(&mut engine, "arr.indexOf(2, -1)"), "4");
+        assert_eq!(forward(&mut engine, "arr.indexOf(1, 10)"), "-1");
+        assert_eq!(forward(&mut engine, "arr.indexOf(1, -10)"), "0");
+        assert_eq!(forward(&mut engine, "arr.indexOf(NaN)"), "-1");
+        // Holes are not read as undefined
+        assert_eq!(
+            forward(&mut engine, "new Array(3).indexOf(undefined)"),
+            "-1"
+        );
+        assert_eq!(forward(&mut engine, "[].indexOf(undefined)"), "-1");
+        // Objects are compared by identity
+        assert_eq!(forward(&mut engine, "arr.indexOf(obj)"), "3");
+        assert_eq!(forward(&mut engine, "arr.indexOf({})"), "-1");
+    }
+
+    #[test]
+    fn last_index_of() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const nan = 0 / 0;
+        const obj = {};
+        const arr = [1, 2, nan, obj, 2];
+        "#;
+        forward(&mut engine, init);
+        assert_eq!(forward(&mut engine, "arr.lastIndexOf(nan)"), "-1");
+        assert_eq!(forward(&mut engine, "arr.lastIndexOf(2)"), "4");
+        assert_eq!(forward(&mut engine, "arr.lastIndexOf(2, 3)"), "1");
+        assert_eq!(forward(&mut engine, "arr.lastIndexOf(2, -2)"), "1");
+        assert_eq!(forward(&mut engine, "arr.lastIndexOf(1, -10)"), "-1");
+        // A fromIndex past the end searches the whole array
+        assert_eq!(forward(&mut engine, "arr.lastIndexOf(2, 10)"), "4");
+        assert_eq!(forward(&mut engine, "arr.lastIndexOf(1, -5)"), "0");
+        assert_eq!(forward(&mut engine, "arr.lastIndexOf(NaN)"), "-1");
+        assert_eq!(forward(&mut engine, "arr.lastIndexOf({})"), "-1");
+        assert_eq!(forward(&mut engine, "arr.lastIndexOf(obj)"), "3");
+    }
+
+    #[test]
+    fn includes() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const nan = 0 / 0;
+        const obj = {};
+        const arr = [1, 2, nan, obj];
+        const sparse = new Array(3);
+        "#;
+        forward(&mut engine, init);
+        // SameValueZero treats NaN as equal to itself
+        assert_eq!(forward(&mut engine, "arr.includes(nan)"), "true");
+        assert_eq!(forward(&mut engine, "arr.includes(obj)"), "true");
+        assert_eq!(forward(&mut engine, "arr.includes({})"), "false");
+        assert_eq!(forward(&mut engine, "arr.includes(1, 1)"), "false");
+        assert_eq!(forward(&mut engine, "arr.includes(nan, -2)"), "true");
+        // Holes are read as undefined
+        assert_eq!(forward(&mut engine, "sparse.includes(undefined)"), "true");
+        assert_eq!(forward(&mut engine, "sparse.indexOf(undefined)"), "-1");
+    }
+
+    #[test]
+    fn sort() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        let numbers = [10, 1, 2];
+        let sparse = [3, undefined, 1];
+        sparse[4] = 2;
+        sparse.length = 6;
+        "#;
+        forward(&mut engine, init);
+        // Elements are compared as strings by default
+        assert_eq!(
+            forward(&mut engine, "[10, 1, 2].sort().join(',')"),
+            "1,10,2"
+        );
+        assert_eq!(
+            forward(&mut engine, "['b', 'c', 'a'].sort().join(',')"),
+            "a,b,c"
+        );
+        // Comparator
+        assert_eq!(
+            forward(&mut engine, "[10, 1, 2].sort((a, b) => a - b).join(',')"),
+            "1,2,10"
+        );
+        assert_eq!(
+            forward(&mut engine, "[10, 1, 2].sort((a, b) => b - a).join(',')"),
+            "10,2,1"
+        );
+        // In place
+        forward(&mut engine, "numbers.sort((a, b) => a - b)");
+        assert_eq!(forward(&mut engine, "numbers.join(',')"), "1,2,10");
+        // Undefined and holes go last
+        forward(&mut engine, "sparse.sort()");
+        assert_eq!(forward(&mut engine, "sparse[2]"), "3");
+        assert_eq!(forward(&mut engine, "sparse[3]"), "undefined");
+        assert_eq!(forward(&mut engine, "sparse.hasOwnProperty('3')"), "true");
+        assert_eq!(forward(&mut engine, "sparse.hasOwnProperty('4')"), "false");
+        assert_eq!(forward(&mut engine, "sparse.length"), "6");
+    }
+
+    #[test]
+    fn sort_comparator_throws() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        let arr = [3, 1, 2];
+        "#;
+        forward(&mut engine, init);
+        let result = forward(&mut engine, "arr.sort(function (a, b) { throw 'boom'; })");
+        assert_eq!(result, "Error: boom");
+        assert_eq!(forward(&mut engine, "arr.join(',')"), "3,1,2");
+    }
+
+    #[test]
+    fn sort_inconsistent_comparator() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        let arr = [5, 3, 8, 1, 9, 2, 7, 4, 6, 10, 12, 11];
+        arr.sort(() => 1);
+        let shuffled = [5, 3, 8, 1, 9, 2, 7, 4, 6, 10, 12, 11];
+        shuffled.sort(() => Math.random() - 0.5);
+        "#;
+        forward(&mut engine, init);
+        // Comparators which are not a consistent order still leave every element in the array
+        assert_eq!(forward(&mut engine, "arr.length"), "12");
+        assert_eq!(
+            forward(&mut engine, "arr.sort((a, b) => a - b).join(',')"),
+            "1,2,3,4,5,6,7,8,9,10,11,12"
+        );
+        assert_eq!(
+            forward(&mut engine, "shuffled.sort((a, b) => a - b).join(',')"),
+            "1,2,3,4,5,6,7,8,9,10,11,12"
+        );
+    }
+
+    #[test]
+    fn sort_by_utf16_code_units() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        // U+FF61 sorts after the surrogate pair of U+1F600 in UTF-16, but before it in UTF-8
+        let init = r#"
+        let halfwidth = String.fromCharCode(0xFF61);
+        let emoji = String.fromCodePoint(0x1F600);
+        let sorted = [halfwidth, emoji].sort();
+        "#;
+        forward(&mut engine, init);
+        assert_eq!(forward(&mut engine, "sorted[0] === emoji"), "true");
+        assert_eq!(forward(&mut engine, "sorted[1] === halfwidth"), "true");
+    }
+
+    #[test]
+    fn is_array() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        assert_eq!(forward(&mut engine, "Array.isArray([])"), "true");
+        assert_eq!(forward(&mut engine, "Array.isArray(new Array(2))"), "true");
+        assert_eq!(forward(&mut engine, "Array.isArray([1].concat(2))"), "true");
+        assert_eq!(forward(&mut engine, "Array.isArray({})"), "false");
+        assert_eq!(
+            forward(&mut engine, "Array.isArray({ length: 0 })"),
+            "false"
+        );
+        assert_eq!(forward(&mut engine, "Array.isArray(Array.of(1))"), "true");
+        assert_eq!(
+            forward(&mut engine, "Array.isArray(Array.from('ab'))"),
+            "true"
+        );
+        assert_eq!(forward(&mut engine, "Array.isArray('')"), "false");
+        assert_eq!(forward(&mut engine, "Array.isArray()"), "false");
+        // Called without `new`, Array makes a new array
+        assert_eq!(forward(&mut engine, "Array.isArray(Array(1, 2))"), "true");
+        assert_eq!(forward(&mut engine, "Array(3).length"), "3");
+        assert_eq!(forward(&mut engine, "Array.isArray(globalThis)"), "false");
+    }
+
+    #[test]
+    fn find_and_find_index() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        let numbers = [1, 5, 8, 12];
+        "#;
+        forward(&mut engine, init);
+        assert_eq!(forward(&mut engine, "numbers.find(x => x > 6)"), "8");
+        assert_eq!(forward(&mut engine, "numbers.findIndex(x => x > 6)"), "2");
+        // Nothing found
+        assert_eq!(
+            forward(&mut engine, "numbers.find(x => x > 20)"),
+            "undefined"
+        );
+        assert_eq!(forward(&mut engine, "numbers.findIndex(x => x > 20)"), "-1");
+        assert_eq!(forward(&mut engine, "[].find(x => true)"), "undefined");
+        assert_eq!(forward(&mut engine, "[].findIndex(x => true)"), "-1");
+        // The callback gets the element, its index and the array
+        assert_eq!(
+            forward(&mut engine, "numbers.find((x, i, arr) => arr[i + 1] == 12)"),
+            "8"
+        );
+        assert_eq!(
+            forward(
+                &mut engine,
+                "numbers.findIndex((x, i, arr) => i == arr.length - 1)"
+            ),
+            "3"
+        );
+    }
+
+    #[test]
+    fn some_and_every() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        let numbers = [1, 2, 3, 4];
+        const calls = { count: 0 };
+        function isTwo(x) {
+            calls.count = calls.count + 1;
+            return x == 2;
+        }
+        function lessThanTwo(x) {
+            calls.count = calls.count + 1;
+            return x < 2;
+        }
+        "#;
+        forward(&mut engine, init);
+        assert_eq!(forward(&mut engine, "numbers.some(isTwo)"), "true");
+        // Iteration stops at the decisive element
+        assert_eq!(forward(&mut engine, "calls.count"), "2");
+        assert_eq!(forward(&mut engine, "numbers.every(lessThanTwo)"), "false");
+        assert_eq!(forward(&mut engine, "calls.count"), "4");
+        assert_eq!(forward(&mut engine, "numbers.every(x => x > 0)"), "true");
+        assert_eq!(forward(&mut engine, "numbers.some(x => x > 4)"), "false");
+        // Empty arrays
+        assert_eq!(forward(&mut engine, "[].some(x => true)"), "false");
+        assert_eq!(forward(&mut engine, "[].every(x => false)"), "true");
+    }
+
+    #[test]
+    fn some_and_every_skip_holes() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        let holes = new Array(3);
+        let sparse = new Array(3);
+        sparse[1] = 5;
+        const calls = { count: 0 };
+        function isFive(x) {
+            calls.count = calls.count + 1;
+            return x == 5;
+        }
+        "#;
+        forward(&mut engine, init);
+        assert_eq!(forward(&mut engine, "holes.every(x => false)"), "true");
+        assert_eq!(forward(&mut engine, "holes.some(x => true)"), "false");
+        assert_eq!(forward(&mut engine, "sparse.every(isFive)"), "true");
+        assert_eq!(forward(&mut engine, "sparse.some(isFive)"), "true");
+        // Only the element which exists is visited
+        assert_eq!(forward(&mut engine, "calls.count"), "2");
+    }
+
+    #[test]
+    fn predicate_callback_throws() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let result = forward(&mut engine, "[1].some(function (x) { throw 'boom'; })");
+        assert_eq!(result, "Error: boom");
+        let result = forward(&mut engine, "[1].every(function (x) { throw 'boom'; })");
+        assert_eq!(result, "Error: boom");
+        let result = forward(&mut engine, "[1].every(1)");
+        assert_eq!(
+            result,
+            "Error: TypeError: Array.prototype.every: callback is not a function"
+        );
+    }
+
+    #[test]
+    fn of() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        assert_eq!(forward(&mut engine, "Array.of(7).length"), "1");
+        assert_eq!(forward(&mut engine, "Array.of(7)[0]"), "7");
+        assert_eq!(forward(&mut engine, "Array.of(1, 2, 3).join(',')"), "1,2,3");
+        assert_eq!(forward(&mut engine, "Array.of().length"), "0");
+    }
+
+    #[test]
+    fn from() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        // Strings are split into characters
+        assert_eq!(forward(&mut engine, "Array.from('abc').join('-')"), "a-b-c");
+        // Arrays are copied
+        assert_eq!(
+            forward(&mut engine, "Array.from([1, 2, 3]).join(',')"),
+            "1,2,3"
+        );
+        // Array-likes use their length
+        assert_eq!(
+            forward(&mut engine, "Array.from({ length: 2 }).length"),
+            "2"
+        );
+        // Elements can be mapped
+        assert_eq!(
+            forward(&mut engine, "Array.from([1, 2, 3], x => x * 2).join(',')"),
+            "2,4,6"
+        );
+        assert_eq!(
+            forward(
+                &mut engine,
+                "Array.from({ length: 3 }, (x, i) => i).join(',')"
+            ),
+            "0,1,2"
+        );
+    }
+
+    #[test]
+    fn length_maintenance() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        var a = [];
+        a[2] = "x";
+        var b = [1, 2, 3];
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "a.length === 3"), "true");
+        assert_eq!(forward(&mut engine, "a[2]"), "x");
+        forward(&mut engine, "a.length = 1");
+        assert_eq!(forward(&mut engine, "a[2]"), "undefined");
+        assert_eq!(forward(&mut engine, "a.length"), "1");
+        forward(&mut engine, "b[1] = 5");
+        assert_eq!(forward(&mut engine, "b.length"), "3");
+        forward(&mut engine, "b.length = 5");
+        assert_eq!(forward(&mut engine, "b.length"), "5");
+        assert_eq!(forward(&mut engine, "b[1]"), "5");
+        assert_eq!(
+            forward(&mut engine, "b.length = -1"),
+            "Error: RangeError: Invalid array length"
+        );
+        assert_eq!(
+            forward(&mut engine, "b.length = 1.5"),
+            "Error: RangeError: Invalid array length"
+        );
+        assert_eq!(forward(&mut engine, "b.length"), "5");
+
+        // Compound assignment to an index keeps the length too
+        let init = r#"
+        var c = [1, 2, 3];
+        c[5] += 1;
+        c[1] += 10;
+        var counts = { apples: 3 };
+        var key = 'apples';
+        counts[key] -= 1;
+        "#;
+        forward(&mut engine, init);
+        assert_eq!(forward(&mut engine, "c.length"), "6");
+        assert_eq!(forward(&mut engine, "c[1]"), "12");
+        assert_eq!(forward(&mut engine, "c[5]"), "NaN");
+        assert_eq!(forward(&mut engine, "counts.apples"), "2");
+        assert_eq!(forward(&mut engine, "counts[key] -= 1"), "1");
+    }
+
+    #[test]
+    fn flat() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const nested = [1, [2, [3]]];
+        const holes = new Array(2);
+        holes[1] = [4];
+        const cyclic = [1];
+        cyclic[1] = cyclic;
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "nested.flat().length === 3"), "true");
+        assert_eq!(forward(&mut engine, "nested.flat(2).join()"), "1,2,3");
+        assert_eq!(forward(&mut engine, "nested.flat(0).length"), "2");
+        assert_eq!(forward(&mut engine, "holes.flat().join()"), "4");
+        assert_eq!(forward(&mut engine, "cyclic.flat(1000).length"), "1002");
+    }
+
+    #[test]
+    fn flat_to_any_depth() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const deep = [[[[1]]]];
+        let deeper = [2];
+        let level = 0;
+        while (level < 500) {
+            deeper = [deeper];
+            level++;
+        }
+        const cyclic = [1];
+        cyclic[1] = cyclic;
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "deep.flat(Infinity).length"), "1");
+        assert_eq!(forward(&mut engine, "deep.flat(Infinity)[0]"), "1");
+        assert_eq!(forward(&mut engine, "deeper.flat(Infinity).join()"), "2");
+        assert_eq!(forward(&mut engine, "deeper.flat(1e9).join()"), "2");
+        assert_eq!(forward(&mut engine, "deeper.flat(499).length"), "1");
+        assert_eq!(
+            forward(&mut engine, "Array.isArray(deeper.flat(499)[0])"),
+            "true"
+        );
+        // A depth of zero or less makes a shallow copy, which is a new array
+        assert_eq!(forward(&mut engine, "deep.flat(0).length"), "1");
+        assert_eq!(forward(&mut engine, "deep.flat(0) !== deep"), "true");
+        assert_eq!(forward(&mut engine, "deep.flat(0)[0] === deep[0]"), "true");
+        assert_eq!(
+            forward(&mut engine, "deep.flat(-Infinity)[0] === deep[0]"),
+            "true"
+        );
+        assert_eq!(
+            forward(&mut engine, "deep.flat(0.9)[0] === deep[0]"),
+            "true"
+        );
+        assert_eq!(
+            forward(&mut engine, "cyclic.flat(Infinity)"),
+            "Error: RangeError: Maximum call stack size exceeded"
+        );
+    }
+
+    #[test]
+    fn flat_map() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        forward(&mut engine, "const arr = [1, 2];");
+
+        assert_eq!(
+            forward(&mut engine, "arr.flatMap(x => [x, x * 10]).join()"),
+            "1,10,2,20"
+        );
+        assert_eq!(forward(&mut engine, "arr.flatMap(x => [[x]]).length"), "2");
+        assert_eq!(
+            forward(&mut engine, "arr.flatMap(1)"),
+            "Error: TypeError: Array.prototype.flatMap: callback is not a function"
+        );
+    }
+
+    #[test]
+    fn fill() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const arr = [1, 2, 3, 4];
+        const filled = arr.fill(0, 1, 3);
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(
+            forward(&mut engine, "filled.join(',') === '1,0,0,4'"),
+            "true"
+        );
+        assert_eq!(forward(&mut engine, "arr.join()"), "1,0,0,4");
+        assert_eq!(forward(&mut engine, "[1, 2, 3].fill(7).join()"), "7,7,7");
+        assert_eq!(
+            forward(&mut engine, "[1, 2, 3].fill(7, -1).join()"),
+            "1,2,7"
+        );
+        assert_eq!(
+            forward(&mut engine, "[1, 2, 3].fill(7, 1, -5).join()"),
+            "1,2,3"
+        );
+        assert_eq!(forward(&mut engine, "[1, 2, 3].fill(7, 5).join()"), "1,2,3");
+        assert_eq!(forward(&mut engine, "[1, 2, 3].fill(7, 0, 10).length"), "3");
+    }
+
+    #[test]
+    fn copy_within() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const arr = [1, 2, 3, 4, 5];
+        const copied = arr.copyWithin(0, 3);
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(
+            forward(&mut engine, "copied.join(',') === '4,5,3,4,5'"),
+            "true"
+        );
+        assert_eq!(forward(&mut engine, "arr.join()"), "4,5,3,4,5");
+        assert_eq!(
+            forward(&mut engine, "[1, 2, 3, 4, 5].copyWithin(1, 0).join()"),
+            "1,1,2,3,4"
+        );
+        assert_eq!(
+            forward(&mut engine, "[1, 2, 3, 4, 5].copyWithin(-2, 0, 1).join()"),
+            "1,2,3,1,5"
+        );
+        assert_eq!(
+            forward(&mut engine, "[1, 2, 3, 4, 5].copyWithin(0, 3, 4).join()"),
+            "4,2,3,4,5"
+        );
+        // Overlapping ranges in both directions
+        assert_eq!(
+            forward(&mut engine, "[1, 2, 3, 4, 5].copyWithin(2, 0).join()"),
+            "1,2,1,2,3"
+        );
+        assert_eq!(
+            forward(&mut engine, "[1, 2, 3, 4, 5].copyWithin(0, 1).join()"),
+            "2,3,4,5,5"
+        );
+        // Out of range arguments are clamped and the length never changes
+        assert_eq!(
+            forward(&mut engine, "[1, 2, 3].copyWithin(1, -10, 10).join()"),
+            "1,1,2"
+        );
+        assert_eq!(
+            forward(&mut engine, "[1, 2, 3].copyWithin(5, 0).length"),
+            "3"
+        );
+    }
+
+    #[test]
+    fn at() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const arr = [1, 2, 3];
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "arr.at(-1) === 3"), "true");
+        assert_eq!(forward(&mut engine, "arr.at(0)"), "1");
+        assert_eq!(forward(&mut engine, "arr.at()"), "1");
+        assert_eq!(forward(&mut engine, "arr.at(1.7)"), "2");
+        assert_eq!(forward(&mut engine, "arr.at(-1.7)"), "3");
+        assert_eq!(forward(&mut engine, "arr.at(3)"), "undefined");
+        assert_eq!(forward(&mut engine, "arr.at(-4)"), "undefined");
+        assert_eq!(forward(&mut engine, "[].at(0) === undefined"), "true");
     }
 }
@@ -1,20 +1,20 @@
 use crate::{
     exec::Interpreter,
     js::{
-        function::NativeFunctionData,
+        function::make_builtin_fn,
         object::{Object, ObjectKind, PROTOTYPE},
         value::{to_value, ResultValue, Value, ValueData},
     },
 };
-use std::{borrow::Borrow, ops::Deref};
+use std::ops::Deref;
 
 /// Create a new boolean object - [[Construct]]
 pub fn construct_boolean(this: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
     this.set_kind(ObjectKind::Boolean);
 
     // Get the argument, if any
-    match args.get(0) {
-        Some(ref value) => {
+    match args.first() {
+        Some(value) => {
             this.set_internal_slot("BooleanData", to_boolean(value));
         }
         None => {
@@ -29,8 +29,8 @@ pub fn construct_boolean(this: &Value, args: &[Value], _: &mut Interpreter) -> R
 /// Return a boolean literal [[Call]]
 pub fn call_boolean(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
     // Get the argument, if any
-    match args.get(0) {
-        Some(ref value) => Ok(to_boolean(value)),
+    match args.first() {
+        Some(value) => Ok(to_boolean(value)),
         None => Ok(to_boolean(&to_value(false))),
     }
 }
@@ -56,8 +56,8 @@ pub fn create_constructor(global: &Value) -> Value {
     // https://tc39.es/ecma262/#sec-properties-of-the-boolean-prototype-object
     let boolean_prototype = ValueData::new_obj(Some(global));
     boolean_prototype.set_internal_slot("BooleanData", to_boolean(&to_value(false)));
-    boolean_prototype.set_field_slice("toString", to_value(to_string as NativeFunctionData));
-    boolean_prototype.set_field_slice("valueOf", to_value(value_of as NativeFunctionData));
+    boolean_prototype.set_field_slice("toString", make_builtin_fn(to_string, global));
+    boolean_prototype.set_field_slice("valueOf", make_builtin_fn(value_of, global));
 
     let boolean_value = to_value(boolean);
     boolean_prototype.set_field_slice("constructor", to_value(boolean_value.clone()));
@@ -69,7 +69,7 @@ pub fn create_constructor(global: &Value) -> Value {
 /// [toBoolean](https://tc39.github.io/ecma262/#sec-toboolean)
 /// Creates a new boolean value from the input
 pub fn to_boolean(value: &Value) -> Value {
-    match *value.deref().borrow() {
+    match *value.deref() {
         ValueData::Object(_) => to_value(true),
         ValueData::String(ref s) if !s.is_empty() => to_value(true),
         ValueData::Number(n) if n != 0.0 && !n.is_nan() => to_value(true),
@@ -80,9 +80,9 @@ pub fn to_boolean(value: &Value) -> Value {
 }
 
 pub fn this_boolean_value(value: &Value) -> Value {
-    match *value.deref().borrow() {
+    match *value.deref() {
         ValueData::Boolean(v) => to_value(v),
-        ValueData::Object(ref v) => (v).deref().borrow().get_internal_slot("BooleanData"),
+        ValueData::Object(ref v) => (v).borrow().get_internal_slot("BooleanData"),
         _ => to_value(false),
     }
 }
@@ -98,7 +98,7 @@ mod tests {
     fn check_boolean_constructor_is_function() {
         let global = ValueData::new_obj(None);
         let boolean_constructor = create_constructor(&global);
-        assert_eq!(boolean_constructor.is_function(), true);
+        assert!(boolean_constructor.is_function());
     }
 
     #[test]
@@ -114,8 +114,8 @@ mod tests {
         let one = forward_val(&mut engine, "one").unwrap();
         let zero = forward_val(&mut engine, "zero").unwrap();
 
-        assert_eq!(one.is_object(), true);
-        assert_eq!(zero.is_boolean(), true);
+        assert!(one.is_object());
+        assert!(zero.is_boolean());
     }
 
     #[test]
@@ -136,16 +136,16 @@ mod tests {
         let true_bool = forward_val(&mut engine, "trueBool").expect("value expected");
 
         // Values should all be objects
-        assert_eq!(true_val.is_object(), true);
-        assert_eq!(true_num.is_object(), true);
-        assert_eq!(true_string.is_object(), true);
-        assert_eq!(true_bool.is_object(), true);
+        assert!(true_val.is_object());
+        assert!(true_num.is_object());
+        assert!(true_string.is_object());
+        assert!(true_bool.is_object());
 
         // Values should all be truthy
-        assert_eq!(true_val.is_true(), true);
-        assert_eq!(true_num.is_true(), true);
-        assert_eq!(true_string.is_true(), true);
-        assert_eq!(true_bool.is_true(), true);
+        assert!(true_val.is_true());
+        assert!(true_num.is_true());
+        assert!(true_string.is_true());
+        assert!(true_bool.is_true());
     }
 
     #[test]
@@ -1,7 +1,7 @@
 use crate::exec::Interpreter;
-use crate::js::function::NativeFunctionData;
+use crate::js::function::make_builtin_fn;
 use crate::js::object::{ObjectKind, INSTANCE_PROTOTYPE};
-use crate::js::value::{from_value, to_value, ResultValue, Value, ValueData};
+use crate::js::value::{from_value, ResultValue, Value, ValueData};
 use gc::Gc;
 use std::fmt::Write;
 use std::iter::FromIterator;
@@ -79,7 +79,7 @@ fn log_string_from(x: Value) -> String {
                                 s,
                                 "{}: {}",
                                 key,
-                                log_string_from(val.value.clone().unwrap().clone())
+                                log_string_from(val.value.clone().unwrap_or_default())
                             )
                             .unwrap();
                             if key != last_key {
@@ -121,9 +121,9 @@ pub fn error(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
 /// Create a new `console` object
 pub fn _create(global: &Value) -> Value {
     let console = ValueData::new_obj(Some(global));
-    console.set_field_slice("log", to_value(log as NativeFunctionData));
-    console.set_field_slice("error", to_value(error as NativeFunctionData));
-    console.set_field_slice("exception", to_value(error as NativeFunctionData));
+    console.set_field_slice("log", make_builtin_fn(log, global));
+    console.set_field_slice("error", make_builtin_fn(error, global));
+    console.set_field_slice("exception", make_builtin_fn(error, global));
     console
 }
 /// Initialise the global object with the `console` object
@@ -0,0 +1,577 @@
+use crate::{
+    exec::Interpreter,
+    js::{
+        error,
+        function::make_builtin_fn,
+        object::{Object, ObjectKind, PROTOTYPE},
+        value::{to_value, ResultValue, Value, ValueData},
+    },
+};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The number of milliseconds in a day
+const MS_PER_DAY: f64 = 86_400_000.0;
+/// The number of milliseconds in an hour
+const MS_PER_HOUR: f64 = 3_600_000.0;
+/// The number of milliseconds in a minute
+const MS_PER_MINUTE: f64 = 60_000.0;
+/// The number of milliseconds in a second
+const MS_PER_SECOND: f64 = 1_000.0;
+/// The largest distance from the epoch a time value can have
+/// <https://tc39.es/ecma262/#sec-time-values-and-time-range>
+const MAX_TIME: f64 = 8.64e15;
+
+const WEEK_DAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// The clock used by `Date` unless the interpreter is given another one, returning
+/// the milliseconds elapsed since the Unix epoch
+pub fn system_clock() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as f64)
+        .unwrap_or(0.0)
+}
+
+/// The calendar fields of a time value, all in UTC
+#[derive(Debug, Clone, Copy)]
+struct DateFields {
+    year: f64,
+    /// Zero based, so January is 0
+    month: f64,
+    /// One based day of the month
+    date: f64,
+    /// Zero based day of the week starting on Sunday
+    week_day: f64,
+    hours: f64,
+    minutes: f64,
+    seconds: f64,
+    milliseconds: f64,
+}
+
+/// The number of days from the epoch to the given day of the proleptic Gregorian calendar,
+/// where `month` is one based
+/// <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+fn days_from_civil(year: f64, month: f64, day: f64) -> f64 {
+    let year = if month <= 2.0 { year - 1.0 } else { year };
+    let era = (year / 400.0).floor();
+    let year_of_era = year - era * 400.0;
+    let shifted_month = if month > 2.0 {
+        month - 3.0
+    } else {
+        month + 9.0
+    };
+    let day_of_year = ((153.0 * shifted_month + 2.0) / 5.0).floor() + day - 1.0;
+    let day_of_era = year_of_era * 365.0 + (year_of_era / 4.0).floor()
+        - (year_of_era / 100.0).floor()
+        + day_of_year;
+    era * 146_097.0 + day_of_era - 719_468.0
+}
+
+/// The year, one based month and day of the day `days` after the epoch
+/// <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+fn civil_from_days(days: f64) -> (f64, f64, f64) {
+    let days = days + 719_468.0;
+    let era = (days / 146_097.0).floor();
+    let day_of_era = days - era * 146_097.0;
+    let year_of_era = ((day_of_era - (day_of_era / 1460.0).floor()
+        + (day_of_era / 36_524.0).floor()
+        - (day_of_era / 146_096.0).floor())
+        / 365.0)
+        .floor();
+    let day_of_year = day_of_era
+        - (365.0 * year_of_era + (year_of_era / 4.0).floor() - (year_of_era / 100.0).floor());
+    let shifted_month = ((5.0 * day_of_year + 2.0) / 153.0).floor();
+    let day = day_of_year - ((153.0 * shifted_month + 2.0) / 5.0).floor() + 1.0;
+    let month = if shifted_month < 10.0 {
+        shifted_month + 3.0
+    } else {
+        shifted_month - 9.0
+    };
+    let year = year_of_era + era * 400.0;
+    (if month <= 2.0 { year + 1.0 } else { year }, month, day)
+}
+
+/// Split a valid time value into its calendar fields
+fn date_fields(time: f64) -> DateFields {
+    let days = (time / MS_PER_DAY).floor();
+    let time_in_day = time - days * MS_PER_DAY;
+    let (year, month, date) = civil_from_days(days);
+    DateFields {
+        year,
+        month: month - 1.0,
+        date,
+        week_day: (days + 4.0).rem_euclid(7.0),
+        hours: (time_in_day / MS_PER_HOUR).floor(),
+        minutes: (time_in_day / MS_PER_MINUTE).floor() % 60.0,
+        seconds: (time_in_day / MS_PER_SECOND).floor() % 60.0,
+        milliseconds: time_in_day % MS_PER_SECOND,
+    }
+}
+
+/// Abstract operation `MakeDate`, combining calendar fields into a time value.
+/// Out of range months, days and times carry over into the larger fields.
+/// <https://tc39.es/ecma262/#sec-makedate>
+fn make_date(fields: &[f64]) -> f64 {
+    if fields.iter().any(|field| !field.is_finite()) {
+        return f64::NAN;
+    }
+    let field = |index: usize, default: f64| fields.get(index).map_or(default, |f| f.trunc());
+    let month = field(1, 0.0);
+    let year = field(0, f64::NAN) + (month / 12.0).floor();
+    let day = days_from_civil(year, month.rem_euclid(12.0) + 1.0, 1.0) + field(2, 1.0) - 1.0;
+    let time = field(3, 0.0) * MS_PER_HOUR
+        + field(4, 0.0) * MS_PER_MINUTE
+        + field(5, 0.0) * MS_PER_SECOND
+        + field(6, 0.0);
+    day * MS_PER_DAY + time
+}
+
+/// Abstract operation `TimeClip`, turning times outside of the valid range into NaN
+/// <https://tc39.es/ecma262/#sec-timeclip>
+fn time_clip(time: f64) -> f64 {
+    if !time.is_finite() || time.abs() > MAX_TIME {
+        f64::NAN
+    } else {
+        // Adding zero turns -0 into +0
+        time.trunc() + 0.0
+    }
+}
+
+/// Parse the date time string format, `YYYY-MM-DDTHH:mm:ss.sssZ`, where every part after the
+/// year is optional. Times without an offset are treated as UTC, since there is no support for
+/// time zones.
+/// <https://tc39.es/ecma262/#sec-date-time-string-format>
+fn parse_date(input: &str) -> f64 {
+    let input = input.trim();
+    let input = input.strip_suffix('Z').unwrap_or(input);
+    let (date, time) = match input.find('T') {
+        Some(index) => (&input[..index], Some(&input[index + 1..])),
+        None => (input, None),
+    };
+
+    let mut fields = Vec::new();
+    for (index, part) in date.split('-').enumerate() {
+        match (index, part.parse::<f64>()) {
+            (0, Ok(year)) if part.len() == 4 => fields.push(year),
+            (1, Ok(month)) if part.len() == 2 && (1.0..=12.0).contains(&month) => {
+                fields.push(month - 1.0)
+            }
+            (2, Ok(day)) if part.len() == 2 && (1.0..=31.0).contains(&day) => fields.push(day),
+            _ => return f64::NAN,
+        }
+    }
+    // A missing month is January and a missing day is the first
+    if fields.len() < 2 {
+        fields.push(0.0);
+    }
+    fields.resize(3, 1.0);
+
+    if let Some(time) = time {
+        let (time, milliseconds) = match time.find('.') {
+            Some(index) => (&time[..index], Some(&time[index + 1..])),
+            None => (time, None),
+        };
+        let parts: Vec<&str> = time.split(':').collect();
+        if parts.len() < 2 || parts.len() > 3 {
+            return f64::NAN;
+        }
+        for (index, part) in parts.iter().enumerate() {
+            let limit = if index == 0 { 24.0 } else { 59.0 };
+            match part.parse::<f64>() {
+                Ok(value) if part.len() == 2 && value <= limit => fields.push(value),
+                _ => return f64::NAN,
+            }
+        }
+        fields.resize(6, 0.0);
+        if let Some(milliseconds) = milliseconds {
+            match milliseconds.parse::<f64>() {
+                Ok(value) if parts.len() == 3 && milliseconds.len() == 3 => fields.push(value),
+                _ => return f64::NAN,
+            }
+        }
+    }
+    time_clip(make_date(&fields))
+}
+
+/// The time value of the Date object `this`, or a TypeError for other values
+/// <https://tc39.es/ecma262/#sec-thistimevalue>
+fn this_time_value(this: &Value, method: &str, ctx: &Interpreter) -> Result<f64, Value> {
+    let time = this.get_internal_slot("DateValue");
+    if time.is_undefined() {
+        Err(error::new_error(
+            "TypeError",
+            &format!("Date.prototype.{} called on incompatible receiver", method),
+            ctx,
+        ))
+    } else {
+        Ok(time.to_num())
+    }
+}
+
+/// Create a new `Date` object - [[Construct]]
+/// <https://tc39.es/ecma262/#sec-date-constructor>
+pub fn make_date_object(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let time = match args.len() {
+        0 => ctx.now(),
+        1 => match *args[0] {
+            ValueData::String(ref string) => parse_date(string),
+            _ => {
+                let primitive = ctx.to_primitive(&args[0], None)?;
+                match *primitive {
+                    ValueData::String(ref string) => parse_date(string),
+                    _ => time_clip(primitive.to_num()),
+                }
+            }
+        },
+        _ => {
+            let mut fields: Vec<f64> = args.iter().take(7).map(|arg| arg.to_num()).collect();
+            // Two digit years are in the 1900s
+            if fields[0] >= 0.0 && fields[0] <= 99.0 {
+                fields[0] = 1900.0 + fields[0].trunc();
+            }
+            time_clip(make_date(&fields))
+        }
+    };
+    this.set_kind(ObjectKind::Date);
+    this.set_internal_slot("DateValue", to_value(time));
+    Ok(this.clone())
+}
+
+/// Return the current date as a string - [[Call]]
+pub fn call_date(_: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    Ok(to_value(date_to_string(ctx.now())))
+}
+
+/// Date.now ( )
+///
+/// Returns the current time value, taken from the interpreter's clock.
+/// <https://tc39.es/ecma262/#sec-date.now>
+pub fn now(_: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    Ok(to_value(ctx.now()))
+}
+
+/// Date.parse ( string )
+///
+/// Returns the time value of a date time string, or NaN if it cannot be parsed.
+/// <https://tc39.es/ecma262/#sec-date.parse>
+pub fn parse(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let input = match args.first() {
+        Some(arg) => ctx.value_to_rust_string(arg)?,
+        None => String::from("undefined"),
+    };
+    Ok(to_value(parse_date(&input)))
+}
+
+/// Date.prototype.getTime ( )
+///
+/// Returns the milliseconds since the epoch of this date.
+/// <https://tc39.es/ecma262/#sec-date.prototype.gettime>
+pub fn get_time(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    Ok(to_value(this_time_value(this, "getTime", ctx)?))
+}
+
+/// Read a single calendar field of the Date object `this`, which is NaN for invalid dates
+fn get_field(
+    this: &Value,
+    method: &str,
+    field: fn(&DateFields) -> f64,
+    ctx: &Interpreter,
+) -> ResultValue {
+    let time = this_time_value(this, method, ctx)?;
+    if time.is_nan() {
+        Ok(to_value(f64::NAN))
+    } else {
+        Ok(to_value(field(&date_fields(time))))
+    }
+}
+
+/// Date.prototype.getFullYear ( )
+/// <https://tc39.es/ecma262/#sec-date.prototype.getfullyear>
+pub fn get_full_year(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    get_field(this, "getFullYear", |fields| fields.year, ctx)
+}
+
+/// Date.prototype.getMonth ( )
+/// <https://tc39.es/ecma262/#sec-date.prototype.getmonth>
+pub fn get_month(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    get_field(this, "getMonth", |fields| fields.month, ctx)
+}
+
+/// Date.prototype.getDate ( )
+/// <https://tc39.es/ecma262/#sec-date.prototype.getdate>
+pub fn get_date(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    get_field(this, "getDate", |fields| fields.date, ctx)
+}
+
+/// Date.prototype.getDay ( )
+/// <https://tc39.es/ecma262/#sec-date.prototype.getday>
+pub fn get_day(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    get_field(this, "getDay", |fields| fields.week_day, ctx)
+}
+
+/// Date.prototype.getHours ( )
+/// <https://tc39.es/ecma262/#sec-date.prototype.gethours>
+pub fn get_hours(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    get_field(this, "getHours", |fields| fields.hours, ctx)
+}
+
+/// Date.prototype.getMinutes ( )
+/// <https://tc39.es/ecma262/#sec-date.prototype.getminutes>
+pub fn get_minutes(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    get_field(this, "getMinutes", |fields| fields.minutes, ctx)
+}
+
+/// Date.prototype.getSeconds ( )
+/// <https://tc39.es/ecma262/#sec-date.prototype.getseconds>
+pub fn get_seconds(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    get_field(this, "getSeconds", |fields| fields.seconds, ctx)
+}
+
+/// Date.prototype.getMilliseconds ( )
+/// <https://tc39.es/ecma262/#sec-date.prototype.getmilliseconds>
+pub fn get_milliseconds(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    get_field(this, "getMilliseconds", |fields| fields.milliseconds, ctx)
+}
+
+/// Date.prototype.toISOString ( )
+///
+/// Returns the date in the date time string format, always in UTC. Throws a
+/// RangeError for invalid dates.
+/// <https://tc39.es/ecma262/#sec-date.prototype.toisostring>
+pub fn to_iso_string(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let time = this_time_value(this, "toISOString", ctx)?;
+    if time.is_nan() {
+        return Err(error::new_error("RangeError", "Invalid time value", ctx));
+    }
+    let fields = date_fields(time);
+    // Years outside of 0 to 9999 use the expanded six digit format with a sign
+    let year = if fields.year >= 0.0 && fields.year <= 9999.0 {
+        format!("{:04}", fields.year)
+    } else if fields.year < 0.0 {
+        format!("-{:06}", -fields.year)
+    } else {
+        format!("+{:06}", fields.year)
+    };
+    Ok(to_value(format!(
+        "{}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        fields.month + 1.0,
+        fields.date,
+        fields.hours,
+        fields.minutes,
+        fields.seconds,
+        fields.milliseconds
+    )))
+}
+
+/// Format a time value like `Thu Jan 01 1970 00:00:00 GMT+0000`
+fn date_to_string(time: f64) -> String {
+    if time.is_nan() {
+        return String::from("Invalid Date");
+    }
+    let fields = date_fields(time);
+    format!(
+        "{} {} {:02} {:04} {:02}:{:02}:{:02} GMT+0000 (Coordinated Universal Time)",
+        WEEK_DAYS[fields.week_day as usize],
+        MONTHS[fields.month as usize],
+        fields.date,
+        fields.year,
+        fields.hours,
+        fields.minutes,
+        fields.seconds
+    )
+}
+
+/// Date.prototype.toString ( )
+/// <https://tc39.es/ecma262/#sec-date.prototype.tostring>
+pub fn to_string(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    Ok(to_value(date_to_string(this_time_value(
+        this, "toString", ctx,
+    )?)))
+}
+
+/// Date.prototype.valueOf ( )
+/// <https://tc39.es/ecma262/#sec-date.prototype.valueof>
+pub fn value_of(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    Ok(to_value(this_time_value(this, "valueOf", ctx)?))
+}
+
+/// Create a new `Date` object
+pub fn create_constructor(global: &Value) -> Value {
+    let mut date_constructor = Object::default();
+    date_constructor.kind = ObjectKind::Function;
+    date_constructor.set_internal_method("construct", make_date_object);
+    date_constructor.set_internal_method("call", call_date);
+
+    // Create prototype
+    let date_prototype = ValueData::new_obj(Some(global));
+    date_prototype.set_field_slice("getTime", make_builtin_fn(get_time, global));
+    date_prototype.set_field_slice("getFullYear", make_builtin_fn(get_full_year, global));
+    date_prototype.set_field_slice("getMonth", make_builtin_fn(get_month, global));
+    date_prototype.set_field_slice("getDate", make_builtin_fn(get_date, global));
+    date_prototype.set_field_slice("getDay", make_builtin_fn(get_day, global));
+    date_prototype.set_field_slice("getHours", make_builtin_fn(get_hours, global));
+    date_prototype.set_field_slice("getMinutes", make_builtin_fn(get_minutes, global));
+    date_prototype.set_field_slice("getSeconds", make_builtin_fn(get_seconds, global));
+    date_prototype.set_field_slice("getMilliseconds", make_builtin_fn(get_milliseconds, global));
+    date_prototype.set_field_slice("toISOString", make_builtin_fn(to_iso_string, global));
+    date_prototype.set_field_slice("toString", make_builtin_fn(to_string, global));
+    date_prototype.set_field_slice("valueOf", make_builtin_fn(value_of, global));
+
+    let date = to_value(date_constructor);
+    date_prototype.set_field_slice("constructor", date.clone());
+    date.set_field_slice(PROTOTYPE, date_prototype);
+    date.set_field_slice("now", make_builtin_fn(now, global));
+    date.set_field_slice("parse", make_builtin_fn(parse, global));
+    date
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exec::Executor;
+    use crate::forward;
+    use crate::realm::Realm;
+
+    fn fixed_clock() -> f64 {
+        1_000_000_000_000.0
+    }
+
+    #[test]
+    fn check_date_constructor_is_function() {
+        let global = ValueData::new_obj(None);
+        let date_constructor = create_constructor(&global);
+        assert!(date_constructor.is_function());
+    }
+
+    #[test]
+    fn from_milliseconds() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const epoch = new Date(0);
+        const date = new Date(1568725425123);
+        const before = new Date(-1);
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(
+            forward(&mut engine, "epoch.toISOString()"),
+            "1970-01-01T00:00:00.000Z"
+        );
+        assert_eq!(
+            forward(&mut engine, "date.toISOString()"),
+            "2019-09-17T13:03:45.123Z"
+        );
+        assert_eq!(forward(&mut engine, "date.getTime()"), "1568725425123");
+        assert_eq!(forward(&mut engine, "date.getFullYear()"), "2019");
+        assert_eq!(forward(&mut engine, "date.getMonth()"), "8");
+        assert_eq!(forward(&mut engine, "date.getDate()"), "17");
+        assert_eq!(forward(&mut engine, "date.getDay()"), "2");
+        assert_eq!(forward(&mut engine, "date.getHours()"), "13");
+        assert_eq!(forward(&mut engine, "date.getMinutes()"), "3");
+        assert_eq!(forward(&mut engine, "date.getSeconds()"), "45");
+        assert_eq!(forward(&mut engine, "date.getMilliseconds()"), "123");
+        assert_eq!(
+            forward(&mut engine, "before.toISOString()"),
+            "1969-12-31T23:59:59.999Z"
+        );
+        assert_eq!(
+            forward(&mut engine, "epoch.toString()"),
+            "Thu Jan 01 1970 00:00:00 GMT+0000 (Coordinated Universal Time)"
+        );
+    }
+
+    #[test]
+    fn from_fields_and_strings() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const leap = new Date(2020, 1, 29, 12, 30);
+        const overflow = new Date(2019, 12, 1);
+        const parsed = new Date('2019-09-17T13:03:45.123Z');
+        const dateOnly = new Date('2000-02');
+        const invalid = new Date('not a date');
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(
+            forward(&mut engine, "leap.toISOString()"),
+            "2020-02-29T12:30:00.000Z"
+        );
+        assert_eq!(
+            forward(&mut engine, "overflow.toISOString()"),
+            "2020-01-01T00:00:00.000Z"
+        );
+        assert_eq!(forward(&mut engine, "parsed.getTime()"), "1568725425123");
+        assert_eq!(
+            forward(&mut engine, "dateOnly.toISOString()"),
+            "2000-02-01T00:00:00.000Z"
+        );
+        assert_eq!(forward(&mut engine, "invalid.getTime()"), "NaN");
+        assert_eq!(forward(&mut engine, "invalid.getFullYear()"), "NaN");
+        assert_eq!(
+            forward(&mut engine, "invalid.toISOString()"),
+            "Error: RangeError: Invalid time value"
+        );
+        assert_eq!(forward(&mut engine, "Date.parse('1970-01-02')"), "86400000");
+    }
+
+    #[test]
+    fn arithmetic_and_round_trip() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const start = new Date(2019, 8, 17, 13, 3, 45);
+        const end = new Date(2019, 8, 18, 14, 4, 46);
+        const iso = start.toISOString();
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "start.getSeconds()"), "45");
+        assert_eq!(forward(&mut engine, "end - start"), "90061000");
+        assert_eq!(
+            forward(&mut engine, "start.valueOf() === start.getTime()"),
+            "true"
+        );
+        assert_eq!(forward(&mut engine, "+new Date(1000)"), "1000");
+        assert_eq!(forward(&mut engine, "end > start"), "true");
+        // Adding to a date joins its string form
+        assert_eq!(
+            forward(&mut engine, "new Date(0) + '!'"),
+            "Thu Jan 01 1970 00:00:00 GMT+0000 (Coordinated Universal Time)!"
+        );
+        assert_eq!(forward(&mut engine, "iso"), "2019-09-17T13:03:45.000Z");
+        assert_eq!(
+            forward(
+                &mut engine,
+                "new Date(Date.parse(iso)).getTime() === start.getTime()"
+            ),
+            "true"
+        );
+        assert_eq!(
+            forward(
+                &mut engine,
+                "new Date(Date.parse(iso)).toISOString() === iso"
+            ),
+            "true"
+        );
+    }
+
+    #[test]
+    fn injected_clock() {
+        let realm = Realm::create();
+        let mut engine: Interpreter = Executor::new(realm);
+        engine.set_clock(fixed_clock);
+
+        assert_eq!(forward(&mut engine, "Date.now()"), "1000000000000");
+        forward(&mut engine, "const now = new Date();");
+        assert_eq!(
+            forward(&mut engine, "now.toISOString()"),
+            "2001-09-09T01:46:40.000Z"
+        );
+    }
+}
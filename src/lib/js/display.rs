@@ -0,0 +1,262 @@
+use crate::js::{
+    object::{Object, ObjectKind},
+    property::Property,
+    value::{Value, ValueData},
+};
+use std::collections::HashSet;
+
+/// Format a value for people to read, in the style of Node.js's `util.inspect`
+///
+/// Strings are quoted, arrays and objects show their contents, functions show their name and
+/// objects which contain themselves show `[Circular]` where they are reached again. Property
+/// getters are not run, and the properties of objects are shown with the array indices first in
+/// ascending order followed by the other keys in alphabetical order.
+pub fn format_value(value: &Value) -> String {
+    let mut ancestors = HashSet::new();
+    format_nested(value, &mut ancestors)
+}
+
+/// Objects are identified by the address of their shared `ValueData`
+fn pointer(value: &Value) -> usize {
+    &**value as *const ValueData as usize
+}
+
+fn format_nested(value: &Value, ancestors: &mut HashSet<usize>) -> String {
+    match **value {
+        ValueData::Undefined => "undefined".to_string(),
+        ValueData::Null => "null".to_string(),
+        ValueData::String(ref string) => quote(string),
+        ValueData::Boolean(_) | ValueData::Number(_) | ValueData::Integer(_) => value.to_string(),
+        ValueData::Function(_) => match *value.get_field_slice("name") {
+            ValueData::String(ref name) if !name.is_empty() => format!("[Function: {}]", name),
+            _ => "[Function (anonymous)]".to_string(),
+        },
+        ValueData::ArrayBuffer(ref bytes) => {
+            format!("ArrayBuffer {{ byteLength: {} }}", bytes.borrow().len())
+        }
+        ValueData::Object(ref object) => {
+            if !ancestors.insert(pointer(value)) {
+                return "[Circular]".to_string();
+            }
+            let formatted = format_object(&object.borrow(), ancestors);
+            ancestors.remove(&pointer(value));
+            formatted
+        }
+    }
+}
+
+fn format_object(object: &Object, ancestors: &mut HashSet<usize>) -> String {
+    match object.kind {
+        ObjectKind::String => {
+            return format!(
+                "[String: {}]",
+                format_internal_slot(object, "StringData", ancestors)
+            )
+        }
+        ObjectKind::Number => {
+            return format!(
+                "[Number: {}]",
+                format_internal_slot(object, "NumberData", ancestors)
+            )
+        }
+        ObjectKind::Boolean => {
+            return format!(
+                "[Boolean: {}]",
+                format_internal_slot(object, "BooleanData", ancestors)
+            )
+        }
+        _ => (),
+    }
+
+    let is_array = matches!(object.kind, ObjectKind::Array);
+    let mut indices: Vec<(u32, &Property)> = Vec::new();
+    let mut keys: Vec<(&String, &Property)> = Vec::new();
+    for (key, property) in object.properties.iter() {
+        match key.parse::<u32>() {
+            Ok(index) if index.to_string() == *key => indices.push((index, property)),
+            _ if key == "length" && is_array => (),
+            _ => keys.push((key, property)),
+        }
+    }
+    indices.sort_by_key(|(index, _)| *index);
+    keys.sort_by_key(|(key, _)| *key);
+
+    let mut entries = Vec::new();
+    if is_array {
+        entries.extend(
+            indices
+                .iter()
+                .map(|(_, property)| format_property(property, ancestors)),
+        );
+    } else {
+        entries.extend(indices.iter().map(|(index, property)| {
+            format!("'{}': {}", index, format_property(property, ancestors))
+        }));
+    }
+    entries.extend(keys.iter().map(|(key, property)| {
+        format!(
+            "{}: {}",
+            format_key(key),
+            format_property(property, ancestors)
+        )
+    }));
+
+    match (is_array, entries.is_empty()) {
+        (true, true) => "[]".to_string(),
+        (true, false) => format!("[{}]", entries.join(", ")),
+        (false, true) => "{}".to_string(),
+        (false, false) => format!("{{ {} }}", entries.join(", ")),
+    }
+}
+
+fn format_internal_slot(object: &Object, slot: &str, ancestors: &mut HashSet<usize>) -> String {
+    match object.internal_slots.get(slot) {
+        Some(value) => format_nested(value, ancestors),
+        None => "undefined".to_string(),
+    }
+}
+
+/// Accessor properties are shown by the kind of accessor, as their getter is not run
+fn format_property(property: &Property, ancestors: &mut HashSet<usize>) -> String {
+    let is_function = |accessor: &Option<Value>| match accessor {
+        Some(ref function) => function.is_function(),
+        None => false,
+    };
+    match property.value {
+        Some(ref value) => format_nested(value, ancestors),
+        None => match (is_function(&property.get), is_function(&property.set)) {
+            (true, true) => "[Getter/Setter]".to_string(),
+            (true, false) => "[Getter]".to_string(),
+            (false, true) => "[Setter]".to_string(),
+            (false, false) => "undefined".to_string(),
+        },
+    }
+}
+
+/// Keys which are identifiers are shown bare, other keys are quoted
+fn format_key(key: &str) -> String {
+    let mut chars = key.chars();
+    let is_identifier = match chars.next() {
+        Some(first) => {
+            (first.is_alphabetic() || first == '$' || first == '_')
+                && chars.all(|ch| ch.is_alphanumeric() || ch == '$' || ch == '_')
+        }
+        None => false,
+    };
+    if is_identifier {
+        key.to_string()
+    } else {
+        quote(key)
+    }
+}
+
+/// Put a string in single quotes, escaping the characters which would make it ambiguous
+fn quote(string: &str) -> String {
+    let mut quoted = String::with_capacity(string.len() + 2);
+    quoted.push('\'');
+    for ch in string.chars() {
+        match ch {
+            '\'' => quoted.push_str("\\'"),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            _ => quoted.push(ch),
+        }
+    }
+    quoted.push('\'');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::exec::{Executor, Interpreter};
+    use crate::realm::Realm;
+    use crate::{forward, forward_val};
+
+    fn inspect(engine: &mut Interpreter, src: &str) -> String {
+        let value = forward_val(engine, src).expect("failed to evaluate");
+        engine.inspect_value(&value)
+    }
+
+    #[test]
+    fn primitives() {
+        let realm = Realm::create();
+        let mut engine: Interpreter = Executor::new(realm);
+
+        assert_eq!(inspect(&mut engine, "undefined"), "undefined");
+        assert_eq!(inspect(&mut engine, "null"), "null");
+        assert_eq!(inspect(&mut engine, "true"), "true");
+        assert_eq!(inspect(&mut engine, "1.5"), "1.5");
+        assert_eq!(inspect(&mut engine, "5 | 0"), "5");
+        assert_eq!(inspect(&mut engine, "NaN"), "NaN");
+        assert_eq!(inspect(&mut engine, "'it\\'s'"), "'it\\'s'");
+        assert_eq!(inspect(&mut engine, "'a\\nb'"), "'a\\nb'");
+    }
+
+    #[test]
+    fn objects_and_arrays() {
+        let realm = Realm::create();
+        let mut engine: Interpreter = Executor::new(realm);
+
+        assert_eq!(inspect(&mut engine, "[1, 2, 3]"), "[1, 2, 3]");
+        assert_eq!(inspect(&mut engine, "[]"), "[]");
+        assert_eq!(inspect(&mut engine, "({})"), "{}");
+        assert_eq!(inspect(&mut engine, "({ b: 2, a: 1 })"), "{ a: 1, b: 2 }");
+        assert_eq!(
+            inspect(&mut engine, "({ list: ['x', null], nested: { ok: true } })"),
+            "{ list: ['x', null], nested: { ok: true } }"
+        );
+        assert_eq!(
+            inspect(&mut engine, "({ 'two words': 1, '2': 'b', '1': 'a' })"),
+            "{ '1': 'a', '2': 'b', 'two words': 1 }"
+        );
+        assert_eq!(
+            inspect(&mut engine, "({ get size() { return 1; } })"),
+            "{ size: [Getter] }"
+        );
+        assert_eq!(inspect(&mut engine, "new Number(3)"), "[Number: 3]");
+        assert_eq!(
+            inspect(&mut engine, "new Boolean(false)"),
+            "[Boolean: false]"
+        );
+    }
+
+    #[test]
+    fn functions() {
+        let realm = Realm::create();
+        let mut engine: Interpreter = Executor::new(realm);
+        forward(&mut engine, "function named() {}");
+
+        assert_eq!(inspect(&mut engine, "named"), "[Function: named]");
+        assert_eq!(inspect(&mut engine, "() => 1"), "[Function (anonymous)]");
+        assert_eq!(
+            inspect(&mut engine, "({ method() {} })"),
+            "{ method: [Function: method] }"
+        );
+    }
+
+    #[test]
+    fn circular_references() {
+        let realm = Realm::create();
+        let mut engine: Interpreter = Executor::new(realm);
+        forward(
+            &mut engine,
+            "var circular = { name: 'loop' }; circular.self = circular; var shared = { n: 1 };",
+        );
+
+        assert_eq!(
+            inspect(&mut engine, "circular"),
+            "{ name: 'loop', self: [Circular] }"
+        );
+        // An object reached twice without a cycle is shown both times
+        assert_eq!(
+            inspect(&mut engine, "[shared, shared]"),
+            "[{ n: 1 }, { n: 1 }]"
+        );
+        assert_eq!(
+            inspect(&mut engine, "var list = [1]; list[1] = list; list"),
+            "[1, [Circular]]"
+        );
+    }
+}
@@ -1,47 +1,214 @@
 use crate::{
     exec::Interpreter,
     js::{
-        function::NativeFunctionData,
-        object::{ObjectKind, PROTOTYPE},
+        function::make_builtin_fn,
+        object::{ObjectKind, INSTANCE_PROTOTYPE, PROTOTYPE},
         value::{to_value, ResultValue, Value, ValueData},
     },
 };
 use gc::Gc;
 
+/// The native error types, whose prototypes inherit from `Error.prototype`
+/// <https://tc39.es/ecma262/#sec-native-error-types-used-in-this-standard>
+pub const NATIVE_ERRORS: [&str; 4] = ["TypeError", "RangeError", "ReferenceError", "SyntaxError"];
+
 /// Create a new error
-pub fn make_error(this: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
-    if !args.is_empty() {
-        this.set_field_slice(
-            "message",
-            to_value(
-                args.get(0)
-                    .expect("failed getting error message")
-                    .to_string(),
-            ),
-        );
+pub fn make_error(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    match args.first() {
+        Some(message) if !message.is_undefined() => {
+            let message = ctx.value_to_rust_string(message)?;
+            this.set_field_slice("message", to_value(message));
+        }
+        _ => (),
     }
     // This value is used by console.log and other routines to match Object type
     // to its Javascript Identifier (global constructor method name)
     this.set_kind(ObjectKind::Error);
     Ok(Gc::new(ValueData::Undefined))
 }
-/// Get the string representation of the error
-pub fn to_string(this: &Value, _: &[Value], _: &mut Interpreter) -> ResultValue {
+
+/// Get the string representation of the error, which leaves out an empty message
+/// <https://tc39.es/ecma262/#sec-error.prototype.tostring>
+pub fn to_string(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
     let name = this.get_field_slice("name");
+    let name = if name.is_undefined() {
+        "Error".to_string()
+    } else {
+        ctx.value_to_rust_string(&name)?
+    };
     let message = this.get_field_slice("message");
-    Ok(to_value(format!("{}: {}", name, message).to_string()))
+    let message = if message.is_undefined() {
+        String::new()
+    } else {
+        ctx.value_to_rust_string(&message)?
+    };
+    Ok(to_value(match (name.is_empty(), message.is_empty()) {
+        (_, true) => name,
+        (true, false) => message,
+        (false, false) => format!("{}: {}", name, message),
+    }))
+}
+
+/// Create an error object of the type `name`, such as `TypeError`, with the given message
+pub fn new_error(name: &str, message: &str, ctx: &Interpreter) -> Value {
+    let prototype = ctx
+        .get_realm()
+        .global_obj
+        .get_field_slice(name)
+        .get_field_slice(PROTOTYPE);
+    let error = ValueData::new_obj_from_prototype(prototype, ObjectKind::Error);
+    error.set_field_slice("message", to_value(message));
+    error
 }
+
 /// Create a new `Error` object
 pub fn _create(global: &Value) -> Value {
     let prototype = ValueData::new_obj(Some(global));
     prototype.set_field_slice("message", to_value(""));
     prototype.set_field_slice("name", to_value("Error"));
-    prototype.set_field_slice("toString", to_value(to_string as NativeFunctionData));
-    let error = to_value(make_error as NativeFunctionData);
+    prototype.set_field_slice("toString", make_builtin_fn(to_string, global));
+    let error = make_builtin_fn(make_error, global);
+    prototype.set_field_slice("constructor", error.clone());
     error.set_field_slice(PROTOTYPE, prototype);
     error
 }
-/// Initialise the global object with the `Error` object
+
+/// Create the constructor of the native error type `name`, whose prototype inherits from
+/// `Error.prototype`
+/// <https://tc39.es/ecma262/#sec-nativeerror-object-structure>
+fn create_native_error(name: &str, error_prototype: &Value, global: &Value) -> Value {
+    let prototype = ValueData::new_obj(Some(global));
+    prototype.set_internal_slot(INSTANCE_PROTOTYPE, error_prototype.clone());
+    prototype.set_field_slice("message", to_value(""));
+    prototype.set_field_slice("name", to_value(name));
+    let error = make_builtin_fn(make_error, global);
+    prototype.set_field_slice("constructor", error.clone());
+    error.set_field_slice(PROTOTYPE, prototype);
+    error
+}
+
+/// Initialise the global object with the `Error` object and the native error types
 pub fn init(global: &Value) {
-    global.set_field_slice("Error", _create(global));
+    let error = _create(global);
+    let error_prototype = error.get_field_slice(PROTOTYPE);
+    global.set_field_slice("Error", error);
+    for name in NATIVE_ERRORS.iter() {
+        global.set_field_slice(name, create_native_error(name, &error_prototype, global));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::exec::Executor;
+    use crate::forward;
+    use crate::realm::Realm;
+
+    #[test]
+    fn constructors() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+
+        assert_eq!(forward(&mut engine, "new Error('plain').message"), "plain");
+        assert_eq!(forward(&mut engine, "new Error().message"), "");
+        assert_eq!(forward(&mut engine, "String(new Error())"), "Error");
+        assert_eq!(
+            forward(&mut engine, "String(new RangeError('r'))"),
+            "RangeError: r"
+        );
+        assert_eq!(
+            forward(&mut engine, "new TypeError('boom').toString()"),
+            "TypeError: boom"
+        );
+        assert_eq!(
+            forward(&mut engine, "new SyntaxError('s').name"),
+            "SyntaxError"
+        );
+        assert_eq!(
+            forward(
+                &mut engine,
+                "new ReferenceError('r') instanceof ReferenceError"
+            ),
+            "true"
+        );
+        assert_eq!(
+            forward(&mut engine, "new TypeError('x') instanceof Error"),
+            "true"
+        );
+        assert_eq!(
+            forward(&mut engine, "new TypeError('x') instanceof RangeError"),
+            "false"
+        );
+        assert_eq!(
+            forward(
+                &mut engine,
+                "Object.getPrototypeOf(TypeError.prototype) === Error.prototype"
+            ),
+            "true"
+        );
+        assert_eq!(
+            forward(&mut engine, "TypeError.prototype.constructor === TypeError"),
+            "true"
+        );
+    }
+
+    #[test]
+    fn thrown_errors() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        function describe(thunk) {
+            try {
+                thunk();
+            } catch (e) {
+                let isError = e instanceof Error;
+                return e.name + ' / ' + e.message + ' / ' + isError;
+            }
+        }
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(
+            forward(
+                &mut engine,
+                "describe(() => { throw new TypeError('boom'); })"
+            ),
+            "TypeError / boom / true"
+        );
+        // The engine's own failures are error objects too
+        assert_eq!(
+            forward(&mut engine, "describe(() => { let value = 1; value(); })"),
+            "TypeError / value is not a function / true"
+        );
+        assert_eq!(
+            forward(
+                &mut engine,
+                "describe(() => { const fixed = 1; fixed = 2; })"
+            ),
+            "TypeError / Assignment to constant variable. / true"
+        );
+        assert_eq!(
+            forward(&mut engine, "describe(() => null.field)"),
+            "TypeError / Cannot read property 'field' of null / true"
+        );
+        assert_eq!(
+            forward(&mut engine, "describe(() => (1).toFixed(101))"),
+            "RangeError / toFixed() digits argument must be between 0 and 100 / true"
+        );
+        assert!(
+            forward(&mut engine, "describe(() => JSON.parse('{'))").starts_with("SyntaxError / ")
+        );
+        // Values thrown by scripts are left as they are, even through built-ins
+        assert_eq!(
+            forward(
+                &mut engine,
+                "try { [1].some(function () { throw 'TypeError: x'; }); } catch (e) { typeof e }"
+            ),
+            "string"
+        );
+        // Uncaught errors are reported by their name and message
+        assert_eq!(
+            forward(&mut engine, "throw new RangeError('out of range')"),
+            "Error: RangeError: out of range"
+        );
+    }
 }
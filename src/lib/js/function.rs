@@ -1,15 +1,20 @@
 use crate::{
+    environment::lexical_environment::Environment,
     exec::Interpreter,
     js::{
-        object::Object,
+        error,
+        object::{Object, INSTANCE_PROTOTYPE, PROTOTYPE},
         property::Property,
         value::{to_value, ResultValue, Value, ValueData},
     },
-    syntax::ast::expr::Expr,
+    syntax::ast::{expr::Expr, sym::Sym},
 };
 use gc::{custom_trace, Gc};
 use gc_derive::{Finalize, Trace};
-use std::fmt::{self, Debug};
+use std::{
+    fmt::{self, Debug},
+    ops::Deref,
+};
 
 /// fn(this, arguments, ctx)
 pub type NativeFunctionData = fn(&Value, &[Value], &mut Interpreter) -> ResultValue;
@@ -18,8 +23,6 @@ pub type NativeFunctionData = fn(&Value, &[Value], &mut Interpreter) -> ResultVa
 /// A member of the Object type that may be invoked as a subroutine
 /// <https://tc39.github.io/ecma262/#sec-terms-and-definitions-function>
 /// In our implementation, Function is extending Object by holding an object field which some extra data
-
-/// A Javascript function
 #[derive(Trace, Finalize, Debug, Clone)]
 pub enum Function {
     /// A native javascript function
@@ -29,26 +32,52 @@ pub enum Function {
 }
 
 /// Represents a regular javascript function in memory
-#[derive(Trace, Finalize, Debug, Clone)]
+#[derive(Trace, Finalize, Clone)]
 pub struct RegularFunction {
     /// The fields associated with the function
     pub object: Object,
-    /// This function's expression
-    pub expr: Expr,
+    /// This function's expression, shared with the declaration it was made from
+    pub expr: Gc<Expr>,
     /// The argument names of the function
-    pub args: Vec<String>,
+    pub args: Vec<Sym>,
+    /// The environment the function was made in, which its calls are nested in
+    pub environment: Environment,
+    /// Whether the function's body is strict mode code
+    pub strict: bool,
+    /// Whether the function is an arrow function, which has no `this` of its own and sees the
+    /// one of the code it was made in
+    pub lexical_this: bool,
 }
 
 impl RegularFunction {
     /// Make a new regular function
     #[allow(clippy::cast_possible_wrap)]
-    pub fn new(expr: Expr, args: Vec<String>) -> Self {
+    pub fn new(expr: Gc<Expr>, args: Vec<String>, environment: Environment, strict: bool) -> Self {
         let mut object = Object::default();
         object.properties.insert(
             "arguments".to_string(),
             Property::default().value(Gc::new(ValueData::Integer(args.len() as i32))),
         );
-        Self { object, expr, args }
+        let args = args.into_iter().map(Sym::from).collect();
+        Self {
+            object,
+            expr,
+            args,
+            environment,
+            strict,
+            lexical_this: false,
+        }
+    }
+}
+
+impl Debug for RegularFunction {
+    // The environment is left out, as it may hold the function itself
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RegularFunction")
+            .field("object", &self.object)
+            .field("expr", &self.expr)
+            .field("args", &self.args)
+            .finish()
     }
 }
 
@@ -91,15 +120,101 @@ unsafe impl gc::Trace for NativeFunction {
     custom_trace!(this, mark(&this.object));
 }
 
+/// Check that `this` is callable before it is invoked by one of the prototype methods
+fn this_function(this: &Value, method: &str, ctx: &Interpreter) -> Result<(), Value> {
+    match this.deref() {
+        ValueData::Function(_) => Ok(()),
+        _ => Err(error::new_error(
+            "TypeError",
+            &format!(
+                "Function.prototype.{} called on a value which is not a function",
+                method
+            ),
+            ctx,
+        )),
+    }
+}
+
+/// Call the function with the given `this` value and the rest of the arguments
+/// <https://tc39.es/ecma262/#sec-function.prototype.call>
+pub fn call(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    this_function(this, "call", ctx)?;
+    let this_arg = args
+        .first()
+        .cloned()
+        .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+    let arguments = args.iter().skip(1).cloned().collect();
+    ctx.call(this, &this_arg, arguments)
+}
+
+/// Call the function with the given `this` value and the elements of an array-like object as arguments
+/// <https://tc39.es/ecma262/#sec-function.prototype.apply>
+pub fn apply(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    this_function(this, "apply", ctx)?;
+    let this_arg = args
+        .first()
+        .cloned()
+        .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+    let arguments = match args.get(1).map(Value::deref) {
+        None | Some(ValueData::Undefined) | Some(ValueData::Null) => Vec::new(),
+        Some(ValueData::Object(_)) | Some(ValueData::Function(_)) => {
+            create_list_from_array_like(&args[1])
+        }
+        Some(_) => {
+            return Err(error::new_error(
+                "TypeError",
+                "Function.prototype.apply was called with a non-object argument list",
+                ctx,
+            ))
+        }
+    };
+    ctx.call(this, &this_arg, arguments)
+}
+
+/// <https://tc39.es/ecma262/#sec-createlistfromarraylike>
+fn create_list_from_array_like(array_like: &Value) -> Vec<Value> {
+    let length = array_like.get_field_slice("length").to_num();
+    let length = if length.is_nan() || length < 0.0 {
+        0
+    } else {
+        length as usize
+    };
+    (0..length)
+        .map(|index| array_like.get_field(&index.to_string()))
+        .collect()
+}
+
 /// Create a new `Function` object
 pub fn _create() -> Value {
     let function: Object = Object::default();
-    to_value(function)
+    let function = to_value(function);
+    // `Object.prototype` does not exist yet, so `object::init` sets what this inherits from
+    let prototype = ValueData::new_obj(None);
+    function.set_field_slice(PROTOTYPE, prototype);
+    function
 }
 /// Initialise the global object with the `Function` object
 pub fn init(global: &Value) {
     let global_ptr = global;
     global_ptr.set_field_slice("Function", _create());
+    // `call` and `apply` are functions as well, so they can only be made once there is a
+    // `Function.prototype` for them to inherit from
+    let prototype = global
+        .get_field_slice("Function")
+        .get_field_slice(PROTOTYPE);
+    prototype.set_field_slice("call", make_builtin_fn(call, global));
+    prototype.set_field_slice("apply", make_builtin_fn(apply, global));
+}
+
+/// Create a built-in function which, like every other function, inherits from
+/// `Function.prototype`
+pub fn make_builtin_fn(function: NativeFunctionData, global: &Value) -> Value {
+    let func = to_value(function);
+    let prototype = global
+        .get_field_slice("Function")
+        .get_field_slice(PROTOTYPE);
+    func.set_internal_slot(INSTANCE_PROTOTYPE, prototype);
+    func
 }
 
 /// Arguments
@@ -108,11 +223,12 @@ pub fn create_unmapped_arguments_object(arguments_list: Vec<Value>) -> Value {
     let len = arguments_list.len();
     let mut obj = Object::default();
     obj.set_internal_slot("ParameterMap", Gc::new(ValueData::Undefined));
-    // Set length
-    let mut length = Property::default();
-    length = length.writable(true).value(to_value(len));
-    // Define length as a property
-    obj.define_own_property("length".to_string(), length);
+    // Set length, which is stored directly as the object is not extensible yet
+    let length = Property::default()
+        .writable(true)
+        .configurable(true)
+        .value(to_value(len));
+    obj.properties.insert("length".to_string(), length);
     let mut index: usize = 0;
     while index < len {
         let val = arguments_list.get(index).unwrap();
@@ -149,7 +265,143 @@ mod tests {
 
         forward(&mut engine, init);
         let return_val = forward_val(&mut engine, "val").expect("value expected");
-        assert_eq!(return_val.is_double(), true);
+        assert!(return_val.is_double());
         assert_eq!(from_value::<f64>(return_val).unwrap(), 100.0);
     }
+
+    #[test]
+    fn call() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        function add(a, b) {
+            return this.base + a + b;
+        }
+        var obj = { base: 10 };
+        "#;
+
+        forward(&mut engine, init);
+        assert_eq!(forward(&mut engine, "add.call(obj, 1, 2)"), "13");
+        assert_eq!(forward(&mut engine, "add.call({ base: 1 }, 1, 1)"), "3");
+        assert_eq!(
+            forward(
+                &mut engine,
+                "(function () { return arguments.length; }).call(obj)"
+            ),
+            "0"
+        );
+        assert_eq!(
+            forward(
+                &mut engine,
+                "var borrowed = { call: add.call }; borrowed.call()"
+            ),
+            "Error: TypeError: Function.prototype.call called on a value which is not a function"
+        );
+    }
+
+    #[test]
+    fn apply() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        function add(a, b) {
+            return this.base + a + b;
+        }
+        var obj = { base: 10 };
+        "#;
+
+        forward(&mut engine, init);
+        assert_eq!(forward(&mut engine, "add.apply(obj, [1, 2])"), "13");
+        assert_eq!(
+            forward(
+                &mut engine,
+                "(function () { return add.apply(obj, arguments); })(3, 4)"
+            ),
+            "17"
+        );
+        assert_eq!(forward(&mut engine, "(() => 5).apply(obj)"), "5");
+        assert_eq!(
+            forward(&mut engine, "add.apply(obj, 1)"),
+            "Error: TypeError: Function.prototype.apply was called with a non-object argument list"
+        );
+    }
+
+    #[test]
+    fn call_and_apply_built_ins() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+
+        assert_eq!(forward(&mut engine, "Math.max.call(null, 1, 2)"), "2");
+        assert_eq!(forward(&mut engine, "Math.max.apply(null, [1, 3, 2])"), "3");
+        assert_eq!(
+            forward(
+                &mut engine,
+                "Array.prototype.join.call({ length: 2, 0: 'a', 1: 'b' }, '-')"
+            ),
+            "a-b"
+        );
+        assert_eq!(
+            forward(&mut engine, "String.prototype.toUpperCase.apply('ab')"),
+            "AB"
+        );
+        assert_eq!(
+            forward(
+                &mut engine,
+                "Function.prototype.call.call(Math.min, null, 4, 5)"
+            ),
+            "4"
+        );
+        // Built-in functions inherit from Function.prototype, which inherits from Object.prototype
+        assert_eq!(
+            forward(
+                &mut engine,
+                "Object.getPrototypeOf(Math.max) === Function.prototype"
+            ),
+            "true"
+        );
+        assert_eq!(
+            forward(
+                &mut engine,
+                "Object.getPrototypeOf(Function.prototype) === Object.prototype"
+            ),
+            "true"
+        );
+    }
+
+    #[test]
+    fn closures_share_their_body() {
+        use crate::js::value::{Value, ValueData};
+        use crate::syntax::ast::expr::Expr;
+
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        function adder(n) {
+            return (x) => x + n;
+        }
+        var closures = [];
+        var i = 0;
+        while (i < 2000) {
+            closures.push(adder(i));
+            i++;
+        }
+        "#;
+        forward(&mut engine, init);
+        assert_eq!(forward(&mut engine, "closures.length"), "2000");
+        assert_eq!(forward(&mut engine, "closures[0](1)"), "1");
+        assert_eq!(forward(&mut engine, "closures[1999](1)"), "2000");
+
+        // Every closure runs the body from the declaration instead of a copy of it
+        let body = |value: &Value| -> *const Expr {
+            if let ValueData::Function(ref function) = **value {
+                if let super::Function::RegularFunc(ref data) = *function.borrow() {
+                    return &*data.expr as *const Expr;
+                }
+            }
+            panic!("expected a regular function");
+        };
+        let first = forward_val(&mut engine, "closures[0]").expect("value expected");
+        let last = forward_val(&mut engine, "closures[1999]").expect("value expected");
+        assert_eq!(body(&first), body(&last));
+    }
 }
@@ -1,36 +1,331 @@
 use crate::exec::Interpreter;
-use crate::js::function::NativeFunctionData;
+use crate::js::array;
+use crate::js::error;
+use crate::js::function::make_builtin_fn;
+use crate::js::object::{own_property_keys, ObjectKind};
 /// The JSON Object
 /// <https://tc39.github.io/ecma262/#sec-json-object>
 use crate::js::value::{to_value, ResultValue, Value, ValueData};
-use serde_json::{self, to_string_pretty, Value as JSONValue};
+use gc::Gc;
+use serde_json::{self, Value as JSONValue};
+use std::ptr;
 
 /// Parse a JSON string into a Javascript object
+///
+/// If a reviver function is given, it is called with each key and value from the innermost
+/// out, and what it returns takes the place of the value, or removes it if `undefined`.
 /// <https://tc39.github.io/ecma262/#sec-json.parse>
-pub fn parse(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
-    match serde_json::from_str::<JSONValue>(
-        &args
-            .get(0)
-            .expect("cannot get argument for JSON.parse")
-            .clone()
-            .to_string(),
-    ) {
-        Ok(json) => Ok(to_value(json)),
-        Err(err) => Err(to_value(err.to_string())),
+pub fn parse(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let text = match args.first() {
+        Some(arg) => ctx.value_to_rust_string(arg)?,
+        None => "undefined".to_string(),
+    };
+    let json = match serde_json::from_str::<JSONValue>(&text) {
+        Ok(json) => json,
+        Err(err) => return Err(error::new_error("SyntaxError", &format!("{}", err), ctx)),
+    };
+    let value = from_json(json, ctx)?;
+    match args.get(1) {
+        Some(reviver) if reviver.is_function() => {
+            let root = ValueData::new_obj(Some(&ctx.get_realm().global_obj));
+            root.set_field_slice("", value);
+            internalize(&root, "", reviver, ctx)
+        }
+        _ => Ok(value),
     }
 }
+
+/// Make the value of parsed JSON, whose objects and arrays inherit from this realm's prototypes
+fn from_json(json: JSONValue, ctx: &Interpreter) -> ResultValue {
+    match json {
+        JSONValue::Array(items) => {
+            let values = items
+                .into_iter()
+                .map(|item| from_json(item, ctx))
+                .collect::<Result<Vec<Value>, Value>>()?;
+            let result = array::new_array(ctx)?;
+            array::add_to_array_object(&result, &values)
+        }
+        JSONValue::Object(entries) => {
+            let object = ValueData::new_obj(Some(&ctx.get_realm().global_obj));
+            for (key, item) in entries {
+                object.set_field_slice(&key, from_json(item, ctx)?);
+            }
+            Ok(object)
+        }
+        primitive => Ok(to_value(primitive)),
+    }
+}
+
+/// Revive the properties of `holder[name]`, then call the reviver on the property itself
+/// <https://tc39.es/ecma262/#sec-internalizejsonproperty>
+fn internalize(holder: &Value, name: &str, reviver: &Value, ctx: &mut Interpreter) -> ResultValue {
+    let value = ctx.get_property_value(holder, name)?;
+    if let ValueData::Object(_) = *value {
+        let keys: Vec<String> = if is_array(&value) {
+            let length = value.get_field_slice("length").to_num() as usize;
+            (0..length).map(|index| index.to_string()).collect()
+        } else {
+            own_property_keys(&value, |prop| prop.enumerable != Some(false))
+        };
+        for key in keys {
+            let element = internalize(&value, &key, reviver, ctx)?;
+            if element.is_undefined() {
+                value.remove_prop(&key);
+            } else {
+                value.set_field_slice(&key, element);
+            }
+        }
+    }
+    ctx.call(reviver, holder, vec![to_value(name), value])
+}
+
+/// The options of a call to `JSON.stringify` and how far it has nested
+struct Serializer {
+    /// Called with each key and value, and what it returns is serialized in their place
+    replacer: Option<Value>,
+    /// The only keys of objects which are serialized, if the replacer is an array
+    property_list: Option<Vec<String>>,
+    /// What each level of nesting is indented by, empty for output on one line
+    gap: String,
+    /// The indentation of the current level of nesting
+    indent: String,
+    /// The objects and arrays being serialized, from the outermost one in
+    stack: Vec<Value>,
+}
+
 /// Process a Javascript object into a JSON string
-pub fn stringify(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
-    let obj = args.get(0).expect("cannot get argument for JSON.stringify");
-    let json = obj.to_json();
-    Ok(to_value(to_string_pretty(&json).expect("")))
+///
+/// `undefined`, functions and symbols are left out of objects and become `null` in arrays, as
+/// there is no JSON for them.
+/// <https://tc39.es/ecma262/#sec-json.stringify>
+pub fn stringify(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let undefined = Gc::new(ValueData::Undefined);
+    let value = args.first().unwrap_or(&undefined);
+    let replacer = args.get(1).unwrap_or(&undefined);
+    let mut serializer = Serializer {
+        replacer: None,
+        property_list: None,
+        gap: gap(args.get(2).unwrap_or(&undefined), ctx)?,
+        indent: String::new(),
+        stack: Vec::new(),
+    };
+    if replacer.is_function() {
+        serializer.replacer = Some(replacer.clone());
+    } else if is_array(replacer) {
+        serializer.property_list = Some(property_list(replacer, ctx)?);
+    }
+
+    let wrapper = ValueData::new_obj(Some(&ctx.get_realm().global_obj));
+    wrapper.set_field_slice("", value.clone());
+    match serializer.property("", &wrapper, ctx)? {
+        Some(json) => Ok(to_value(json)),
+        None => Ok(undefined),
+    }
+}
+
+/// The keys given by an array replacer, which can be strings, numbers or their wrapper objects
+fn property_list(replacer: &Value, ctx: &mut Interpreter) -> Result<Vec<String>, Value> {
+    let length = replacer.get_field_slice("length").to_num() as usize;
+    let mut list: Vec<String> = Vec::new();
+    for index in 0..length {
+        let element = replacer.get_field_slice(&index.to_string());
+        let item = match *element {
+            ValueData::String(ref key) => Some(key.clone()),
+            ValueData::Number(_) | ValueData::Integer(_) => Some(element.to_string()),
+            _ => match object_kind(&element) {
+                Some(ObjectKind::String) | Some(ObjectKind::Number) => {
+                    Some(ctx.value_to_rust_string(&element)?)
+                }
+                _ => None,
+            },
+        };
+        if let Some(item) = item {
+            if !list.contains(&item) {
+                list.push(item);
+            }
+        }
+    }
+    Ok(list)
+}
+
+/// The indentation given by the `space` argument, as up to 10 spaces or the first 10 characters
+/// of a string
+fn gap(space: &Value, ctx: &mut Interpreter) -> Result<String, Value> {
+    let space = match object_kind(space) {
+        Some(ObjectKind::Number) => to_value(ctx.to_primitive(space, Some("number"))?.to_num()),
+        Some(ObjectKind::String) => ctx.to_string(space)?,
+        _ => space.clone(),
+    };
+    Ok(match *space {
+        ValueData::Number(_) | ValueData::Integer(_) => {
+            let count = space.to_num();
+            let count = if count.is_nan() { 0.0 } else { count.trunc() };
+            " ".repeat(count.clamp(0.0, 10.0) as usize)
+        }
+        ValueData::String(ref string) => string.chars().take(10).collect(),
+        _ => String::new(),
+    })
+}
+
+/// The kind of `value` if it is an object
+fn object_kind(value: &Value) -> Option<ObjectKind> {
+    match **value {
+        ValueData::Object(ref object) => Some(object.borrow().kind.clone()),
+        _ => None,
+    }
+}
+
+fn is_array(value: &Value) -> bool {
+    matches!(object_kind(value), Some(ObjectKind::Array))
+}
+
+impl Serializer {
+    /// Serialize the property `key` of `holder`, or `None` if it has no JSON representation
+    /// <https://tc39.es/ecma262/#sec-serializejsonproperty>
+    fn property(
+        &mut self,
+        key: &str,
+        holder: &Value,
+        ctx: &mut Interpreter,
+    ) -> Result<Option<String>, Value> {
+        let mut value = ctx.get_property_value(holder, key)?;
+        if value.is_object() || value.is_function() {
+            let to_json = ctx.get_property_value(&value, "toJSON")?;
+            if to_json.is_function() {
+                value = ctx.call(&to_json, &value, vec![to_value(key)])?;
+            }
+        }
+        if let Some(ref replacer) = self.replacer {
+            value = ctx.call(replacer, holder, vec![to_value(key), value])?;
+        }
+
+        // Wrapper objects are serialized as the primitive they wrap
+        value = match object_kind(&value) {
+            Some(ObjectKind::Number) => {
+                to_value(ctx.to_primitive(&value, Some("number"))?.to_num())
+            }
+            Some(ObjectKind::String) => ctx.to_string(&value)?,
+            Some(ObjectKind::Boolean) => value.get_internal_slot("BooleanData"),
+            _ => value,
+        };
+
+        Ok(match *value {
+            ValueData::Null => Some(String::from("null")),
+            ValueData::Boolean(boolean) => Some(boolean.to_string()),
+            ValueData::String(ref string) => Some(quote(string)),
+            ValueData::Number(number) if !number.is_finite() => Some(String::from("null")),
+            ValueData::Number(_) | ValueData::Integer(_) => Some(value.to_string()),
+            ValueData::Object(_) if is_array(&value) => Some(self.array(&value, ctx)?),
+            ValueData::Object(_) | ValueData::ArrayBuffer(_) => Some(self.object(&value, ctx)?),
+            ValueData::Undefined | ValueData::Function(_) => None,
+        })
+    }
+
+    /// Start serializing `value`, which must not be one of the objects it is inside of
+    fn enter(&mut self, value: &Value, ctx: &Interpreter) -> Result<(), Value> {
+        if self
+            .stack
+            .iter()
+            .any(|outer| ptr::eq::<ValueData>(&**outer, &**value))
+        {
+            return Err(error::new_error(
+                "TypeError",
+                "Converting circular structure to JSON",
+                ctx,
+            ));
+        }
+        self.stack.push(value.clone());
+        Ok(())
+    }
+
+    /// <https://tc39.es/ecma262/#sec-serializejsonobject>
+    fn object(&mut self, value: &Value, ctx: &mut Interpreter) -> Result<String, Value> {
+        self.enter(value, ctx)?;
+        let stepback = self.indent.clone();
+        self.indent.push_str(&self.gap);
+        let keys = match self.property_list {
+            Some(ref list) => list.clone(),
+            None => own_property_keys(value, |prop| prop.enumerable != Some(false)),
+        };
+        let mut members = Vec::new();
+        for key in keys {
+            if let Some(json) = self.property(&key, value, ctx)? {
+                let separator = if self.gap.is_empty() { ":" } else { ": " };
+                members.push(format!("{}{}{}", quote(&key), separator, json));
+            }
+        }
+        let result = self.wrap('{', '}', &members, &stepback);
+        self.indent = stepback;
+        self.stack.pop();
+        Ok(result)
+    }
+
+    /// <https://tc39.es/ecma262/#sec-serializejsonarray>
+    fn array(&mut self, value: &Value, ctx: &mut Interpreter) -> Result<String, Value> {
+        self.enter(value, ctx)?;
+        let stepback = self.indent.clone();
+        self.indent.push_str(&self.gap);
+        let length = value.get_field_slice("length").to_num() as usize;
+        let mut elements = Vec::with_capacity(length);
+        for index in 0..length {
+            let json = self.property(&index.to_string(), value, ctx)?;
+            elements.push(json.unwrap_or_else(|| String::from("null")));
+        }
+        let result = self.wrap('[', ']', &elements, &stepback);
+        self.indent = stepback;
+        self.stack.pop();
+        Ok(result)
+    }
+
+    /// Join the serialized members of an object or array, putting each on its own line when
+    /// there is a gap
+    fn wrap(&self, open: char, close: char, members: &[String], stepback: &str) -> String {
+        if members.is_empty() {
+            format!("{}{}", open, close)
+        } else if self.gap.is_empty() {
+            format!("{}{}{}", open, members.join(","), close)
+        } else {
+            let separator = format!(",\n{}", self.indent);
+            format!(
+                "{}\n{}{}\n{}{}",
+                open,
+                self.indent,
+                members.join(&separator),
+                stepback,
+                close
+            )
+        }
+    }
+}
+
+/// Put a string in double quotes, escaping the characters JSON does not allow in strings
+/// <https://tc39.es/ecma262/#sec-quotejsonstring>
+fn quote(string: &str) -> String {
+    let mut quoted = String::with_capacity(string.len() + 2);
+    quoted.push('"');
+    for ch in string.chars() {
+        match ch {
+            '\u{8}' => quoted.push_str("\\b"),
+            '\t' => quoted.push_str("\\t"),
+            '\n' => quoted.push_str("\\n"),
+            '\u{c}' => quoted.push_str("\\f"),
+            '\r' => quoted.push_str("\\r"),
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            ch if ch < ' ' => quoted.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => quoted.push(ch),
+        }
+    }
+    quoted.push('"');
+    quoted
 }
 
 /// Create a new `JSON` object
 pub fn _create(global: &Value) -> Value {
     let object = ValueData::new_obj(Some(global));
-    object.set_field_slice("stringify", to_value(stringify as NativeFunctionData));
-    object.set_field_slice("parse", to_value(parse as NativeFunctionData));
+    object.set_field_slice("stringify", make_builtin_fn(stringify, global));
+    object.set_field_slice("parse", make_builtin_fn(parse, global));
     object
 }
 
@@ -38,3 +333,326 @@ pub fn _create(global: &Value) -> Value {
 pub fn init(global: &Value) {
     global.set_field_slice("JSON", _create(global));
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::exec::Executor;
+    use crate::forward;
+    use crate::realm::Realm;
+
+    #[test]
+    fn stringify_compact() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+
+        assert_eq!(
+            forward(
+                &mut engine,
+                "JSON.stringify({ a: [1, 'x', null, true], b: { c: 'd' } })"
+            ),
+            r#"{"a":[1,"x",null,true],"b":{"c":"d"}}"#
+        );
+        assert_eq!(
+            forward(&mut engine, "JSON.stringify('plain')"),
+            r#""plain""#
+        );
+        assert_eq!(
+            forward(&mut engine, r#"JSON.stringify('say "hi"\\\n\t\u0001')"#),
+            r#""say \"hi\"\\\n\t\u0001""#
+        );
+        assert_eq!(forward(&mut engine, "JSON.stringify(1.5)"), "1.5");
+        assert_eq!(forward(&mut engine, "JSON.stringify({})"), "{}");
+        assert_eq!(forward(&mut engine, "JSON.stringify([])"), "[]");
+    }
+
+    #[test]
+    fn stringify_values_without_json() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+
+        // Left out of objects, and null in arrays
+        assert_eq!(
+            forward(
+                &mut engine,
+                "JSON.stringify({ a: undefined, b: function () {}, c: 1 })"
+            ),
+            r#"{"c":1}"#
+        );
+        assert_eq!(
+            forward(
+                &mut engine,
+                "JSON.stringify([undefined, function () {}, NaN, -Infinity])"
+            ),
+            "[null,null,null,null]"
+        );
+        assert_eq!(
+            forward(&mut engine, "JSON.stringify(undefined)"),
+            "undefined"
+        );
+        assert_eq!(
+            forward(&mut engine, "JSON.stringify(function () {})"),
+            "undefined"
+        );
+        assert_eq!(
+            forward(
+                &mut engine,
+                "JSON.stringify([new Number(1), new String('s'), new Boolean(false)])"
+            ),
+            r#"[1,"s",false]"#
+        );
+    }
+
+    #[test]
+    fn stringify_with_indentation() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        forward(
+            &mut engine,
+            "var nested = { a: 1, b: [1, 'two', { c: 3 }], d: { e: {}, f: [] } };",
+        );
+
+        let expected = r#"{
+  "a": 1,
+  "b": [
+    1,
+    "two",
+    {
+      "c": 3
+    }
+  ],
+  "d": {
+    "e": {},
+    "f": []
+  }
+}"#;
+        assert_eq!(
+            forward(&mut engine, "JSON.stringify(nested, null, 2)"),
+            expected
+        );
+        assert_eq!(
+            forward(&mut engine, "JSON.stringify(nested, null, '  ')"),
+            expected
+        );
+        assert_eq!(
+            forward(&mut engine, "JSON.stringify(nested, null, new Number(2))"),
+            expected
+        );
+        assert_eq!(
+            forward(&mut engine, "JSON.stringify([1, [2]], null, '--')"),
+            "[\n--1,\n--[\n----2\n--]\n]"
+        );
+        // At most 10 spaces or 10 characters are used
+        assert_eq!(
+            forward(&mut engine, "JSON.stringify({ a: 1 }, null, 20)"),
+            "{\n          \"a\": 1\n}"
+        );
+        assert_eq!(
+            forward(&mut engine, "JSON.stringify([1], null, '0123456789abc')"),
+            "[\n01234567891\n]"
+        );
+        assert_eq!(
+            forward(&mut engine, "JSON.stringify({ a: 1 }, null, 0)"),
+            r#"{"a":1}"#
+        );
+    }
+
+    #[test]
+    fn stringify_with_replacer() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        function double(key, value) {
+            if (typeof value === 'number') {
+                return value * 2;
+            }
+            if (key === 'hidden') {
+                return undefined;
+            }
+            return value;
+        }
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(
+            forward(
+                &mut engine,
+                "JSON.stringify({ a: 1, b: 'two', c: [3], hidden: true }, double)"
+            ),
+            r#"{"a":2,"b":"two","c":[6]}"#
+        );
+        // An array replacer lists the keys to keep, in order, at every level
+        assert_eq!(
+            forward(
+                &mut engine,
+                "JSON.stringify({ a: 1, b: 2, c: { a: 3, d: 4 } }, ['c', 'a', 'c'])"
+            ),
+            r#"{"c":{"a":3},"a":1}"#
+        );
+        assert_eq!(
+            forward(
+                &mut engine,
+                "JSON.stringify({ '1': 'one', '2': 'two' }, [1])"
+            ),
+            r#"{"1":"one"}"#
+        );
+        assert_eq!(
+            forward(
+                &mut engine,
+                "JSON.stringify({ x: { toJSON(key) { return 'from ' + key; } } })"
+            ),
+            r#"{"x":"from x"}"#
+        );
+    }
+
+    #[test]
+    fn stringify_cycles() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        var circular = { name: 'loop' };
+        circular.self = circular;
+        var list = [1];
+        list[1] = { back: list };
+        var shared = { n: 1 };
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(
+            forward(&mut engine, "JSON.stringify(circular)"),
+            "Error: TypeError: Converting circular structure to JSON"
+        );
+        assert_eq!(
+            forward(&mut engine, "JSON.stringify(list, null, 2)"),
+            "Error: TypeError: Converting circular structure to JSON"
+        );
+        // An object reached twice without a cycle is serialized both times
+        assert_eq!(
+            forward(&mut engine, "JSON.stringify([shared, { again: shared }])"),
+            r#"[{"n":1},{"again":{"n":1}}]"#
+        );
+        // toJSON can break a cycle
+        assert_eq!(
+            forward(
+                &mut engine,
+                "circular.toJSON = function () { return this.name; }; JSON.stringify(circular)"
+            ),
+            r#""loop""#
+        );
+    }
+
+    #[test]
+    fn stringify_to_json() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+
+        assert_eq!(
+            forward(
+                &mut engine,
+                "JSON.stringify({ toJSON() { return 42; } }) === '42'"
+            ),
+            "true"
+        );
+        assert_eq!(
+            forward(
+                &mut engine,
+                "JSON.stringify([{ toJSON(key) { return { at: key }; } }])"
+            ),
+            r#"[{"at":"0"}]"#
+        );
+        assert_eq!(
+            forward(
+                &mut engine,
+                "JSON.stringify({ a: { toJSON() { return undefined; } } })"
+            ),
+            "{}"
+        );
+        assert_eq!(
+            forward(
+                &mut engine,
+                "JSON.stringify({ toJSON: 'not a function', b: 1 })"
+            ),
+            r#"{"toJSON":"not a function","b":1}"#
+        );
+    }
+
+    #[test]
+    fn parse_arrays_and_objects() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+
+        assert_eq!(forward(&mut engine, "JSON.parse('[1,2,3]').length"), "3");
+        assert_eq!(
+            forward(&mut engine, "Array.isArray(JSON.parse('[]'))"),
+            "true"
+        );
+        assert_eq!(
+            forward(
+                &mut engine,
+                "JSON.parse('[[1, [2]], []]')[0][1].concat(3).join()"
+            ),
+            "2,3"
+        );
+        assert_eq!(
+            forward(
+                &mut engine,
+                r#"JSON.parse('{"a": {"b": [true]}}').a.b.length"#
+            ),
+            "1"
+        );
+        assert_eq!(
+            forward(&mut engine, r#"JSON.parse('{"a": 1}').hasOwnProperty('a')"#),
+            "true"
+        );
+        assert_eq!(forward(&mut engine, "JSON.parse(' \"text\" ')"), "text");
+    }
+
+    #[test]
+    fn parse_with_reviver() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        let visited = [];
+        let revived = JSON.parse('{"keep": 1, "drop": 2, "list": [1, 2, 3]}', function (key, value) {
+            visited.push(key);
+            if (key === 'drop') {
+                return undefined;
+            }
+            if (typeof value === 'number') {
+                return value * 10;
+            }
+            return value;
+        });
+        "#;
+        forward(&mut engine, init);
+        assert_eq!(forward(&mut engine, "revived.keep"), "10");
+        assert_eq!(
+            forward(&mut engine, "revived.hasOwnProperty('drop')"),
+            "false"
+        );
+        assert_eq!(forward(&mut engine, "revived.list.join()"), "10,20,30");
+        // Nested values are revived before what contains them, and the root last
+        assert_eq!(
+            forward(&mut engine, "visited.join()"),
+            "drop,keep,0,1,2,list,"
+        );
+    }
+
+    #[test]
+    fn parse_malformed() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+
+        assert!(forward(&mut engine, "JSON.parse('{')").starts_with("Error: SyntaxError: "));
+        assert!(forward(&mut engine, "JSON.parse('[1,]')").starts_with("Error: SyntaxError: "));
+        let scenario = r#"
+        let caught;
+        try {
+            JSON.parse('{');
+        } catch (e) {
+            caught = e;
+        }
+        caught.name
+        "#;
+        assert_eq!(forward(&mut engine, scenario), "SyntaxError");
+    }
+}
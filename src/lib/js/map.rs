@@ -0,0 +1,408 @@
+//! The `Map` object
+//!
+//! Entries are kept in insertion order in an internal list object, whose elements are
+//! `{ key, value }` records. Deleting an entry leaves a hole in the list rather than shifting
+//! the entries after it, so a `forEach` in progress neither skips nor repeats entries.
+//! <https://tc39.es/ecma262/#sec-map-objects>
+use crate::{
+    exec::Interpreter,
+    js::{
+        error,
+        function::make_builtin_fn,
+        object::{Object, ObjectKind, PROTOTYPE},
+        property::Property,
+        value::{from_value, same_value_zero, to_value, ResultValue, Value, ValueData},
+    },
+};
+use gc::Gc;
+
+/// Create a new `Map` - [[Construct]]
+///
+/// Iterables are not supported yet, but another `Map` or an array of `[key, value]` pairs is.
+/// <https://tc39.es/ecma262/#sec-map-iterable>
+pub fn make_map(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let entries = ValueData::new_obj(None);
+    entries.set_field_slice("length", to_value(0_i32));
+    this.set_internal_slot("MapData", entries);
+    this.set_internal_slot("MapSize", to_value(0_i32));
+
+    if let Some(iterable) = args.first() {
+        if let Some(pairs) = self::entries(iterable) {
+            for (key, value) in pairs {
+                set_entry(this, key, value);
+            }
+        } else if iterable.is_object() {
+            let length: i32 = from_value(iterable.get_field_slice("length")).unwrap_or(0);
+            for n in 0..length {
+                let entry = iterable.get_field(&n.to_string());
+                if !entry.is_object() {
+                    return Err(error::new_error(
+                        "TypeError",
+                        &format!("Iterator value {} is not an entry object", entry),
+                        ctx,
+                    ));
+                }
+                set_entry(this, entry.get_field_slice("0"), entry.get_field_slice("1"));
+            }
+        }
+    }
+    Ok(this.clone())
+}
+
+/// Calling `Map` without `new` is a TypeError - [[Call]]
+pub fn call_map(_: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    Err(error::new_error(
+        "TypeError",
+        "Constructor Map requires 'new'",
+        ctx,
+    ))
+}
+
+/// Get the entry list of `this`, throwing a TypeError if it is not a `Map`
+fn map_data(this: &Value, method: &str, ctx: &Interpreter) -> Result<Value, Value> {
+    let entries = this.get_internal_slot("MapData");
+    if entries.is_undefined() {
+        return Err(error::new_error(
+            "TypeError",
+            &format!("Map.prototype.{} called on incompatible receiver", method),
+            ctx,
+        ));
+    }
+    Ok(entries)
+}
+
+/// Find the position of the entry whose key is SameValueZero to `key`
+fn find_entry(entries: &Value, key: &Value) -> Option<i32> {
+    let length: i32 = from_value(entries.get_field_slice("length")).unwrap();
+    (0..length).find(|n| {
+        let index = n.to_string();
+        entries.has_field(&index)
+            && same_value_zero(&entries.get_field(&index).get_field_slice("key"), key)
+    })
+}
+
+/// Get the `[key, value]` pairs of `map` in insertion order, or `None` if it is not a `Map`
+pub fn entries(map: &Value) -> Option<Vec<(Value, Value)>> {
+    let entries = map.get_internal_slot("MapData");
+    if entries.is_undefined() {
+        return None;
+    }
+    let length: i32 = from_value(entries.get_field_slice("length")).unwrap();
+    Some(
+        (0..length)
+            .map(|n| n.to_string())
+            .filter(|index| entries.has_field(index))
+            .map(|index| {
+                let entry = entries.get_field(&index);
+                (entry.get_field_slice("key"), entry.get_field_slice("value"))
+            })
+            .collect(),
+    )
+}
+
+/// Add an entry for `key` to the `Map` `this`, or replace the value of its existing entry
+fn set_entry(this: &Value, key: Value, value: Value) {
+    let entries = this.get_internal_slot("MapData");
+    if let Some(n) = find_entry(&entries, &key) {
+        entries
+            .get_field(&n.to_string())
+            .set_field_slice("value", value);
+        return;
+    }
+
+    // -0 keys are normalised to +0
+    let key = match *key {
+        ValueData::Number(0.0) => to_value(0_i32),
+        _ => key,
+    };
+    let entry = ValueData::new_obj(None);
+    entry.set_field_slice("key", key);
+    entry.set_field_slice("value", value);
+
+    let length: i32 = from_value(entries.get_field_slice("length")).unwrap();
+    entries.set_field(length.to_string(), entry);
+    entries.set_field_slice("length", to_value(length + 1));
+    let size: i32 = from_value(this.get_internal_slot("MapSize")).unwrap();
+    this.set_internal_slot("MapSize", to_value(size + 1));
+}
+
+/// Map.prototype.set ( key, value )
+///
+/// Sets the value for `key`, returning the `Map`.
+/// <https://tc39.es/ecma262/#sec-map.prototype.set>
+pub fn set(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    map_data(this, "set", ctx)?;
+    let undefined = Gc::new(ValueData::Undefined);
+    let key = args.first().cloned().unwrap_or_else(|| undefined.clone());
+    let value = args.get(1).cloned().unwrap_or(undefined);
+    set_entry(this, key, value);
+    Ok(this.clone())
+}
+
+/// Map.prototype.get ( key )
+///
+/// Returns the value for `key`, or undefined if the `Map` has no entry for it.
+/// <https://tc39.es/ecma262/#sec-map.prototype.get>
+pub fn get(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let entries = map_data(this, "get", ctx)?;
+    let key = args
+        .first()
+        .cloned()
+        .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+    Ok(match find_entry(&entries, &key) {
+        Some(n) => entries.get_field(&n.to_string()).get_field_slice("value"),
+        None => Gc::new(ValueData::Undefined),
+    })
+}
+
+/// Map.prototype.has ( key )
+///
+/// Returns whether the `Map` has an entry for `key`.
+/// <https://tc39.es/ecma262/#sec-map.prototype.has>
+pub fn has(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let entries = map_data(this, "has", ctx)?;
+    let key = args
+        .first()
+        .cloned()
+        .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+    Ok(to_value(find_entry(&entries, &key).is_some()))
+}
+
+/// Map.prototype.delete ( key )
+///
+/// Removes the entry for `key`, returning whether there was one.
+/// <https://tc39.es/ecma262/#sec-map.prototype.delete>
+pub fn delete(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let entries = map_data(this, "delete", ctx)?;
+    let key = args
+        .first()
+        .cloned()
+        .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+    match find_entry(&entries, &key) {
+        Some(n) => {
+            entries.remove_prop(&n.to_string());
+            let size: i32 = from_value(this.get_internal_slot("MapSize")).unwrap();
+            this.set_internal_slot("MapSize", to_value(size - 1));
+            Ok(to_value(true))
+        }
+        None => Ok(to_value(false)),
+    }
+}
+
+/// Map.prototype.clear ( )
+///
+/// Removes every entry of the `Map`.
+/// <https://tc39.es/ecma262/#sec-map.prototype.clear>
+pub fn clear(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let entries = map_data(this, "clear", ctx)?;
+    let length: i32 = from_value(entries.get_field_slice("length")).unwrap();
+    for n in 0..length {
+        entries.remove_prop(&n.to_string());
+    }
+    this.set_internal_slot("MapSize", to_value(0_i32));
+    Ok(Gc::new(ValueData::Undefined))
+}
+
+/// get Map.prototype.size
+///
+/// Returns the number of entries in the `Map`.
+/// <https://tc39.es/ecma262/#sec-get-map.prototype.size>
+pub fn get_size(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    map_data(this, "size", ctx)?;
+    Ok(this.get_internal_slot("MapSize"))
+}
+
+/// Map.prototype.forEach ( callbackfn [ , thisArg ] )
+///
+/// Calls `callbackfn` with the value, the key and the `Map` for each entry, in insertion
+/// order. Entries added during the iteration are visited, deleted ones are not.
+/// <https://tc39.es/ecma262/#sec-map.prototype.foreach>
+pub fn for_each(this: &Value, args: &[Value], interpreter: &mut Interpreter) -> ResultValue {
+    let entries = map_data(this, "forEach", interpreter)?;
+    let callback = match args.first() {
+        Some(callback) if callback.is_function() => callback,
+        _ => {
+            return Err(error::new_error(
+                "TypeError",
+                "Map.prototype.forEach: callback is not a function",
+                interpreter,
+            ))
+        }
+    };
+    let this_arg = args
+        .get(1)
+        .cloned()
+        .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+
+    // The length is read on every step so entries added by the callback are visited
+    let mut n = 0;
+    while n < from_value::<i32>(entries.get_field_slice("length")).unwrap() {
+        let index = n.to_string();
+        if entries.has_field(&index) {
+            let entry = entries.get_field(&index);
+            let arguments = vec![
+                entry.get_field_slice("value"),
+                entry.get_field_slice("key"),
+                this.clone(),
+            ];
+            interpreter.call(callback, &this_arg, arguments)?;
+        }
+        n += 1;
+    }
+    Ok(Gc::new(ValueData::Undefined))
+}
+
+/// Create a new `Map` object
+pub fn create_constructor(global: &Value) -> Value {
+    let mut map_constructor = Object::default();
+    map_constructor.kind = ObjectKind::Function;
+    map_constructor.set_internal_method("construct", make_map);
+    map_constructor.set_internal_method("call", call_map);
+
+    // Create prototype
+    let proto = ValueData::new_obj(Some(global));
+    proto.set_field_slice("set", make_builtin_fn(set, global));
+    proto.set_field_slice("get", make_builtin_fn(get, global));
+    proto.set_field_slice("has", make_builtin_fn(has, global));
+    proto.set_field_slice("delete", make_builtin_fn(delete, global));
+    proto.set_field_slice("clear", make_builtin_fn(clear, global));
+    proto.set_field_slice("forEach", make_builtin_fn(for_each, global));
+    proto.set_prop_slice(
+        "size",
+        Property::default().get(make_builtin_fn(get_size, global)),
+    );
+
+    let map = to_value(map_constructor);
+    proto.set_field_slice("constructor", map.clone());
+    map.set_field_slice(PROTOTYPE, proto);
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::exec::Executor;
+    use crate::forward;
+    use crate::realm::Realm;
+
+    #[test]
+    fn set_get_has_delete() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const map = new Map();
+        map.set('a', 1).set('b', 2);
+        map.set('a', 3);
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "map.get('a')"), "3");
+        assert_eq!(forward(&mut engine, "map.get('c')"), "undefined");
+        assert_eq!(forward(&mut engine, "map.has('b')"), "true");
+        assert_eq!(forward(&mut engine, "map.size"), "2");
+        assert_eq!(forward(&mut engine, "map.delete('a')"), "true");
+        assert_eq!(forward(&mut engine, "map.delete('a')"), "false");
+        assert_eq!(forward(&mut engine, "map.size"), "1");
+        forward(&mut engine, "map.clear()");
+        assert_eq!(forward(&mut engine, "map.size"), "0");
+        assert_eq!(forward(&mut engine, "map.has('b')"), "false");
+    }
+
+    #[test]
+    fn keys_use_same_value_zero() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const map = new Map();
+        const key = { id: 1 };
+        const twin = { id: 1 };
+        map.set(key, 'object');
+        map.set(NaN, 'nan');
+        map.set(0, 'zero');
+        map.set('1', 'string');
+        "#;
+        forward(&mut engine, init);
+
+        // Objects are compared by identity
+        assert_eq!(forward(&mut engine, "map.get(key)"), "object");
+        assert_eq!(forward(&mut engine, "map.has(twin)"), "false");
+        // NaN matches itself and -0 matches +0
+        assert_eq!(forward(&mut engine, "map.get(NaN)"), "nan");
+        assert_eq!(forward(&mut engine, "map.get(-0)"), "zero");
+        // Keys are not converted to strings
+        assert_eq!(forward(&mut engine, "map.get(1)"), "undefined");
+        assert_eq!(forward(&mut engine, "map.size"), "4");
+    }
+
+    #[test]
+    fn for_each_in_insertion_order() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const map = new Map([['x', 1], ['y', 2], ['z', 3]]);
+        const seen = { text: '' };
+        map.forEach((value, key) => {
+            seen.text = seen.text + key + value;
+            if (key == 'x') {
+                map.delete('y');
+                map.set('w', 4);
+            }
+        });
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "seen.text"), "x1z3w4");
+        assert_eq!(
+            forward(&mut engine, "Map()"),
+            "Error: TypeError: Constructor Map requires 'new'"
+        );
+    }
+
+    #[test]
+    fn construct_and_chain() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const key = {};
+        const original = new Map([[key, 'first'], ['b', 2]]);
+        const copy = new Map(original);
+        const chained = new Map();
+        const same = chained.set(1, 'one').set(2, 'two').set(1, 'uno') === chained;
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "same"), "true");
+        assert_eq!(forward(&mut engine, "chained.size"), "2");
+        assert_eq!(forward(&mut engine, "chained.get(1)"), "uno");
+        assert_eq!(forward(&mut engine, "copy.get(key)"), "first");
+        assert_eq!(forward(&mut engine, "copy.size"), "2");
+        // A copy has entries of its own
+        forward(&mut engine, "copy.delete('b');");
+        assert_eq!(forward(&mut engine, "copy.size"), "1");
+        assert_eq!(forward(&mut engine, "original.size"), "2");
+        assert_eq!(forward(&mut engine, "original.has('b')"), "true");
+        assert_eq!(
+            forward(&mut engine, "new Map([1])"),
+            "Error: TypeError: Iterator value 1 is not an entry object"
+        );
+    }
+
+    #[test]
+    fn for_each_this_argument() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const map = new Map([['a', 1], ['b', 2]]);
+        const totals = { sum: 0 };
+        map.forEach(function (value) {
+            this.sum = this.sum + value;
+        }, totals);
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "totals.sum"), "3");
+        assert_eq!(
+            forward(&mut engine, "map.forEach(1)"),
+            "Error: TypeError: Map.prototype.forEach: callback is not a function"
+        );
+    }
+}
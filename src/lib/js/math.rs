@@ -1,19 +1,28 @@
 use crate::{
     exec::Interpreter,
     js::{
-        function::NativeFunctionData,
+        function::make_builtin_fn,
         value::{from_value, to_value, ResultValue, Value, ValueData},
     },
 };
-use rand::random;
 use std::f64;
 
+/// Get the argument at `index` as a number, or NaN if it is missing
+fn number_arg(args: &[Value], index: usize) -> f64 {
+    args.get(index).map_or(f64::NAN, |arg| arg.to_num())
+}
+
+/// Get the argument at `index` as an unsigned 32 bit integer, or 0 if it is missing
+fn uint32_arg(args: &[Value], index: usize) -> u32 {
+    args.get(index).map_or(0, |arg| arg.to_uint32())
+}
+
 /// Get the absolute value of a number
 pub fn abs(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
     Ok(to_value(if args.is_empty() {
         f64::NAN
     } else {
-        from_value::<f64>(args.get(0).unwrap().clone())
+        from_value::<f64>(args.first().unwrap().clone())
             .unwrap()
             .abs()
     }))
@@ -23,7 +32,7 @@ pub fn acos(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
     Ok(to_value(if args.is_empty() {
         f64::NAN
     } else {
-        from_value::<f64>(args.get(0).unwrap().clone())
+        from_value::<f64>(args.first().unwrap().clone())
             .unwrap()
             .acos()
     }))
@@ -33,7 +42,7 @@ pub fn asin(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
     Ok(to_value(if args.is_empty() {
         f64::NAN
     } else {
-        from_value::<f64>(args.get(0).unwrap().clone())
+        from_value::<f64>(args.first().unwrap().clone())
             .unwrap()
             .asin()
     }))
@@ -43,27 +52,33 @@ pub fn atan(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
     Ok(to_value(if args.is_empty() {
         f64::NAN
     } else {
-        from_value::<f64>(args.get(0).unwrap().clone())
+        from_value::<f64>(args.first().unwrap().clone())
             .unwrap()
             .atan()
     }))
 }
-/// Get the arctangent of a numbers
+/// Get the arctangent of the quotient of two numbers, `y` and `x`
 pub fn atan2(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
-    Ok(to_value(if args.is_empty() {
-        f64::NAN
-    } else {
-        from_value::<f64>(args.get(0).unwrap().clone())
-            .unwrap()
-            .atan2(args.get(1).unwrap().to_num())
-    }))
+    Ok(to_value(number_arg(args, 0).atan2(number_arg(args, 1))))
+}
+/// Get the hyperbolic arccosine of a number
+pub fn acosh(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    Ok(to_value(number_arg(args, 0).acosh()))
+}
+/// Get the hyperbolic arcsine of a number
+pub fn asinh(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    Ok(to_value(number_arg(args, 0).asinh()))
+}
+/// Get the hyperbolic arctangent of a number
+pub fn atanh(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    Ok(to_value(number_arg(args, 0).atanh()))
 }
 /// Get the cubic root of a number
 pub fn cbrt(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
     Ok(to_value(if args.is_empty() {
         f64::NAN
     } else {
-        from_value::<f64>(args.get(0).unwrap().clone())
+        from_value::<f64>(args.first().unwrap().clone())
             .unwrap()
             .cbrt()
     }))
@@ -73,109 +88,184 @@ pub fn ceil(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
     Ok(to_value(if args.is_empty() {
         f64::NAN
     } else {
-        from_value::<f64>(args.get(0).unwrap().clone())
+        from_value::<f64>(args.first().unwrap().clone())
             .unwrap()
             .ceil()
     }))
 }
+/// Get the number of leading zero bits of a number as an unsigned 32 bit integer
+pub fn clz32(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    Ok(to_value(uint32_arg(args, 0).leading_zeros() as i32))
+}
 /// Get the cosine of a number
 pub fn cos(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
     Ok(to_value(if args.is_empty() {
         f64::NAN
     } else {
-        from_value::<f64>(args.get(0).unwrap().clone())
+        from_value::<f64>(args.first().unwrap().clone())
             .unwrap()
             .cos()
     }))
 }
+/// Get the hyperbolic cosine of a number
+pub fn cosh(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    Ok(to_value(number_arg(args, 0).cosh()))
+}
 /// Get the power to raise the natural logarithm to get the number
 pub fn exp(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
     Ok(to_value(if args.is_empty() {
         f64::NAN
     } else {
-        from_value::<f64>(args.get(0).unwrap().clone())
+        from_value::<f64>(args.first().unwrap().clone())
             .unwrap()
             .exp()
     }))
 }
+/// Get the exponential of a number minus one, which is precise for numbers near zero
+pub fn expm1(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    Ok(to_value(number_arg(args, 0).exp_m1()))
+}
 /// Get the highest integer below a number
 pub fn floor(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
     Ok(to_value(if args.is_empty() {
         f64::NAN
     } else {
-        from_value::<f64>(args.get(0).unwrap().clone())
+        from_value::<f64>(args.first().unwrap().clone())
             .unwrap()
             .floor()
     }))
 }
+/// Round a number to the nearest single precision float
+pub fn fround(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    Ok(to_value(f64::from(number_arg(args, 0) as f32)))
+}
+/// Get the square root of the sum of the squares of the numbers, which is infinite if any
+/// of them is infinite, even if others are NaN
+pub fn hypot(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    Ok(to_value(
+        args.iter().map(|arg| arg.to_num()).fold(0.0, f64::hypot),
+    ))
+}
+/// Multiply two numbers as 32 bit integers, wrapping around
+pub fn imul(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    let a = uint32_arg(args, 0);
+    let b = uint32_arg(args, 1);
+    Ok(to_value(a.wrapping_mul(b) as i32))
+}
 /// Get the natural logarithm of a number
 pub fn log(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
     Ok(to_value(if args.is_empty() {
         f64::NAN
     } else {
-        from_value::<f64>(args.get(0).unwrap().clone())
+        from_value::<f64>(args.first().unwrap().clone())
             .unwrap()
             .log(f64::consts::E)
     }))
 }
-/// Get the maximum of several numbers
-pub fn max(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
-    let mut max = f64::NEG_INFINITY;
-    for arg in args {
-        let num = arg.to_num();
-        max = max.max(num);
-    }
-    Ok(to_value(max))
+/// Get the base 10 logarithm of a number
+pub fn log10(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    Ok(to_value(number_arg(args, 0).log10()))
 }
-/// Get the minimum of several numbers
-pub fn min(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
-    let mut max = f64::INFINITY;
-    for arg in args {
-        let num = arg.to_num();
-        max = max.min(num);
-    }
+/// Get the natural logarithm of one plus a number, which is precise for numbers near zero
+pub fn log1p(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    Ok(to_value(number_arg(args, 0).ln_1p()))
+}
+/// Get the base 2 logarithm of a number
+pub fn log2(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    Ok(to_value(number_arg(args, 0).log2()))
+}
+/// Convert every argument to a number, in order
+fn number_args(args: &[Value], ctx: &mut Interpreter) -> Result<Vec<f64>, Value> {
+    args.iter()
+        .map(|arg| Ok(ctx.to_primitive(arg, Some("number"))?.to_num()))
+        .collect()
+}
+/// Get the maximum of several numbers, which is NaN if any of them is NaN, and -Infinity if
+/// there are none. +0 is larger than -0.
+pub fn max(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let max = number_args(args, ctx)?
+        .into_iter()
+        .fold(f64::NEG_INFINITY, |max, number| {
+            if max.is_nan() || number.is_nan() {
+                f64::NAN
+            } else if number > max || (number == 0.0 && max == 0.0 && max.is_sign_negative()) {
+                number
+            } else {
+                max
+            }
+        });
     Ok(to_value(max))
 }
+/// Get the minimum of several numbers, which is NaN if any of them is NaN, and Infinity if
+/// there are none. -0 is smaller than +0.
+pub fn min(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let min = number_args(args, ctx)?
+        .into_iter()
+        .fold(f64::INFINITY, |min, number| {
+            if min.is_nan() || number.is_nan() {
+                f64::NAN
+            } else if number < min || (number == 0.0 && min == 0.0 && number.is_sign_negative()) {
+                number
+            } else {
+                min
+            }
+        });
+    Ok(to_value(min))
+}
 /// Raise a number to a power
 pub fn pow(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
     Ok(to_value(if args.len() >= 2 {
-        let num: f64 = from_value(args.get(0).unwrap().clone()).unwrap();
+        let num: f64 = from_value(args.first().unwrap().clone()).unwrap();
         let power: f64 = from_value(args.get(1).unwrap().clone()).unwrap();
         num.powf(power)
     } else {
         f64::NAN
     }))
 }
-/// Generate a random floating-point number between 0 and 1
-pub fn _random(_: &Value, _: &[Value], _: &mut Interpreter) -> ResultValue {
-    Ok(to_value(random::<f64>()))
+/// Generate a random floating-point number between 0 and 1, from the random number
+/// generator of the interpreter
+pub fn random(_: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    Ok(to_value(ctx.random()))
 }
 /// Round a number to the nearest integer
 pub fn round(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
     Ok(to_value(if args.is_empty() {
         f64::NAN
     } else {
-        from_value::<f64>(args.get(0).unwrap().clone())
+        from_value::<f64>(args.first().unwrap().clone())
             .unwrap()
             .round()
     }))
 }
+/// Get the sign of a number as 1 or -1, keeping zeros and NaN as they are
+pub fn sign(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    let number = number_arg(args, 0);
+    Ok(to_value(if number == 0.0 || number.is_nan() {
+        number
+    } else {
+        number.signum()
+    }))
+}
 /// Get the sine of a number
 pub fn sin(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
     Ok(to_value(if args.is_empty() {
         f64::NAN
     } else {
-        from_value::<f64>(args.get(0).unwrap().clone())
+        from_value::<f64>(args.first().unwrap().clone())
             .unwrap()
             .sin()
     }))
 }
+/// Get the hyperbolic sine of a number
+pub fn sinh(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    Ok(to_value(number_arg(args, 0).sinh()))
+}
 /// Get the square root of a number
 pub fn sqrt(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
     Ok(to_value(if args.is_empty() {
         f64::NAN
     } else {
-        from_value::<f64>(args.get(0).unwrap().clone())
+        from_value::<f64>(args.first().unwrap().clone())
             .unwrap()
             .sqrt()
     }))
@@ -185,11 +275,19 @@ pub fn tan(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
     Ok(to_value(if args.is_empty() {
         f64::NAN
     } else {
-        from_value::<f64>(args.get(0).unwrap().clone())
+        from_value::<f64>(args.first().unwrap().clone())
             .unwrap()
             .tan()
     }))
 }
+/// Get the hyperbolic tangent of a number
+pub fn tanh(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    Ok(to_value(number_arg(args, 0).tanh()))
+}
+/// Get the integer part of a number, removing any fractional digits
+pub fn trunc(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    Ok(to_value(number_arg(args, 0).trunc()))
+}
 /// Create a new `Math` object
 pub fn _create(global: &Value) -> Value {
     let math = ValueData::new_obj(Some(global));
@@ -201,28 +299,140 @@ pub fn _create(global: &Value) -> Value {
     math.set_field_slice("SQRT1_2", to_value(0.5_f64.sqrt()));
     math.set_field_slice("SQRT2", to_value(f64::consts::SQRT_2));
     math.set_field_slice("PI", to_value(f64::consts::PI));
-    math.set_field_slice("abs", to_value(abs as NativeFunctionData));
-    math.set_field_slice("acos", to_value(acos as NativeFunctionData));
-    math.set_field_slice("asin", to_value(asin as NativeFunctionData));
-    math.set_field_slice("atan", to_value(atan as NativeFunctionData));
-    math.set_field_slice("atan2", to_value(atan2 as NativeFunctionData));
-    math.set_field_slice("cbrt", to_value(cbrt as NativeFunctionData));
-    math.set_field_slice("ceil", to_value(ceil as NativeFunctionData));
-    math.set_field_slice("cos", to_value(cos as NativeFunctionData));
-    math.set_field_slice("exp", to_value(exp as NativeFunctionData));
-    math.set_field_slice("floor", to_value(floor as NativeFunctionData));
-    math.set_field_slice("log", to_value(log as NativeFunctionData));
-    math.set_field_slice("max", to_value(max as NativeFunctionData));
-    math.set_field_slice("min", to_value(min as NativeFunctionData));
-    math.set_field_slice("pow", to_value(pow as NativeFunctionData));
-    math.set_field_slice("random", to_value(_random as NativeFunctionData));
-    math.set_field_slice("round", to_value(round as NativeFunctionData));
-    math.set_field_slice("sin", to_value(sin as NativeFunctionData));
-    math.set_field_slice("sqrt", to_value(sqrt as NativeFunctionData));
-    math.set_field_slice("tan", to_value(tan as NativeFunctionData));
+    math.set_field_slice("abs", make_builtin_fn(abs, global));
+    math.set_field_slice("acos", make_builtin_fn(acos, global));
+    math.set_field_slice("acosh", make_builtin_fn(acosh, global));
+    math.set_field_slice("asin", make_builtin_fn(asin, global));
+    math.set_field_slice("asinh", make_builtin_fn(asinh, global));
+    math.set_field_slice("atan", make_builtin_fn(atan, global));
+    math.set_field_slice("atanh", make_builtin_fn(atanh, global));
+    math.set_field_slice("atan2", make_builtin_fn(atan2, global));
+    math.set_field_slice("cbrt", make_builtin_fn(cbrt, global));
+    math.set_field_slice("ceil", make_builtin_fn(ceil, global));
+    math.set_field_slice("clz32", make_builtin_fn(clz32, global));
+    math.set_field_slice("cos", make_builtin_fn(cos, global));
+    math.set_field_slice("cosh", make_builtin_fn(cosh, global));
+    math.set_field_slice("exp", make_builtin_fn(exp, global));
+    math.set_field_slice("expm1", make_builtin_fn(expm1, global));
+    math.set_field_slice("floor", make_builtin_fn(floor, global));
+    math.set_field_slice("fround", make_builtin_fn(fround, global));
+    math.set_field_slice("hypot", make_builtin_fn(hypot, global));
+    math.set_field_slice("imul", make_builtin_fn(imul, global));
+    math.set_field_slice("log", make_builtin_fn(log, global));
+    math.set_field_slice("log1p", make_builtin_fn(log1p, global));
+    math.set_field_slice("log10", make_builtin_fn(log10, global));
+    math.set_field_slice("log2", make_builtin_fn(log2, global));
+    math.set_field_slice("max", make_builtin_fn(max, global));
+    math.set_field_slice("min", make_builtin_fn(min, global));
+    math.set_field_slice("pow", make_builtin_fn(pow, global));
+    math.set_field_slice("random", make_builtin_fn(random, global));
+    math.set_field_slice("round", make_builtin_fn(round, global));
+    math.set_field_slice("sign", make_builtin_fn(sign, global));
+    math.set_field_slice("sin", make_builtin_fn(sin, global));
+    math.set_field_slice("sinh", make_builtin_fn(sinh, global));
+    math.set_field_slice("sqrt", make_builtin_fn(sqrt, global));
+    math.set_field_slice("tan", make_builtin_fn(tan, global));
+    math.set_field_slice("tanh", make_builtin_fn(tanh, global));
+    math.set_field_slice("trunc", make_builtin_fn(trunc, global));
     math
 }
 /// Initialise the `Math` object on the global object
 pub fn init(global: &Value) {
     global.set_field_slice("Math", _create(global));
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::exec::{Executor, Interpreter};
+    use crate::forward;
+    use crate::realm::Realm;
+
+    #[test]
+    fn sign_and_rounding() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+
+        assert_eq!(forward(&mut engine, "Math.sign(-5)"), "-1");
+        assert_eq!(forward(&mut engine, "Math.sign(3)"), "1");
+        // The sign of zero is kept, so dividing by it gives an infinity of the same sign
+        assert_eq!(forward(&mut engine, "1 / Math.sign(-0)"), "-Infinity");
+        assert_eq!(forward(&mut engine, "Math.sign('x')"), "NaN");
+        assert_eq!(forward(&mut engine, "Math.trunc(-4.7)"), "-4");
+        assert_eq!(forward(&mut engine, "Math.fround(5.5)"), "5.5");
+        assert_eq!(forward(&mut engine, "Math.fround(5.05) === 5.05"), "false");
+    }
+
+    #[test]
+    fn logarithms_and_powers() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+
+        assert_eq!(forward(&mut engine, "Math.hypot(3, 4) === 5"), "true");
+        assert_eq!(forward(&mut engine, "Math.hypot()"), "0");
+        assert_eq!(forward(&mut engine, "Math.hypot(1 / 0, 'x')"), "Infinity");
+        assert_eq!(forward(&mut engine, "Math.hypot(1, 'x')"), "NaN");
+        assert_eq!(forward(&mut engine, "Math.cbrt(27)"), "3");
+        assert_eq!(forward(&mut engine, "Math.log2(8)"), "3");
+        assert_eq!(forward(&mut engine, "Math.log10(1000)"), "3");
+        assert_eq!(forward(&mut engine, "Math.expm1(0)"), "0");
+        assert_eq!(forward(&mut engine, "Math.log1p(0)"), "0");
+        assert_eq!(forward(&mut engine, "Math.log2(-1)"), "NaN");
+        assert_eq!(forward(&mut engine, "Math.atanh(1)"), "Infinity");
+        assert_eq!(forward(&mut engine, "Math.acosh(0)"), "NaN");
+        assert_eq!(forward(&mut engine, "Math.tanh(0)"), "0");
+        assert_eq!(forward(&mut engine, "Math.atan2(1)"), "NaN");
+        assert_eq!(forward(&mut engine, "Math.atan2(0, 1)"), "0");
+    }
+
+    #[test]
+    fn integer_operations() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+
+        assert_eq!(forward(&mut engine, "Math.clz32(1) === 31"), "true");
+        assert_eq!(forward(&mut engine, "Math.clz32(0)"), "32");
+        assert_eq!(forward(&mut engine, "Math.clz32(-1)"), "0");
+        assert_eq!(forward(&mut engine, "Math.imul(3, 4)"), "12");
+        assert_eq!(forward(&mut engine, "Math.imul(4294967295, 5)"), "-5");
+        assert_eq!(forward(&mut engine, "Math.imul(65536, 65536)"), "0");
+    }
+
+    #[test]
+    fn seeded_random() {
+        let sequence = |seed| {
+            let realm = Realm::create();
+            let mut engine: Interpreter = Executor::new(realm);
+            engine.set_random_seed(seed);
+            (0..3)
+                .map(|_| forward(&mut engine, "Math.random()"))
+                .collect::<Vec<String>>()
+        };
+
+        assert_eq!(sequence(7), sequence(7));
+        assert_ne!(sequence(7), sequence(8));
+
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        forward(&mut engine, "var n = Math.random();");
+        assert_eq!(forward(&mut engine, "n >= 0 && n < 1"), "true");
+    }
+
+    #[test]
+    fn min_and_max() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+
+        assert_eq!(forward(&mut engine, "Math.max(1, '2', 3)"), "3");
+        assert_eq!(forward(&mut engine, "Math.min(1, '-2', 3)"), "-2");
+        assert_eq!(forward(&mut engine, "Math.max()"), "-Infinity");
+        assert_eq!(forward(&mut engine, "Math.min()"), "Infinity");
+        assert_eq!(forward(&mut engine, "Math.max(1, 'x', 3)"), "NaN");
+        assert_eq!(forward(&mut engine, "Math.min(0 / 0, 1)"), "NaN");
+        assert_eq!(forward(&mut engine, "1 / Math.min(0, -0)"), "-Infinity");
+        assert_eq!(forward(&mut engine, "1 / Math.max(-0, 0)"), "Infinity");
+        assert_eq!(
+            forward(&mut engine, "Math.max({ valueOf() { return 7; } }, 2)"),
+            "7"
+        );
+    }
+}
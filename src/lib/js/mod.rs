@@ -4,21 +4,37 @@ pub mod array;
 pub mod boolean;
 /// The global `console` object
 pub mod console;
+/// The global `Date` object
+pub mod date;
+/// Formatting values for people to read, as a debugger or REPL shows them
+pub mod display;
 /// The global `Error` object
 pub mod error;
 /// The global `Function` object and function value representations
 pub mod function;
 /// The global `JSON` object
 pub mod json;
+/// The global `Map` object
+pub mod map;
 /// The global `Math` object
 pub mod math;
+/// The global `Number` object and the global number functions
+pub mod number;
 /// The global `Object` object
 pub mod object;
+/// The global `Promise` object
+pub mod promise;
 /// The global 'RegExp' object
 pub mod regexp;
+/// The global `Set` object
+pub mod set;
 /// The global `String` object
 pub mod string;
+/// The global `ArrayBuffer` object and typed array constructors
+pub mod typed_array;
 /// Javascript values, utility methods and conversion between Javascript values and Rust values
 pub mod value;
+/// The global `WeakRef` and `FinalizationRegistry` objects
+pub mod weak_ref;
 // Property, used by `Object`
 pub mod property;
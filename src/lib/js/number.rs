@@ -0,0 +1,871 @@
+use crate::{
+    exec::Interpreter,
+    js::{
+        error,
+        function::make_builtin_fn,
+        object::{Object, ObjectKind, PROTOTYPE},
+        property::Property,
+        value::{to_value, ResultValue, Value, ValueData},
+    },
+};
+use gc::Gc;
+use std::{f64, str::FromStr};
+
+/// The largest integer n such that n and n + 1 are both exactly representable, 2^53 - 1
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+/// Convert the first argument to a number, calling `valueOf` on objects
+fn to_number_arg(args: &[Value], ctx: &mut Interpreter) -> Result<f64, Value> {
+    match args.first() {
+        Some(arg) => Ok(ctx.to_primitive(arg, Some("number"))?.to_num()),
+        None => Ok(f64::NAN),
+    }
+}
+
+/// Create a new number object - [[Construct]]
+pub fn make_number(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let data = if args.is_empty() {
+        0.0
+    } else {
+        to_number_arg(args, ctx)?
+    };
+    this.set_kind(ObjectKind::Number);
+    this.set_internal_slot("NumberData", to_value(data));
+    Ok(this.clone())
+}
+
+/// Convert the argument to a number - [[Call]]
+/// <https://tc39.es/ecma262/#sec-number-constructor-number-value>
+pub fn call_number(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    if args.is_empty() {
+        return Ok(to_value(0_i32));
+    }
+    Ok(to_value(to_number_arg(args, ctx)?))
+}
+
+/// Get the number `this` holds, if it is a number or a `Number` object
+/// <https://tc39.es/ecma262/#sec-thisnumbervalue>
+fn this_number_value(this: &Value, method: &str, ctx: &Interpreter) -> Result<Value, Value> {
+    match **this {
+        ValueData::Number(_) | ValueData::Integer(_) => return Ok(this.clone()),
+        ValueData::Object(_) => {
+            let data = this.get_internal_slot("NumberData");
+            if !data.is_undefined() {
+                return Ok(data);
+            }
+        }
+        _ => (),
+    }
+    Err(error::new_error(
+        "TypeError",
+        &format!(
+            "Number.prototype.{} requires that 'this' be a Number",
+            method
+        ),
+        ctx,
+    ))
+}
+
+/// Convert the argument at `index` to an integer, or `None` if it is undefined
+/// <https://tc39.es/ecma262/#sec-tointegerorinfinity>
+fn to_integer_arg(
+    args: &[Value],
+    index: usize,
+    ctx: &mut Interpreter,
+) -> Result<Option<f64>, Value> {
+    let arg = match args.get(index) {
+        Some(arg) if !arg.is_undefined() => arg,
+        _ => return Ok(None),
+    };
+    let number = ctx.to_primitive(arg, Some("number"))?.to_num();
+    Ok(Some(if number.is_nan() { 0.0 } else { number.trunc() }))
+}
+
+/// The exact decimal digits of a finite, non-negative number, and how many of them are before
+/// the decimal point
+fn exact_digits(number: f64) -> (Vec<u8>, usize) {
+    // Every double has a finite decimal expansion, with at most 1074 digits after the point
+    let exact = format!("{:.1074}", number);
+    let point = exact.find('.').expect("failed to find the decimal point");
+    let digits = exact
+        .bytes()
+        .filter(|byte| *byte != b'.')
+        .map(|byte| byte - b'0')
+        .collect();
+    (digits, point)
+}
+
+/// Round `digits` to the first `len` of them, rounding ties up. Returns the rounded digits and
+/// whether carrying added a digit in front.
+fn round_digits(digits: &[u8], len: usize) -> (Vec<u8>, bool) {
+    let mut rounded: Vec<u8> = digits.iter().take(len).cloned().collect();
+    rounded.resize(len, 0);
+    let round_up = digits.get(len).is_some_and(|digit| *digit >= 5);
+    let carried = round_up
+        && rounded.iter_mut().rev().all(|digit| {
+            if *digit == 9 {
+                *digit = 0;
+                true
+            } else {
+                *digit += 1;
+                false
+            }
+        });
+    if carried {
+        rounded.insert(0, 1);
+    }
+    (rounded, carried)
+}
+
+/// Write decimal digits as a string
+fn digits_to_string(digits: &[u8]) -> String {
+    digits
+        .iter()
+        .map(|digit| char::from(b'0' + digit))
+        .collect()
+}
+
+/// Number.prototype.toString ( [ radix ] )
+///
+/// Returns the number as a string in the given base between 2 and 36, which defaults to 10.
+/// Fractions which do not end within 52 digits in that base are cut short.
+/// <https://tc39.es/ecma262/#sec-number.prototype.tostring>
+pub fn to_string(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let number = this_number_value(this, "toString", ctx)?;
+    let radix = to_integer_arg(args, 0, ctx)?.unwrap_or(10.0);
+    if !(2.0..=36.0).contains(&radix) {
+        return Err(error::new_error(
+            "RangeError",
+            "toString() radix must be between 2 and 36",
+            ctx,
+        ));
+    }
+    let value = number.to_num();
+    if radix == 10.0 || !value.is_finite() {
+        return Ok(to_value(number.to_string()));
+    }
+
+    let radix = radix as u32;
+    let mut integer = value.abs().trunc();
+    let mut fraction = value.abs().fract();
+    let mut digits = Vec::new();
+    loop {
+        let digit = (integer % f64::from(radix)) as u32;
+        digits.push(std::char::from_digit(digit, radix).expect("invalid digit"));
+        integer = (integer / f64::from(radix)).trunc();
+        if integer == 0.0 {
+            break;
+        }
+    }
+    if value < 0.0 {
+        digits.push('-');
+    }
+    let mut string: String = digits.into_iter().rev().collect();
+    if fraction > 0.0 {
+        string.push('.');
+        for _ in 0..52 {
+            fraction *= f64::from(radix);
+            let digit = fraction.trunc() as u32;
+            string.push(std::char::from_digit(digit, radix).expect("invalid digit"));
+            fraction = fraction.fract();
+            if fraction == 0.0 {
+                break;
+            }
+        }
+    }
+    Ok(to_value(string))
+}
+
+/// Number.prototype.toFixed ( fractionDigits )
+///
+/// Returns the number written with `fractionDigits` digits after the decimal point, between
+/// 0 and 100. Rounding uses the exact value of the number, so `(1.005).toFixed(2)` is `1.00`.
+/// <https://tc39.es/ecma262/#sec-number.prototype.tofixed>
+pub fn to_fixed(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let number = this_number_value(this, "toFixed", ctx)?;
+    let fraction_digits = to_integer_arg(args, 0, ctx)?.unwrap_or(0.0);
+    if !(0.0..=100.0).contains(&fraction_digits) {
+        return Err(error::new_error(
+            "RangeError",
+            "toFixed() digits argument must be between 0 and 100",
+            ctx,
+        ));
+    }
+    let value = number.to_num();
+    if !value.is_finite() || value.abs() >= 1e21 {
+        return Ok(to_value(number.to_string()));
+    }
+
+    let fraction_digits = fraction_digits as usize;
+    let (digits, point) = exact_digits(value.abs());
+    let (rounded, carried) = round_digits(&digits, point + fraction_digits);
+    let point = if carried { point + 1 } else { point };
+    let string = digits_to_string(&rounded);
+    let (integer, fraction) = string.split_at(point);
+    let sign = if value < 0.0 { "-" } else { "" };
+    if fraction.is_empty() {
+        Ok(to_value(format!("{}{}", sign, integer)))
+    } else {
+        Ok(to_value(format!("{}{}.{}", sign, integer, fraction)))
+    }
+}
+
+/// Number.prototype.toPrecision ( precision )
+///
+/// Returns the number written with `precision` significant digits, between 1 and 100, using
+/// exponential notation when the exponent is below -6 or not less than the precision.
+/// <https://tc39.es/ecma262/#sec-number.prototype.toprecision>
+pub fn to_precision(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let number = this_number_value(this, "toPrecision", ctx)?;
+    let precision = match to_integer_arg(args, 0, ctx)? {
+        Some(precision) => precision,
+        None => return Ok(to_value(number.to_string())),
+    };
+    let value = number.to_num();
+    if !value.is_finite() {
+        return Ok(to_value(number.to_string()));
+    }
+    if !(1.0..=100.0).contains(&precision) {
+        return Err(error::new_error(
+            "RangeError",
+            "toPrecision() argument must be between 1 and 100",
+            ctx,
+        ));
+    }
+
+    let precision = precision as usize;
+    let sign = if value < 0.0 { "-" } else { "" };
+    let (digits, exponent) = if value == 0.0 {
+        (vec![0; precision], 0)
+    } else {
+        let (digits, point) = exact_digits(value.abs());
+        let first = digits
+            .iter()
+            .position(|digit| *digit != 0)
+            .expect("failed to find a significant digit");
+        let (_, significant) = digits.split_at(first);
+        let (mut rounded, carried) = round_digits(significant, precision);
+        let mut exponent = point as i32 - 1 - first as i32;
+        if carried {
+            rounded.truncate(precision);
+            exponent += 1;
+        }
+        (rounded, exponent)
+    };
+
+    let string = digits_to_string(&digits);
+    let formatted = if exponent < -6 || exponent >= precision as i32 {
+        let (first, rest) = string.split_at(1);
+        let mantissa = if rest.is_empty() {
+            first.to_string()
+        } else {
+            format!("{}.{}", first, rest)
+        };
+        let exponent_sign = if exponent > 0 { "+" } else { "-" };
+        format!("{}e{}{}", mantissa, exponent_sign, exponent.abs())
+    } else if exponent == precision as i32 - 1 {
+        string
+    } else if exponent >= 0 {
+        let (integer, fraction) = string.split_at(exponent as usize + 1);
+        format!("{}.{}", integer, fraction)
+    } else {
+        format!("0.{}{}", "0".repeat((-exponent - 1) as usize), string)
+    };
+    Ok(to_value(format!("{}{}", sign, formatted)))
+}
+
+/// Number.prototype.valueOf ( )
+///
+/// Returns the number a `Number` object holds.
+/// <https://tc39.es/ecma262/#sec-number.prototype.valueof>
+pub fn value_of(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    this_number_value(this, "valueOf", ctx)
+}
+
+/// Get the first argument as a number if it is one, without converting it
+fn number_arg(args: &[Value]) -> Option<f64> {
+    match args.first().map(|arg| &**arg) {
+        Some(ValueData::Number(number)) => Some(*number),
+        Some(ValueData::Integer(integer)) => Some(f64::from(*integer)),
+        _ => None,
+    }
+}
+
+/// Number.isNaN ( number )
+///
+/// Returns whether the argument is the number NaN. Unlike the global `isNaN`, other
+/// values are not converted, so `Number.isNaN("foo")` is false.
+/// <https://tc39.es/ecma262/#sec-number.isnan>
+pub fn number_is_nan(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    Ok(to_value(number_arg(args).is_some_and(f64::is_nan)))
+}
+
+/// Number.isFinite ( number )
+///
+/// Returns whether the argument is a number other than NaN and the infinities.
+/// <https://tc39.es/ecma262/#sec-number.isfinite>
+pub fn number_is_finite(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    Ok(to_value(number_arg(args).is_some_and(f64::is_finite)))
+}
+
+/// Whether `number` is finite and has no fractional part
+/// <https://tc39.es/ecma262/#sec-isintegralnumber>
+fn is_integral(number: f64) -> bool {
+    number.is_finite() && number.trunc() == number
+}
+
+/// Number.isInteger ( number )
+///
+/// Returns whether the argument is a number with no fractional part.
+/// <https://tc39.es/ecma262/#sec-number.isinteger>
+pub fn number_is_integer(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    Ok(to_value(number_arg(args).is_some_and(is_integral)))
+}
+
+/// Number.isSafeInteger ( number )
+///
+/// Returns whether the argument is an integer which a number represents exactly,
+/// between -(2^53 - 1) and 2^53 - 1.
+/// <https://tc39.es/ecma262/#sec-number.issafeinteger>
+pub fn number_is_safe_integer(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    Ok(to_value(number_arg(args).is_some_and(|number| {
+        is_integral(number) && number.abs() <= MAX_SAFE_INTEGER
+    })))
+}
+
+/// isNaN ( number )
+///
+/// Returns whether the argument is NaN once converted to a number.
+/// <https://tc39.es/ecma262/#sec-isnan-number>
+pub fn is_nan(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    Ok(to_value(to_number_arg(args, ctx)?.is_nan()))
+}
+
+/// isFinite ( number )
+///
+/// Returns whether the argument is finite once converted to a number.
+/// <https://tc39.es/ecma262/#sec-isfinite-number>
+pub fn is_finite(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    Ok(to_value(to_number_arg(args, ctx)?.is_finite()))
+}
+
+/// Whether `ch` is white space or a line terminator, which both parse functions skip
+/// <https://tc39.es/ecma262/#prod-StrWhiteSpaceChar>
+fn is_str_white_space(ch: char) -> bool {
+    ch.is_whitespace() || ch == '\u{FEFF}'
+}
+
+/// parseInt ( string, radix )
+///
+/// Parses the leading integer of a string, in base `radix` or base 10 by default.
+/// A `0x` prefix selects base 16 when the radix is missing, 0 or 16. Parsing stops at the
+/// first character which isn't a digit, and NaN is returned if there were no digits.
+/// <https://tc39.es/ecma262/#sec-parseint-string-radix>
+pub fn parse_int(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let input = match args.first() {
+        Some(arg) => ctx.value_to_rust_string(arg)?,
+        None => String::from("undefined"),
+    };
+    let mut s = input.trim_start_matches(is_str_white_space);
+
+    let mut sign = 1.0;
+    if s.starts_with('-') {
+        sign = -1.0;
+    }
+    if s.starts_with('-') || s.starts_with('+') {
+        s = &s[1..];
+    }
+
+    let mut radix = match args.get(1) {
+        Some(radix) => radix.to_num() as i32,
+        None => 0,
+    };
+    let mut strip_prefix = true;
+    if radix != 0 {
+        if !(2..=36).contains(&radix) {
+            return Ok(to_value(f64::NAN));
+        }
+        if radix != 16 {
+            strip_prefix = false;
+        }
+    } else {
+        radix = 10;
+    }
+    if strip_prefix && (s.starts_with("0x") || s.starts_with("0X")) {
+        s = &s[2..];
+        radix = 16;
+    }
+
+    let digits: Vec<u32> = s
+        .chars()
+        .map(|ch| ch.to_digit(radix as u32))
+        .take_while(Option::is_some)
+        .map(Option::unwrap)
+        .collect();
+    if digits.is_empty() {
+        return Ok(to_value(f64::NAN));
+    }
+    let value = digits.iter().fold(0.0, |value, &digit| {
+        value * f64::from(radix) + f64::from(digit)
+    });
+    Ok(to_value(sign * value))
+}
+
+/// Parse the longest prefix of `s` which is a decimal literal, or return NaN if there is none
+fn parse_float_prefix(s: &str) -> f64 {
+    let chars: Vec<char> = s.chars().collect();
+    let count_digits = |from: usize| {
+        chars[from..]
+            .iter()
+            .take_while(|ch| ch.is_ascii_digit())
+            .count()
+    };
+
+    let mut i = 0;
+    let mut sign = 1.0;
+    if let Some(&ch) = chars.first() {
+        if ch == '-' || ch == '+' {
+            if ch == '-' {
+                sign = -1.0;
+            }
+            i += 1;
+        }
+    }
+    if s[i..].starts_with("Infinity") {
+        return sign * f64::INFINITY;
+    }
+
+    let int_digits = count_digits(i);
+    let int_part: String = chars[i..i + int_digits].iter().collect();
+    i += int_digits;
+
+    let mut frac_part = String::new();
+    if chars.get(i) == Some(&'.') {
+        let frac_digits = count_digits(i + 1);
+        frac_part = chars[i + 1..i + 1 + frac_digits].iter().collect();
+        i += 1 + frac_digits;
+    }
+    if int_part.is_empty() && frac_part.is_empty() {
+        return f64::NAN;
+    }
+
+    // The exponent is only part of the literal if it has digits
+    let mut exponent = String::from("0");
+    if chars.get(i) == Some(&'e') || chars.get(i) == Some(&'E') {
+        let mut j = i + 1;
+        let mut exponent_sign = "";
+        if chars.get(j) == Some(&'-') || chars.get(j) == Some(&'+') {
+            if chars[j] == '-' {
+                exponent_sign = "-";
+            }
+            j += 1;
+        }
+        let exponent_digits = count_digits(j);
+        if exponent_digits > 0 {
+            let digits: String = chars[j..j + exponent_digits].iter().collect();
+            exponent = format!("{}{}", exponent_sign, digits);
+        }
+    }
+
+    let literal = format!("0{}.{}0e{}", int_part, frac_part, exponent);
+    sign * f64::from_str(&literal).unwrap_or(f64::NAN)
+}
+
+/// parseFloat ( string )
+///
+/// Parses the leading decimal number of a string, which may have a fraction and an
+/// exponent or be `Infinity`. NaN is returned if the string doesn't start with a number.
+/// <https://tc39.es/ecma262/#sec-parsefloat-string>
+pub fn parse_float(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let input = match args.first() {
+        Some(arg) => ctx.value_to_rust_string(arg)?,
+        None => String::from("undefined"),
+    };
+    let trimmed = input.trim_start_matches(is_str_white_space);
+    Ok(to_value(parse_float_prefix(trimmed)))
+}
+
+/// Create a new `Number` object
+pub fn create_constructor(global: &Value) -> Value {
+    let mut number = Object::default();
+    number.kind = ObjectKind::Function;
+    number.set_internal_method("construct", make_number);
+    number.set_internal_method("call", call_number);
+
+    // Create prototype
+    // https://tc39.es/ecma262/#sec-properties-of-the-number-prototype-object
+    let number_prototype = ValueData::new_obj(Some(global));
+    number_prototype.set_kind(ObjectKind::Number);
+    number_prototype.set_internal_slot("NumberData", to_value(0_i32));
+    number_prototype.set_field_slice("toString", make_builtin_fn(to_string, global));
+    number_prototype.set_field_slice("toFixed", make_builtin_fn(to_fixed, global));
+    number_prototype.set_field_slice("toPrecision", make_builtin_fn(to_precision, global));
+    number_prototype.set_field_slice("valueOf", make_builtin_fn(value_of, global));
+
+    let number_value = to_value(number);
+    number_prototype.set_field_slice("constructor", number_value.clone());
+    number_value.set_field_slice(PROTOTYPE, number_prototype);
+    number_value.set_field_slice("isNaN", make_builtin_fn(number_is_nan, global));
+    number_value.set_field_slice("isFinite", make_builtin_fn(number_is_finite, global));
+    number_value.set_field_slice("isInteger", make_builtin_fn(number_is_integer, global));
+    number_value.set_field_slice(
+        "isSafeInteger",
+        make_builtin_fn(number_is_safe_integer, global),
+    );
+    // https://tc39.es/ecma262/#sec-properties-of-the-number-constructor
+    let constants = [
+        ("EPSILON", f64::EPSILON),
+        ("MAX_SAFE_INTEGER", MAX_SAFE_INTEGER),
+        ("MAX_VALUE", f64::MAX),
+        ("MIN_SAFE_INTEGER", -MAX_SAFE_INTEGER),
+        // The smallest positive number, which is denormalized
+        ("MIN_VALUE", f64::from_bits(1)),
+        ("NaN", f64::NAN),
+        ("NEGATIVE_INFINITY", f64::NEG_INFINITY),
+        ("POSITIVE_INFINITY", f64::INFINITY),
+    ];
+    for (name, value) in constants.iter() {
+        number_value.set_prop_slice(name, constant_property(*value));
+    }
+    number_value
+}
+
+/// A property holding a number which cannot be changed
+fn constant_property(value: f64) -> Property {
+    Property::default()
+        .value(to_value(value))
+        .writable(false)
+        .enumerable(false)
+        .configurable(false)
+}
+
+/// Initialise the global number values and functions on the global object
+pub fn init(global: &Value) {
+    // https://tc39.es/ecma262/#sec-value-properties-of-the-global-object
+    global.set_prop_slice("NaN", constant_property(f64::NAN));
+    global.set_prop_slice("Infinity", constant_property(f64::INFINITY));
+    global.set_prop_slice(
+        "undefined",
+        Property::default()
+            .value(Gc::new(ValueData::Undefined))
+            .writable(false)
+            .enumerable(false)
+            .configurable(false),
+    );
+    global.set_field_slice("isNaN", make_builtin_fn(is_nan, global));
+    global.set_field_slice("isFinite", make_builtin_fn(is_finite, global));
+    let parse_int_func = make_builtin_fn(parse_int, global);
+    parse_int_func.set_field_slice("length", to_value(2_i32));
+    global.set_field_slice("parseInt", parse_int_func);
+    let parse_float_func = make_builtin_fn(parse_float, global);
+    parse_float_func.set_field_slice("length", to_value(1_i32));
+    global.set_field_slice("parseFloat", parse_float_func);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::exec::Executor;
+    use crate::forward;
+    use crate::realm::Realm;
+
+    #[test]
+    fn parse_int() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+
+        assert_eq!(forward(&mut engine, "parseInt('  0x1F') === 31"), "true");
+        assert_eq!(forward(&mut engine, "parseInt('12px') === 12"), "true");
+        assert_eq!(forward(&mut engine, "parseInt('z', 36) === 35"), "true");
+        assert_eq!(forward(&mut engine, "parseInt('-101', 2)"), "-5");
+        assert_eq!(forward(&mut engine, "parseInt('0x1F', 10)"), "0");
+        assert_eq!(forward(&mut engine, "parseInt('0x1F', 16)"), "31");
+        assert_eq!(forward(&mut engine, "parseInt('12', 1)"), "NaN");
+        assert_eq!(forward(&mut engine, "parseInt('')"), "NaN");
+        assert_eq!(
+            forward(&mut engine, "parseInt('') !== parseInt('')"),
+            "true"
+        );
+        assert_eq!(forward(&mut engine, "parseInt('px')"), "NaN");
+    }
+
+    #[test]
+    fn parse_float() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+
+        assert_eq!(forward(&mut engine, "parseFloat('3.5e2') === 350"), "true");
+        assert_eq!(forward(&mut engine, "parseFloat('  -1.25rem')"), "-1.25");
+        assert_eq!(forward(&mut engine, "parseFloat('.5')"), "0.5");
+        assert_eq!(forward(&mut engine, "parseFloat('5.')"), "5");
+        assert_eq!(forward(&mut engine, "parseFloat('1e')"), "1");
+        assert_eq!(
+            forward(&mut engine, "parseFloat('-Infinityx')"),
+            "-Infinity"
+        );
+        assert_eq!(forward(&mut engine, "parseFloat('e5')"), "NaN");
+        assert_eq!(forward(&mut engine, "parseFloat('.')"), "NaN");
+    }
+
+    #[test]
+    fn nan_and_finite_checks() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const nan = 0 / 0;
+        const infinity = 1 / 0;
+        "#;
+        forward(&mut engine, init);
+
+        // The globals convert their argument first
+        assert_eq!(forward(&mut engine, "isNaN('foo')"), "true");
+        assert_eq!(forward(&mut engine, "isNaN('12')"), "false");
+        assert_eq!(forward(&mut engine, "isNaN(undefined)"), "true");
+        assert_eq!(forward(&mut engine, "isNaN(nan)"), "true");
+        assert_eq!(forward(&mut engine, "isFinite('12')"), "true");
+        assert_eq!(forward(&mut engine, "isFinite(infinity)"), "false");
+        assert_eq!(forward(&mut engine, "isFinite(null)"), "true");
+
+        // The statics only accept numbers
+        assert_eq!(forward(&mut engine, "Number.isNaN('foo')"), "false");
+        assert_eq!(forward(&mut engine, "Number.isNaN(undefined)"), "false");
+        assert_eq!(forward(&mut engine, "Number.isNaN(nan)"), "true");
+        assert_eq!(forward(&mut engine, "Number.isFinite('12')"), "false");
+        assert_eq!(forward(&mut engine, "Number.isFinite(12)"), "true");
+        assert_eq!(forward(&mut engine, "Number.isFinite(infinity)"), "false");
+    }
+
+    #[test]
+    fn integer_checks() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+
+        assert_eq!(forward(&mut engine, "Number.isInteger(5)"), "true");
+        // Bitwise operators produce `Integer` values
+        assert_eq!(forward(&mut engine, "Number.isInteger(5 | 0)"), "true");
+        assert_eq!(forward(&mut engine, "Number.isSafeInteger(5 | 0)"), "true");
+        assert_eq!(forward(&mut engine, "Number.isInteger(5.5)"), "false");
+        assert_eq!(forward(&mut engine, "Number.isInteger('5')"), "false");
+        assert_eq!(forward(&mut engine, "Number.isInteger(1 / 0)"), "false");
+        assert_eq!(
+            forward(&mut engine, "Number.isSafeInteger(9007199254740991)"),
+            "true"
+        );
+        assert_eq!(
+            forward(&mut engine, "Number.isSafeInteger(9007199254740992)"),
+            "false"
+        );
+        assert_eq!(
+            forward(&mut engine, "Number.isInteger(9007199254740992)"),
+            "true"
+        );
+        assert_eq!(forward(&mut engine, "Number.isSafeInteger(0 / 0)"), "false");
+    }
+
+    #[test]
+    fn constructor() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+
+        assert_eq!(forward(&mut engine, "Number('42') + 1"), "43");
+        assert_eq!(forward(&mut engine, "Number()"), "0");
+        assert_eq!(forward(&mut engine, "new Number(7) + 1"), "8");
+        assert_eq!(forward(&mut engine, "(5).toString()"), "5");
+    }
+
+    #[test]
+    fn to_number_conversions() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+
+        let conversions = [
+            ("'  42 '", "42"),
+            ("''", "0"),
+            ("' \\n\\t '", "0"),
+            ("'-12.5'", "-12.5"),
+            ("'+.5'", "0.5"),
+            ("'1e3'", "1000"),
+            ("'0x10'", "16"),
+            ("'0b101'", "5"),
+            ("'0o17'", "15"),
+            ("'-0x10'", "NaN"),
+            ("'Infinity'", "Infinity"),
+            ("'-Infinity'", "-Infinity"),
+            ("'infinity'", "NaN"),
+            ("'NaN'", "NaN"),
+            ("'12abc'", "NaN"),
+            ("'1 2'", "NaN"),
+            ("true", "1"),
+            ("false", "0"),
+            ("null", "0"),
+            ("undefined", "NaN"),
+            ("[5]", "5"),
+            ("[]", "0"),
+            ("[1, 2]", "NaN"),
+            ("{}", "NaN"),
+        ];
+        for (value, expected) in conversions.iter() {
+            assert_eq!(
+                forward(&mut engine, &format!("Number({})", value)),
+                *expected,
+                "Number({})",
+                value
+            );
+            assert_eq!(
+                forward(&mut engine, &format!("+({})", value)),
+                *expected,
+                "+({})",
+                value
+            );
+        }
+        assert_eq!(forward(&mut engine, "Number([5]) === 5"), "true");
+    }
+
+    #[test]
+    fn to_fixed() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+
+        assert_eq!(forward(&mut engine, "(1.005).toFixed(2)"), "1.00");
+        assert_eq!(forward(&mut engine, "(0.1).toFixed(1) === '0.1'"), "true");
+        // 1.45 is stored as 1.4499999999999999555910790149937...
+        assert_eq!(forward(&mut engine, "(1.45).toFixed(1)"), "1.4");
+        assert_eq!(forward(&mut engine, "(1.25).toFixed(1)"), "1.3");
+        assert_eq!(forward(&mut engine, "(2.5).toFixed(0)"), "3");
+        assert_eq!(forward(&mut engine, "(-1.5).toFixed(0)"), "-2");
+        assert_eq!(forward(&mut engine, "(9.99).toFixed(1)"), "10.0");
+        assert_eq!(forward(&mut engine, "(123.456).toFixed()"), "123");
+        assert_eq!(forward(&mut engine, "(5 | 0).toFixed(2)"), "5.00");
+        assert_eq!(
+            forward(&mut engine, "(5).toFixed(101)"),
+            "Error: RangeError: toFixed() digits argument must be between 0 and 100"
+        );
+    }
+
+    #[test]
+    fn to_precision() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+
+        assert_eq!(forward(&mut engine, "(123.456).toPrecision(4)"), "123.5");
+        assert_eq!(forward(&mut engine, "(123.456).toPrecision(3)"), "123");
+        assert_eq!(forward(&mut engine, "(0.000123).toPrecision(2)"), "0.00012");
+        assert_eq!(forward(&mut engine, "(123456).toPrecision(2)"), "1.2e+5");
+        assert_eq!(forward(&mut engine, "(0.00000001).toPrecision(1)"), "1e-8");
+        assert_eq!(forward(&mut engine, "(99.99).toPrecision(2)"), "1.0e+2");
+        assert_eq!(forward(&mut engine, "(0).toPrecision(3)"), "0.00");
+        assert_eq!(forward(&mut engine, "(-1.5).toPrecision(1)"), "-2");
+        assert_eq!(
+            forward(&mut engine, "(1).toPrecision(0)"),
+            "Error: RangeError: toPrecision() argument must be between 1 and 100"
+        );
+    }
+
+    #[test]
+    fn to_string_radix() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+
+        assert_eq!(forward(&mut engine, "(10).toString(2) === '1010'"), "true");
+        assert_eq!(forward(&mut engine, "(255).toString(16) === 'ff'"), "true");
+        assert_eq!(forward(&mut engine, "(-255).toString(36)"), "-73");
+        assert_eq!(forward(&mut engine, "(0.5).toString(2)"), "0.1");
+        assert_eq!(forward(&mut engine, "(0).toString(2)"), "0");
+        assert_eq!(forward(&mut engine, "(255).toString()"), "255");
+        assert_eq!(
+            forward(&mut engine, "(255).toString(1)"),
+            "Error: RangeError: toString() radix must be between 2 and 36"
+        );
+    }
+
+    #[test]
+    fn global_values() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+
+        assert_eq!(forward(&mut engine, "typeof Infinity === 'number'"), "true");
+        assert_eq!(forward(&mut engine, "1 / 0 === Infinity"), "true");
+        assert_eq!(forward(&mut engine, "-Infinity"), "-Infinity");
+        assert_eq!(forward(&mut engine, "typeof NaN"), "number");
+        assert_eq!(forward(&mut engine, "isNaN(NaN)"), "true");
+        assert_eq!(forward(&mut engine, "typeof undefined"), "undefined");
+        assert_eq!(forward(&mut engine, "undefined === void 0"), "true");
+
+        forward(&mut engine, "var undefined = 1;");
+        assert_eq!(forward(&mut engine, "undefined === void 0"), "true");
+    }
+
+    #[test]
+    fn constants() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+
+        assert_eq!(
+            forward(&mut engine, "Number.MAX_SAFE_INTEGER === 9007199254740991"),
+            "true"
+        );
+        assert_eq!(
+            forward(&mut engine, "Number.MIN_SAFE_INTEGER === -9007199254740991"),
+            "true"
+        );
+        assert_eq!(
+            forward(&mut engine, "Number.POSITIVE_INFINITY === Infinity"),
+            "true"
+        );
+        assert_eq!(
+            forward(&mut engine, "Number.NEGATIVE_INFINITY === -Infinity"),
+            "true"
+        );
+        assert_eq!(forward(&mut engine, "Number.isNaN(Number.NaN)"), "true");
+        assert_eq!(forward(&mut engine, "Number.MIN_VALUE > 0"), "true");
+        assert_eq!(forward(&mut engine, "Number.MIN_VALUE / 2"), "0");
+        assert_eq!(forward(&mut engine, "Number.MAX_VALUE * 2"), "Infinity");
+        assert_eq!(forward(&mut engine, "1 + Number.EPSILON === 1"), "false");
+        assert_eq!(forward(&mut engine, "1 + Number.EPSILON / 2 === 1"), "true");
+    }
+
+    #[test]
+    fn constants_cannot_be_reassigned() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        Number.MAX_SAFE_INTEGER = 0;
+        NaN = 1;
+        Infinity = 1;
+        undefined = 1;
+        "#;
+        forward(&mut engine, init);
+        assert_eq!(
+            forward(&mut engine, "Number.MAX_SAFE_INTEGER"),
+            "9007199254740991"
+        );
+        assert_eq!(forward(&mut engine, "NaN"), "NaN");
+        assert_eq!(forward(&mut engine, "Infinity"), "Infinity");
+        assert_eq!(forward(&mut engine, "typeof undefined"), "undefined");
+        // Strict mode code throws instead
+        let strict = r#"
+        "use strict";
+        try {
+            Number.MAX_SAFE_INTEGER = 0;
+        } catch (e) {
+            e.name + ": " + e.message
+        }
+        "#;
+        assert_eq!(
+            forward(&mut engine, strict),
+            "TypeError: Cannot assign to read only property 'MAX_SAFE_INTEGER'"
+        );
+        let strict_function = r#"
+        function setNaN() {
+            "use strict";
+            NaN = 1;
+        }
+        try {
+            setNaN();
+        } catch (e) {
+            e instanceof TypeError
+        }
+        "#;
+        assert_eq!(forward(&mut engine, strict_function), "true");
+        assert_eq!(forward(&mut engine, "NaN"), "NaN");
+    }
+}
@@ -1,14 +1,21 @@
 use crate::{
     exec::Interpreter,
     js::{
-        function::NativeFunctionData,
-        property::Property,
+        array, error,
+        function::{make_builtin_fn, Function, NativeFunctionData},
+        map,
+        property::{Property, PropertyMap},
+        regexp, typed_array,
         value::{from_value, same_value, to_value, ResultValue, Value, ValueData},
     },
 };
 use gc::Gc;
 use gc_derive::{Finalize, Trace};
-use std::{borrow::Borrow, collections::HashMap, ops::Deref};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    ops::Deref,
+};
 
 pub use internal_state::{InternalState, InternalStateCell};
 
@@ -28,7 +35,7 @@ pub struct Object {
     /// Internal Slots
     pub internal_slots: Box<HashMap<String, Value>>,
     /// Properties
-    pub properties: Box<HashMap<String, Property>>,
+    pub properties: Box<PropertyMap>,
     /// Symbol Properties
     pub sym_properties: Box<HashMap<usize, Property>>,
     /// Some rust object that stores internal state
@@ -37,11 +44,12 @@ pub struct Object {
 
 impl Object {
     /// Return a new ObjectData struct, with `kind` set to Ordinary
+    #[allow(clippy::should_implement_trait)]
     pub fn default() -> Self {
         Object {
             kind: ObjectKind::Ordinary,
             internal_slots: Box::new(HashMap::new()),
-            properties: Box::new(HashMap::new()),
+            properties: Box::new(PropertyMap::new()),
             sym_properties: Box::new(HashMap::new()),
             state: None,
         }
@@ -82,7 +90,7 @@ impl Object {
         let mut obj = Object {
             kind: ObjectKind::Boolean,
             internal_slots: Box::new(HashMap::new()),
-            properties: Box::new(HashMap::new()),
+            properties: Box::new(PropertyMap::new()),
             sym_properties: Box::new(HashMap::new()),
             state: None,
         };
@@ -97,7 +105,7 @@ impl Object {
         let mut obj = Object {
             kind: ObjectKind::Number,
             internal_slots: Box::new(HashMap::new()),
-            properties: Box::new(HashMap::new()),
+            properties: Box::new(PropertyMap::new()),
             sym_properties: Box::new(HashMap::new()),
             state: None,
         };
@@ -112,7 +120,7 @@ impl Object {
         let mut obj = Object {
             kind: ObjectKind::String,
             internal_slots: Box::new(HashMap::new()),
-            properties: Box::new(HashMap::new()),
+            properties: Box::new(PropertyMap::new()),
             sym_properties: Box::new(HashMap::new()),
             state: None,
         };
@@ -123,8 +131,9 @@ impl Object {
     }
 
     // https://tc39.es/ecma262/#sec-toobject
+    #[allow(clippy::result_unit_err)]
     pub fn from(value: &Value) -> Result<Self, ()> {
-        match *value.deref().borrow() {
+        match *value.deref() {
             ValueData::Boolean(_) => Ok(Self::from_boolean(value)),
             ValueData::Number(_) => Ok(Self::from_number(value)),
             ValueData::String(_) => Ok(Self::from_string(value)),
@@ -171,9 +180,9 @@ impl Object {
     /// https://tc39.es/ecma262/#sec-ordinary-object-internal-methods-and-internal-slots-isextensible
     pub fn is_extensible(&self) -> bool {
         match self.internal_slots.get("extensible") {
-            Some(ref v) => {
+            Some(v) => {
                 // try dereferencing it: `&(*v).clone()`
-                from_value((*v).clone()).expect("boolean expected")
+                from_value(v.clone()).expect("boolean expected")
             }
             None => false,
         }
@@ -192,7 +201,7 @@ impl Object {
             // If O does not have an own property with key P, return undefined.
             // In this case we return a new empty Property
             None => Property::default(),
-            Some(ref v) => {
+            Some(v) => {
                 let mut d = Property::default();
                 if v.is_data_descriptor() {
                     d.value = v.value.clone();
@@ -229,7 +238,7 @@ impl Object {
         true
     }
 
-    #[allow(clippy::option_unwrap_used)]
+    #[allow(clippy::unwrap_used)]
     pub fn define_own_property(&mut self, property_key: String, desc: Property) -> bool {
         let mut current = self.get_own_property(&to_value(property_key.to_string()));
         let extensible = self.is_extensible();
@@ -412,6 +421,9 @@ pub enum ObjectKind {
     Ordinary,
     Boolean,
     Number,
+    ArrayBuffer,
+    TypedArray,
+    Date,
 }
 
 /// Create a new object
@@ -420,22 +432,87 @@ pub fn make_object(_: &Value, _: &[Value], _: &mut Interpreter) -> ResultValue {
 }
 
 /// Get the prototype of an object
-pub fn get_proto_of(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
-    let obj = args.get(0).expect("Cannot get object");
-    Ok(obj.get_field_slice(INSTANCE_PROTOTYPE))
+/// <https://tc39.es/ecma262/#sec-object.getprototypeof>
+pub fn get_proto_of(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let obj = match args.first() {
+        Some(obj) if !obj.is_null_or_undefined() => obj,
+        _ => {
+            return Err(error::new_error(
+                "TypeError",
+                "Cannot convert undefined or null to object",
+                ctx,
+            ))
+        }
+    };
+    let proto = obj.get_internal_slot(INSTANCE_PROTOTYPE);
+    if proto.is_undefined() {
+        Ok(Gc::new(ValueData::Null))
+    } else {
+        Ok(proto)
+    }
 }
 
 /// Set the prototype of an object
-pub fn set_proto_of(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
-    let obj = args.get(0).expect("Cannot get object").clone();
-    let proto = args.get(1).expect("Cannot get object").clone();
+/// <https://tc39.es/ecma262/#sec-object.setprototypeof>
+pub fn set_proto_of(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let obj = match args.first() {
+        Some(obj) if !obj.is_null_or_undefined() => obj.clone(),
+        _ => {
+            return Err(error::new_error(
+                "TypeError",
+                "Cannot convert undefined or null to object",
+                ctx,
+            ))
+        }
+    };
+    let proto = match args.get(1) {
+        Some(proto) if proto.is_object() || proto.is_null() => proto.clone(),
+        _ => {
+            return Err(error::new_error(
+                "TypeError",
+                "Object prototype may only be an Object or null",
+                ctx,
+            ))
+        }
+    };
+    if !obj.is_object() {
+        return Ok(obj);
+    }
+
+    // Refuse to create a cycle in the prototype chain
+    let mut ancestor = proto.clone();
+    while ancestor.is_object() {
+        if same_value(&ancestor, &obj, true) {
+            return Err(error::new_error("TypeError", "Cyclic __proto__ value", ctx));
+        }
+        ancestor = ancestor.get_internal_slot(INSTANCE_PROTOTYPE);
+    }
+
     obj.set_internal_slot(INSTANCE_PROTOTYPE, proto);
     Ok(obj)
 }
 
+/// Object.prototype.isPrototypeOf ( V )
+///
+/// Checks if this object is in the prototype chain of the argument.
+/// <https://tc39.es/ecma262/#sec-object.prototype.isprototypeof>
+pub fn is_prototype_of(this: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    let mut ancestor = match args.first() {
+        Some(value) if value.is_object() => value.get_internal_slot(INSTANCE_PROTOTYPE),
+        _ => return Ok(to_value(false)),
+    };
+    while ancestor.is_object() {
+        if same_value(&ancestor, this, true) {
+            return Ok(to_value(true));
+        }
+        ancestor = ancestor.get_internal_slot(INSTANCE_PROTOTYPE);
+    }
+    Ok(to_value(false))
+}
+
 /// Define a property in an object
 pub fn define_prop(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
-    let obj = args.get(0).expect("Cannot get object");
+    let obj = args.first().expect("Cannot get object");
     let prop = from_value::<String>(args.get(1).expect("Cannot get object").clone())
         .expect("Cannot get object");
     let desc = from_value::<Property>(args.get(2).expect("Cannot get object").clone())
@@ -444,50 +521,513 @@ pub fn define_prop(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValu
     Ok(Gc::new(ValueData::Undefined))
 }
 
-/// To string
+/// Get the string keys of the own properties of `obj` for which `filter` holds.
+///
+/// Array indices come first in ascending order, followed by the other keys in the order they
+/// were added.
+/// <https://tc39.es/ecma262/#sec-ordinaryownpropertykeys>
+pub fn own_property_keys<F>(obj: &Value, filter: F) -> Vec<String>
+where
+    F: Fn(&Property) -> bool,
+{
+    let matching_keys = |properties: &PropertyMap| -> Vec<String> {
+        properties
+            .iter()
+            .filter(|(_, prop)| filter(prop))
+            .map(|(key, _)| key.clone())
+            .collect()
+    };
+    let mut keys: Vec<String> = match **obj {
+        ValueData::Object(ref obj) => matching_keys(&obj.borrow().properties),
+        ValueData::Function(ref func) => match *(**func).borrow() {
+            Function::NativeFunc(ref func) => matching_keys(&func.object.properties),
+            Function::RegularFunc(ref func) => matching_keys(&func.object.properties),
+        },
+        _ => Vec::new(),
+    };
+    keys.sort_by(|a, b| match (a.parse::<u32>(), b.parse::<u32>()) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        // Other keys keep the order they were added in, as the sort is stable
+        (Err(_), Err(_)) => Ordering::Equal,
+    });
+    keys
+}
+
+/// Get the keys a `for-in` loop visits: the enumerable keys of `obj` followed by those of the
+/// objects it inherits from, leaving out keys which have already been seen, enumerable or not
+/// <https://tc39.es/ecma262/#sec-enumerate-object-properties>
+pub fn enumerable_keys(obj: &Value) -> Vec<String> {
+    let mut keys = Vec::new();
+    let mut seen = HashSet::new();
+    let mut current = obj.clone();
+    while current.is_object() || current.is_function() {
+        let enumerable = own_property_keys(&current, |prop| prop.enumerable != Some(false));
+        for key in own_property_keys(&current, |_| true) {
+            if seen.insert(key.clone()) && enumerable.contains(&key) {
+                keys.push(key);
+            }
+        }
+        current = current.get_internal_slot(INSTANCE_PROTOTYPE);
+    }
+    keys
+}
+
+/// Create an array of the keys of `obj` for which `filter` holds
+fn own_keys_array<F>(args: &[Value], ctx: &mut Interpreter, filter: F) -> ResultValue
+where
+    F: Fn(&Property) -> bool,
+{
+    let obj = match args.first() {
+        Some(obj) if !obj.is_null_or_undefined() => obj,
+        _ => {
+            return Err(error::new_error(
+                "TypeError",
+                "Cannot convert undefined or null to object",
+                ctx,
+            ))
+        }
+    };
+    let keys: Vec<Value> = own_property_keys(obj, filter)
+        .into_iter()
+        .map(to_value)
+        .collect();
+    let result = array::new_array(ctx)?;
+    array::add_to_array_object(&result, &keys)
+}
+
+/// Object.keys ( O )
+///
+/// Returns the names of the enumerable own properties of an object.
+/// <https://tc39.es/ecma262/#sec-object.keys>
+pub fn keys(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    own_keys_array(args, ctx, |prop| prop.enumerable != Some(false))
+}
+
+/// Object.getOwnPropertyNames ( O )
+///
+/// Returns the names of all the own properties of an object, enumerable or not.
+/// <https://tc39.es/ecma262/#sec-object.getownpropertynames>
+pub fn get_own_property_names(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    own_keys_array(args, ctx, |_| true)
+}
+
+/// Object.getOwnPropertySymbols ( O )
+///
+/// Returns the symbols of the own properties of an object. There is no `Symbol` yet, so no
+/// property can be keyed by one and the result is always empty.
+/// <https://tc39.es/ecma262/#sec-object.getownpropertysymbols>
+pub fn get_own_property_symbols(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    own_keys_array(args, ctx, |_| false)
+}
+
+/// Object.fromEntries ( iterable )
+///
+/// Creates an object from `[key, value]` pairs. The iterator protocol is not implemented yet,
+/// so the entries are read from a `Map` or by index from an array-like object.
+/// <https://tc39.es/ecma262/#sec-object.fromentries>
+pub fn from_entries(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let iterable = match args.first() {
+        Some(iterable) if iterable.is_object() => iterable,
+        Some(iterable) => {
+            return Err(error::new_error(
+                "TypeError",
+                &format!("{} is not iterable", iterable),
+                ctx,
+            ))
+        }
+        None => {
+            return Err(error::new_error(
+                "TypeError",
+                "undefined is not iterable",
+                ctx,
+            ))
+        }
+    };
+    let pairs = match map::entries(iterable) {
+        Some(pairs) => pairs,
+        None => {
+            if !iterable.has_field("length") {
+                return Err(error::new_error("TypeError", "object is not iterable", ctx));
+            }
+            let length = iterable.get_field_slice("length").to_int();
+            let mut pairs = Vec::with_capacity(length.max(0) as usize);
+            for n in 0..length {
+                let entry = iterable.get_field(&n.to_string());
+                if !entry.is_object() {
+                    return Err(error::new_error(
+                        "TypeError",
+                        &format!("Iterator value {} is not an entry object", entry),
+                        ctx,
+                    ));
+                }
+                pairs.push((entry.get_field_slice("0"), entry.get_field_slice("1")));
+            }
+            pairs
+        }
+    };
+
+    let obj = ValueData::new_obj(Some(&ctx.get_realm().global_obj));
+    for (key, value) in pairs {
+        obj.set_field(key.to_string(), value);
+    }
+    Ok(obj)
+}
+
+/// Object.prototype.toString ( )
+///
+/// Returns `[object Tag]`, where the tag names the built-in type of this value.
+/// There are no well-known symbols yet, so `Symbol.toStringTag` is not consulted.
+/// <https://tc39.es/ecma262/#sec-object.prototype.tostring>
 pub fn to_string(this: &Value, _: &[Value], _: &mut Interpreter) -> ResultValue {
-    Ok(to_value(this.to_string()))
+    let tag = match **this {
+        ValueData::Undefined => "Undefined",
+        ValueData::Null => "Null",
+        ValueData::Boolean(_) => "Boolean",
+        ValueData::Number(_) | ValueData::Integer(_) => "Number",
+        ValueData::String(_) => "String",
+        ValueData::Function(_) => "Function",
+        ValueData::ArrayBuffer(_) => "ArrayBuffer",
+        ValueData::Object(ref obj) => match obj.borrow().kind {
+            ObjectKind::Array => "Array",
+            ObjectKind::Function => "Function",
+            ObjectKind::Error => "Error",
+            ObjectKind::Boolean => "Boolean",
+            ObjectKind::Number => "Number",
+            ObjectKind::String => "String",
+            ObjectKind::Symbol => "Symbol",
+            ObjectKind::Date => "Date",
+            ObjectKind::ArrayBuffer => "ArrayBuffer",
+            ObjectKind::TypedArray => typed_array::typed_array_kind(this)
+                .map_or("Object", typed_array::TypedArrayKind::name),
+            ObjectKind::Ordinary if regexp::is_regexp(this) => "RegExp",
+            ObjectKind::Ordinary => "Object",
+        },
+    };
+    Ok(to_value(format!("[object {}]", tag)))
 }
 
-/// Check if it has a property
-pub fn has_own_prop(this: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
-    let prop = if args.is_empty() {
-        None
-    } else {
-        from_value::<String>(args.get(0).expect("Cannot get object").clone()).ok()
+/// Object.prototype.hasOwnProperty ( V )
+///
+/// Check if the object has the property itself, rather than through its prototype chain.
+/// <https://tc39.es/ecma262/#sec-object.prototype.hasownproperty>
+pub fn has_own_prop(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let prop = match args.first() {
+        Some(prop) => ctx.value_to_rust_string(prop)?,
+        None => String::from("undefined"),
     };
-    Ok(to_value(
-        prop.is_some() && this.get_prop(&prop.expect("Cannot get object")).is_some(),
-    ))
+    Ok(to_value(this.has_own_property(&prop)))
 }
 
 /// Create a new `Object` object
 pub fn _create(global: &Value) -> Value {
-    let object = to_value(make_object as NativeFunctionData);
+    let object = make_builtin_fn(make_object, global);
     let prototype = ValueData::new_obj(Some(global));
-    prototype.set_field_slice(
-        "hasOwnProperty",
-        to_value(has_own_prop as NativeFunctionData),
-    );
-    prototype.set_field_slice("toString", to_value(to_string as NativeFunctionData));
+    prototype.set_field_slice("hasOwnProperty", make_builtin_fn(has_own_prop, global));
+    prototype.set_field_slice("toString", make_builtin_fn(to_string, global));
+    prototype.set_field_slice("isPrototypeOf", make_builtin_fn(is_prototype_of, global));
     object.set_field_slice("length", to_value(1_i32));
     object.set_field_slice(PROTOTYPE, prototype);
+    object.set_field_slice("setPrototypeOf", make_builtin_fn(set_proto_of, global));
+    object.set_field_slice("getPrototypeOf", make_builtin_fn(get_proto_of, global));
+    object.set_field_slice("defineProperty", make_builtin_fn(define_prop, global));
+    object.set_field_slice("fromEntries", make_builtin_fn(from_entries, global));
+    object.set_field_slice("keys", make_builtin_fn(keys, global));
     object.set_field_slice(
-        "setPrototypeOf",
-        to_value(set_proto_of as NativeFunctionData),
-    );
-    object.set_field_slice(
-        "getPrototypeOf",
-        to_value(get_proto_of as NativeFunctionData),
+        "getOwnPropertyNames",
+        make_builtin_fn(get_own_property_names, global),
     );
     object.set_field_slice(
-        "defineProperty",
-        to_value(define_prop as NativeFunctionData),
+        "getOwnPropertySymbols",
+        make_builtin_fn(get_own_property_symbols, global),
     );
     object
 }
 
 /// Initialise the `Object` object on the global object
 pub fn init(global: &Value) {
-    global.set_field_slice("Object", _create(global));
+    let object = _create(global);
+    // `Function.prototype` is made first, as the methods here inherit from it, so it only gets
+    // `Object.prototype` to inherit from now
+    let function_prototype = global
+        .get_field_slice("Function")
+        .get_field_slice(PROTOTYPE);
+    function_prototype.set_internal_slot(INSTANCE_PROTOTYPE, object.get_field_slice(PROTOTYPE));
+    global.set_field_slice("Object", object);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::exec::{Executor, Interpreter};
+    use crate::realm::Realm;
+    use crate::{forward, forward_val, js::value::same_value};
+
+    #[test]
+    fn get_prototype_of() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        function Foo() {}
+        const foo = new Foo();
+        const plain = {};
+        "#;
+        forward(&mut engine, init);
+
+        let proto = forward_val(&mut engine, "Object.getPrototypeOf(foo)").unwrap();
+        let foo_proto = forward_val(&mut engine, "Foo.prototype").unwrap();
+        assert!(same_value(&proto, &foo_proto, true));
+
+        let proto = forward_val(&mut engine, "Object.getPrototypeOf(plain)").unwrap();
+        let object_proto = forward_val(&mut engine, "Object.prototype").unwrap();
+        assert!(same_value(&proto, &object_proto, true));
+
+        assert_eq!(
+            forward(&mut engine, "Object.getPrototypeOf(Object.prototype)"),
+            "null"
+        );
+    }
+
+    #[test]
+    fn set_prototype_of() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const proto = { greeting: "hello" };
+        const obj = {};
+        Object.setPrototypeOf(obj, proto);
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "obj.greeting"), "hello");
+        let obj_proto = forward_val(&mut engine, "Object.getPrototypeOf(obj)").unwrap();
+        let proto = forward_val(&mut engine, "proto").unwrap();
+        assert!(same_value(&obj_proto, &proto, true));
+        // Cycles are rejected
+        assert_eq!(
+            forward(&mut engine, "Object.setPrototypeOf(proto, obj)"),
+            "Error: TypeError: Cyclic __proto__ value"
+        );
+    }
+
+    #[test]
+    fn is_prototype_of() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        function Foo() {}
+        const foo = new Foo();
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(
+            forward(&mut engine, "Foo.prototype.isPrototypeOf(foo)"),
+            "true"
+        );
+        assert_eq!(
+            forward(&mut engine, "Object.prototype.isPrototypeOf(foo)"),
+            "true"
+        );
+        assert_eq!(forward(&mut engine, "foo.isPrototypeOf(foo)"), "false");
+        assert_eq!(
+            forward(&mut engine, "Foo.prototype.isPrototypeOf({})"),
+            "false"
+        );
+        assert_eq!(
+            forward(&mut engine, "Foo.prototype.isPrototypeOf(1)"),
+            "false"
+        );
+    }
+
+    #[test]
+    fn to_string_tags() {
+        let realm = Realm::create();
+        let mut engine: Interpreter = Executor::new(realm);
+        let init = r#"
+        function f() {}
+        const date = new Date(0);
+        const regexp = new RegExp('a');
+        const bytes = new Uint8Array(2);
+        "#;
+        forward(&mut engine, init);
+
+        let mut tag = |src: &str| {
+            forward(
+                &mut engine,
+                &format!("Object.prototype.toString.call({})", src),
+            )
+        };
+        assert_eq!(tag("[]"), "[object Array]");
+        assert_eq!(tag("({})"), "[object Object]");
+        assert_eq!(tag("f"), "[object Function]");
+        assert_eq!(tag("Array"), "[object Function]");
+        assert_eq!(tag("date"), "[object Date]");
+        assert_eq!(tag("regexp"), "[object RegExp]");
+        assert_eq!(tag("bytes"), "[object Uint8Array]");
+        assert_eq!(tag("new String('a')"), "[object String]");
+        assert_eq!(tag("1"), "[object Number]");
+        assert_eq!(tag("null"), "[object Null]");
+        assert_eq!(tag("undefined"), "[object Undefined]");
+        assert_eq!(forward(&mut engine, "({}).toString()"), "[object Object]");
+        assert_eq!(forward(&mut engine, "String({})"), "[object Object]");
+        assert_eq!(forward(&mut engine, "'' + {}"), "[object Object]");
+        assert_eq!(
+            forward(&mut engine, "'value: ' + { a: 1 }"),
+            "value: [object Object]"
+        );
+    }
+
+    #[test]
+    fn from_entries() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const pairs = Object.fromEntries([["a", 1], ["b", 2]]);
+        const fromMap = Object.fromEntries(new Map([["x", 42]]));
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "pairs.a"), "1");
+        assert_eq!(forward(&mut engine, "pairs.b"), "2");
+        assert_eq!(forward(&mut engine, "pairs.hasOwnProperty('a')"), "true");
+        assert_eq!(forward(&mut engine, "fromMap.x"), "42");
+        assert_eq!(
+            forward(&mut engine, "Object.fromEntries(1)"),
+            "Error: TypeError: 1 is not iterable"
+        );
+        assert_eq!(
+            forward(&mut engine, "Object.fromEntries({})"),
+            "Error: TypeError: object is not iterable"
+        );
+        assert_eq!(
+            forward(&mut engine, "Object.fromEntries([1])"),
+            "Error: TypeError: Iterator value 1 is not an entry object"
+        );
+    }
+
+    #[test]
+    fn own_property_names() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const obj = { b: 1, a: 2, 10: 'ten', 2: 'two' };
+        Object.defineProperty(obj, 'hidden', { value: 3 });
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(
+            forward(&mut engine, "Object.getOwnPropertyNames(obj).join()"),
+            "2,10,b,a,hidden"
+        );
+        assert_eq!(forward(&mut engine, "Object.keys(obj).join()"), "2,10,b,a");
+        assert_eq!(
+            forward(&mut engine, "Object.getOwnPropertyNames([5, 6]).join()"),
+            "0,1,length"
+        );
+        assert_eq!(
+            forward(&mut engine, "Object.getOwnPropertySymbols(obj).length"),
+            "0"
+        );
+        assert_eq!(
+            forward(&mut engine, "Object.getOwnPropertyNames(null)"),
+            "Error: TypeError: Cannot convert undefined or null to object"
+        );
+    }
+
+    #[test]
+    fn keys_leave_out_non_enumerable_properties() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        function f(a, b) {}
+        f.extra = 1;
+        const arrow = x => x;
+        "#;
+        forward(&mut engine, init);
+
+        // The length of arrays and the name, length and prototype of functions are not enumerable
+        assert_eq!(forward(&mut engine, "Object.keys([5, 6]).join()"), "0,1");
+        assert_eq!(forward(&mut engine, "Object.keys(f).join()"), "extra");
+        assert_eq!(
+            forward(&mut engine, "Object.getOwnPropertyNames(f).join()"),
+            "arguments,prototype,name,length,extra"
+        );
+        assert_eq!(forward(&mut engine, "Object.keys(arrow).length"), "0");
+        assert_eq!(
+            forward(&mut engine, "Object.keys(f.prototype).length"),
+            "0"
+        );
+        // Nor are the properties of built-in objects
+        assert_eq!(forward(&mut engine, "Object.keys(Math).length"), "0");
+        assert_eq!(
+            forward(&mut engine, "Object.keys(Array.prototype).length"),
+            "0"
+        );
+        assert_eq!(
+            forward(&mut engine, "Object.keys({ ...[5, 6] }).join()"),
+            "0,1"
+        );
+    }
+
+    #[test]
+    fn has_own_property() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        function Base() {
+            this.ownProp = 1;
+        }
+        Base.prototype.inheritedMethod = function () {};
+        const instance = new Base();
+        function Child() {
+            this.extra = undefined;
+        }
+        Child.prototype = instance;
+        const child = new Child();
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(
+            forward(&mut engine, "instance.hasOwnProperty('ownProp')"),
+            "true"
+        );
+        assert_eq!(
+            forward(&mut engine, "instance.hasOwnProperty('inheritedMethod')"),
+            "false"
+        );
+        assert_eq!(
+            forward(&mut engine, "typeof instance.inheritedMethod"),
+            "function"
+        );
+        assert_eq!(
+            forward(&mut engine, "instance.hasOwnProperty('hasOwnProperty')"),
+            "false"
+        );
+        assert_eq!(
+            forward(&mut engine, "child.hasOwnProperty('ownProp')"),
+            "false"
+        );
+        // Properties holding undefined are still own properties
+        assert_eq!(
+            forward(&mut engine, "child.hasOwnProperty('extra')"),
+            "true"
+        );
+        assert_eq!(
+            forward(&mut engine, "child.hasOwnProperty('missing')"),
+            "false"
+        );
+        // Keys are converted to strings
+        assert_eq!(forward(&mut engine, "[5].hasOwnProperty(0)"), "true");
+        assert_eq!(
+            forward(&mut engine, "new String('ab').hasOwnProperty('1')"),
+            "true"
+        );
+        assert_eq!(
+            forward(
+                &mut engine,
+                "Base.prototype.hasOwnProperty('inheritedMethod')"
+            ),
+            "true"
+        );
+    }
 }
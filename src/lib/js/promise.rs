@@ -0,0 +1,683 @@
+//! The `Promise` object
+//!
+//! Reactions to a promise being settled run as jobs on the interpreter's microtask queue, which
+//! is drained once the script has finished. The state of the built-in closures the spec
+//! creates, such as the resolving functions, is kept in the internal slots of their function
+//! objects, which they read back through the interpreter's active function.
+//! <https://tc39.es/ecma262/#sec-promise-objects>
+use crate::{
+    exec::Interpreter,
+    js::{
+        array, error,
+        function::{make_builtin_fn, NativeFunctionData},
+        object::{Object, ObjectKind, INSTANCE_PROTOTYPE, PROTOTYPE},
+        value::{from_value, same_value, to_value, ResultValue, Value, ValueData},
+    },
+};
+use gc::Gc;
+
+/// Create a built-in function object which runs `behaviour`, with `slots` as internal slots
+fn create_builtin_function(behaviour: NativeFunctionData, slots: &[(&str, Value)]) -> Value {
+    let mut function = Object::default();
+    function.kind = ObjectKind::Function;
+    function.set_internal_method("call", behaviour);
+    for (name, value) in slots {
+        function.set_internal_slot(name, value.clone());
+    }
+    to_value(function)
+}
+
+/// Create an object with a single field, used for state shared between built-in functions
+fn create_record(field: &str, value: Value) -> Value {
+    let record = ValueData::new_obj(None);
+    record.set_field_slice(field, value);
+    record
+}
+
+/// Create an empty list object
+fn create_list() -> Value {
+    let list = ValueData::new_obj(None);
+    list.set_field_slice("length", to_value(0_i32));
+    list
+}
+
+/// Append `value` to the list object `list`
+fn push_to_list(list: &Value, value: Value) {
+    let length: i32 = from_value(list.get_field_slice("length")).unwrap();
+    list.set_field(length.to_string(), value);
+    list.set_field_slice("length", to_value(length + 1));
+}
+
+/// Returns true if `value` is a `Promise` object
+pub fn is_promise(value: &Value) -> bool {
+    !value.get_internal_slot("PromiseState").is_undefined()
+}
+
+/// Set up the internal slots of a pending promise on `promise`
+fn initialize_promise(promise: &Value) {
+    promise.set_internal_slot("PromiseState", to_value("pending"));
+    promise.set_internal_slot("PromiseResult", Gc::new(ValueData::Undefined));
+    promise.set_internal_slot("PromiseFulfillReactions", create_list());
+    promise.set_internal_slot("PromiseRejectReactions", create_list());
+}
+
+/// Create a new pending promise, as `new Promise` would
+fn new_promise(ctx: &mut Interpreter) -> Value {
+    let global = ctx.get_realm().global_obj.clone();
+    let promise = ValueData::new_obj(Some(&global));
+    promise.set_internal_slot(
+        INSTANCE_PROTOTYPE,
+        global.get_field_slice("Promise").get_field_slice(PROTOTYPE),
+    );
+    initialize_promise(&promise);
+    promise
+}
+
+/// Abstract operation `CreateResolvingFunctions`
+///
+/// Returns the `resolve` and `reject` functions of `promise`, which only have an effect the
+/// first time either of them is called.
+/// <https://tc39.es/ecma262/#sec-createresolvingfunctions>
+fn create_resolving_functions(promise: &Value) -> (Value, Value) {
+    let already_resolved = create_record("value", to_value(false));
+    let slots = [
+        ("Promise", promise.clone()),
+        ("AlreadyResolved", already_resolved),
+    ];
+    (
+        create_builtin_function(resolve_function, &slots),
+        create_builtin_function(reject_function, &slots),
+    )
+}
+
+/// Mark the resolving functions which are being called as used, returning the promise they
+/// resolve, or `None` if they already were used
+fn take_resolving_functions(ctx: &Interpreter) -> Option<Value> {
+    let function = ctx.active_function();
+    let already_resolved = function.get_internal_slot("AlreadyResolved");
+    if already_resolved.get_field_slice("value").is_true() {
+        return None;
+    }
+    already_resolved.set_field_slice("value", to_value(true));
+    Some(function.get_internal_slot("Promise"))
+}
+
+/// Promise Resolve Functions
+/// <https://tc39.es/ecma262/#sec-promise-resolve-functions>
+fn resolve_function(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    if let Some(promise) = take_resolving_functions(ctx) {
+        let resolution = args
+            .first()
+            .cloned()
+            .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+        resolve_promise(&promise, resolution, ctx)?;
+    }
+    Ok(Gc::new(ValueData::Undefined))
+}
+
+/// Promise Reject Functions
+/// <https://tc39.es/ecma262/#sec-promise-reject-functions>
+fn reject_function(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    if let Some(promise) = take_resolving_functions(ctx) {
+        let reason = args
+            .first()
+            .cloned()
+            .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+        reject_promise(&promise, reason, ctx);
+    }
+    Ok(Gc::new(ValueData::Undefined))
+}
+
+/// Resolve `promise` with `resolution`: a thenable is followed in a later job, anything
+/// else fulfills the promise
+fn resolve_promise(promise: &Value, resolution: Value, ctx: &mut Interpreter) -> ResultValue {
+    if same_value(promise, &resolution, true) {
+        reject_promise(
+            promise,
+            error::new_error("TypeError", "Chaining cycle detected for promise", ctx),
+            ctx,
+        );
+        return Ok(Gc::new(ValueData::Undefined));
+    }
+    if !resolution.is_object() {
+        fulfill_promise(promise, resolution, ctx);
+        return Ok(Gc::new(ValueData::Undefined));
+    }
+    let then = match ctx.get_property_value(&resolution, "then") {
+        Ok(then) => then,
+        Err(reason) => {
+            reject_promise(promise, reason, ctx);
+            return Ok(Gc::new(ValueData::Undefined));
+        }
+    };
+    if !then.is_function() {
+        fulfill_promise(promise, resolution, ctx);
+        return Ok(Gc::new(ValueData::Undefined));
+    }
+
+    let job = ValueData::new_obj(None);
+    job.set_field_slice("promise", promise.clone());
+    job.set_field_slice("thenable", resolution);
+    job.set_field_slice("then", then);
+    ctx.enqueue_microtask(
+        make_builtin_fn(resolve_thenable_job, &ctx.get_realm().global_obj),
+        job,
+        Vec::new(),
+    );
+    Ok(Gc::new(ValueData::Undefined))
+}
+
+/// Abstract operation `NewPromiseResolveThenableJob`
+///
+/// Calls the `then` method of a thenable with the resolving functions of the promise it
+/// resolved, so the promise follows the thenable.
+/// <https://tc39.es/ecma262/#sec-newpromiseresolvethenablejob>
+fn resolve_thenable_job(job: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let (resolve, reject) = create_resolving_functions(&job.get_field_slice("promise"));
+    let then = job.get_field_slice("then");
+    let thenable = job.get_field_slice("thenable");
+    if let Err(reason) = ctx.call(&then, &thenable, vec![resolve, reject.clone()]) {
+        ctx.call(&reject, &Gc::new(ValueData::Undefined), vec![reason])?;
+    }
+    Ok(Gc::new(ValueData::Undefined))
+}
+
+/// Settle `promise` with `value` in the state `state`, triggering the reactions of that kind
+fn settle_promise(promise: &Value, state: &str, value: Value, ctx: &mut Interpreter) {
+    let reactions = promise.get_internal_slot(if state == "fulfilled" {
+        "PromiseFulfillReactions"
+    } else {
+        "PromiseRejectReactions"
+    });
+    promise.set_internal_slot("PromiseState", to_value(state));
+    promise.set_internal_slot("PromiseResult", value.clone());
+    promise.set_internal_slot("PromiseFulfillReactions", Gc::new(ValueData::Undefined));
+    promise.set_internal_slot("PromiseRejectReactions", Gc::new(ValueData::Undefined));
+
+    let length: i32 = from_value(reactions.get_field_slice("length")).unwrap();
+    for n in 0..length {
+        let reaction = reactions.get_field(&n.to_string());
+        enqueue_reaction_job(reaction, value.clone(), ctx);
+    }
+}
+
+/// Abstract operation `FulfillPromise`
+/// <https://tc39.es/ecma262/#sec-fulfillpromise>
+fn fulfill_promise(promise: &Value, value: Value, ctx: &mut Interpreter) {
+    settle_promise(promise, "fulfilled", value, ctx);
+}
+
+/// Abstract operation `RejectPromise`
+/// <https://tc39.es/ecma262/#sec-rejectpromise>
+fn reject_promise(promise: &Value, reason: Value, ctx: &mut Interpreter) {
+    settle_promise(promise, "rejected", reason, ctx);
+}
+
+/// Abstract operation `NewPromiseReactionJob`
+/// <https://tc39.es/ecma262/#sec-newpromisereactionjob>
+fn enqueue_reaction_job(reaction: Value, argument: Value, ctx: &mut Interpreter) {
+    ctx.enqueue_microtask(
+        make_builtin_fn(reaction_job, &ctx.get_realm().global_obj),
+        reaction,
+        vec![argument],
+    );
+}
+
+/// Run the handler of a reaction with the value its promise settled with, and settle the
+/// promise derived from it with the result
+fn reaction_job(reaction: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let argument = args
+        .first()
+        .cloned()
+        .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+    let derived = reaction.get_field_slice("promise");
+    let handler = reaction.get_field_slice("handler");
+
+    let result = if handler.is_function() {
+        ctx.call(&handler, &Gc::new(ValueData::Undefined), vec![argument])
+    } else if reaction.get_field_slice("type").to_string() == "fulfill" {
+        Ok(argument)
+    } else {
+        Err(argument)
+    };
+    match result {
+        Ok(value) => resolve_promise(&derived, value, ctx),
+        Err(reason) => {
+            reject_promise(&derived, reason, ctx);
+            Ok(Gc::new(ValueData::Undefined))
+        }
+    }
+}
+
+/// Abstract operation `PerformPromiseThen`
+///
+/// Registers the handlers on `promise`, or queues the one for its state if it is already
+/// settled, returning the promise which is settled with the result of the handler.
+/// <https://tc39.es/ecma262/#sec-performpromisethen>
+fn perform_then(
+    promise: &Value,
+    on_fulfilled: Value,
+    on_rejected: Value,
+    ctx: &mut Interpreter,
+) -> Value {
+    let derived = new_promise(ctx);
+    let create_reaction = |kind: &str, handler: Value| {
+        let reaction = ValueData::new_obj(None);
+        reaction.set_field_slice("promise", derived.clone());
+        reaction.set_field_slice("type", to_value(kind));
+        reaction.set_field_slice("handler", handler);
+        reaction
+    };
+    let fulfill_reaction = create_reaction("fulfill", on_fulfilled);
+    let reject_reaction = create_reaction("reject", on_rejected);
+
+    let result = promise.get_internal_slot("PromiseResult");
+    match promise
+        .get_internal_slot("PromiseState")
+        .to_string()
+        .as_str()
+    {
+        "pending" => {
+            push_to_list(
+                &promise.get_internal_slot("PromiseFulfillReactions"),
+                fulfill_reaction,
+            );
+            push_to_list(
+                &promise.get_internal_slot("PromiseRejectReactions"),
+                reject_reaction,
+            );
+        }
+        "fulfilled" => enqueue_reaction_job(fulfill_reaction, result, ctx),
+        _ => enqueue_reaction_job(reject_reaction, result, ctx),
+    }
+    derived
+}
+
+/// Abstract operation `PromiseResolve`
+///
+/// Returns `value` if it is a promise, otherwise a new promise resolved with it.
+/// <https://tc39.es/ecma262/#sec-promise-resolve>
+fn promise_resolve(value: Value, ctx: &mut Interpreter) -> ResultValue {
+    if is_promise(&value) {
+        return Ok(value);
+    }
+    let promise = new_promise(ctx);
+    resolve_promise(&promise, value, ctx)?;
+    Ok(promise)
+}
+
+/// Throw a TypeError unless `this` is a promise
+fn this_promise(this: &Value, method: &str, ctx: &Interpreter) -> Result<(), Value> {
+    if is_promise(this) {
+        Ok(())
+    } else {
+        Err(error::new_error(
+            "TypeError",
+            &format!(
+                "Promise.prototype.{} called on incompatible receiver",
+                method
+            ),
+            ctx,
+        ))
+    }
+}
+
+/// Create a new `Promise` - [[Construct]]
+///
+/// Calls the executor with the resolving functions of the new promise, rejecting it if the
+/// executor throws.
+/// <https://tc39.es/ecma262/#sec-promise-executor>
+pub fn make_promise(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let executor = match args.first() {
+        Some(executor) if executor.is_function() => executor,
+        _ => {
+            return Err(error::new_error(
+                "TypeError",
+                "Promise resolver is not a function",
+                ctx,
+            ))
+        }
+    };
+    initialize_promise(this);
+    let (resolve, reject) = create_resolving_functions(this);
+    let undefined = Gc::new(ValueData::Undefined);
+    if let Err(reason) = ctx.call(executor, &undefined, vec![resolve, reject.clone()]) {
+        ctx.call(&reject, &undefined, vec![reason])?;
+    }
+    Ok(this.clone())
+}
+
+/// Calling `Promise` without `new` is a TypeError - [[Call]]
+pub fn call_promise(_: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    Err(error::new_error(
+        "TypeError",
+        "Constructor Promise requires 'new'",
+        ctx,
+    ))
+}
+
+/// Promise.prototype.then ( onFulfilled, onRejected )
+///
+/// Returns a promise settled with the result of the handler for the state this promise
+/// settles in. A handler which is not a function passes the value or reason through.
+/// <https://tc39.es/ecma262/#sec-promise.prototype.then>
+pub fn then(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    this_promise(this, "then", ctx)?;
+    let undefined = Gc::new(ValueData::Undefined);
+    let on_fulfilled = args.first().cloned().unwrap_or_else(|| undefined.clone());
+    let on_rejected = args.get(1).cloned().unwrap_or(undefined);
+    Ok(perform_then(this, on_fulfilled, on_rejected, ctx))
+}
+
+/// Promise.prototype.catch ( onRejected )
+///
+/// Registers a handler for the promise being rejected, like `then(undefined, onRejected)`.
+/// <https://tc39.es/ecma262/#sec-promise.prototype.catch>
+pub fn catch(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let on_rejected = args
+        .first()
+        .cloned()
+        .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+    then(this, &[Gc::new(ValueData::Undefined), on_rejected], ctx)
+}
+
+/// Promise.prototype.finally ( onFinally )
+///
+/// Registers a handler called without arguments however the promise settles. The returned
+/// promise settles like this one, once any promise returned by the handler has fulfilled.
+/// <https://tc39.es/ecma262/#sec-promise.prototype.finally>
+pub fn finally(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let on_finally = args
+        .first()
+        .cloned()
+        .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+    if !on_finally.is_function() {
+        return then(this, &[on_finally.clone(), on_finally], ctx);
+    }
+    let slots = [("OnFinally", on_finally)];
+    let on_fulfilled = create_builtin_function(then_finally, &slots);
+    let on_rejected = create_builtin_function(catch_finally, &slots);
+    then(this, &[on_fulfilled, on_rejected], ctx)
+}
+
+/// Call the `onFinally` handler of the active function, returning a promise which is
+/// fulfilled once its result is
+fn call_on_finally(ctx: &mut Interpreter) -> ResultValue {
+    let on_finally = ctx.active_function().get_internal_slot("OnFinally");
+    let result = ctx.call(&on_finally, &Gc::new(ValueData::Undefined), Vec::new())?;
+    promise_resolve(result, ctx)
+}
+
+/// Then Finally Functions
+/// <https://tc39.es/ecma262/#sec-thenfinallyfunctions>
+fn then_finally(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let value = args
+        .first()
+        .cloned()
+        .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+    let promise = call_on_finally(ctx)?;
+    let value_thunk = create_builtin_function(return_value, &[("Value", value)]);
+    Ok(perform_then(
+        &promise,
+        value_thunk,
+        Gc::new(ValueData::Undefined),
+        ctx,
+    ))
+}
+
+/// Catch Finally Functions
+/// <https://tc39.es/ecma262/#sec-catchfinallyfunctions>
+fn catch_finally(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let reason = args
+        .first()
+        .cloned()
+        .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+    let promise = call_on_finally(ctx)?;
+    let thrower = create_builtin_function(throw_value, &[("Value", reason)]);
+    Ok(perform_then(
+        &promise,
+        thrower,
+        Gc::new(ValueData::Undefined),
+        ctx,
+    ))
+}
+
+/// Returns the value held by the active function
+fn return_value(_: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    Ok(ctx.active_function().get_internal_slot("Value"))
+}
+
+/// Throws the value held by the active function
+fn throw_value(_: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    Err(ctx.active_function().get_internal_slot("Value"))
+}
+
+/// Promise.resolve ( x )
+///
+/// Returns `x` if it is a promise, otherwise a promise resolved with it.
+/// <https://tc39.es/ecma262/#sec-promise.resolve>
+pub fn resolve(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let value = args
+        .first()
+        .cloned()
+        .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+    promise_resolve(value, ctx)
+}
+
+/// Promise.reject ( r )
+///
+/// Returns a promise rejected with `r`.
+/// <https://tc39.es/ecma262/#sec-promise.reject>
+pub fn reject(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let reason = args
+        .first()
+        .cloned()
+        .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+    let promise = new_promise(ctx);
+    reject_promise(&promise, reason, ctx);
+    Ok(promise)
+}
+
+/// Promise.all ( iterable )
+///
+/// Returns a promise fulfilled with an array of the values of every element once they have
+/// all fulfilled, or rejected with the reason of the first one to be rejected. Only arrays
+/// are supported as the iterable.
+/// <https://tc39.es/ecma262/#sec-promise.all>
+pub fn all(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let iterable = args
+        .first()
+        .cloned()
+        .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+    let promise = new_promise(ctx);
+    let (resolve, reject) = create_resolving_functions(&promise);
+    let length: i32 = match from_value(iterable.get_field_slice("length")) {
+        Ok(length) if iterable.is_object() => length,
+        _ => {
+            let reason = error::new_error("TypeError", "Promise.all requires an array", ctx);
+            ctx.call(&reject, &Gc::new(ValueData::Undefined), vec![reason])?;
+            return Ok(promise);
+        }
+    };
+
+    let values = array::new_array(ctx)?;
+    let holes: Vec<Value> = (0..length).map(|_| Gc::new(ValueData::Undefined)).collect();
+    array::add_to_array_object(&values, &holes)?;
+    // One more than the number of elements left, so the promise is not resolved while
+    // the elements are still being visited
+    let remaining = create_record("count", to_value(length + 1));
+
+    for n in 0..length {
+        let element = promise_resolve(iterable.get_field(&n.to_string()), ctx)?;
+        let resolve_element = create_builtin_function(
+            resolve_element_function,
+            &[
+                ("Index", to_value(n)),
+                ("Values", values.clone()),
+                ("Remaining", remaining.clone()),
+                ("Resolve", resolve.clone()),
+                ("AlreadyCalled", create_record("value", to_value(false))),
+            ],
+        );
+        perform_then(&element, resolve_element, reject.clone(), ctx);
+    }
+
+    if decrement_remaining(&remaining) {
+        ctx.call(&resolve, &Gc::new(ValueData::Undefined), vec![values])?;
+    }
+    Ok(promise)
+}
+
+/// Count one element of `Promise.all` as fulfilled, returning true if it was the last one
+fn decrement_remaining(remaining: &Value) -> bool {
+    let count: i32 = from_value(remaining.get_field_slice("count")).unwrap();
+    remaining.set_field_slice("count", to_value(count - 1));
+    count == 1
+}
+
+/// Promise.all Resolve Element Functions
+/// <https://tc39.es/ecma262/#sec-promise.all-resolve-element-functions>
+fn resolve_element_function(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let function = ctx.active_function();
+    let already_called = function.get_internal_slot("AlreadyCalled");
+    if already_called.get_field_slice("value").is_true() {
+        return Ok(Gc::new(ValueData::Undefined));
+    }
+    already_called.set_field_slice("value", to_value(true));
+
+    let value = args
+        .first()
+        .cloned()
+        .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+    let index: i32 = from_value(function.get_internal_slot("Index")).unwrap();
+    let values = function.get_internal_slot("Values");
+    values.set_field(index.to_string(), value);
+
+    if decrement_remaining(&function.get_internal_slot("Remaining")) {
+        let resolve = function.get_internal_slot("Resolve");
+        ctx.call(&resolve, &Gc::new(ValueData::Undefined), vec![values])?;
+    }
+    Ok(Gc::new(ValueData::Undefined))
+}
+
+/// Create a new `Promise` object
+pub fn create_constructor(global: &Value) -> Value {
+    let mut promise_constructor = Object::default();
+    promise_constructor.kind = ObjectKind::Function;
+    promise_constructor.set_internal_method("construct", make_promise);
+    promise_constructor.set_internal_method("call", call_promise);
+
+    // Create prototype
+    let proto = ValueData::new_obj(Some(global));
+    proto.set_field_slice("then", make_builtin_fn(then, global));
+    proto.set_field_slice("catch", make_builtin_fn(catch, global));
+    proto.set_field_slice("finally", make_builtin_fn(finally, global));
+
+    let promise = to_value(promise_constructor);
+    proto.set_field_slice("constructor", promise.clone());
+    promise.set_field_slice(PROTOTYPE, proto);
+    promise.set_field_slice("resolve", make_builtin_fn(resolve, global));
+    promise.set_field_slice("reject", make_builtin_fn(reject, global));
+    promise.set_field_slice("all", make_builtin_fn(all, global));
+    promise
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::exec::Executor;
+    use crate::forward;
+    use crate::realm::Realm;
+
+    #[test]
+    fn then_runs_after_the_script() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const log = { text: '' };
+        const promise = new Promise((resolve, reject) => {
+            log.text = log.text + 'executor;';
+            resolve(42);
+        });
+        promise.then(value => {
+            log.text = log.text + 'then ' + value + ';';
+        });
+        log.text = log.text + 'script;';
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "log.text"), "executor;script;then 42;");
+    }
+
+    #[test]
+    fn chaining_and_rejection() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const log = { text: '' };
+        Promise.resolve(1)
+            .then(value => value + 1)
+            .then(value => Promise.resolve(value * 10))
+            .then(value => {
+                log.text = log.text + 'value ' + value + ';';
+                throw 'oops';
+            })
+            .then(value => {
+                log.text = log.text + 'skipped;';
+            })
+            .catch(reason => {
+                log.text = log.text + 'caught ' + reason + ';';
+                return 'recovered';
+            })
+            .finally(() => {
+                log.text = log.text + 'finally;';
+            })
+            .then(value => {
+                log.text = log.text + value + ';';
+            });
+        new Promise((resolve, reject) => {
+            throw 'executor';
+        }).catch(reason => {
+            log.text = log.text + 'rejected ' + reason + ';';
+        });
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(
+            forward(&mut engine, "log.text"),
+            "rejected executor;value 20;caught oops;finally;recovered;"
+        );
+    }
+
+    #[test]
+    fn static_methods() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const log = { text: '' };
+        const pending = new Promise((resolve, reject) => {
+            log.resolve = resolve;
+        });
+        Promise.all([1, Promise.resolve(2), pending]).then(values => {
+            log.text = log.text + 'all ' + values.join() + ';';
+        });
+        Promise.all([]).then(values => {
+            log.text = log.text + 'empty ' + values.length + ';';
+        });
+        Promise.all([1, Promise.reject('no')]).catch(reason => {
+            log.text = log.text + 'all rejected ' + reason + ';';
+        });
+        log.resolve(3);
+        log.resolve(4);
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(
+            forward(&mut engine, "log.text"),
+            "empty 0;all rejected no;all 1,2,3;"
+        );
+        assert_eq!(
+            forward(&mut engine, "Promise()"),
+            "Error: TypeError: Constructor Promise requires 'new'"
+        );
+    }
+}
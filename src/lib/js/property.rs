@@ -1,6 +1,7 @@
 use crate::js::value::{from_value, to_value, FromValue, ToValue, Value, ValueData};
 use gc::Gc;
 use gc_derive::{Finalize, Trace};
+use std::collections::HashMap;
 
 /// A Javascript Property AKA The Property Descriptor   
 /// [[SPEC] - The Property Descriptor Specification Type](https://tc39.github.io/ecma262/#sec-property-descriptor-specification-type)   
@@ -189,6 +190,82 @@ impl FromValue for Property {
     }
 }
 
+/// The properties of an object, which are visited in the order they were added
+/// <https://tc39.es/ecma262/#sec-ordinaryownpropertykeys>
+#[derive(Trace, Finalize, Clone, Debug, Default)]
+pub struct PropertyMap {
+    properties: HashMap<String, Property>,
+    /// Every key, in the order it was added
+    order: Vec<String>,
+}
+
+impl PropertyMap {
+    /// Make an empty property map
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the property `key`, which keeps its place if it is already there
+    pub fn insert(&mut self, key: String, prop: Property) -> Option<Property> {
+        let old = self.properties.insert(key.clone(), prop);
+        if old.is_none() {
+            self.order.push(key);
+        }
+        old
+    }
+
+    /// Get the property `key`
+    pub fn get(&self, key: &str) -> Option<&Property> {
+        self.properties.get(key)
+    }
+
+    /// Get the property `key` to change it
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Property> {
+        self.properties.get_mut(key)
+    }
+
+    /// Whether there is a property `key`
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.properties.contains_key(key)
+    }
+
+    /// Remove the property `key`
+    pub fn remove(&mut self, key: &str) -> Option<Property> {
+        let old = self.properties.remove(key);
+        if old.is_some() {
+            self.order.retain(|other| other != key);
+        }
+        old
+    }
+
+    /// The keys and properties, in the order they were added
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Property)> {
+        self.order
+            .iter()
+            .map(move |key| (key, &self.properties[key]))
+    }
+
+    /// The keys, in the order they were added
+    pub fn keys(&self) -> impl Iterator<Item = &String> {
+        self.order.iter()
+    }
+
+    /// The properties, in no particular order, to change them
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut Property> {
+        self.properties.values_mut()
+    }
+
+    /// The number of properties
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    /// Whether there are no properties
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -6,8 +6,9 @@ use regex::Regex;
 use crate::{
     exec::Interpreter,
     js::{
-        function::NativeFunctionData,
-        object::{InternalState, Object, ObjectKind, PROTOTYPE},
+        array, error,
+        function::{make_builtin_fn, NativeFunctionData},
+        object::{InternalState, Object, ObjectKind, INSTANCE_PROTOTYPE, PROTOTYPE},
         property::Property,
         value::{from_value, to_value, FromValue, ResultValue, Value, ValueData},
     },
@@ -44,15 +45,49 @@ fn get_argument<T: FromValue>(args: &[Value], idx: usize) -> Result<T, Value> {
     }
 }
 
-/// Create a new `RegExp`
-pub fn make_regexp(this: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
-    if args.is_empty() {
-        return Err(Gc::new(ValueData::Undefined));
+/// Rewrite the parts of a JavaScript pattern which the `regex` crate spells differently:
+/// escaped characters which have no special meaning, like `\/`, and named groups
+fn translate_pattern(body: &str) -> String {
+    let mut pattern = String::with_capacity(body.len());
+    let mut chars = body.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                // The `regex` crate rejects escapes of characters which are not meta characters
+                Some('/') => pattern.push('/'),
+                Some(escaped) => {
+                    pattern.push('\\');
+                    pattern.push(escaped);
+                }
+                None => pattern.push('\\'),
+            },
+            '(' if chars.peek() == Some(&'?') => {
+                pattern.push('(');
+                pattern.push(chars.next().expect("failed to get group prefix"));
+                // `(?<name>` is written `(?P<name>`, but lookbehinds `(?<=` and `(?<!` are not
+                let mut lookahead = chars.clone();
+                if lookahead.next() == Some('<') {
+                    match lookahead.next() {
+                        Some('=') | Some('!') => (),
+                        _ => pattern.push('P'),
+                    }
+                }
+            }
+            _ => pattern.push(c),
+        }
     }
+    pattern
+}
+
+/// Create a new `RegExp`
+pub fn make_regexp(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     let mut regex_body = String::new();
     let mut regex_flags = String::new();
-    #[allow(clippy::indexing_slicing)] // length has been checked
-    match args[0].deref() {
+    let undefined = Gc::new(ValueData::Undefined);
+    let pattern_arg = args.first().unwrap_or(&undefined);
+    match pattern_arg.deref() {
+        // A missing pattern matches the empty string
+        ValueData::Undefined => {}
         ValueData::String(ref body) => {
             // first argument is a string -> use it as regex pattern
             regex_body = body.into();
@@ -67,18 +102,16 @@ pub fn make_regexp(this: &Value, args: &[Value], _: &mut Interpreter) -> ResultV
                 if let Some(flags) = slots.get("OriginalFlags") {
                     regex_flags = from_value(flags.clone()).unwrap();
                 }
+            } else {
+                regex_body = ctx.value_to_rust_string(pattern_arg)?;
             }
         }
-        _ => return Err(Gc::new(ValueData::Undefined)),
+        _ => regex_body = ctx.value_to_rust_string(pattern_arg)?,
     }
-    // if a second argument is given and it's a string, use it as flags
+    // if a second argument is given, it replaces the flags
     match args.get(1) {
-        None => {}
-        Some(flags) => {
-            if let ValueData::String(flags) = flags.deref() {
-                regex_flags = flags.into();
-            }
-        }
+        Some(flags) if !flags.is_undefined() => regex_flags = ctx.value_to_rust_string(flags)?,
+        _ => {}
     }
 
     // parse flags
@@ -122,9 +155,18 @@ pub fn make_regexp(this: &Value, args: &[Value], _: &mut Interpreter) -> ResultV
     if !pattern.is_empty() {
         pattern = format!("(?{})", pattern);
     }
-    pattern.push_str(regex_body.as_str());
-
-    let matcher = Regex::new(pattern.as_str()).expect("failed to create matcher");
+    pattern.push_str(&translate_pattern(&regex_body));
+
+    let matcher = match Regex::new(pattern.as_str()) {
+        Ok(matcher) => matcher,
+        Err(_) => {
+            return Err(error::new_error(
+                "SyntaxError",
+                &format!("Invalid regular expression: /{}/", regex_body),
+                ctx,
+            ))
+        }
+    };
     let regexp = RegExp {
         matcher,
         use_last_index: global || sticky,
@@ -180,8 +222,8 @@ fn get_unicode(this: &Value, _: &[Value], _: &mut Interpreter) -> ResultValue {
     this.with_internal_state_ref(|regex: &RegExp| Ok(to_value(regex.unicode)))
 }
 
-fn _make_prop(getter: NativeFunctionData) -> Property {
-    Property::default().get(to_value(getter))
+fn _make_prop(getter: NativeFunctionData, global: &Value) -> Property {
+    Property::default().get(make_builtin_fn(getter, global))
 }
 
 /// Search for a match between this regex and a specified string
@@ -189,7 +231,13 @@ pub fn test(this: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
     let arg_str = get_argument::<String>(args, 0)?;
     let mut last_index = from_value::<usize>(this.get_field("lastIndex")).map_err(to_value)?;
     let result = this.with_internal_state_ref(|regex: &RegExp| {
-        let result = match regex.matcher.find_at(arg_str.as_str(), last_index) {
+        // Only global and sticky expressions continue from `lastIndex`
+        let start = if regex.use_last_index { last_index } else { 0 };
+        if start > arg_str.len() {
+            last_index = 0;
+            return Ok(Gc::new(ValueData::Boolean(false)));
+        }
+        let result = match regex.matcher.find_at(arg_str.as_str(), start) {
             Some(m) => {
                 if regex.use_last_index {
                     last_index = m.end();
@@ -209,45 +257,229 @@ pub fn test(this: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
     result
 }
 
-/// Search for a match between this regex and a specified string
-pub fn exec(this: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+/// Search for a match between this regex and a specified string, returning an array of
+/// the match and its capture groups with the `index` and `input` of the match, or null.
+/// Named groups are also collected in a `groups` object, which is undefined without them.
+/// <https://tc39.es/ecma262/#sec-regexp.prototype.exec>
+pub fn exec(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     let arg_str = get_argument::<String>(args, 0)?;
     let mut last_index = from_value::<usize>(this.get_field("lastIndex")).map_err(to_value)?;
-    let result = this.with_internal_state_ref(|regex: &RegExp| {
+    let matched = this.with_internal_state_ref(|regex: &RegExp| {
         let mut locations = regex.matcher.capture_locations();
-        let result =
-            match regex
-                .matcher
-                .captures_read_at(&mut locations, arg_str.as_str(), last_index)
-            {
-                Some(m) => {
-                    if regex.use_last_index {
-                        last_index = m.end();
-                    }
-                    let mut result = Vec::with_capacity(locations.len());
-                    for i in 0..locations.len() {
-                        if let Some((start, end)) = locations.get(i) {
-                            result.push(to_value(&arg_str[start..end]));
-                        } else {
-                            result.push(Gc::new(ValueData::Undefined));
-                        }
+        // Only global and sticky expressions continue from `lastIndex`, and they fail to match
+        // once it is past the end of the string
+        let start = if regex.use_last_index { last_index } else { 0 };
+        if start > arg_str.len() {
+            last_index = 0;
+            return None;
+        }
+        let result = regex
+            .matcher
+            .captures_read_at(&mut locations, arg_str.as_str(), start)
+            .map(|m| {
+                if regex.use_last_index {
+                    last_index = m.end();
+                }
+                let mut groups = Vec::with_capacity(locations.len());
+                for i in 0..locations.len() {
+                    if let Some((start, end)) = locations.get(i) {
+                        groups.push(to_value(&arg_str[start..end]));
+                    } else {
+                        groups.push(Gc::new(ValueData::Undefined));
                     }
-                    let result = to_value(result);
-                    result.set_prop_slice("index", Property::default().value(to_value(m.start())));
-                    result.set_prop_slice("input", Property::default().value(to_value(arg_str)));
-                    result
                 }
-                None => {
-                    if regex.use_last_index {
-                        last_index = 0;
+                let names: Vec<Option<String>> = regex
+                    .matcher
+                    .capture_names()
+                    .map(|name| name.map(String::from))
+                    .collect();
+                (groups, names, m.start())
+            });
+        if result.is_none() && regex.use_last_index {
+            last_index = 0;
+        }
+        result
+    });
+    this.set_field_slice("lastIndex", to_value(last_index));
+    match matched {
+        Some((groups, names, index)) => {
+            let result = array::new_array(ctx)?;
+            array::add_to_array_object(&result, &groups)?;
+            result.set_field_slice("index", to_value(index));
+            result.set_field_slice("input", to_value(arg_str));
+            let named_groups = if names.iter().any(Option::is_some) {
+                let named_groups = ValueData::new_obj(None);
+                for (name, group) in names.iter().zip(groups.iter()) {
+                    if let Some(name) = name {
+                        named_groups.set_field_slice(name, group.clone());
                     }
-                    Gc::new(ValueData::Null)
                 }
+                named_groups
+            } else {
+                Gc::new(ValueData::Undefined)
             };
-        Ok(result)
+            result.set_field_slice("groups", named_groups);
+            Ok(result)
+        }
+        None => Ok(Gc::new(ValueData::Null)),
+    }
+}
+
+/// Returns true if `value` is a RegExp object
+pub fn is_regexp(value: &Value) -> bool {
+    value.has_internal_state() && !value.get_internal_slot("OriginalSource").is_undefined()
+}
+
+/// Create a RegExp object, as `new RegExp(pattern, flags)` would
+pub fn create(pattern: &str, flags: &str, ctx: &mut Interpreter) -> ResultValue {
+    let global = ctx.get_realm().global_obj.clone();
+    let regexp = ValueData::new_obj(Some(&global));
+    regexp.set_internal_slot(
+        INSTANCE_PROTOTYPE,
+        global.get_field_slice("RegExp").get_field_slice(PROTOTYPE),
+    );
+    make_regexp(&regexp, &[to_value(pattern), to_value(flags)], ctx)
+}
+
+/// Returns true if the RegExp object `this` has the global flag
+pub fn is_global(this: &Value) -> bool {
+    this.with_internal_state_ref(|regex: &RegExp| regex.global)
+}
+
+/// The byte ranges of a match and of each of its capture groups, which are `None`
+/// when the group did not participate in the match
+pub type Captures = Vec<Option<(usize, usize)>>;
+
+/// Find the matches of the RegExp object `this` in `string`: all of them when the
+/// regex is global, which also resets `lastIndex`, and otherwise only the first
+pub fn matches(this: &Value, string: &str) -> Vec<Captures> {
+    let (global, matches) = this.with_internal_state_ref(|regex: &RegExp| {
+        let matches: Vec<Captures> = regex
+            .matcher
+            .captures_iter(string)
+            .take(if regex.global { usize::MAX } else { 1 })
+            .map(|captures| {
+                captures
+                    .iter()
+                    .map(|group| group.map(|group| (group.start(), group.end())))
+                    .collect()
+            })
+            .collect();
+        (regex.global, matches)
     });
-    this.set_field_slice("lastIndex", to_value(last_index));
-    result
+    if global {
+        this.set_field_slice("lastIndex", to_value(0));
+    }
+    matches
+}
+
+/// The names of the capture groups of the RegExp object `this`, with `None` for
+/// unnamed groups and the whole match
+pub fn capture_names(this: &Value) -> Vec<Option<String>> {
+    this.with_internal_state_ref(|regex: &RegExp| {
+        regex
+            .matcher
+            .capture_names()
+            .map(|name| name.map(String::from))
+            .collect()
+    })
+}
+
+/// Split `string` around every match of the RegExp object `this`, putting the groups
+/// captured by each match between the pieces
+/// <https://tc39.es/ecma262/#sec-regexp.prototype-@@split>
+pub fn split(this: &Value, string: &str) -> Vec<Value> {
+    this.with_internal_state_ref(|regex: &RegExp| {
+        // The empty string is only split when the regex cannot match it
+        if string.is_empty() {
+            return if regex.matcher.is_match(string) {
+                Vec::new()
+            } else {
+                vec![to_value(string)]
+            };
+        }
+        let mut parts = Vec::new();
+        let mut last_end = 0;
+        for captures in regex.matcher.captures_iter(string) {
+            let whole = captures.get(0).expect("failed to get match");
+            // Empty matches at the end of the string or of the last split are not separators
+            if whole.start() >= string.len() || whole.end() == last_end {
+                continue;
+            }
+            parts.push(to_value(&string[last_end..whole.start()]));
+            for group in captures.iter().skip(1) {
+                parts.push(match group {
+                    Some(group) => to_value(group.as_str()),
+                    None => Gc::new(ValueData::Undefined),
+                });
+            }
+            last_end = whole.end();
+        }
+        parts.push(to_value(&string[last_end..]));
+        parts
+    })
+}
+
+/// Create an iterator over the matches of the RegExp object `this` in `string`, as
+/// `String.prototype.matchAll` returns. The regex is copied, so that iterating does not
+/// change its `lastIndex`.
+/// <https://tc39.es/ecma262/#sec-regexp-prototype-matchall>
+pub fn match_all(this: &Value, string: String, ctx: &mut Interpreter) -> ResultValue {
+    let source =
+        from_value::<String>(this.get_internal_slot("OriginalSource")).map_err(to_value)?;
+    let (flags, global) =
+        this.with_internal_state_ref(|regex: &RegExp| (regex.flags.clone(), regex.global));
+    let matcher = create(&source, &flags, ctx)?;
+    matcher.set_field_slice("lastIndex", this.get_field_slice("lastIndex"));
+
+    let iterator = ValueData::new_obj(Some(&ctx.get_realm().global_obj));
+    iterator.set_internal_slot("IteratingRegExp", matcher);
+    iterator.set_internal_slot("IteratedString", to_value(string));
+    iterator.set_internal_slot("Global", to_value(global));
+    iterator.set_internal_slot("Done", to_value(false));
+    iterator.set_field_slice(
+        "next",
+        make_builtin_fn(string_iterator_next, &ctx.get_realm().global_obj),
+    );
+    Ok(iterator)
+}
+
+/// %RegExpStringIteratorPrototype%.next ( )
+///
+/// Gives the next match as `exec` returns it. An empty match moves `lastIndex` past the
+/// next character so that the iterator always makes progress.
+/// <https://tc39.es/ecma262/#sec-%regexpstringiteratorprototype%.next>
+pub fn string_iterator_next(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let matcher = this.get_internal_slot("IteratingRegExp");
+    if matcher.is_undefined() {
+        return Err(error::new_error(
+            "TypeError",
+            "next method called on incompatible receiver",
+            ctx,
+        ));
+    }
+    let undefined = Gc::new(ValueData::Undefined);
+    if this.get_internal_slot("Done").is_true() {
+        return Ok(array::create_iter_result_object(undefined, true, ctx));
+    }
+    let string = this.get_internal_slot("IteratedString");
+    let matched = exec(&matcher, std::slice::from_ref(&string), ctx)?;
+    if matched.is_null() {
+        this.set_internal_slot("Done", to_value(true));
+        return Ok(array::create_iter_result_object(undefined, true, ctx));
+    }
+    if !this.get_internal_slot("Global").is_true() {
+        this.set_internal_slot("Done", to_value(true));
+    } else if matched.get_field_slice("0").to_string().is_empty() {
+        let string = string.to_string();
+        let last_index = from_value::<usize>(matcher.get_field_slice("lastIndex")).unwrap_or(0);
+        let next_index = string[last_index..]
+            .chars()
+            .next()
+            .map_or(last_index + 1, |c| last_index + c.len_utf8());
+        matcher.set_field_slice("lastIndex", to_value(next_index));
+    }
+    Ok(array::create_iter_result_object(matched, false, ctx))
 }
 
 /// Return a string representing the regular expression
@@ -268,18 +500,18 @@ pub fn create_constructor(global: &Value) -> Value {
 
     // Create prototype
     let proto = ValueData::new_obj(Some(global));
-    proto.set_field_slice("test", to_value(test as NativeFunctionData));
-    proto.set_field_slice("exec", to_value(exec as NativeFunctionData));
-    proto.set_field_slice("toString", to_value(to_string as NativeFunctionData));
+    proto.set_field_slice("test", make_builtin_fn(test, global));
+    proto.set_field_slice("exec", make_builtin_fn(exec, global));
+    proto.set_field_slice("toString", make_builtin_fn(to_string, global));
     proto.set_field_slice("lastIndex", to_value(0));
-    proto.set_prop_slice("dotAll", _make_prop(get_dot_all));
-    proto.set_prop_slice("flags", _make_prop(get_flags));
-    proto.set_prop_slice("global", _make_prop(get_global));
-    proto.set_prop_slice("ignoreCase", _make_prop(get_ignore_case));
-    proto.set_prop_slice("multiline", _make_prop(get_multiline));
-    proto.set_prop_slice("source", _make_prop(get_source));
-    proto.set_prop_slice("sticky", _make_prop(get_sticky));
-    proto.set_prop_slice("unicode", _make_prop(get_unicode));
+    proto.set_prop_slice("dotAll", _make_prop(get_dot_all, global));
+    proto.set_prop_slice("flags", _make_prop(get_flags, global));
+    proto.set_prop_slice("global", _make_prop(get_global, global));
+    proto.set_prop_slice("ignoreCase", _make_prop(get_ignore_case, global));
+    proto.set_prop_slice("multiline", _make_prop(get_multiline, global));
+    proto.set_prop_slice("source", _make_prop(get_source, global));
+    proto.set_prop_slice("sticky", _make_prop(get_sticky, global));
+    proto.set_prop_slice("unicode", _make_prop(get_unicode, global));
 
     let regexp = to_value(regexp_constructor);
     regexp.set_field_slice(PROTOTYPE, proto.clone());
@@ -314,36 +546,83 @@ mod tests {
     fn check_regexp_constructor_is_function() {
         let global = ValueData::new_obj(None);
         let regexp_constructor = create_constructor(&global);
-        assert_eq!(regexp_constructor.is_function(), true);
+        assert!(regexp_constructor.is_function());
     }
 
-    // TODO: uncomment this test when property getters are supported
-
-    //    #[test]
-    //    fn test_flags() {
-    //        let mut engine = Executor::new();
-    //        let init = r#"
-    //                var re_gi = /test/gi;
-    //                var re_sm = /test/sm;
-    //                "#;
-    //
-    //        forward(&mut engine, init);
-    //        assert_eq!(forward(&mut engine, "re_gi.global"), "true");
-    //        assert_eq!(forward(&mut engine, "re_gi.ignoreCase"), "true");
-    //        assert_eq!(forward(&mut engine, "re_gi.multiline"), "false");
-    //        assert_eq!(forward(&mut engine, "re_gi.dotAll"), "false");
-    //        assert_eq!(forward(&mut engine, "re_gi.unicode"), "false");
-    //        assert_eq!(forward(&mut engine, "re_gi.sticky"), "false");
-    //        assert_eq!(forward(&mut engine, "re_gi.flags"), "gi");
-    //
-    //        assert_eq!(forward(&mut engine, "re_sm.global"), "false");
-    //        assert_eq!(forward(&mut engine, "re_sm.ignoreCase"), "false");
-    //        assert_eq!(forward(&mut engine, "re_sm.multiline"), "true");
-    //        assert_eq!(forward(&mut engine, "re_sm.dotAll"), "true");
-    //        assert_eq!(forward(&mut engine, "re_sm.unicode"), "false");
-    //        assert_eq!(forward(&mut engine, "re_sm.sticky"), "false");
-    //        assert_eq!(forward(&mut engine, "re_sm.flags"), "ms");
-    //    }
+    #[test]
+    fn test_flags() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+                var re_gi = /test/gi;
+                var re_sm = /test/sm;
+                "#;
+
+        forward(&mut engine, init);
+        assert_eq!(forward(&mut engine, "re_gi.global"), "true");
+        assert_eq!(forward(&mut engine, "re_gi.ignoreCase"), "true");
+        assert_eq!(forward(&mut engine, "re_gi.multiline"), "false");
+        assert_eq!(forward(&mut engine, "re_gi.dotAll"), "false");
+        assert_eq!(forward(&mut engine, "re_gi.unicode"), "false");
+        assert_eq!(forward(&mut engine, "re_gi.sticky"), "false");
+        assert_eq!(forward(&mut engine, "re_gi.flags"), "gi");
+
+        assert_eq!(forward(&mut engine, "re_sm.global"), "false");
+        assert_eq!(forward(&mut engine, "re_sm.ignoreCase"), "false");
+        assert_eq!(forward(&mut engine, "re_sm.multiline"), "true");
+        assert_eq!(forward(&mut engine, "re_sm.dotAll"), "true");
+        assert_eq!(forward(&mut engine, "re_sm.unicode"), "false");
+        assert_eq!(forward(&mut engine, "re_sm.sticky"), "false");
+        assert_eq!(forward(&mut engine, "re_sm.flags"), "ms");
+    }
+
+    #[test]
+    fn test_literals() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        let insensitive = /ABC/i;
+        let slashes = /a\/b/;
+        "#;
+
+        forward(&mut engine, init);
+        assert_eq!(forward(&mut engine, r#"/\d+/.test("a1")"#), "true");
+        assert_eq!(forward(&mut engine, r#"/\d+/.test("ab")"#), "false");
+        assert_eq!(forward(&mut engine, "insensitive.test('xabcx')"), "true");
+        assert_eq!(forward(&mut engine, "slashes.test('a/b')"), "true");
+        assert_eq!(
+            forward(&mut engine, "new RegExp('(')"),
+            "Error: SyntaxError: Invalid regular expression: /(/"
+        );
+    }
+
+    #[test]
+    fn test_exec_captures() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        let date = /(\d+)-(\d+)(x)?/;
+        let named = /(?<year>\d+)/;
+        let result = date.exec('on 2024-01!');
+        "#;
+
+        forward(&mut engine, init);
+        assert_eq!(forward(&mut engine, "result.length"), "4");
+        assert_eq!(forward(&mut engine, "result[0]"), "2024-01");
+        assert_eq!(forward(&mut engine, "result[1]"), "2024");
+        assert_eq!(forward(&mut engine, "result[2]"), "01");
+        assert_eq!(forward(&mut engine, "result[3]"), "undefined");
+        assert_eq!(forward(&mut engine, "result.index"), "3");
+        assert_eq!(forward(&mut engine, "result.input"), "on 2024-01!");
+        assert_eq!(forward(&mut engine, "Array.isArray(result)"), "true");
+        assert_eq!(forward(&mut engine, "date.exec('none')"), "null");
+        assert_eq!(forward(&mut engine, "named.exec('in 1999')[1]"), "1999");
+        assert_eq!(
+            forward(&mut engine, "named.exec('in 1999').groups.year"),
+            "1999"
+        );
+        assert_eq!(forward(&mut engine, "result.groups"), "undefined");
+    }
 
     #[test]
     fn test_last_index() {
@@ -381,6 +660,81 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_repeated_exec() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        function collect(regex, text) {
+            let found = [];
+            let result = regex.exec(text);
+            while (result !== null) {
+                found.push(result[0] + '@' + result.index);
+                result = regex.exec(text);
+            }
+            return found.join(' ');
+        }
+        var plain = /x/;
+        plain.lastIndex = 5;
+        "#;
+
+        forward(&mut engine, init);
+        assert_eq!(
+            forward(&mut engine, "/a(b+)/.exec('xabbb')[1] === 'bbb'"),
+            "true"
+        );
+        assert_eq!(
+            forward(&mut engine, "collect(/x/g, 'axbxxc')"),
+            "x@1 x@3 x@4"
+        );
+        assert_eq!(
+            forward(&mut engine, "collect(/\\d+/g, 'a1b22c333')"),
+            "1@1 22@3 333@6"
+        );
+        // Expressions without the global flag always search from the start
+        assert_eq!(forward(&mut engine, "plain.exec('x').index"), "0");
+        assert_eq!(forward(&mut engine, "plain.test('x')"), "true");
+        assert_eq!(forward(&mut engine, "plain.lastIndex"), "5");
+    }
+
+    #[test]
+    fn test_constructor_arguments() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+
+        assert_eq!(
+            forward(&mut engine, "new RegExp().test('anything')"),
+            "true"
+        );
+        assert_eq!(forward(&mut engine, "new RegExp('a', undefined).flags"), "");
+        assert_eq!(forward(&mut engine, "new RegExp(/a/g, 'i').flags"), "i");
+        assert_eq!(forward(&mut engine, "new RegExp(12).test('x12')"), "true");
+        assert_eq!(forward(&mut engine, "new RegExp('b', 'g').source"), "b");
+        assert_eq!(forward(&mut engine, "new RegExp('b', 'g').lastIndex"), "0");
+        assert_eq!(
+            forward(
+                &mut engine,
+                "try { new RegExp('[a'); } catch (e) { e instanceof SyntaxError }"
+            ),
+            "true"
+        );
+    }
+
+    #[test]
+    fn test_string_methods() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+
+        assert_eq!(forward(&mut engine, "'a1b2'.match(/\\d/g).join()"), "1,2");
+        assert_eq!(forward(&mut engine, "'a1b2'.match(/(\\d)/)[1]"), "1");
+        assert_eq!(forward(&mut engine, "'a1b2'.replace(/\\d/g, '#')"), "a#b#");
+        assert_eq!(forward(&mut engine, "'a1b2'.replace(/\\d/, '#')"), "a#b2");
+        assert_eq!(
+            forward(&mut engine, "'a, b,c'.split(/,\\s*/).join('|')"),
+            "a|b|c"
+        );
+    }
+
     #[test]
     fn test_to_string() {
         let realm = Realm::create();
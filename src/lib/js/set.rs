@@ -0,0 +1,283 @@
+//! The `Set` object
+//!
+//! Values are kept in insertion order in an internal list object. As with `Map`, deleting a
+//! value leaves a hole in the list, so a `forEach` in progress neither skips nor repeats values.
+//! <https://tc39.es/ecma262/#sec-set-objects>
+use crate::{
+    exec::Interpreter,
+    js::{
+        error,
+        function::make_builtin_fn,
+        object::{Object, ObjectKind, PROTOTYPE},
+        property::Property,
+        value::{from_value, same_value_zero, to_value, ResultValue, Value, ValueData},
+    },
+};
+use gc::Gc;
+
+/// Create a new `Set` - [[Construct]]
+///
+/// An iterable argument is not supported yet, but the values of an array are added.
+/// <https://tc39.es/ecma262/#sec-set-iterable>
+pub fn make_set(this: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    let values = ValueData::new_obj(None);
+    values.set_field_slice("length", to_value(0_i32));
+    this.set_internal_slot("SetData", values);
+    this.set_internal_slot("SetSize", to_value(0_i32));
+
+    if let Some(iterable) = args.first() {
+        if iterable.is_object() {
+            let length: i32 = from_value(iterable.get_field_slice("length")).unwrap_or(0);
+            for n in 0..length {
+                add_value(this, iterable.get_field(&n.to_string()));
+            }
+        }
+    }
+    Ok(this.clone())
+}
+
+/// Calling `Set` without `new` is a TypeError - [[Call]]
+pub fn call_set(_: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    Err(error::new_error(
+        "TypeError",
+        "Constructor Set requires 'new'",
+        ctx,
+    ))
+}
+
+/// Get the value list of `this`, throwing a TypeError if it is not a `Set`
+fn set_data(this: &Value, method: &str, ctx: &Interpreter) -> Result<Value, Value> {
+    let values = this.get_internal_slot("SetData");
+    if values.is_undefined() {
+        return Err(error::new_error(
+            "TypeError",
+            &format!("Set.prototype.{} called on incompatible receiver", method),
+            ctx,
+        ));
+    }
+    Ok(values)
+}
+
+/// Find the position of the value which is SameValueZero to `value`
+fn find_value(values: &Value, value: &Value) -> Option<i32> {
+    let length: i32 = from_value(values.get_field_slice("length")).unwrap();
+    (0..length).find(|n| {
+        let index = n.to_string();
+        values.has_field(&index) && same_value_zero(&values.get_field(&index), value)
+    })
+}
+
+/// Add `value` to the `Set` `this` unless it is already there
+fn add_value(this: &Value, value: Value) {
+    let values = this.get_internal_slot("SetData");
+    if find_value(&values, &value).is_some() {
+        return;
+    }
+
+    // -0 is normalised to +0
+    let value = match *value {
+        ValueData::Number(0.0) => to_value(0_i32),
+        _ => value,
+    };
+    let length: i32 = from_value(values.get_field_slice("length")).unwrap();
+    values.set_field(length.to_string(), value);
+    values.set_field_slice("length", to_value(length + 1));
+    let size: i32 = from_value(this.get_internal_slot("SetSize")).unwrap();
+    this.set_internal_slot("SetSize", to_value(size + 1));
+}
+
+/// Set.prototype.add ( value )
+///
+/// Adds `value` to the `Set` if it is not already there, returning the `Set`.
+/// <https://tc39.es/ecma262/#sec-set.prototype.add>
+pub fn add(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    set_data(this, "add", ctx)?;
+    let value = args
+        .first()
+        .cloned()
+        .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+    add_value(this, value);
+    Ok(this.clone())
+}
+
+/// Set.prototype.has ( value )
+///
+/// Returns whether `value` is in the `Set`.
+/// <https://tc39.es/ecma262/#sec-set.prototype.has>
+pub fn has(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let values = set_data(this, "has", ctx)?;
+    let value = args
+        .first()
+        .cloned()
+        .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+    Ok(to_value(find_value(&values, &value).is_some()))
+}
+
+/// Set.prototype.delete ( value )
+///
+/// Removes `value` from the `Set`, returning whether it was there.
+/// <https://tc39.es/ecma262/#sec-set.prototype.delete>
+pub fn delete(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let values = set_data(this, "delete", ctx)?;
+    let value = args
+        .first()
+        .cloned()
+        .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+    match find_value(&values, &value) {
+        Some(n) => {
+            values.remove_prop(&n.to_string());
+            let size: i32 = from_value(this.get_internal_slot("SetSize")).unwrap();
+            this.set_internal_slot("SetSize", to_value(size - 1));
+            Ok(to_value(true))
+        }
+        None => Ok(to_value(false)),
+    }
+}
+
+/// Set.prototype.clear ( )
+///
+/// Removes every value from the `Set`.
+/// <https://tc39.es/ecma262/#sec-set.prototype.clear>
+pub fn clear(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let values = set_data(this, "clear", ctx)?;
+    let length: i32 = from_value(values.get_field_slice("length")).unwrap();
+    for n in 0..length {
+        values.remove_prop(&n.to_string());
+    }
+    this.set_internal_slot("SetSize", to_value(0_i32));
+    Ok(Gc::new(ValueData::Undefined))
+}
+
+/// get Set.prototype.size
+///
+/// Returns the number of values in the `Set`.
+/// <https://tc39.es/ecma262/#sec-get-set.prototype.size>
+pub fn get_size(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    set_data(this, "size", ctx)?;
+    Ok(this.get_internal_slot("SetSize"))
+}
+
+/// Set.prototype.forEach ( callbackfn [ , thisArg ] )
+///
+/// Calls `callbackfn` with each value twice, as the value and as the key, and the `Set`,
+/// in insertion order. Values added during the iteration are visited, deleted ones are not.
+/// <https://tc39.es/ecma262/#sec-set.prototype.foreach>
+pub fn for_each(this: &Value, args: &[Value], interpreter: &mut Interpreter) -> ResultValue {
+    let values = set_data(this, "forEach", interpreter)?;
+    let callback = match args.first() {
+        Some(callback) if callback.is_function() => callback,
+        _ => {
+            return Err(error::new_error(
+                "TypeError",
+                "Set.prototype.forEach: callback is not a function",
+                interpreter,
+            ))
+        }
+    };
+    let this_arg = args
+        .get(1)
+        .cloned()
+        .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+
+    // The length is read on every step so values added by the callback are visited
+    let mut n = 0;
+    while n < from_value::<i32>(values.get_field_slice("length")).unwrap() {
+        let index = n.to_string();
+        if values.has_field(&index) {
+            let value = values.get_field(&index);
+            let arguments = vec![value.clone(), value, this.clone()];
+            interpreter.call(callback, &this_arg, arguments)?;
+        }
+        n += 1;
+    }
+    Ok(Gc::new(ValueData::Undefined))
+}
+
+/// Create a new `Set` object
+pub fn create_constructor(global: &Value) -> Value {
+    let mut set_constructor = Object::default();
+    set_constructor.kind = ObjectKind::Function;
+    set_constructor.set_internal_method("construct", make_set);
+    set_constructor.set_internal_method("call", call_set);
+
+    // Create prototype
+    let proto = ValueData::new_obj(Some(global));
+    proto.set_field_slice("add", make_builtin_fn(add, global));
+    proto.set_field_slice("has", make_builtin_fn(has, global));
+    proto.set_field_slice("delete", make_builtin_fn(delete, global));
+    proto.set_field_slice("clear", make_builtin_fn(clear, global));
+    proto.set_field_slice("forEach", make_builtin_fn(for_each, global));
+    proto.set_prop_slice(
+        "size",
+        Property::default().get(make_builtin_fn(get_size, global)),
+    );
+
+    let set = to_value(set_constructor);
+    proto.set_field_slice("constructor", set.clone());
+    set.set_field_slice(PROTOTYPE, proto);
+    set
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::exec::Executor;
+    use crate::forward;
+    use crate::realm::Realm;
+
+    #[test]
+    fn deduplicates_values() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const key = { id: 1 };
+        const set = new Set([1, 2, 2, 'a', 'a', key, key, { id: 1 }]);
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "set.size"), "5");
+        assert_eq!(forward(&mut engine, "set.has(2)"), "true");
+        assert_eq!(forward(&mut engine, "set.has('2')"), "false");
+        assert_eq!(forward(&mut engine, "set.has(key)"), "true");
+        assert_eq!(forward(&mut engine, "set.add(1).size"), "5");
+    }
+
+    #[test]
+    fn nan_and_zero_membership() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const set = new Set();
+        set.add(NaN).add(NaN).add(-0);
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "set.size"), "2");
+        assert_eq!(forward(&mut engine, "set.has(NaN)"), "true");
+        assert_eq!(forward(&mut engine, "set.has(0)"), "true");
+    }
+
+    #[test]
+    fn size_after_changes() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const set = new Set();
+        set.add('x').add('y').add('z');
+        const seen = { text: '' };
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "set.size"), "3");
+        assert_eq!(forward(&mut engine, "set.delete('y')"), "true");
+        assert_eq!(forward(&mut engine, "set.delete('y')"), "false");
+        assert_eq!(forward(&mut engine, "set.size"), "2");
+        forward(
+            &mut engine,
+            "set.forEach((value, key) => { seen.text = seen.text + value + key; })",
+        );
+        assert_eq!(forward(&mut engine, "seen.text"), "xxzz");
+        forward(&mut engine, "set.clear()");
+        assert_eq!(forward(&mut engine, "set.size"), "0");
+        assert_eq!(forward(&mut engine, "set.has('x')"), "false");
+    }
+}
@@ -1,17 +1,20 @@
 use crate::{
     exec::Interpreter,
     js::{
-        function::NativeFunctionData,
+        array, error,
+        function::make_builtin_fn,
         object::{Object, ObjectKind, PROTOTYPE},
         property::Property,
+        regexp,
         value::{from_value, to_value, ResultValue, Value, ValueData},
     },
 };
 use gc::Gc;
-use std::{
-    cmp::{max, min},
-    f64::NAN,
-};
+use std::cmp::{max, min};
+
+/// The most UTF-16 code units a string made by a method such as `repeat` may have, the same as
+/// V8's limit. Longer strings throw a `RangeError` rather than exhausting memory.
+const MAX_STRING_LENGTH: usize = (1 << 29) - 24;
 
 /// Create new string [[Construct]]
 /// <https://searchfox.org/mozilla-central/source/js/src/vm/StringObject.h#19>
@@ -27,7 +30,7 @@ pub fn make_string(this: &Value, args: &[Value], _: &mut Interpreter) -> ResultV
     this.set_kind(ObjectKind::String);
     this.set_internal_slot(
         "StringData",
-        args.get(0)
+        args.first()
             .expect("failed to get StringData for make_string()")
             .clone(),
     );
@@ -36,23 +39,17 @@ pub fn make_string(this: &Value, args: &[Value], _: &mut Interpreter) -> ResultV
 
 /// Call new string [[Call]]
 /// https://tc39.es/ecma262/#sec-string-constructor-string-value
-pub fn call_string(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
-    let arg = match args.get(0) {
-        Some(v) => v.clone(),
-        None => Gc::new(ValueData::Undefined),
-    };
-
-    if arg.is_undefined() {
-        return Ok(to_value(""));
+pub fn call_string(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    match args.first() {
+        Some(arg) => Ok(ctx.to_string(arg)?),
+        None => Ok(to_value("")),
     }
-
-    Ok(to_value(arg.to_string()))
 }
 
-/// Get a string's length
+/// Get a string's length, in UTF-16 code units
 pub fn get_string_length(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
-    let this_str = ctx.value_to_rust_string(this);
-    Ok(to_value::<i32>(this_str.chars().count() as i32))
+    let this_str = ctx.value_to_rust_string(this)?;
+    Ok(to_value::<i32>(this_str.encode_utf16().count() as i32))
 }
 
 /// Get the string value to a primitive string
@@ -69,31 +66,62 @@ pub fn to_string(this: &Value, _: &[Value], _: &mut Interpreter) -> ResultValue
 pub fn char_at(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     // First we get it the actual string a private field stored on the object only the engine has access to.
     // Then we convert it into a Rust String by wrapping it in from_value
-    let primitive_val = ctx.value_to_rust_string(this);
-    let pos: i32 = from_value(
-        args.get(0)
-            .expect("failed to get argument for String method")
-            .clone(),
-    )
-    .expect("failed to parse argument for String method");
-
-    // Calling .len() on a string would give the wrong result, as they are bytes not the number of
-    // unicode code points
-    // Note that this is an O(N) operation (because UTF-8 is complex) while getting the number of
-    // bytes is an O(1) operation.
-    let length = primitive_val.chars().count();
+    let primitive_val = ctx.value_to_rust_string(this)?;
+    let code_units: Vec<u16> = primitive_val.encode_utf16().collect();
+    let pos = integer_arg(args, 0, 0.0, ctx)?;
 
     // We should return an empty string is pos is out of range
-    if pos >= length as i32 || pos < 0 {
+    if pos >= code_units.len() as f64 || pos < 0.0 {
         return Ok(to_value::<String>(String::new()));
     }
 
-    Ok(to_value::<char>(
-        primitive_val
-            .chars()
-            .nth(pos as usize)
-            .expect("failed to get value"),
-    ))
+    // Rust strings must be valid UTF-8, so half of a surrogate pair becomes U+FFFD
+    let unit = code_units[pos as usize];
+    Ok(to_value(String::from_utf16_lossy(&[unit])))
+}
+
+/// Returns the code point starting at the code unit at index pos, combining a surrogate pair
+/// into a single code point, or undefined if there is no element at that index
+/// <https://tc39.es/ecma262/#sec-string.prototype.codepointat>
+pub fn code_point_at(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let primitive_val = ctx.value_to_rust_string(this)?;
+    let code_units: Vec<u16> = primitive_val.encode_utf16().collect();
+    let pos = integer_arg(args, 0, 0.0, ctx)?;
+
+    if pos >= code_units.len() as f64 || pos < 0.0 {
+        return Ok(Gc::new(ValueData::Undefined));
+    }
+
+    let pos = pos as usize;
+    let code_point = std::char::decode_utf16(code_units[pos..].iter().cloned())
+        .next()
+        .expect("failed to get code point");
+    Ok(match code_point {
+        Ok(code_point) => to_value(code_point as i32),
+        // A lone surrogate is its own code point
+        Err(_) => to_value(i32::from(code_units[pos])),
+    })
+}
+
+/// Returns a String containing the code unit at index pos, counting negative
+/// indices back from the end, or undefined if the index is out of range
+/// <https://tc39.es/ecma262/#sec-string.prototype.at>
+pub fn at(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let primitive_val: String = ctx.value_to_rust_string(this)?;
+    let code_units: Vec<u16> = primitive_val.encode_utf16().collect();
+    let length = code_units.len() as f64;
+
+    let relative = integer_arg(args, 0, 0.0, ctx)?;
+    let index = if relative < 0.0 {
+        length + relative
+    } else {
+        relative
+    };
+    if index < 0.0 || index >= length {
+        return Ok(Gc::new(ValueData::Undefined));
+    }
+    let unit = code_units[index as usize];
+    Ok(to_value(String::from_utf16_lossy(&[unit])))
 }
 
 /// Returns a Number (a nonnegative integer less than 216) that is the numeric value of the code
@@ -103,28 +131,24 @@ pub fn char_at(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultVal
 pub fn char_code_at(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     // First we get it the actual string a private field stored on the object only the engine has access to.
     // Then we convert it into a Rust String by wrapping it in from_value
-    let primitive_val: String = ctx.value_to_rust_string(this);
-
-    // Calling .len() on a string would give the wrong result, as they are bytes not the number of unicode code points
-    // Note that this is an O(N) operation (because UTF-8 is complex) while getting the number of bytes is an O(1) operation.
-    let length = primitive_val.chars().count();
-    let pos: i32 = from_value(
-        args.get(0)
-            .expect("failed to get argument for String method")
-            .clone(),
-    )
-    .expect("failed to parse argument for String method");
+    let primitive_val: String = ctx.value_to_rust_string(this)?;
 
-    if pos >= length as i32 || pos < 0 {
-        return Ok(to_value(NAN));
+    // JS strings are indexed by UTF-16 code units rather than by unicode code points, so a
+    // character outside the basic multilingual plane counts twice
+    let length = primitive_val.encode_utf16().count() as f64;
+    // A missing position is 'undefined', which converts to 0
+    let pos = args.first().map_or(0.0, |arg| arg.to_num());
+    let pos = if pos.is_nan() { 0.0 } else { pos.trunc() };
+
+    // If there is no element at that index, the result is NaN
+    if pos >= length || pos < 0.0 {
+        return Ok(to_value(f64::NAN));
     }
 
     let utf16_val = primitive_val
         .encode_utf16()
         .nth(pos as usize)
         .expect("failed to get utf16 value");
-    // If there is no element at that index, the result is NaN
-    // TODO: We currently don't have NaN
     Ok(to_value(f64::from(utf16_val)))
 }
 
@@ -134,7 +158,7 @@ pub fn char_code_at(this: &Value, args: &[Value], ctx: &mut Interpreter) -> Resu
 pub fn concat(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     // First we get it the actual string a private field stored on the object only the engine has access to.
     // Then we convert it into a Rust String by wrapping it in from_value
-    let primitive_val: String = ctx.value_to_rust_string(this);
+    let primitive_val: String = ctx.value_to_rust_string(this)?;
 
     let mut new_str = primitive_val.clone();
 
@@ -152,15 +176,29 @@ pub fn concat(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValu
 pub fn repeat(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     // First we get it the actual string a private field stored on the object only the engine has access to.
     // Then we convert it into a Rust String by wrapping it in from_value
-    let primitive_val: String = ctx.value_to_rust_string(this);
-
-    let repeat_times: usize = from_value(
-        args.get(0)
-            .expect("failed to get argument for String method")
-            .clone(),
-    )
-    .expect("failed to parse argument for String method");
-    Ok(to_value(primitive_val.repeat(repeat_times)))
+    let primitive_val: String = ctx.value_to_rust_string(this)?;
+
+    // A missing count is 'undefined', which converts to 0
+    let count = args.first().map_or(0.0, |arg| arg.to_num());
+    let count = if count.is_nan() { 0.0 } else { count.trunc() };
+    if count < 0.0 || count.is_infinite() {
+        return Err(error::new_error(
+            "RangeError",
+            &format!("Invalid count value: {}", to_value(count)),
+            ctx,
+        ));
+    }
+    // Counts too large for a usize saturate, and so are caught by the multiplication
+    match primitive_val
+        .encode_utf16()
+        .count()
+        .checked_mul(count as usize)
+    {
+        Some(length) if length <= MAX_STRING_LENGTH => {
+            Ok(to_value(primitive_val.repeat(count as usize)))
+        }
+        _ => Err(error::new_error("RangeError", "Invalid string length", ctx)),
+    }
 }
 
 /// Returns a String which contains the slice of the JS String from character at "start" index up
@@ -169,43 +207,96 @@ pub fn repeat(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValu
 pub fn slice(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     // First we get it the actual string a private field stored on the object only the engine has access to.
     // Then we convert it into a Rust String by wrapping it in from_value
-    let primitive_val: String = ctx.value_to_rust_string(this);
+    let primitive_val: String = ctx.value_to_rust_string(this)?;
+    // Indices count UTF-16 code units, not bytes or unicode code points
+    let code_units: Vec<u16> = primitive_val.encode_utf16().collect();
+    let length = code_units.len() as f64;
+
+    let start = integer_arg(args, 0, 0.0, ctx)?;
+    let end = integer_arg(args, 1, length, ctx)?;
+
+    // Negative indices are counted back from the end of the string
+    let relative = |index: f64| {
+        if index < 0.0 {
+            (length + index).max(0.0)
+        } else {
+            index.min(length)
+        }
+    };
+    let from = relative(start);
+    let to = relative(end);
 
-    let start: i32 = from_value(
-        args.get(0)
-            .expect("failed to get argument for String method")
-            .clone(),
-    )
-    .expect("failed to parse argument for String method");
-    let end: i32 = from_value(
-        args.get(1)
-            .expect("failed to get argument in slice")
-            .clone(),
-    )
-    .expect("failed to parse argument");
+    if from >= to {
+        return Ok(to_value(String::new()));
+    }
+    Ok(to_value(String::from_utf16_lossy(
+        &code_units[from as usize..to as usize],
+    )))
+}
 
-    // Calling .len() on a string would give the wrong result, as they are bytes not the number of unicode code points
-    // Note that this is an O(N) operation (because UTF-8 is complex) while getting the number of bytes is an O(1) operation.
-    let length: i32 = primitive_val.chars().count() as i32;
+/// Converts the argument at `index` to an integer (ToIntegerOrInfinity),
+/// using `default` when it is missing or 'undefined'
+fn integer_arg(
+    args: &[Value],
+    index: usize,
+    default: f64,
+    ctx: &mut Interpreter,
+) -> Result<f64, Value> {
+    match args.get(index) {
+        Some(arg) if !arg.is_undefined() => {
+            // Objects are converted through their valueOf or toString methods
+            let number = ctx.to_primitive(arg, Some("number"))?.to_num();
+            Ok(if number.is_nan() { 0.0 } else { number.trunc() })
+        }
+        _ => Ok(default),
+    }
+}
 
-    let from: i32 = if start < 0 {
-        max(length.wrapping_add(start), 0)
-    } else {
-        min(start, length)
-    };
-    let to: i32 = if end < 0 {
-        max(length.wrapping_add(end), 0)
-    } else {
-        min(end, length)
-    };
+/// Converts the "search string" argument of the search methods to a String,
+/// which is "undefined" when it is missing
+fn search_string_arg(args: &[Value], ctx: &mut Interpreter) -> Result<String, Value> {
+    match args.first() {
+        Some(arg) => ctx.value_to_rust_string(arg),
+        None => Ok(String::from("undefined")),
+    }
+}
 
-    let span = max(to - from, 0);
+/// Converts the optional position argument of the search methods to an
+/// integer, using `default` when it is 'undefined'
+fn position_arg(args: &[Value], default: i32) -> i32 {
+    match args.get(1) {
+        Some(arg) if !arg.is_undefined() => {
+            let position = arg.to_num();
+            if position.is_nan() {
+                0
+            } else {
+                position as i32
+            }
+        }
+        _ => default,
+    }
+}
 
-    let mut new_str = String::new();
-    for i in from..from + span {
-        new_str.push(primitive_val.chars().nth(i as usize).unwrap());
+/// Throws a TypeError when the "search string" argument is a regular expression, which
+/// `startsWith`, `endsWith` and `includes` reject rather than converting to a String
+fn reject_regexp_arg(args: &[Value], method: &str, ctx: &Interpreter) -> Result<(), Value> {
+    match args.first() {
+        Some(arg) if regexp::is_regexp(arg) => Err(error::new_error(
+            "TypeError",
+            &format!(
+                "First argument to String.prototype.{} must not be a regular expression",
+                method
+            ),
+            ctx,
+        )),
+        _ => Ok(()),
     }
-    Ok(to_value(new_str))
+}
+
+/// Returns true if the code units of `search` appear in `string` at code unit `index`
+fn matches_at(string: &[u16], search: &[u16], index: i32) -> bool {
+    let index = index as usize;
+    string.get(index..index + search.len()) == Some(search)
 }
 
 /// Returns a Boolean indicating whether the sequence of code units of the
@@ -215,36 +306,21 @@ pub fn slice(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue
 pub fn starts_with(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     // First we get it the actual string a private field stored on the object only the engine has access to.
     // Then we convert it into a Rust String by wrapping it in from_value
-    let primitive_val: String = ctx.value_to_rust_string(this);
+    let primitive_val: String = ctx.value_to_rust_string(this)?;
 
-    // TODO: Should throw TypeError if pattern is regular expression
-    let search_string: String = from_value(
-        args.get(0)
-            .expect("failed to get argument for String method")
-            .clone(),
-    )
-    .expect("failed to parse argument for String method");
+    reject_regexp_arg(args, "startsWith", ctx)?;
+    let search_string = search_string_arg(args, ctx)?;
 
-    let length: i32 = primitive_val.chars().count() as i32;
-    let search_length: i32 = search_string.chars().count() as i32;
+    // Positions count UTF-16 code units
+    let this_units: Vec<u16> = primitive_val.encode_utf16().collect();
+    let search_units: Vec<u16> = search_string.encode_utf16().collect();
+    let length = this_units.len() as i32;
 
-    // If less than 2 args specified, position is 'undefined', defaults to 0
-    let position: i32 = if args.len() < 2 {
-        0
-    } else {
-        from_value(args.get(1).expect("failed to get arg").clone()).expect("failed to get argument")
-    };
+    // If position is 'undefined', defaults to 0
+    let position = position_arg(args, 0);
 
     let start = min(max(position, 0), length);
-    let end = start.wrapping_add(search_length);
-
-    if end > length {
-        Ok(to_value(false))
-    } else {
-        // Only use the part of the string from "start"
-        let this_string: String = primitive_val.chars().skip(start as usize).collect();
-        Ok(to_value(this_string.starts_with(&search_string)))
-    }
+    Ok(to_value(matches_at(&this_units, &search_units, start)))
 }
 
 /// Returns a Boolean indicating whether the sequence of code units of the
@@ -254,36 +330,25 @@ pub fn starts_with(this: &Value, args: &[Value], ctx: &mut Interpreter) -> Resul
 pub fn ends_with(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     // First we get it the actual string a private field stored on the object only the engine has access to.
     // Then we convert it into a Rust String by wrapping it in from_value
-    let primitive_val: String = ctx.value_to_rust_string(this);
+    let primitive_val: String = ctx.value_to_rust_string(this)?;
 
-    // TODO: Should throw TypeError if search_string is regular expression
-    let search_string: String = from_value(
-        args.get(0)
-            .expect("failed to get argument for String method")
-            .clone(),
-    )
-    .expect("failed to parse argument for String method");
+    reject_regexp_arg(args, "endsWith", ctx)?;
+    let search_string = search_string_arg(args, ctx)?;
 
-    let length: i32 = primitive_val.chars().count() as i32;
-    let search_length: i32 = search_string.chars().count() as i32;
+    let this_units: Vec<u16> = primitive_val.encode_utf16().collect();
+    let search_units: Vec<u16> = search_string.encode_utf16().collect();
+    let length = this_units.len() as i32;
 
-    // If less than 2 args specified, end_position is 'undefined', defaults to
-    // length of this
-    let end_position: i32 = if args.len() < 2 {
-        length
-    } else {
-        from_value(args[1].clone()).unwrap()
-    };
+    // If end_position is 'undefined', defaults to length of this
+    let end_position = position_arg(args, length);
 
     let end = min(max(end_position, 0), length);
-    let start = end - search_length;
+    let start = end - search_units.len() as i32;
 
     if start < 0 {
         Ok(to_value(false))
     } else {
-        // Only use the part of the string up to "end"
-        let this_string: String = primitive_val.chars().take(end as usize).collect();
-        Ok(to_value(this_string.ends_with(&search_string)))
+        Ok(to_value(matches_at(&this_units, &search_units, start)))
     }
 }
 
@@ -293,33 +358,10 @@ pub fn ends_with(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultV
 /// assumed, so as to search all of the String.
 /// <https://tc39.github.io/ecma262/#sec-string.prototype.includes>
 pub fn includes(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
-    // First we get it the actual string a private field stored on the object only the engine has access to.
-    // Then we convert it into a Rust String by wrapping it in from_value
-    let primitive_val: String = ctx.value_to_rust_string(this);
-
-    // TODO: Should throw TypeError if search_string is regular expression
-    let search_string: String = from_value(
-        args.get(0)
-            .expect("failed to get argument for String method")
-            .clone(),
-    )
-    .expect("failed to parse argument for String method");
-
-    let length: i32 = primitive_val.chars().count() as i32;
-
-    // If less than 2 args specified, position is 'undefined', defaults to 0
-    let position: i32 = if args.len() < 2 {
-        0
-    } else {
-        from_value(args[1].clone()).unwrap()
-    };
-
-    let start = min(max(position, 0), length);
-
-    // Take the string from "this" and use only the part of it after "start"
-    let this_string: String = primitive_val.chars().skip(start as usize).collect();
-
-    Ok(to_value(this_string.contains(&search_string)))
+    reject_regexp_arg(args, "includes", ctx)?;
+    // The search string is included exactly when indexOf finds it
+    let index = index_of(this, args, ctx)?;
+    Ok(to_value(index.to_num() >= 0.0))
 }
 
 /// If searchString appears as a substring of the result of converting this
@@ -327,47 +369,31 @@ pub fn includes(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultVa
 /// position, then the smallest such index is returned; otherwise, -1 is
 /// returned. If position is undefined, 0 is assumed, so as to search all of the
 /// String.
-/// <https://tc39.github.io/ecma262/#sec-string.prototype.includes>
+/// <https://tc39.github.io/ecma262/#sec-string.prototype.indexof>
 pub fn index_of(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     // First we get it the actual string a private field stored on the object only the engine has access to.
     // Then we convert it into a Rust String by wrapping it in from_value
-    let primitive_val: String = ctx.value_to_rust_string(this);
+    let primitive_val: String = ctx.value_to_rust_string(this)?;
 
-    // TODO: Should throw TypeError if search_string is regular expression
-    let search_string: String = from_value(
-        args.get(0)
-            .expect("failed to get argument for String method")
-            .clone(),
-    )
-    .expect("failed to parse argument for String method");
+    let search_string = search_string_arg(args, ctx)?;
 
-    let length: i32 = primitive_val.chars().count() as i32;
+    // The &str method "find" returns a byte index, but JS String indices count
+    // UTF-16 code units, so search the code units instead
+    let this_units: Vec<u16> = primitive_val.encode_utf16().collect();
+    let search_units: Vec<u16> = search_string.encode_utf16().collect();
+    let length = this_units.len() as i32;
 
-    // If less than 2 args specified, position is 'undefined', defaults to 0
-    let position: i32 = if args.len() < 2 {
-        0
-    } else {
-        from_value(args[1].clone()).unwrap()
-    };
+    // If position is 'undefined', defaults to 0
+    let position = position_arg(args, 0);
 
     let start = min(max(position, 0), length);
 
-    // Here cannot use the &str method "find", because this returns the byte
-    // index: we need to return the char index in the JS String
-    // Instead, iterate over the part we're checking until the slice we're
-    // checking "starts with" the search string
-    for index in start..length {
-        let this_string: String = primitive_val.chars().skip(index as usize).collect();
-        if this_string.starts_with(&search_string) {
-            // Explicitly return early with the index value
-            return Ok(to_value(index));
-        }
-    }
-    // Didn't find a match, so return -1
-    Ok(to_value(-1))
+    // The empty string is found at the clamped position itself
+    let index = (start..=length).find(|&index| matches_at(&this_units, &search_units, index));
+    Ok(to_value(index.unwrap_or(-1)))
 }
 
-//// If searchString appears as a substring of the result of converting this
+/// If searchString appears as a substring of the result of converting this
 /// object to a String at one or more indices that are smaller than or equal to
 /// position, then the greatest such index is returned; otherwise, -1 is
 /// returned. If position is undefined, the length of the String value is
@@ -376,41 +402,322 @@ pub fn index_of(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultVa
 pub fn last_index_of(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     // First we get it the actual string a private field stored on the object only the engine has access to.
     // Then we convert it into a Rust String by wrapping it in from_value
-    let primitive_val: String = ctx.value_to_rust_string(this);
+    let primitive_val: String = ctx.value_to_rust_string(this)?;
 
-    // TODO: Should throw TypeError if search_string is regular expression
-    let search_string: String = from_value(
-        args.get(0)
-            .expect("failed to get argument for String method")
-            .clone(),
-    )
-    .expect("failed to parse argument for String method");
+    let search_string = search_string_arg(args, ctx)?;
+
+    let this_units: Vec<u16> = primitive_val.encode_utf16().collect();
+    let search_units: Vec<u16> = search_string.encode_utf16().collect();
+    let length = this_units.len() as i32;
+
+    // If position is 'undefined' or NaN, defaults to the length of this
+    let position = match args.get(1) {
+        Some(arg) if !arg.to_num().is_nan() => arg.to_num() as i32,
+        _ => length,
+    };
+
+    let end = min(max(position, 0), length);
+
+    // Search backwards from "end" for the highest index which matches
+    let index = (0..=end)
+        .rev()
+        .find(|&index| matches_at(&this_units, &search_units, index));
+    Ok(to_value(index.unwrap_or(-1)))
+}
+
+/// String.prototype.split ( separator, limit )
+///
+/// Returns an array of the substrings between each occurrence of the separator,
+/// which may be a string or a RegExp. An empty separator splits the string into
+/// characters, and no separator at all leaves it whole. At most `limit` substrings
+/// are returned.
+/// <https://tc39.es/ecma262/#sec-string.prototype.split>
+pub fn split(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let primitive_val: String = ctx.value_to_rust_string(this)?;
+
+    // ToUint32, where an 'undefined' limit means no limit
+    let limit = match args.get(1) {
+        Some(arg) if !arg.is_undefined() => {
+            let limit = arg.to_num();
+            if limit.is_finite() {
+                limit.trunc().rem_euclid(4_294_967_296.0) as usize
+            } else {
+                0
+            }
+        }
+        _ => u32::MAX as usize,
+    };
+
+    let parts: Vec<Value> = match args.first() {
+        Some(separator) if regexp::is_regexp(separator) => regexp::split(separator, &primitive_val),
+        Some(separator) if !separator.is_undefined() => {
+            let separator = ctx.value_to_rust_string(separator)?;
+            if separator.is_empty() {
+                primitive_val.chars().map(to_value).collect()
+            } else {
+                primitive_val
+                    .split(separator.as_str())
+                    .map(to_value)
+                    .collect()
+            }
+        }
+        _ => vec![to_value(primitive_val)],
+    };
+
+    let result = array::new_array(ctx)?;
+    let parts: Vec<Value> = parts.into_iter().take(limit).collect();
+    array::add_to_array_object(&result, &parts)
+}
+
+/// Returns the result of matching this string against a regular expression: an array of every
+/// matched substring when the regex is global, otherwise the match and its groups as returned by
+/// `RegExp.prototype.exec`. Arguments which are not RegExp objects are used as patterns.
+/// <https://tc39.es/ecma262/#sec-string.prototype.match>
+pub fn r#match(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let primitive_val: String = ctx.value_to_rust_string(this)?;
+    let regexp = match args.first() {
+        Some(arg) if regexp::is_regexp(arg) => arg.clone(),
+        Some(arg) if !arg.is_undefined() => {
+            let pattern = ctx.value_to_rust_string(arg)?;
+            regexp::create(&pattern, "", ctx)?
+        }
+        _ => regexp::create("", "", ctx)?,
+    };
+
+    if !regexp::is_global(&regexp) {
+        return regexp::exec(&regexp, &[to_value(primitive_val)], ctx);
+    }
+    let matched: Vec<Value> = regexp::matches(&regexp, &primitive_val)
+        .into_iter()
+        .filter_map(|captures| captures[0])
+        .map(|(start, end)| to_value(&primitive_val[start..end]))
+        .collect();
+    if matched.is_empty() {
+        return Ok(Gc::new(ValueData::Null));
+    }
+    let result = array::new_array(ctx)?;
+    array::add_to_array_object(&result, &matched)
+}
 
-    let length: i32 = primitive_val.chars().count() as i32;
+/// Returns an iterator over every match of a global regular expression in this string, each
+/// as returned by `RegExp.prototype.exec`. Arguments which are not RegExp objects are used as
+/// global patterns, while non-global RegExp objects are rejected.
+/// <https://tc39.es/ecma262/#sec-string.prototype.matchall>
+pub fn match_all(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let primitive_val: String = ctx.value_to_rust_string(this)?;
+    let regexp = match args.first() {
+        Some(arg) if regexp::is_regexp(arg) => {
+            if !regexp::is_global(arg) {
+                return Err(error::new_error(
+                    "TypeError",
+                    "String.prototype.matchAll called with a non-global RegExp argument",
+                    ctx,
+                ));
+            }
+            arg.clone()
+        }
+        Some(arg) if !arg.is_undefined() => {
+            let pattern = ctx.value_to_rust_string(arg)?;
+            regexp::create(&pattern, "g", ctx)?
+        }
+        _ => regexp::create("", "g", ctx)?,
+    };
+    regexp::match_all(&regexp, primitive_val, ctx)
+}
 
-    // If less than 2 args specified, position is 'undefined', defaults to 0
-    let position: i32 = if args.len() < 2 {
-        0
+/// Returns a new string with the first match of a pattern replaced by a replacement. The pattern
+/// is a string or a RegExp, which replaces every match when it is global. The replacement is a
+/// string, in which `$$`, `$&`, `` $` ``, `$'`, `$n` and `$<name>` are substituted, or a function
+/// called with the match, its groups, its offset and the whole string.
+/// <https://tc39.es/ecma262/#sec-string.prototype.replace>
+pub fn replace(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let primitive_val: String = ctx.value_to_rust_string(this)?;
+    let search_value = args
+        .first()
+        .cloned()
+        .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+
+    let (matches, names) = if regexp::is_regexp(&search_value) {
+        (
+            regexp::matches(&search_value, &primitive_val),
+            regexp::capture_names(&search_value),
+        )
     } else {
-        from_value(args[1].clone()).unwrap()
+        let search_string = ctx.value_to_rust_string(&search_value)?;
+        let matches = primitive_val
+            .find(search_string.as_str())
+            .map(|start| vec![Some((start, start + search_string.len()))])
+            .into_iter()
+            .collect();
+        (matches, Vec::new())
     };
 
-    let start = min(max(position, 0), length);
+    replace_matches(&primitive_val, &matches, &names, args.get(1), ctx)
+}
 
-    // Here cannot use the &str method "rfind", because this returns the last
-    // byte index: we need to return the last char index in the JS String
-    // Instead, iterate over the part we're checking keeping track of the higher
-    // index we found that "starts with" the search string
-    let mut highest_index: i32 = -1;
-    for index in start..length {
-        let this_string: String = primitive_val.chars().skip(index as usize).collect();
-        if this_string.starts_with(&search_string) {
-            highest_index = index;
+/// Returns a new string with every match of a pattern replaced by a replacement. The pattern is a
+/// string or a global RegExp, and the replacement is as for `replace`.
+/// <https://tc39.es/ecma262/#sec-string.prototype.replaceall>
+pub fn replace_all(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let primitive_val: String = ctx.value_to_rust_string(this)?;
+    let search_value = args
+        .first()
+        .cloned()
+        .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+
+    let (matches, names) = if regexp::is_regexp(&search_value) {
+        if !regexp::is_global(&search_value) {
+            return Err(error::new_error(
+                "TypeError",
+                "String.prototype.replaceAll called with a non-global RegExp argument",
+                ctx,
+            ));
+        }
+        (
+            regexp::matches(&search_value, &primitive_val),
+            regexp::capture_names(&search_value),
+        )
+    } else {
+        let search_string = ctx.value_to_rust_string(&search_value)?;
+        // A replacement without `$` patterns is inserted as it is
+        if let Some(replace_value) = args.get(1).filter(|value| !value.is_function()) {
+            let replacement = ctx.value_to_rust_string(replace_value)?;
+            if !replacement.contains('$') {
+                return Ok(to_value(
+                    primitive_val.replace(search_string.as_str(), &replacement),
+                ));
+            }
         }
+        let matches = primitive_val
+            .match_indices(search_string.as_str())
+            .map(|(start, matched)| vec![Some((start, start + matched.len()))])
+            .collect();
+        (matches, Vec::new())
+    };
+
+    replace_matches(&primitive_val, &matches, &names, args.get(1), ctx)
+}
+
+/// Replaces each of `matches` in `string` with the replacement given by `replace_value`
+fn replace_matches(
+    string: &str,
+    matches: &[regexp::Captures],
+    names: &[Option<String>],
+    replace_value: Option<&Value>,
+    ctx: &mut Interpreter,
+) -> ResultValue {
+    let replace_fn = replace_value.filter(|value| value.is_function());
+    let template = match (replace_fn, replace_value) {
+        (None, Some(value)) => ctx.value_to_rust_string(value)?,
+        (None, None) => String::from("undefined"),
+        _ => String::new(),
+    };
+
+    let mut result = String::with_capacity(string.len());
+    let mut last_end = 0;
+    for captures in matches {
+        let (start, end) = captures[0].expect("failed to get match");
+        let groups: Vec<Option<&str>> = captures[1..]
+            .iter()
+            .map(|group| group.map(|(start, end)| &string[start..end]))
+            .collect();
+
+        let replacement = match replace_fn {
+            Some(replace_fn) => {
+                let mut arguments = vec![to_value(&string[start..end])];
+                arguments.extend(groups.iter().map(|group| match group {
+                    Some(group) => to_value(*group),
+                    None => Gc::new(ValueData::Undefined),
+                }));
+                // The offset counts UTF-16 code units like every other string index
+                arguments.push(to_value(string[..start].encode_utf16().count() as f64));
+                arguments.push(to_value(string));
+                let undefined = Gc::new(ValueData::Undefined);
+                let replacement = ctx.call(replace_fn, &undefined, arguments)?;
+                ctx.value_to_rust_string(&replacement)?
+            }
+            None => get_substitution(string, (start, end), &groups, names, &template),
+        };
+
+        result.push_str(&string[last_end..start]);
+        result.push_str(&replacement);
+        last_end = end;
     }
+    result.push_str(&string[last_end..]);
+    Ok(to_value(result))
+}
 
-    // This will still be -1 if no matches were found, else with be >= 0
-    Ok(to_value(highest_index))
+/// Abstract method `GetSubstitution`
+/// Expands the `$` patterns of a replacement template for the match at `position` in `string`.
+/// <https://tc39.es/ecma262/#sec-getsubstitution>
+fn get_substitution(
+    string: &str,
+    position: (usize, usize),
+    groups: &[Option<&str>],
+    names: &[Option<String>],
+    template: &str,
+) -> String {
+    let (start, end) = position;
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(dollar) = rest.find('$') {
+        result.push_str(&rest[..dollar]);
+        rest = &rest[dollar + 1..];
+        let mut next = rest.chars();
+        match next.next() {
+            Some('$') => {
+                result.push('$');
+                rest = &rest[1..];
+            }
+            Some('&') => {
+                result.push_str(&string[start..end]);
+                rest = &rest[1..];
+            }
+            Some('`') => {
+                result.push_str(&string[..start]);
+                rest = &rest[1..];
+            }
+            Some('\'') => {
+                result.push_str(&string[end..]);
+                rest = &rest[1..];
+            }
+            Some(first) if first.is_ascii_digit() => {
+                let first = first.to_digit(10).expect("failed to parse digit") as usize;
+                // A two digit group number is preferred when the regex has that many groups
+                let is_group = |index: usize| index >= 1 && index <= groups.len();
+                let index = match next.next().and_then(|second| second.to_digit(10)) {
+                    Some(second) if is_group(first * 10 + second as usize) => {
+                        Some((first * 10 + second as usize, 2))
+                    }
+                    _ if is_group(first) => Some((first, 1)),
+                    _ => None,
+                };
+                match index {
+                    Some((index, digits)) => {
+                        result.push_str(groups[index - 1].unwrap_or(""));
+                        rest = &rest[digits..];
+                    }
+                    None => result.push('$'),
+                }
+            }
+            Some('<') if names.iter().any(Option::is_some) => match rest.find('>') {
+                Some(close) => {
+                    let name = &rest[1..close];
+                    let index = names
+                        .iter()
+                        .position(|group| group.as_ref().map(String::as_str) == Some(name));
+                    if let Some(group) = index.and_then(|index| groups[index - 1]) {
+                        result.push_str(group);
+                    }
+                    rest = &rest[close + 1..];
+                }
+                None => result.push('$'),
+            },
+            _ => result.push('$'),
+        }
+    }
+    result.push_str(rest);
+    result
 }
 
 /// Abstract method `StringPad`
@@ -422,7 +729,8 @@ fn string_pad(
     fill_string: Option<String>,
     at_start: bool,
 ) -> ResultValue {
-    let primitive_length = primitive.len() as i32;
+    // Lengths count UTF-16 code units
+    let primitive_length = primitive.encode_utf16().count() as i32;
 
     if max_length <= primitive_length {
         return Ok(to_value(primitive));
@@ -433,18 +741,15 @@ fn string_pad(
         None => String::from(" "),
     };
 
-    if filler == "" {
+    if filler.is_empty() {
         return Ok(to_value(primitive));
     }
 
-    let fill_len = max_length - primitive_length;
-    let mut fill_str = String::new();
-
-    while fill_str.len() < fill_len as usize {
-        fill_str.push_str(&filler);
-    }
-    // Cut to size max_length
-    let concat_fill_str: String = fill_str.chars().take(fill_len as usize).collect();
+    // The filler is repeated, and the last repetition truncated, to fill exactly the
+    // code units missing up to max_length
+    let fill_len = (max_length - primitive_length) as usize;
+    let fill_units: Vec<u16> = filler.encode_utf16().cycle().take(fill_len).collect();
+    let concat_fill_str = String::from_utf16_lossy(&fill_units);
 
     if at_start {
         Ok(to_value(concat_fill_str + &primitive))
@@ -459,19 +764,15 @@ fn string_pad(
 /// Filler defaults to single space.
 /// <https://tc39.es/ecma262/#sec-string.prototype.padend/>
 pub fn pad_end(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
-    let primitive_val: String = ctx.value_to_rust_string(this);
-    if args.is_empty() {
-        return Err(to_value("padEnd requires maxLength argument"));
-    }
-    let max_length = from_value(
-        args.get(0)
-            .expect("failed to get argument for String method")
-            .clone(),
-    )
-    .expect("failed to parse argument for String method");
-    let fill_string: Option<String> = match args.len() {
-        1 => None,
-        _ => Some(from_value(args[1].clone()).unwrap()),
+    let primitive_val: String = ctx.value_to_rust_string(this)?;
+    // An 'undefined' maxLength converts to 0, leaving the string as it is
+    let max_length = match args.first() {
+        Some(arg) if !arg.to_num().is_nan() => arg.to_num() as i32,
+        _ => 0,
+    };
+    let fill_string: Option<String> = match args.get(1) {
+        Some(arg) if !arg.is_undefined() => Some(ctx.value_to_rust_string(arg)?),
+        _ => None,
     };
 
     string_pad(primitive_val, max_length, fill_string, false)
@@ -483,19 +784,15 @@ pub fn pad_end(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultVal
 /// Filler defaults to single space.
 /// <https://tc39.es/ecma262/#sec-string.prototype.padstart/>
 pub fn pad_start(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
-    let primitive_val: String = ctx.value_to_rust_string(this);
-    if args.is_empty() {
-        return Err(to_value("padStart requires maxLength argument"));
-    }
-    let max_length = from_value(
-        args.get(0)
-            .expect("failed to get argument for String method")
-            .clone(),
-    )
-    .expect("failed to parse argument for String method");
-    let fill_string: Option<String> = match args.len() {
-        1 => None,
-        _ => Some(from_value(args[1].clone()).unwrap()),
+    let primitive_val: String = ctx.value_to_rust_string(this)?;
+    // An 'undefined' maxLength converts to 0, leaving the string as it is
+    let max_length = match args.first() {
+        Some(arg) if !arg.to_num().is_nan() => arg.to_num() as i32,
+        _ => 0,
+    };
+    let fill_string: Option<String> = match args.get(1) {
+        Some(arg) if !arg.is_undefined() => Some(ctx.value_to_rust_string(arg)?),
+        _ => None,
     };
 
     string_pad(primitive_val, max_length, fill_string, true)
@@ -519,20 +816,26 @@ fn is_trimmable_whitespace(c: char) -> bool {
     }
 }
 
+/// Returns a String with the whitespace and line terminators removed from both ends
+/// <https://tc39.es/ecma262/#sec-string.prototype.trim>
 pub fn trim(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
-    let this_str: String = ctx.value_to_rust_string(this);
+    let this_str: String = ctx.value_to_rust_string(this)?;
     Ok(to_value(this_str.trim_matches(is_trimmable_whitespace)))
 }
 
+/// Returns a String with the whitespace and line terminators removed from the start
+/// <https://tc39.es/ecma262/#sec-string.prototype.trimstart>
 pub fn trim_start(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
-    let this_str: String = ctx.value_to_rust_string(this);
+    let this_str: String = ctx.value_to_rust_string(this)?;
     Ok(to_value(
         this_str.trim_start_matches(is_trimmable_whitespace),
     ))
 }
 
+/// Returns a String with the whitespace and line terminators removed from the end
+/// <https://tc39.es/ecma262/#sec-string.prototype.trimend>
 pub fn trim_end(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
-    let this_str: String = ctx.value_to_rust_string(this);
+    let this_str: String = ctx.value_to_rust_string(this)?;
     Ok(to_value(this_str.trim_end_matches(is_trimmable_whitespace)))
 }
 
@@ -542,7 +845,7 @@ pub fn trim_end(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue
 pub fn to_lowercase(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
     // First we get it the actual string a private field stored on the object only the engine has access to.
     // Then we convert it into a Rust String by wrapping it in from_value
-    let this_str: String = ctx.value_to_rust_string(this);
+    let this_str: String = ctx.value_to_rust_string(this)?;
     // The Rust String is mapped to uppercase using the builtin .to_lowercase().
     // There might be corner cases where it does not behave exactly like Javascript expects
     Ok(to_value(this_str.to_lowercase()))
@@ -554,7 +857,7 @@ pub fn to_lowercase(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultV
 pub fn to_uppercase(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
     // First we get it the actual string a private field stored on the object only the engine has access to.
     // Then we convert it into a Rust String by wrapping it in from_value
-    let this_str: String = ctx.value_to_rust_string(this);
+    let this_str: String = ctx.value_to_rust_string(this)?;
     // The Rust String is mapped to uppercase using the builtin .to_uppercase().
     // There might be corner cases where it does not behave exactly like Javascript expects
     Ok(to_value(this_str.to_uppercase()))
@@ -570,36 +873,23 @@ pub fn to_uppercase(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultV
 pub fn substring(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     // First we get it the actual string a private field stored on the object only the engine has access to.
     // Then we convert it into a Rust String by wrapping it in from_value
-    let primitive_val: String = ctx.value_to_rust_string(this);
+    let primitive_val: String = ctx.value_to_rust_string(this)?;
+    let code_units: Vec<u16> = primitive_val.encode_utf16().collect();
+    let length = code_units.len() as f64;
     // If no args are specified, start is 'undefined', defaults to 0
-    let start = if args.is_empty() {
-        0
-    } else {
-        from_value(
-            args.get(0)
-                .expect("failed to get argument for String method")
-                .clone(),
-        )
-        .expect("failed to parse argument for String method")
-    };
-    let length: i32 = primitive_val.chars().count() as i32;
+    let start = integer_arg(args, 0, 0.0, ctx)?;
     // If less than 2 args specified, end is the length of the this object converted to a String
-    let end = if args.len() < 2 {
-        length
-    } else {
-        from_value(args[1].clone()).expect("failed to parse argument for String method")
-    };
+    let end = integer_arg(args, 1, length, ctx)?;
     // Both start and end args replaced by 0 if they were negative
     // or by the length of the String if they were greater
-    let final_start = min(max(start, 0), length);
-    let final_end = min(max(end, 0), length);
+    let final_start = start.max(0.0).min(length);
+    let final_end = end.max(0.0).min(length);
     // Start and end are swapped if start is greater than end
-    let from = min(final_start, final_end) as usize;
-    let to = max(final_start, final_end) as usize;
+    let from = final_start.min(final_end) as usize;
+    let to = final_start.max(final_end) as usize;
     // Extract the part of the string contained between the start index and the end index
     // where start is guaranteed to be smaller or equals to end
-    let extracted_string: String = primitive_val.chars().skip(from).take(to - from).collect();
-    Ok(to_value(extracted_string))
+    Ok(to_value(String::from_utf16_lossy(&code_units[from..to])))
 }
 
 /// Return a String which is a subset of the String value resulting from converting this object to a String.
@@ -611,46 +901,29 @@ pub fn substring(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultV
 pub fn substr(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     // First we get it the actual string a private field stored on the object only the engine has access to.
     // Then we convert it into a Rust String by wrapping it in from_value
-    let primitive_val: String = ctx.value_to_rust_string(this);
+    let primitive_val: String = ctx.value_to_rust_string(this)?;
+    let code_units: Vec<u16> = primitive_val.encode_utf16().collect();
+    let length = code_units.len() as f64;
     // If no args are specified, start is 'undefined', defaults to 0
-    let mut start = if args.is_empty() {
-        0
-    } else {
-        from_value(
-            args.get(0)
-                .expect("failed to get argument for String method")
-                .clone(),
-        )
-        .expect("failed to parse argument for String method")
-    };
-    let length: i32 = primitive_val.chars().count() as i32;
-    // If less than 2 args specified, end is +infinity, the maximum number value.
-    // Using i32::max_value() should be safe because the final length used is at most
-    // the number of code units from start to the end of the string,
-    // which should always be smaller or equals to both +infinity and i32::max_value
-    let end = if args.len() < 2 {
-        i32::max_value()
-    } else {
-        from_value(args[1].clone()).expect("failed to parse argument for String method")
-    };
+    let mut start = integer_arg(args, 0, 0.0, ctx)?;
+    // If less than 2 args specified, the length is +infinity
+    let end = integer_arg(args, 1, f64::INFINITY, ctx)?;
     // If start is negative it become the number of code units from the end of the string
-    if start < 0 {
-        start = max(length + start, 0);
+    if start < 0.0 {
+        start = (length + start).max(0.0);
     }
+    start = start.min(length);
     // length replaced by 0 if it was negative
     // or by the number of code units from start to the end of the string if it was greater
-    let result_length = min(max(end, 0), length - start);
-    // If length is negative we return an empty string
+    let result_length = end.max(0.0).min(length - start);
+    // If length is not positive we return an empty string
     // otherwise we extract the part of the string from start and is length code units long
-    if result_length <= 0 {
-        Ok(to_value("".to_string()))
+    if result_length <= 0.0 {
+        Ok(to_value(String::new()))
     } else {
-        let extracted_string: String = primitive_val
-            .chars()
-            .skip(start as usize)
-            .take(result_length as usize)
-            .collect();
-        Ok(to_value(extracted_string))
+        let from = start as usize;
+        let to = from + result_length as usize;
+        Ok(to_value(String::from_utf16_lossy(&code_units[from..to])))
     }
 }
 
@@ -661,6 +934,51 @@ pub fn value_of(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultVa
     to_string(this, args, ctx)
 }
 
+/// String.fromCharCode ( ...codeUnits )
+///
+/// Returns a String built from the given UTF-16 code units. Each argument is
+/// converted to a number and truncated to 16 bits.
+/// <https://tc39.es/ecma262/#sec-string.fromcharcode>
+pub fn from_char_code(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let code_units = args
+        .iter()
+        .map(|arg| {
+            let code = ctx.to_primitive(arg, Some("number"))?.to_num();
+            Ok(if code.is_finite() {
+                // ToUint16: the integer part modulo 2^16
+                code.trunc().rem_euclid(65536.0) as u16
+            } else {
+                0
+            })
+        })
+        .collect::<Result<Vec<u16>, Value>>()?;
+    // Rust strings must be valid UTF-8, so lone surrogates become U+FFFD
+    Ok(to_value(String::from_utf16_lossy(&code_units)))
+}
+
+/// String.fromCodePoint ( ...codePoints )
+///
+/// Returns a String built from the given code points, which are encoded as one UTF-16 code unit
+/// or as a surrogate pair. Throws a RangeError for anything which is not an integer between 0 and
+/// 0x10FFFF.
+/// <https://tc39.es/ecma262/#sec-string.fromcodepoint>
+pub fn from_code_point(_: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let mut result = String::with_capacity(args.len());
+    for arg in args {
+        let code = ctx.to_primitive(arg, Some("number"))?.to_num();
+        if code.trunc() != code || !(0.0..=1_114_111.0).contains(&code) {
+            return Err(error::new_error(
+                "RangeError",
+                &format!("Invalid code point {}", ctx.to_string(arg)?),
+                ctx,
+            ));
+        }
+        // Rust strings must be valid UTF-8, so lone surrogates become U+FFFD
+        result.push(std::char::from_u32(code as u32).unwrap_or('\u{FFFD}'));
+    }
+    Ok(to_value(result))
+}
+
 /// Create a new `String` object
 pub fn create_constructor(global: &Value) -> Value {
     // Create constructor function object
@@ -674,33 +992,43 @@ pub fn create_constructor(global: &Value) -> Value {
 
     // Create prototype
     let proto = ValueData::new_obj(Some(global));
-    let prop = Property::default().get(to_value(get_string_length as NativeFunctionData));
+    let prop = Property::default().get(make_builtin_fn(get_string_length, global));
 
     proto.set_prop_slice("length", prop);
-    proto.set_field_slice("charAt", to_value(char_at as NativeFunctionData));
-    proto.set_field_slice("charCodeAt", to_value(char_code_at as NativeFunctionData));
-    proto.set_field_slice("toString", to_value(to_string as NativeFunctionData));
-    proto.set_field_slice("concat", to_value(concat as NativeFunctionData));
-    proto.set_field_slice("repeat", to_value(repeat as NativeFunctionData));
-    proto.set_field_slice("slice", to_value(slice as NativeFunctionData));
-    proto.set_field_slice("startsWith", to_value(starts_with as NativeFunctionData));
-    proto.set_field_slice("endsWith", to_value(ends_with as NativeFunctionData));
-    proto.set_field_slice("includes", to_value(includes as NativeFunctionData));
-    proto.set_field_slice("indexOf", to_value(index_of as NativeFunctionData));
-    proto.set_field_slice("lastIndexOf", to_value(last_index_of as NativeFunctionData));
-    proto.set_field_slice("padEnd", to_value(pad_end as NativeFunctionData));
-    proto.set_field_slice("padStart", to_value(pad_start as NativeFunctionData));
-    proto.set_field_slice("trim", to_value(trim as NativeFunctionData));
-    proto.set_field_slice("trimStart", to_value(trim_start as NativeFunctionData));
-    proto.set_field_slice("toLowerCase", to_value(to_lowercase as NativeFunctionData));
-    proto.set_field_slice("toUpperCase", to_value(to_uppercase as NativeFunctionData));
-    proto.set_field_slice("substring", to_value(substring as NativeFunctionData));
-    proto.set_field_slice("substr", to_value(substr as NativeFunctionData));
-    proto.set_field_slice("valueOf", to_value(value_of as NativeFunctionData));
+    proto.set_field_slice("charAt", make_builtin_fn(char_at, global));
+    proto.set_field_slice("at", make_builtin_fn(at, global));
+    proto.set_field_slice("charCodeAt", make_builtin_fn(char_code_at, global));
+    proto.set_field_slice("codePointAt", make_builtin_fn(code_point_at, global));
+    proto.set_field_slice("toString", make_builtin_fn(to_string, global));
+    proto.set_field_slice("concat", make_builtin_fn(concat, global));
+    proto.set_field_slice("repeat", make_builtin_fn(repeat, global));
+    proto.set_field_slice("slice", make_builtin_fn(slice, global));
+    proto.set_field_slice("split", make_builtin_fn(split, global));
+    proto.set_field_slice("match", make_builtin_fn(r#match, global));
+    proto.set_field_slice("matchAll", make_builtin_fn(match_all, global));
+    proto.set_field_slice("replace", make_builtin_fn(replace, global));
+    proto.set_field_slice("replaceAll", make_builtin_fn(replace_all, global));
+    proto.set_field_slice("startsWith", make_builtin_fn(starts_with, global));
+    proto.set_field_slice("endsWith", make_builtin_fn(ends_with, global));
+    proto.set_field_slice("includes", make_builtin_fn(includes, global));
+    proto.set_field_slice("indexOf", make_builtin_fn(index_of, global));
+    proto.set_field_slice("lastIndexOf", make_builtin_fn(last_index_of, global));
+    proto.set_field_slice("padEnd", make_builtin_fn(pad_end, global));
+    proto.set_field_slice("padStart", make_builtin_fn(pad_start, global));
+    proto.set_field_slice("trim", make_builtin_fn(trim, global));
+    proto.set_field_slice("trimStart", make_builtin_fn(trim_start, global));
+    proto.set_field_slice("trimEnd", make_builtin_fn(trim_end, global));
+    proto.set_field_slice("toLowerCase", make_builtin_fn(to_lowercase, global));
+    proto.set_field_slice("toUpperCase", make_builtin_fn(to_uppercase, global));
+    proto.set_field_slice("substring", make_builtin_fn(substring, global));
+    proto.set_field_slice("substr", make_builtin_fn(substr, global));
+    proto.set_field_slice("valueOf", make_builtin_fn(value_of, global));
 
     let string = to_value(string_constructor);
     proto.set_field_slice("constructor", string.clone());
     string.set_field_slice(PROTOTYPE, proto);
+    string.set_field_slice("fromCharCode", make_builtin_fn(from_char_code, global));
+    string.set_field_slice("fromCodePoint", make_builtin_fn(from_code_point, global));
     string
 }
 
@@ -720,34 +1048,56 @@ mod tests {
     fn check_string_constructor_is_function() {
         let global = ValueData::new_obj(None);
         let string_constructor = create_constructor(&global);
-        assert_eq!(string_constructor.is_function(), true);
+        assert!(string_constructor.is_function());
     }
 
     #[test]
-    // TODO: re-enable when getProperty() is finished;
-    // fn length() {
-    //     //TEST262: https://github.com/tc39/test262/blob/master/test/built-ins/String/length.js
-    //     let mut engine = Executor::new();
-    //     let init = r#"
-    //     const a = new String(' ');
-    //     const b = new String('\ud834\udf06');
-    //     const c = new String(' \b ');
-    //     cosnt d = new String('中文长度')
-    //     "#;
-    //     forward(&mut engine, init);
-    //     let a = forward(&mut engine, "a.length");
-    //     assert_eq!(a, String::from("1"));
-    //     let b = forward(&mut engine, "b.length");
-    //     // TODO: fix this
-    //     // unicode surrogate pair length should be 1
-    //     // utf16/usc2 length should be 2
-    //     // utf8 length should be 4
-    //     //assert_eq!(b, String::from("2"));
-    //     let c = forward(&mut engine, "c.length");
-    //     assert_eq!(c, String::from("3"));
-    //     let d = forward(&mut engine, "d.length");
-    //     assert_eq!(d, String::from("4"));
-    // }
+    fn length() {
+        //TEST262: https://github.com/tc39/test262/blob/master/test/built-ins/String/length.js
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const a = new String(' ');
+        const b = new String('𝌆');
+        const c = new String(' \b ');
+        const d = new String('中文长度');
+        "#;
+        forward(&mut engine, init);
+        let a = forward(&mut engine, "a.length");
+        assert_eq!(a, String::from("1"));
+        // A surrogate pair is two UTF-16 code units
+        let b = forward(&mut engine, "b.length");
+        assert_eq!(b, String::from("2"));
+        let c = forward(&mut engine, "c.length");
+        assert_eq!(c, String::from("3"));
+        let d = forward(&mut engine, "d.length");
+        assert_eq!(d, String::from("4"));
+    }
+
+    #[test]
+    fn index_and_length_in_code_units() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const ascii = 'abc';
+        const latin = 'café';
+        const astral = '😀!';
+        const boxed = new String('héllo');
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "ascii.length"), "3");
+        assert_eq!(forward(&mut engine, "ascii[1]"), "b");
+        assert_eq!(forward(&mut engine, "ascii[3]"), "undefined");
+        assert_eq!(forward(&mut engine, "latin.length"), "4");
+        assert_eq!(forward(&mut engine, "latin[3] === '\\u00e9'"), "true");
+        assert_eq!(forward(&mut engine, "astral.length"), "3");
+        assert_eq!(forward(&mut engine, "astral[2]"), "!");
+        assert_eq!(forward(&mut engine, "boxed.length"), "5");
+        assert_eq!(forward(&mut engine, "boxed[1] === '\\u00e9'"), "true");
+        assert_eq!(forward(&mut engine, "boxed['01']"), "undefined");
+    }
+
     #[test]
     fn concat() {
         let realm = Realm::create();
@@ -778,8 +1128,8 @@ mod tests {
         let hello = forward_val(&mut engine, "hello").unwrap();
         let world = forward_val(&mut engine, "world").unwrap();
 
-        assert_eq!(hello.is_object(), true);
-        assert_eq!(world.is_string(), true);
+        assert!(hello.is_object());
+        assert!(world.is_string());
     }
 
     #[test]
@@ -857,4 +1207,539 @@ mod tests {
         assert_eq!(forward(&mut engine, "enLiteral.endsWith('h')"), pass);
         assert_eq!(forward(&mut engine, "zhLiteral.endsWith('文')"), pass);
     }
+
+    #[test]
+    fn search_positions() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const en = 'hello world';
+        const zh = '中文中文';
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "en.startsWith('world', 6)"), "true");
+        assert_eq!(forward(&mut engine, "en.startsWith('hello', 1)"), "false");
+        assert_eq!(forward(&mut engine, "en.endsWith('hello', 5)"), "true");
+        assert_eq!(forward(&mut engine, "en.endsWith('world', 5)"), "false");
+        assert_eq!(forward(&mut engine, "en.includes('o w')"), "true");
+        assert_eq!(forward(&mut engine, "en.includes('hello', 1)"), "false");
+        assert_eq!(forward(&mut engine, "en.includes('xyz')"), "false");
+        assert_eq!(forward(&mut engine, "zh.includes('文中')"), "true");
+    }
+
+    #[test]
+    fn index_of() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const en = 'hello world';
+        const zh = '中文中文';
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "en.indexOf('o')"), "4");
+        assert_eq!(forward(&mut engine, "en.indexOf('o', 5)"), "7");
+        assert_eq!(forward(&mut engine, "en.indexOf('xyz')"), "-1");
+        assert_eq!(forward(&mut engine, "en.indexOf('', 11)"), "11");
+        assert_eq!(forward(&mut engine, "zh.indexOf('文', 2)"), "3");
+        assert_eq!(forward(&mut engine, "en.lastIndexOf('o')"), "7");
+        assert_eq!(forward(&mut engine, "en.lastIndexOf('o', 5)"), "4");
+        assert_eq!(forward(&mut engine, "en.lastIndexOf('xyz')"), "-1");
+        assert_eq!(forward(&mut engine, "zh.lastIndexOf('中')"), "2");
+    }
+
+    #[test]
+    fn repeat_range_error() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        forward(&mut engine, "const ab = 'ab';");
+
+        assert_eq!(forward(&mut engine, "ab.repeat(3)"), "ababab");
+        assert_eq!(forward(&mut engine, "ab.repeat(2.7)"), "abab");
+        assert_eq!(
+            forward(&mut engine, "ab.repeat(-1)"),
+            "Error: RangeError: Invalid count value: -1"
+        );
+        assert_eq!(
+            forward(&mut engine, "ab.repeat(Infinity)"),
+            "Error: RangeError: Invalid count value: Infinity"
+        );
+        assert_eq!(forward(&mut engine, "ab.repeat(0) === ''"), "true");
+        // Strings too long to make throw rather than running out of memory
+        assert_eq!(
+            forward(&mut engine, "'a'.repeat(1e10)"),
+            "Error: RangeError: Invalid string length"
+        );
+        assert_eq!(
+            forward(&mut engine, "ab.repeat(Math.pow(2, 62))"),
+            "Error: RangeError: Invalid string length"
+        );
+        assert_eq!(
+            forward(&mut engine, "ab.repeat(1e300)"),
+            "Error: RangeError: Invalid string length"
+        );
+        assert_eq!(forward(&mut engine, "''.repeat(1e10) === ''"), "true");
+    }
+
+    #[test]
+    fn pad_start_and_end() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const five = '5';
+        const zh = '中文';
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "five.padStart(3, '0')"), "005");
+        assert_eq!(forward(&mut engine, "five.padEnd(3, '0')"), "500");
+        assert_eq!(forward(&mut engine, "five.padStart(3)"), "  5");
+        assert_eq!(forward(&mut engine, "five.padEnd(6, 'abc')"), "5abcab");
+        assert_eq!(forward(&mut engine, "five.padStart(0, '0')"), "5");
+        assert_eq!(forward(&mut engine, "zh.padStart(4, '-')"), "--中文");
+        assert_eq!(
+            forward(&mut engine, "'x'.padEnd(4, 'ab') === 'xaba'"),
+            "true"
+        );
+        assert_eq!(forward(&mut engine, "'x'.padEnd(4, '')"), "x");
+        // A character outside the basic multilingual plane counts as two code units
+        assert_eq!(
+            forward(&mut engine, "'\u{1F600}'.padStart(3, '-') === '-\u{1F600}'"),
+            "true"
+        );
+    }
+
+    #[test]
+    fn char_codes() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const hi = 'Hi';
+        const clef = '𝄞a';
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "String.fromCharCode(72, 105)"), "Hi");
+        assert_eq!(forward(&mut engine, "String.fromCharCode(65608)"), "H");
+        assert_eq!(
+            forward(&mut engine, "String.fromCharCode(0xD834, 0xDD1E)"),
+            "𝄞"
+        );
+        assert_eq!(forward(&mut engine, "String.fromCharCode()"), "");
+        assert_eq!(forward(&mut engine, "hi.charCodeAt(0)"), "72");
+        assert_eq!(forward(&mut engine, "hi.charCodeAt()"), "72");
+        assert_eq!(forward(&mut engine, "hi.charCodeAt(2)"), "NaN");
+        assert_eq!(forward(&mut engine, "clef.charCodeAt(1)"), "56606");
+        assert_eq!(forward(&mut engine, "clef.charCodeAt(2)"), "97");
+    }
+
+    #[test]
+    fn from_code_point() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+
+        assert_eq!(forward(&mut engine, "String.fromCodePoint(72, 105)"), "Hi");
+        assert_eq!(
+            forward(&mut engine, "String.fromCodePoint(0x1F600, 97)"),
+            "\u{1F600}a"
+        );
+        assert_eq!(
+            forward(&mut engine, "String.fromCodePoint(0x1D11E).length"),
+            "2"
+        );
+        assert_eq!(
+            forward(&mut engine, "String.fromCodePoint(128512).codePointAt(0)"),
+            "128512"
+        );
+        assert_eq!(
+            forward(
+                &mut engine,
+                "String.fromCodePoint(0x1F600) === String.fromCharCode(0xD83D, 0xDE00)"
+            ),
+            "true"
+        );
+        assert_eq!(forward(&mut engine, "String.fromCodePoint('233')"), "é");
+        assert_eq!(forward(&mut engine, "String.fromCodePoint()"), "");
+        assert_eq!(
+            forward(&mut engine, "String.fromCodePoint(0x110000)"),
+            "Error: RangeError: Invalid code point 1114112"
+        );
+        assert_eq!(
+            forward(&mut engine, "String.fromCodePoint(-1)"),
+            "Error: RangeError: Invalid code point -1"
+        );
+        assert_eq!(
+            forward(&mut engine, "String.fromCodePoint(1.5)"),
+            "Error: RangeError: Invalid code point 1.5"
+        );
+        assert_eq!(
+            forward(&mut engine, "String.fromCodePoint(NaN)"),
+            "Error: RangeError: Invalid code point NaN"
+        );
+    }
+
+    #[test]
+    fn char_at_and_code_point_at() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const emoji = '\u{1F600}';
+        const accented = 'héllo';
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(
+            forward(&mut engine, "emoji.charCodeAt(0) === 55357"),
+            "true"
+        );
+        assert_eq!(
+            forward(&mut engine, "emoji.codePointAt(0) === 128512"),
+            "true"
+        );
+        // Inside a surrogate pair only the trailing surrogate is left
+        assert_eq!(forward(&mut engine, "emoji.codePointAt(1)"), "56832");
+        assert_eq!(forward(&mut engine, "emoji.codePointAt(2)"), "undefined");
+        assert_eq!(forward(&mut engine, "accented.charAt(1)"), "é");
+        assert_eq!(forward(&mut engine, "accented.charAt()"), "h");
+        assert_eq!(forward(&mut engine, "accented.charAt(5)"), "");
+        assert_eq!(forward(&mut engine, "accented.charAt(-1)"), "");
+        assert_eq!(forward(&mut engine, "accented.codePointAt(1)"), "233");
+        assert_eq!(
+            forward(&mut engine, "String.fromCharCode(55357, 56832) === emoji"),
+            "true"
+        );
+    }
+
+    #[test]
+    fn split() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const empties = 'a,,b';
+        const abc = 'abc';
+        const x = 'x';
+        const empty = '';
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(
+            forward(&mut engine, "empties.split(',').length === 3"),
+            "true"
+        );
+        assert_eq!(
+            forward(&mut engine, "abc.split('').join('-') === 'a-b-c'"),
+            "true"
+        );
+        assert_eq!(forward(&mut engine, "x.split(',')[0] === 'x'"), "true");
+        assert_eq!(forward(&mut engine, "abc.split().length"), "1");
+        assert_eq!(forward(&mut engine, "abc.split()[0]"), "abc");
+        assert_eq!(
+            forward(&mut engine, "empties.split(',', 2).join('|')"),
+            "a|"
+        );
+        assert_eq!(forward(&mut engine, "empties.split(',', 0).length"), "0");
+        assert_eq!(forward(&mut engine, "empty.split(',').length"), "1");
+        assert_eq!(forward(&mut engine, "empty.split('').length"), "0");
+        assert_eq!(forward(&mut engine, "Array.isArray(abc.split(''))"), "true");
+    }
+
+    #[test]
+    fn split_with_regexp() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const mixed = 'a1b22c';
+        const dashed = 'a-b';
+        const digits = new RegExp('[0-9]+');
+        const dash = new RegExp('(-)');
+        const nothing = new RegExp('');
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "mixed.split(digits).join()"), "a,b,c");
+        assert_eq!(forward(&mut engine, "dashed.split(dash).join()"), "a,-,b");
+        assert_eq!(
+            forward(&mut engine, "dashed.split(nothing).join()"),
+            "a,-,b"
+        );
+    }
+
+    #[test]
+    fn extraction_methods() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const hello = 'hello';
+        const accented = 'héllo';
+        const emoji = 'a\u{1F600}b';
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "hello.slice(-3)"), "llo");
+        assert_eq!(forward(&mut engine, "hello.slice(1, -1)"), "ell");
+        assert_eq!(forward(&mut engine, "hello.slice(3, 1)"), "");
+        assert_eq!(forward(&mut engine, "hello.substring(3, 1)"), "el");
+        assert_eq!(forward(&mut engine, "hello.substring(-2)"), "hello");
+        assert_eq!(forward(&mut engine, "hello.substring()"), "hello");
+        assert_eq!(forward(&mut engine, "hello.substr(1, 3)"), "ell");
+        assert_eq!(forward(&mut engine, "hello.substr(-3, 2)"), "ll");
+        assert_eq!(forward(&mut engine, "hello.substr(2)"), "llo");
+        assert_eq!(forward(&mut engine, "accented.slice(1, 2)"), "é");
+        assert_eq!(forward(&mut engine, "accented.substring(2)"), "llo");
+        assert_eq!(forward(&mut engine, "emoji.slice(1, 3)"), "\u{1F600}");
+        assert_eq!(forward(&mut engine, "emoji.substr(3)"), "b");
+    }
+
+    #[test]
+    fn extraction_edge_cases() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        forward(
+            &mut engine,
+            "const hello = 'hello'; const two = { valueOf() { return 2; } };",
+        );
+
+        // slice: negative indices count from the end and are clamped to the start
+        assert_eq!(forward(&mut engine, "hello.slice(-10)"), "hello");
+        assert_eq!(forward(&mut engine, "hello.slice(-10, -3)"), "he");
+        assert_eq!(forward(&mut engine, "hello.slice(2, 100)"), "llo");
+        assert_eq!(forward(&mut engine, "hello.slice(NaN, 2)"), "he");
+        assert_eq!(forward(&mut engine, "hello.slice(1, undefined)"), "ello");
+        assert_eq!(forward(&mut engine, "hello.slice(Infinity)"), "");
+        // substring: NaN and negative indices become 0, and the indices are swapped when the
+        // start is after the end
+        assert_eq!(forward(&mut engine, "hello.substring(NaN, 2)"), "he");
+        assert_eq!(forward(&mut engine, "hello.substring(2, NaN)"), "he");
+        assert_eq!(forward(&mut engine, "hello.substring(-1, 3)"), "hel");
+        assert_eq!(forward(&mut engine, "hello.substring(4, -1)"), "hell");
+        assert_eq!(forward(&mut engine, "hello.substring(1.7, 3.2)"), "el");
+        // substr: the second argument is a length, and a negative start counts from the end
+        assert_eq!(forward(&mut engine, "hello.substr(-2)"), "lo");
+        assert_eq!(forward(&mut engine, "hello.substr(-10, 2)"), "he");
+        assert_eq!(forward(&mut engine, "hello.substr(1, -1)"), "");
+        assert_eq!(forward(&mut engine, "hello.substr(1, 100)"), "ello");
+        assert_eq!(forward(&mut engine, "hello.substr(NaN, NaN)"), "");
+        // Indices are converted to numbers like any other argument
+        assert_eq!(forward(&mut engine, "hello.slice('1', '3')"), "el");
+        assert_eq!(forward(&mut engine, "hello.substring(two)"), "llo");
+        assert_eq!(forward(&mut engine, "hello.substr(two, two)"), "ll");
+    }
+
+    #[test]
+    fn match_regexp() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const all = 'a1b22c333'.match(/\d+/g);
+        const first = 'on 2024-01'.match(/(\d+)-(\d+)/);
+        const none = 'abc'.match(/\d/g);
+        const pattern = 'a.c'.match('.');
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "all.join()"), "1,22,333");
+        assert_eq!(forward(&mut engine, "first.join()"), "2024-01,2024,01");
+        assert_eq!(forward(&mut engine, "first.index"), "3");
+        assert_eq!(forward(&mut engine, "none"), "null");
+        assert_eq!(forward(&mut engine, "pattern[0]"), "a");
+        assert_eq!(forward(&mut engine, "first.input"), "on 2024-01");
+        assert_eq!(forward(&mut engine, "first.groups"), "undefined");
+        assert_eq!(
+            forward(&mut engine, "'y2024'.match(/(?<year>\\d+)/).groups.year"),
+            "2024"
+        );
+    }
+
+    #[test]
+    fn match_all() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const re = /(\d)(\w)/g;
+        const matches = 'a1b2c3d'.matchAll(re);
+        const first = matches.next();
+        const second = matches.next();
+        const third = matches.next();
+        const last = matches.next();
+        const empty = 'ab'.matchAll('');
+        empty.next();
+        empty.next();
+        const end = empty.next();
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "first.done"), "false");
+        assert_eq!(forward(&mut engine, "first.value.join()"), "1b,1,b");
+        assert_eq!(forward(&mut engine, "first.value.index"), "1");
+        assert_eq!(forward(&mut engine, "first.value.input"), "a1b2c3d");
+        assert_eq!(forward(&mut engine, "second.value.join()"), "2c,2,c");
+        assert_eq!(forward(&mut engine, "third.value.join()"), "3d,3,d");
+        assert_eq!(forward(&mut engine, "last.done"), "true");
+        assert_eq!(forward(&mut engine, "last.value"), "undefined");
+        // The regex itself is left untouched
+        assert_eq!(forward(&mut engine, "re.lastIndex"), "0");
+        // Empty matches make progress, one per position
+        assert_eq!(forward(&mut engine, "end.done"), "false");
+        assert_eq!(forward(&mut engine, "end.value.index"), "2");
+        assert_eq!(forward(&mut engine, "empty.next().done"), "true");
+        assert_eq!(
+            forward(&mut engine, "'abc'.matchAll(/b/)"),
+            "Error: TypeError: String.prototype.matchAll called with a non-global RegExp argument"
+        );
+    }
+
+    #[test]
+    fn replace_with_regexp() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const swapped = '2024-01'.replace(/(\d+)-(\d+)/, '$2/$1');
+        const global = 'a-b-c'.replace(/-/g, '+');
+        const first = 'a-b-c'.replace('-', '+');
+        const special = 'abc'.replace('b', '[$&$$]');
+        const named = '2024'.replace(/(?<year>\d+)/, 'y$<year>');
+        const called = 'x1y22'.replace(/(\d)(\d)?/g, (match, a, b, offset) => {
+            return '<' + match + a + b + offset + '>';
+        });
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "swapped"), "01/2024");
+        assert_eq!(forward(&mut engine, "global"), "a+b+c");
+        assert_eq!(forward(&mut engine, "first"), "a+b-c");
+        assert_eq!(forward(&mut engine, "special"), "a[b$]c");
+        assert_eq!(forward(&mut engine, "named"), "y2024");
+        assert_eq!(forward(&mut engine, "called"), "x<11undefined1>y<22223>");
+    }
+
+    #[test]
+    fn search_predicates() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const abc = 'abcabc';
+        const emoji = '\u{1F600}ab';
+        const numbers = 'a1b2';
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "abc.lastIndexOf('a')"), "3");
+        assert_eq!(forward(&mut engine, "abc.lastIndexOf('a', 2)"), "0");
+        assert_eq!(forward(&mut engine, "abc.indexOf('', 10)"), "6");
+        assert_eq!(forward(&mut engine, "'hello'.startsWith('ell', 1)"), "true");
+        assert_eq!(forward(&mut engine, "'x'.includes('')"), "true");
+        assert_eq!(forward(&mut engine, "numbers.includes(2)"), "true");
+        assert_eq!(forward(&mut engine, "numbers.indexOf(1)"), "1");
+        // Positions count UTF-16 code units
+        assert_eq!(forward(&mut engine, "emoji.indexOf('a')"), "2");
+        assert_eq!(forward(&mut engine, "emoji.startsWith('b', 3)"), "true");
+        assert_eq!(forward(&mut engine, "emoji.endsWith('a', 3)"), "true");
+        assert_eq!(
+            forward(&mut engine, "abc.includes(/a/)"),
+            "Error: TypeError: First argument to String.prototype.includes must not be a regular expression"
+        );
+    }
+
+    #[test]
+    fn case_and_trim() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const padded = '  hi\t\n';
+        const unicode = '\u{FEFF}\u{3000}hi\u{2028}\u{A0}';
+        const boxed = new String(' AbC ');
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "padded.trim() === 'hi'"), "true");
+        assert_eq!(forward(&mut engine, "padded.trimStart()"), "hi\t\n");
+        assert_eq!(forward(&mut engine, "padded.trimEnd()"), "  hi");
+        assert_eq!(forward(&mut engine, "unicode.trim()"), "hi");
+        assert_eq!(
+            forward(&mut engine, "'AbC'.toLowerCase() === 'abc'"),
+            "true"
+        );
+        assert_eq!(forward(&mut engine, "'X'.toLowerCase()"), "x");
+        assert_eq!(forward(&mut engine, "'straße'.toUpperCase()"), "STRASSE");
+        assert_eq!(forward(&mut engine, "boxed.trim().toUpperCase()"), "ABC");
+    }
+
+    #[test]
+    fn at() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const abc = 'abc';
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "abc.at(-1) === 'c'"), "true");
+        assert_eq!(forward(&mut engine, "abc.at(0)"), "a");
+        assert_eq!(forward(&mut engine, "abc.at(1.9)"), "b");
+        assert_eq!(forward(&mut engine, "abc.at(3) === undefined"), "true");
+        assert_eq!(forward(&mut engine, "abc.at(-4) === undefined"), "true");
+        assert_eq!(forward(&mut engine, "''.at(0) === undefined"), "true");
+    }
+
+    #[test]
+    fn replace_and_replace_all() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const aaa = 'aaa';
+        const upper = 'a-b-c'.replaceAll('-', '+').replace(/[a-z]/g, (match) => {
+            return match.toUpperCase();
+        });
+        const offsets = 'xyxy'.replaceAll('x', (match, offset, string) => {
+            return offset + string.length;
+        });
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(
+            forward(&mut engine, "aaa.replace('a', 'b') === 'baa'"),
+            "true"
+        );
+        assert_eq!(
+            forward(&mut engine, "aaa.replaceAll('a', 'b') === 'bbb'"),
+            "true"
+        );
+        assert_eq!(
+            forward(&mut engine, "aaa.replaceAll('a', '[$&$$]')"),
+            "[a$][a$][a$]"
+        );
+        assert_eq!(forward(&mut engine, "'ab'.replaceAll('', '-')"), "-a-b-");
+        assert_eq!(forward(&mut engine, "upper"), "A+B+C");
+        assert_eq!(forward(&mut engine, "offsets"), "4y6y");
+        assert_eq!(
+            forward(&mut engine, "'1-2'.replaceAll(/(\\d)/g, '<$1>')"),
+            "<1>-<2>"
+        );
+    }
+
+    #[test]
+    fn replace_all_arguments() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const calls = { text: '' };
+        const replaced = 'aabbaa'.replaceAll('b', (match, offset, string) => {
+            calls.text = calls.text + match + offset + string + ';';
+            return 'y';
+        });
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(
+            forward(&mut engine, "'aabbaa'.replaceAll('a', 'x')"),
+            "xxbbxx"
+        );
+        assert_eq!(forward(&mut engine, "replaced"), "aayyaa");
+        assert_eq!(forward(&mut engine, "calls.text"), "b2aabbaa;b3aabbaa;");
+        assert_eq!(
+            forward(&mut engine, "'aa'.replaceAll(/a/, 'x')"),
+            "Error: TypeError: String.prototype.replaceAll called with a non-global RegExp argument"
+        );
+    }
 }
@@ -0,0 +1,770 @@
+//! The `ArrayBuffer` object and the typed array views over it
+//!
+//! An `ArrayBuffer` object holds its bytes in the `ArrayBufferData` internal slot as a
+//! `ValueData::ArrayBuffer`, which lets several views share the same memory.
+//! Typed arrays keep the viewed buffer object, their byte offset and their element count in
+//! internal slots, and their elements are read and written through the buffer on every access.
+//! <https://tc39.es/ecma262/#sec-arraybuffer-objects>
+//! <https://tc39.es/ecma262/#sec-typedarray-objects>
+use crate::{
+    exec::Interpreter,
+    js::{
+        error,
+        function::{make_builtin_fn, NativeFunctionData},
+        object::{Object, ObjectKind, INSTANCE_PROTOTYPE, PROTOTYPE},
+        property::Property,
+        value::{from_value, to_value, ResultValue, Value, ValueData},
+    },
+};
+use gc::{Gc, GcCell};
+
+/// The element types a typed array can view a buffer as
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TypedArrayKind {
+    Int8,
+    Uint8,
+    Int16,
+    Uint16,
+    Int32,
+    Uint32,
+    Float32,
+    Float64,
+}
+
+impl TypedArrayKind {
+    /// Every kind of typed array, in the order their constructors are created
+    pub const ALL: [TypedArrayKind; 8] = [
+        TypedArrayKind::Int8,
+        TypedArrayKind::Uint8,
+        TypedArrayKind::Int16,
+        TypedArrayKind::Uint16,
+        TypedArrayKind::Int32,
+        TypedArrayKind::Uint32,
+        TypedArrayKind::Float32,
+        TypedArrayKind::Float64,
+    ];
+
+    /// The name of the global constructor for this kind
+    pub fn name(self) -> &'static str {
+        match self {
+            TypedArrayKind::Int8 => "Int8Array",
+            TypedArrayKind::Uint8 => "Uint8Array",
+            TypedArrayKind::Int16 => "Int16Array",
+            TypedArrayKind::Uint16 => "Uint16Array",
+            TypedArrayKind::Int32 => "Int32Array",
+            TypedArrayKind::Uint32 => "Uint32Array",
+            TypedArrayKind::Float32 => "Float32Array",
+            TypedArrayKind::Float64 => "Float64Array",
+        }
+    }
+
+    /// Find the kind from the name of its constructor
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().cloned().find(|kind| kind.name() == name)
+    }
+
+    /// The number of bytes taken by a single element
+    pub fn element_size(self) -> usize {
+        match self {
+            TypedArrayKind::Int8 | TypedArrayKind::Uint8 => 1,
+            TypedArrayKind::Int16 | TypedArrayKind::Uint16 => 2,
+            TypedArrayKind::Int32 | TypedArrayKind::Uint32 | TypedArrayKind::Float32 => 4,
+            TypedArrayKind::Float64 => 8,
+        }
+    }
+
+    /// Read a little-endian element from the start of `bytes`
+    fn read(self, bytes: &[u8]) -> f64 {
+        let mut raw = [0_u8; 8];
+        raw[..self.element_size()].copy_from_slice(&bytes[..self.element_size()]);
+        let (b0, b1, b2, b3) = (raw[0], raw[1], raw[2], raw[3]);
+        match self {
+            TypedArrayKind::Int8 => f64::from(b0 as i8),
+            TypedArrayKind::Uint8 => f64::from(b0),
+            TypedArrayKind::Int16 => f64::from(i16::from_le_bytes([b0, b1])),
+            TypedArrayKind::Uint16 => f64::from(u16::from_le_bytes([b0, b1])),
+            TypedArrayKind::Int32 => f64::from(i32::from_le_bytes([b0, b1, b2, b3])),
+            TypedArrayKind::Uint32 => f64::from(u32::from_le_bytes([b0, b1, b2, b3])),
+            TypedArrayKind::Float32 => {
+                f64::from(f32::from_bits(u32::from_le_bytes([b0, b1, b2, b3])))
+            }
+            TypedArrayKind::Float64 => f64::from_bits(u64::from_le_bytes(raw)),
+        }
+    }
+
+    /// Write `value` as a little-endian element to the start of `bytes`
+    ///
+    /// Integer kinds wrap modulo 2^n as ToInt8, ToUint8 and friends do.
+    fn write(self, value: f64, bytes: &mut [u8]) {
+        let size = self.element_size();
+        match self {
+            TypedArrayKind::Float32 => {
+                bytes[..size].copy_from_slice(&(value as f32).to_bits().to_le_bytes())
+            }
+            TypedArrayKind::Float64 => {
+                bytes[..size].copy_from_slice(&value.to_bits().to_le_bytes())
+            }
+            _ => {
+                let int = if value.is_finite() {
+                    value.trunc()
+                } else {
+                    0.0
+                };
+                let modulo = 2_f64.powi(8 * size as i32);
+                let wrapped = ((int % modulo) + modulo) % modulo;
+                bytes[..size].copy_from_slice(&(wrapped as u64).to_le_bytes()[..size]);
+            }
+        }
+    }
+}
+
+/// Run `f` over the bytes held by an `ArrayBuffer` object
+fn with_bytes<R>(buffer: &Value, f: impl FnOnce(&mut Vec<u8>) -> R) -> Option<R> {
+    let data = buffer.get_internal_slot("ArrayBufferData");
+    match *data {
+        ValueData::ArrayBuffer(ref bytes) => Some(f(&mut bytes.borrow_mut())),
+        _ => None,
+    }
+}
+
+/// Check if a value is an `ArrayBuffer` object
+fn is_array_buffer(value: &Value) -> bool {
+    match *value.get_internal_slot("ArrayBufferData") {
+        ValueData::ArrayBuffer(_) => true,
+        _ => false,
+    }
+}
+
+/// Get the kind named by the `TypedArrayName` internal slot of an object
+fn typed_array_name(value: &Value) -> Option<TypedArrayKind> {
+    match *value.get_internal_slot("TypedArrayName") {
+        ValueData::String(ref name) => TypedArrayKind::from_name(name),
+        _ => None,
+    }
+}
+
+/// Get the kind of a typed array object, or `None` if the value is not a typed array
+pub fn typed_array_kind(value: &Value) -> Option<TypedArrayKind> {
+    if is_array_buffer(&value.get_internal_slot("ViewedArrayBuffer")) {
+        typed_array_name(value)
+    } else {
+        None
+    }
+}
+
+/// Get the byte offset and element count of a typed array object
+fn view_bounds(view: &Value) -> (usize, usize) {
+    (
+        from_value(view.get_internal_slot("ByteOffset")).unwrap(),
+        from_value(view.get_internal_slot("ArrayLength")).unwrap(),
+    )
+}
+
+/// Convert a relative index argument (negative values count from `length`) into an index
+fn relative_index(arg: Option<&Value>, length: usize, default: usize) -> usize {
+    match arg {
+        Some(value) if !value.is_undefined() => {
+            let relative = value.to_num();
+            let relative = if relative.is_nan() {
+                0.0
+            } else {
+                relative.trunc()
+            };
+            if relative < 0.0 {
+                (length as f64 + relative).max(0.0) as usize
+            } else {
+                relative.min(length as f64) as usize
+            }
+        }
+        _ => default,
+    }
+}
+
+/// Create a new `ArrayBuffer` object holding `bytes`
+fn allocate_array_buffer(bytes: Vec<u8>, interpreter: &Interpreter) -> Value {
+    let proto = interpreter
+        .get_realm()
+        .global_obj
+        .get_field_slice("ArrayBuffer")
+        .get_field_slice(PROTOTYPE);
+    let buffer = ValueData::new_obj_from_prototype(proto, ObjectKind::ArrayBuffer);
+    buffer.set_internal_slot(
+        "ArrayBufferData",
+        Gc::new(ValueData::ArrayBuffer(GcCell::new(bytes))),
+    );
+    buffer
+}
+
+/// Turn `view` into a typed array of `kind` over `buffer`
+fn initialize_view(
+    view: &Value,
+    kind: TypedArrayKind,
+    buffer: Value,
+    byte_offset: usize,
+    length: usize,
+) {
+    view.set_kind(ObjectKind::TypedArray);
+    view.set_internal_slot("TypedArrayName", to_value(kind.name()));
+    view.set_internal_slot("ViewedArrayBuffer", buffer);
+    view.set_internal_slot("ByteOffset", to_value(byte_offset));
+    view.set_internal_slot("ArrayLength", to_value(length));
+}
+
+/// Create a new typed array object of `kind` over `buffer`
+fn create_view(
+    kind: TypedArrayKind,
+    buffer: Value,
+    byte_offset: usize,
+    length: usize,
+    interpreter: &Interpreter,
+) -> Value {
+    let proto = interpreter
+        .get_realm()
+        .global_obj
+        .get_field_slice(kind.name())
+        .get_field_slice(PROTOTYPE);
+    let view = ValueData::new_obj_from_prototype(proto, ObjectKind::TypedArray);
+    initialize_view(&view, kind, buffer, byte_offset, length);
+    view
+}
+
+/// Read every element of a typed array or array-like object as a number
+fn source_values(source: &Value) -> Vec<f64> {
+    match typed_array_kind(source) {
+        Some(kind) => {
+            let (byte_offset, length) = view_bounds(source);
+            with_bytes(&source.get_internal_slot("ViewedArrayBuffer"), |bytes| {
+                (0..length)
+                    .map(|n| kind.read(&bytes[byte_offset + n * kind.element_size()..]))
+                    .collect()
+            })
+            .unwrap_or_default()
+        }
+        None => {
+            let length: usize = from_value(source.get_field_slice("length")).unwrap();
+            (0..length)
+                .map(|n| source.get_field(&n.to_string()).to_num())
+                .collect()
+        }
+    }
+}
+
+/// Get an element of a typed array
+///
+/// Returns `None` if `obj` is not a typed array or `field` is not an array index, so the
+/// lookup can fall back to an ordinary property access.
+/// <https://tc39.es/ecma262/#sec-integerindexedelementget>
+pub fn get_element(obj: &Value, field: &str) -> Option<Value> {
+    let kind = typed_array_kind(obj)?;
+    let index: usize = field
+        .parse()
+        .ok()
+        .filter(|n: &usize| n.to_string() == field)?;
+    let (byte_offset, length) = view_bounds(obj);
+    if index >= length {
+        return Some(Gc::new(ValueData::Undefined));
+    }
+    let value = with_bytes(&obj.get_internal_slot("ViewedArrayBuffer"), |bytes| {
+        kind.read(&bytes[byte_offset + index * kind.element_size()..])
+    })?;
+    Some(to_value(value))
+}
+
+/// Set an element of a typed array, returning whether `field` was handled as an element
+///
+/// Writes outside the bounds of the view are ignored.
+/// <https://tc39.es/ecma262/#sec-integerindexedelementset>
+pub fn set_element(obj: &Value, field: &str, value: &Value) -> bool {
+    let kind = match typed_array_kind(obj) {
+        Some(kind) => kind,
+        None => return false,
+    };
+    let index: usize = match field.parse::<usize>() {
+        Ok(index) if index.to_string() == field => index,
+        _ => return false,
+    };
+    let (byte_offset, length) = view_bounds(obj);
+    if index < length {
+        let number = value.to_num();
+        with_bytes(&obj.get_internal_slot("ViewedArrayBuffer"), |bytes| {
+            kind.write(
+                number,
+                &mut bytes[byte_offset + index * kind.element_size()..],
+            )
+        });
+    }
+    true
+}
+
+/// Create a new `ArrayBuffer` - [[Construct]]
+/// <https://tc39.es/ecma262/#sec-arraybuffer-length>
+pub fn make_array_buffer(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let byte_length = args.first().map_or(0.0, |length| length.to_num());
+    let byte_length = if byte_length.is_nan() {
+        0.0
+    } else {
+        byte_length.trunc()
+    };
+    if byte_length < 0.0 || byte_length > f64::from(i32::max_value()) {
+        return Err(error::new_error(
+            "RangeError",
+            "Invalid array buffer length",
+            ctx,
+        ));
+    }
+
+    this.set_kind(ObjectKind::ArrayBuffer);
+    this.set_internal_slot(
+        "ArrayBufferData",
+        Gc::new(ValueData::ArrayBuffer(GcCell::new(vec![
+            0;
+            byte_length
+                as usize
+        ]))),
+    );
+    Ok(this.clone())
+}
+
+/// Calling `ArrayBuffer` without `new` is a TypeError - [[Call]]
+pub fn call_array_buffer(_: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    Err(error::new_error(
+        "TypeError",
+        "Constructor ArrayBuffer requires 'new'",
+        ctx,
+    ))
+}
+
+/// get ArrayBuffer.prototype.byteLength
+/// <https://tc39.es/ecma262/#sec-get-arraybuffer.prototype.bytelength>
+pub fn get_array_buffer_byte_length(
+    this: &Value,
+    _: &[Value],
+    ctx: &mut Interpreter,
+) -> ResultValue {
+    match with_bytes(this, |bytes| bytes.len()) {
+        Some(length) => Ok(to_value(length)),
+        None => Err(error::new_error(
+            "TypeError",
+            "this is not an ArrayBuffer",
+            ctx,
+        )),
+    }
+}
+
+/// ArrayBuffer.prototype.slice ( start, end )
+///
+/// Returns a new `ArrayBuffer` holding a copy of the bytes from `start` up to `end`.
+/// <https://tc39.es/ecma262/#sec-arraybuffer.prototype.slice>
+pub fn array_buffer_slice(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let bytes = match with_bytes(this, |bytes| bytes.clone()) {
+        Some(bytes) => bytes,
+        None => {
+            return Err(error::new_error(
+                "TypeError",
+                "this is not an ArrayBuffer",
+                ctx,
+            ))
+        }
+    };
+    let start = relative_index(args.first(), bytes.len(), 0);
+    let end = relative_index(args.get(1), bytes.len(), bytes.len());
+    let copy = if start < end {
+        bytes[start..end].to_vec()
+    } else {
+        Vec::new()
+    };
+    Ok(allocate_array_buffer(copy, ctx))
+}
+
+/// ArrayBuffer.isView ( arg )
+/// <https://tc39.es/ecma262/#sec-arraybuffer.isview>
+pub fn is_view(_: &Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    Ok(to_value(args.first().and_then(typed_array_kind).is_some()))
+}
+
+/// Create a new typed array - [[Construct]]
+///
+/// The kind of array is taken from the prototype of the object under construction, so all the
+/// typed array constructors share this function.
+/// <https://tc39.es/ecma262/#sec-typedarray-constructors>
+pub fn make_typed_array(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let proto = this.get_internal_slot(INSTANCE_PROTOTYPE);
+    let kind = match typed_array_name(&proto) {
+        Some(kind) => kind,
+        None => {
+            return Err(error::new_error(
+                "TypeError",
+                "invalid typed array constructor",
+                ctx,
+            ))
+        }
+    };
+    let size = kind.element_size();
+    let first = args
+        .first()
+        .cloned()
+        .unwrap_or_else(|| Gc::new(ValueData::Undefined));
+
+    if is_array_buffer(&first) {
+        // new TypedArray(buffer [, byteOffset [, length]])
+        let buffer_length = with_bytes(&first, |bytes| bytes.len()).unwrap_or(0);
+        let byte_offset = args.get(1).map_or(0.0, |offset| offset.to_num());
+        let byte_offset = if byte_offset.is_nan() {
+            0.0
+        } else {
+            byte_offset.trunc()
+        };
+        if byte_offset < 0.0 || !(byte_offset as usize).is_multiple_of(size) {
+            return Err(error::new_error(
+                "RangeError",
+                &format!(
+                    "start offset of {} should be a multiple of {}",
+                    kind.name(),
+                    size
+                ),
+                ctx,
+            ));
+        }
+        let byte_offset = byte_offset as usize;
+        let byte_length = match args.get(2) {
+            Some(length) if !length.is_undefined() => {
+                let length = length.to_num();
+                if length.is_nan() || length < 0.0 {
+                    return Err(error::new_error(
+                        "RangeError",
+                        "Invalid typed array length",
+                        ctx,
+                    ));
+                }
+                length as usize * size
+            }
+            _ => {
+                if buffer_length % size != 0 || byte_offset > buffer_length {
+                    return Err(error::new_error(
+                        "RangeError",
+                        &format!(
+                            "byte length of {} should be a multiple of {}",
+                            kind.name(),
+                            size
+                        ),
+                        ctx,
+                    ));
+                }
+                buffer_length - byte_offset
+            }
+        };
+        if byte_offset + byte_length > buffer_length {
+            return Err(error::new_error(
+                "RangeError",
+                "Invalid typed array length",
+                ctx,
+            ));
+        }
+        initialize_view(this, kind, first, byte_offset, byte_length / size);
+        return Ok(this.clone());
+    }
+
+    let values = if first.is_object() {
+        // new TypedArray(typedArray) and new TypedArray(arrayLike)
+        source_values(&first)
+    } else {
+        // new TypedArray(length)
+        let length = if first.is_undefined() {
+            0.0
+        } else {
+            first.to_num()
+        };
+        if length.is_nan() || length < 0.0 || length.fract() != 0.0 {
+            return Err(error::new_error(
+                "RangeError",
+                "Invalid typed array length",
+                ctx,
+            ));
+        }
+        vec![0.0; length as usize]
+    };
+
+    let mut bytes = vec![0; values.len() * size];
+    for (n, value) in values.iter().enumerate() {
+        kind.write(*value, &mut bytes[n * size..]);
+    }
+    let buffer = allocate_array_buffer(bytes, ctx);
+    initialize_view(this, kind, buffer, 0, values.len());
+    Ok(this.clone())
+}
+
+/// Calling a typed array constructor without `new` is a TypeError - [[Call]]
+pub fn call_typed_array(_: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    Err(error::new_error(
+        "TypeError",
+        "typed array constructors require 'new'",
+        ctx,
+    ))
+}
+
+/// Get the kind of `this`, throwing if it is not a typed array
+fn this_typed_array_kind(this: &Value, ctx: &Interpreter) -> Result<TypedArrayKind, Value> {
+    typed_array_kind(this)
+        .ok_or_else(|| error::new_error("TypeError", "this is not a typed array", ctx))
+}
+
+/// get %TypedArray%.prototype.length
+/// <https://tc39.es/ecma262/#sec-get-%typedarray%.prototype.length>
+pub fn get_length(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    this_typed_array_kind(this, ctx)?;
+    Ok(this.get_internal_slot("ArrayLength"))
+}
+
+/// get %TypedArray%.prototype.byteLength
+/// <https://tc39.es/ecma262/#sec-get-%typedarray%.prototype.bytelength>
+pub fn get_byte_length(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let kind = this_typed_array_kind(this, ctx)?;
+    let (_, length) = view_bounds(this);
+    Ok(to_value(length * kind.element_size()))
+}
+
+/// get %TypedArray%.prototype.byteOffset
+/// <https://tc39.es/ecma262/#sec-get-%typedarray%.prototype.byteoffset>
+pub fn get_byte_offset(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    this_typed_array_kind(this, ctx)?;
+    Ok(this.get_internal_slot("ByteOffset"))
+}
+
+/// get %TypedArray%.prototype.buffer
+/// <https://tc39.es/ecma262/#sec-get-%typedarray%.prototype.buffer>
+pub fn get_buffer(this: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    this_typed_array_kind(this, ctx)?;
+    Ok(this.get_internal_slot("ViewedArrayBuffer"))
+}
+
+/// %TypedArray%.prototype.set ( source [ , offset ] )
+///
+/// Copies the elements of a typed array or array-like `source` into this array, starting at
+/// `offset`.
+/// <https://tc39.es/ecma262/#sec-%typedarray%.prototype.set>
+pub fn set(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let kind = this_typed_array_kind(this, ctx)?;
+    let source = match args.first() {
+        Some(source) if source.is_object() => source,
+        _ => {
+            return Err(error::new_error(
+                "TypeError",
+                "source must be an object",
+                ctx,
+            ))
+        }
+    };
+    let offset = args.get(1).map_or(0.0, |offset| offset.to_num());
+    let offset = if offset.is_nan() { 0.0 } else { offset.trunc() };
+    if offset < 0.0 {
+        return Err(error::new_error(
+            "RangeError",
+            "offset is out of bounds",
+            ctx,
+        ));
+    }
+    let offset = offset as usize;
+
+    // Read the whole source first, it may share the buffer with this array
+    let values = source_values(source);
+    let (byte_offset, length) = view_bounds(this);
+    if offset + values.len() > length {
+        return Err(error::new_error(
+            "RangeError",
+            "offset is out of bounds",
+            ctx,
+        ));
+    }
+    with_bytes(&this.get_internal_slot("ViewedArrayBuffer"), |bytes| {
+        for (n, value) in values.iter().enumerate() {
+            let start = byte_offset + (offset + n) * kind.element_size();
+            kind.write(*value, &mut bytes[start..]);
+        }
+    });
+    Ok(Gc::new(ValueData::Undefined))
+}
+
+/// %TypedArray%.prototype.subarray ( begin, end )
+///
+/// Returns a new typed array of the same kind viewing part of the same buffer.
+/// <https://tc39.es/ecma262/#sec-%typedarray%.prototype.subarray>
+pub fn subarray(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let kind = this_typed_array_kind(this, ctx)?;
+    let (byte_offset, length) = view_bounds(this);
+    let begin = relative_index(args.first(), length, 0);
+    let end = relative_index(args.get(1), length, length);
+    let new_length = end.saturating_sub(begin);
+    Ok(create_view(
+        kind,
+        this.get_internal_slot("ViewedArrayBuffer"),
+        byte_offset + begin * kind.element_size(),
+        new_length,
+        ctx,
+    ))
+}
+
+/// %TypedArray%.prototype.slice ( start, end )
+///
+/// Returns a new typed array of the same kind holding a copy of the elements.
+/// <https://tc39.es/ecma262/#sec-%typedarray%.prototype.slice>
+pub fn slice(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let kind = this_typed_array_kind(this, ctx)?;
+    let (byte_offset, length) = view_bounds(this);
+    let start = relative_index(args.first(), length, 0);
+    let end = relative_index(args.get(1), length, length);
+    let size = kind.element_size();
+    let bytes = if start < end {
+        with_bytes(&this.get_internal_slot("ViewedArrayBuffer"), |bytes| {
+            bytes[byte_offset + start * size..byte_offset + end * size].to_vec()
+        })
+        .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+    let new_length = bytes.len() / size;
+    let buffer = allocate_array_buffer(bytes, ctx);
+    Ok(create_view(kind, buffer, 0, new_length, ctx))
+}
+
+/// Create a new `ArrayBuffer` object
+pub fn create_array_buffer_constructor(global: &Value) -> Value {
+    let mut array_buffer_constructor = Object::default();
+    array_buffer_constructor.kind = ObjectKind::Function;
+    array_buffer_constructor.set_internal_method("construct", make_array_buffer);
+    array_buffer_constructor.set_internal_method("call", call_array_buffer);
+
+    // Create prototype
+    let proto = ValueData::new_obj(Some(global));
+    let byte_length =
+        Property::default().get(make_builtin_fn(get_array_buffer_byte_length, global));
+    proto.set_prop_slice("byteLength", byte_length);
+    proto.set_field_slice("slice", make_builtin_fn(array_buffer_slice, global));
+
+    let array_buffer = to_value(array_buffer_constructor);
+    array_buffer.set_field_slice("isView", make_builtin_fn(is_view, global));
+    proto.set_field_slice("constructor", array_buffer.clone());
+    array_buffer.set_field_slice(PROTOTYPE, proto);
+    array_buffer
+}
+
+/// Create the typed array constructors, returned with the names they are bound to
+pub fn create_typed_array_constructors(global: &Value) -> Vec<(&'static str, Value)> {
+    // %TypedArray%.prototype, which holds the methods shared by every kind
+    let typed_array_proto = ValueData::new_obj(Some(global));
+    let getters: [(&str, NativeFunctionData); 4] = [
+        ("length", get_length),
+        ("byteLength", get_byte_length),
+        ("byteOffset", get_byte_offset),
+        ("buffer", get_buffer),
+    ];
+    for (name, getter) in getters.iter() {
+        typed_array_proto.set_prop_slice(
+            name,
+            Property::default().get(make_builtin_fn(*getter, global)),
+        );
+    }
+    typed_array_proto.set_field_slice("set", make_builtin_fn(set, global));
+    typed_array_proto.set_field_slice("subarray", make_builtin_fn(subarray, global));
+    typed_array_proto.set_field_slice("slice", make_builtin_fn(slice, global));
+
+    TypedArrayKind::ALL
+        .iter()
+        .map(|kind| {
+            let mut constructor = Object::default();
+            constructor.kind = ObjectKind::Function;
+            constructor.set_internal_method("construct", make_typed_array);
+            constructor.set_internal_method("call", call_typed_array);
+
+            let proto =
+                ValueData::new_obj_from_prototype(typed_array_proto.clone(), ObjectKind::Ordinary);
+            proto.set_internal_slot("TypedArrayName", to_value(kind.name()));
+            let bytes_per_element = to_value(kind.element_size());
+            proto.set_field_slice("BYTES_PER_ELEMENT", bytes_per_element.clone());
+
+            let constructor = to_value(constructor);
+            constructor.set_field_slice("BYTES_PER_ELEMENT", bytes_per_element);
+            proto.set_field_slice("constructor", constructor.clone());
+            constructor.set_field_slice(PROTOTYPE, proto);
+            (kind.name(), constructor)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::exec::Executor;
+    use crate::realm::Realm;
+    use crate::{forward, forward_val, js::value::same_value};
+
+    #[test]
+    fn views_share_buffer() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const buffer = new ArrayBuffer(8);
+        const bytes = new Uint8Array(buffer);
+        const signed = new Int8Array(buffer);
+        const ints = new Int32Array(buffer);
+        "#;
+        forward(&mut engine, init);
+
+        forward(&mut engine, "bytes[0] = 255");
+        assert_eq!(forward(&mut engine, "signed[0]"), "-1");
+        forward(&mut engine, "bytes[1] = 256");
+        assert_eq!(forward(&mut engine, "bytes[1]"), "0");
+        forward(&mut engine, "ints[1] = 258");
+        assert_eq!(forward(&mut engine, "bytes[4]"), "2");
+        assert_eq!(forward(&mut engine, "bytes[5]"), "1");
+        assert_eq!(forward(&mut engine, "bytes[8]"), "undefined");
+    }
+
+    #[test]
+    fn view_properties() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const buffer = new ArrayBuffer(16);
+        const doubles = new Float64Array(buffer);
+        const ints = new Int32Array(buffer, 4, 2);
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "buffer.byteLength"), "16");
+        assert_eq!(forward(&mut engine, "doubles.length"), "2");
+        assert_eq!(forward(&mut engine, "ints.length"), "2");
+        assert_eq!(forward(&mut engine, "ints.byteOffset"), "4");
+        assert_eq!(forward(&mut engine, "ints.byteLength"), "8");
+        let buffer = forward_val(&mut engine, "buffer").unwrap();
+        let viewed = forward_val(&mut engine, "ints.buffer").unwrap();
+        assert!(same_value(&buffer, &viewed, true));
+        forward(&mut engine, "doubles[1] = 1.5");
+        assert_eq!(forward(&mut engine, "doubles[1]"), "1.5");
+    }
+
+    #[test]
+    fn slice_subarray_and_set() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const bytes = new Uint8Array([1, 2, 3, 4]);
+        const sub = bytes.subarray(1, 3);
+        const copy = bytes.slice(-2);
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "sub.length"), "2");
+        assert_eq!(forward(&mut engine, "sub[0]"), "2");
+        assert_eq!(forward(&mut engine, "copy.length"), "2");
+        assert_eq!(forward(&mut engine, "copy[0]"), "3");
+
+        // A subarray shares its buffer, a slice does not
+        forward(&mut engine, "sub[0] = 9");
+        forward(&mut engine, "copy[0] = 9");
+        assert_eq!(forward(&mut engine, "bytes[1]"), "9");
+        assert_eq!(forward(&mut engine, "bytes[2]"), "3");
+
+        forward(&mut engine, "bytes.set([7, 8], 2)");
+        assert_eq!(forward(&mut engine, "bytes[2]"), "7");
+        assert_eq!(forward(&mut engine, "bytes[3]"), "8");
+    }
+}
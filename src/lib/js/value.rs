@@ -1,20 +1,24 @@
-use crate::js::{
-    function::{Function, NativeFunction, NativeFunctionData},
-    object::{InternalState, InternalStateCell, Object, ObjectKind, INSTANCE_PROTOTYPE, PROTOTYPE},
-    property::Property,
+use crate::{
+    exec::Interpreter,
+    js::{
+        function::{Function, NativeFunction, NativeFunctionData},
+        object::{
+            InternalState, InternalStateCell, Object, ObjectKind, INSTANCE_PROTOTYPE, PROTOTYPE,
+        },
+        property::{Property, PropertyMap},
+    },
 };
 use gc::{Gc, GcCell};
 use gc_derive::{Finalize, Trace};
 use serde_json::{map::Map, Number as JSONNumber, Value as JSONValue};
 use std::{
     any::Any,
-    f64::NAN,
+    collections::HashMap,
     fmt::{self, Display},
     ops::{Add, BitAnd, BitOr, BitXor, Deref, DerefMut, Div, Mul, Not, Rem, Shl, Shr, Sub},
     str::FromStr,
 };
 
-#[must_use]
 /// The result of a Javascript expression is represented like this so it can succeed (`Ok`) or fail (`Err`)
 pub type ResultValue = Result<Value, Value>;
 /// A Garbage-collected Javascript value as represented in the interpreter
@@ -25,11 +29,12 @@ pub fn undefined() -> Value {
 }
 
 /// A Javascript value
-#[derive(Trace, Finalize, Debug, Clone)]
+#[derive(Trace, Finalize, Debug, Clone, Default)]
 pub enum ValueData {
     /// `null` - A null value, for when a value doesn't exist
     Null,
     /// `undefined` - An undefined value, for when a field or index doesn't exist
+    #[default]
     Undefined,
     /// `boolean` - A `true` / `false` value, for if a certain criteria is met
     Boolean(bool),
@@ -43,6 +48,8 @@ pub enum ValueData {
     Object(GcCell<Object>),
     /// `Function` - A runnable block of code, such as `Math.sqrt`, which can take some variables and return a useful value or act upon an object
     Function(Box<GcCell<Function>>),
+    /// `ArrayBuffer` - The raw bytes behind an `ArrayBuffer` object, shared by its typed array views
+    ArrayBuffer(GcCell<Vec<u8>>),
 }
 
 impl ValueData {
@@ -84,8 +91,13 @@ impl ValueData {
 
     /// Returns true if the value is an object
     pub fn is_object(&self) -> bool {
+        matches!(*self, ValueData::Object(_))
+    }
+
+    /// Returns true if the value is an error object, made by `Error` or one of its subclasses
+    pub fn is_error(&self) -> bool {
         match *self {
-            ValueData::Object(_) => true,
+            ValueData::Object(ref o) => matches!(o.borrow().kind, ObjectKind::Error),
             _ => false,
         }
     }
@@ -94,64 +106,54 @@ impl ValueData {
     pub fn is_function(&self) -> bool {
         match *self {
             ValueData::Function(_) => true,
-            ValueData::Object(ref o) => o.deref().borrow().get_internal_slot("call").is_function(),
+            ValueData::Object(ref o) => o.borrow().get_internal_slot("call").is_function(),
             _ => false,
         }
     }
 
     /// Returns true if the value is undefined
     pub fn is_undefined(&self) -> bool {
-        match *self {
-            ValueData::Undefined => true,
-            _ => false,
-        }
+        matches!(*self, ValueData::Undefined)
     }
 
     /// Returns true if the value is null
     pub fn is_null(&self) -> bool {
-        match *self {
-            ValueData::Null => true,
-            _ => false,
-        }
+        matches!(*self, ValueData::Null)
     }
 
     /// Returns true if the value is null or undefined
     pub fn is_null_or_undefined(&self) -> bool {
+        matches!(*self, ValueData::Null | ValueData::Undefined)
+    }
+
+    /// Returns true if the value is an array object
+    pub fn is_array(&self) -> bool {
         match *self {
-            ValueData::Null | ValueData::Undefined => true,
+            ValueData::Object(ref obj) => matches!(obj.borrow().kind, ObjectKind::Array),
             _ => false,
         }
     }
 
     /// Returns true if the value is a 64-bit floating-point number
     pub fn is_double(&self) -> bool {
-        match *self {
-            ValueData::Number(_) => true,
-            _ => false,
-        }
+        matches!(*self, ValueData::Number(_))
     }
 
     /// Returns true if the value is a string
     pub fn is_string(&self) -> bool {
-        match *self {
-            ValueData::String(_) => true,
-            _ => false,
-        }
+        matches!(*self, ValueData::String(_))
     }
 
     /// Returns true if the value is a boolean
     pub fn is_boolean(&self) -> bool {
-        match *self {
-            ValueData::Boolean(_) => true,
-            _ => false,
-        }
+        matches!(*self, ValueData::Boolean(_))
     }
 
     /// Returns true if the value is true
     /// [toBoolean](https://tc39.github.io/ecma262/#sec-toboolean)
     pub fn is_true(&self) -> bool {
         match *self {
-            ValueData::Object(_) => true,
+            ValueData::Object(_) | ValueData::ArrayBuffer(_) => true,
             ValueData::String(ref s) if !s.is_empty() => true,
             ValueData::Number(n) if n != 0.0 && !n.is_nan() => true,
             ValueData::Integer(n) if n != 0 => true,
@@ -163,11 +165,11 @@ impl ValueData {
     /// Converts the value into a 64-bit floating point number
     pub fn to_num(&self) -> f64 {
         match *self {
-            ValueData::Object(_) | ValueData::Undefined | ValueData::Function(_) => NAN,
-            ValueData::String(ref str) => match FromStr::from_str(str) {
-                Ok(num) => num,
-                Err(_) => NAN,
-            },
+            ValueData::Object(_)
+            | ValueData::Undefined
+            | ValueData::Function(_)
+            | ValueData::ArrayBuffer(_) => f64::NAN,
+            ValueData::String(ref str) => string_to_number(str),
             ValueData::Number(num) => num,
             ValueData::Boolean(true) => 1.0,
             ValueData::Boolean(false) | ValueData::Null => 0.0,
@@ -175,6 +177,32 @@ impl ValueData {
         }
     }
 
+    /// Converts the value into a signed 32-bit integer, wrapping numbers outside its range
+    ///
+    /// <https://tc39.es/ecma262/#sec-toint32>
+    pub fn to_int32(&self) -> i32 {
+        self.to_uint32() as i32
+    }
+
+    /// Converts the value into an unsigned 32-bit integer, wrapping numbers outside its range
+    ///
+    /// <https://tc39.es/ecma262/#sec-touint32>
+    pub fn to_uint32(&self) -> u32 {
+        if let ValueData::Integer(num) = *self {
+            return num as u32;
+        }
+        let num = self.to_num();
+        if !num.is_finite() {
+            return 0;
+        }
+        num.trunc().rem_euclid(4_294_967_296.0) as u32
+    }
+
+    /// Shifts the value rightwards as an unsigned 32-bit integer, filling with zeros (`>>>`)
+    pub fn unsigned_shr(&self, other: &Self) -> Self {
+        ValueData::Number(f64::from(self.to_uint32() >> (other.to_uint32() & 31)))
+    }
+
     /// Converts the value into a 32-bit integer
     pub fn to_int(&self) -> i32 {
         match *self {
@@ -182,11 +210,9 @@ impl ValueData {
             | ValueData::Undefined
             | ValueData::Null
             | ValueData::Boolean(false)
-            | ValueData::Function(_) => 0,
-            ValueData::String(ref str) => match FromStr::from_str(str) {
-                Ok(num) => num,
-                Err(_) => 0,
-            },
+            | ValueData::Function(_)
+            | ValueData::ArrayBuffer(_) => 0,
+            ValueData::String(ref str) => str.parse().unwrap_or_default(),
             ValueData::Number(num) => num as i32,
             ValueData::Boolean(true) => 1,
             ValueData::Integer(num) => num,
@@ -207,15 +233,41 @@ impl ValueData {
         };
     }
 
+    /// Check whether the value has the field as an own property, without looking through its
+    /// prototype chain
+    pub fn has_own_property(&self, field: &str) -> bool {
+        match *self {
+            ValueData::String(ref s) => string_prop(s, field).is_some(),
+            ValueData::Object(ref obj) => {
+                let obj = obj.borrow();
+                if obj.properties.contains_key(field) {
+                    return true;
+                }
+                // String objects have the index and length properties of their string
+                match (
+                    &obj.kind,
+                    obj.internal_slots.get("StringData").map(|v| &**v),
+                ) {
+                    (ObjectKind::String, Some(ValueData::String(ref s))) => {
+                        string_prop(s, field).is_some()
+                    }
+                    _ => false,
+                }
+            }
+            ValueData::Function(ref func) => match *func.borrow() {
+                Function::NativeFunc(ref func) => func.object.properties.contains_key(field),
+                Function::RegularFunc(ref func) => func.object.properties.contains_key(field),
+            },
+            _ => false,
+        }
+    }
+
     /// Resolve the property in the object
     /// Returns a copy of the Property
     pub fn get_prop(&self, field: &str) -> Option<Property> {
         // Spidermonkey has its own GetLengthProperty: https://searchfox.org/mozilla-central/source/js/src/vm/Interpreter-inl.h#154
-        // This is only for primitive strings, String() objects have their lengths calculated in string.rs
-        if self.is_string() && field == "length" {
-            if let ValueData::String(ref s) = *self {
-                return Some(Property::default().value(to_value(s.len() as i32)));
-            }
+        if let ValueData::String(ref s) = *self {
+            return string_prop(s, field);
         }
 
         let obj: Object = match *self {
@@ -233,6 +285,17 @@ impl ValueData {
             _ => return None,
         };
 
+        // String objects have the index and length properties of their string
+        if let ObjectKind::String = obj.kind {
+            if let Some(ValueData::String(ref s)) =
+                obj.internal_slots.get("StringData").map(|v| &**v)
+            {
+                if let Some(prop) = string_prop(s, field) {
+                    return Some(prop);
+                }
+            }
+        }
+
         match obj.properties.get(field) {
             Some(val) => Some(val.clone()),
             None => match obj.internal_slots.get(&INSTANCE_PROTOTYPE.to_string()) {
@@ -282,6 +345,10 @@ impl ValueData {
                 let hash = obj.clone();
                 hash.into_inner()
             }
+            ValueData::Function(ref func) => match *func.borrow() {
+                Function::NativeFunc(ref f) => f.object.clone(),
+                Function::RegularFunc(ref f) => f.object.clone(),
+            },
             _ => return Gc::new(ValueData::Undefined),
         };
 
@@ -296,22 +363,11 @@ impl ValueData {
     /// TODO: this function should use the get Value if its set
     pub fn get_field(&self, field: &str) -> Value {
         match self.get_prop(field) {
-            Some(prop) => {
-                // If the Property has [[Get]] set to a function, we should run that and return the Value
-                let prop_getter = match prop.get {
-                    Some(_) => None,
-                    None => None,
-                };
-
-                // If the getter is populated, use that. If not use [[Value]] instead
-                match prop_getter {
-                    Some(val) => val,
-                    None => {
-                        let val = prop.value.as_ref().unwrap();
-                        val.clone()
-                    }
-                }
-            }
+            // Getters are run by the interpreter, accessor properties have no [[Value]] here
+            Some(prop) => prop
+                .value
+                .clone()
+                .unwrap_or_else(|| Gc::new(ValueData::Undefined)),
             None => Gc::new(ValueData::Undefined),
         }
     }
@@ -399,38 +455,64 @@ impl ValueData {
     pub fn set_field(&self, field: String, val: Value) -> Value {
         match *self {
             ValueData::Object(ref obj) => {
-                obj.borrow_mut()
-                    .properties
-                    .insert(field, Property::default().value(val.clone()));
-            }
-            ValueData::Function(ref func) => {
-                match *func.borrow_mut().deref_mut() {
-                    Function::NativeFunc(ref mut f) => f
-                        .object
-                        .properties
-                        .insert(field, Property::default().value(val.clone())),
-                    Function::RegularFunc(ref mut f) => f
-                        .object
-                        .properties
-                        .insert(field, Property::default().value(val.clone())),
-                };
+                set_data_property(&mut obj.borrow_mut().properties, field, val.clone());
             }
+            ValueData::Function(ref func) => match *func.borrow_mut().deref_mut() {
+                Function::NativeFunc(ref mut f) => {
+                    set_data_property(&mut f.object.properties, field, val.clone())
+                }
+                Function::RegularFunc(ref mut f) => {
+                    set_data_property(&mut f.object.properties, field, val.clone())
+                }
+            },
             _ => (),
         }
         val
     }
 
     /// Set the field in the value
-    pub fn set_field_slice<'a>(&self, field: &'a str, val: Value) -> Value {
+    pub fn set_field_slice(&self, field: &str, val: Value) -> Value {
         self.set_field(field.to_string(), val)
     }
 
+    /// Set the field in the value, making it a writable and configurable property which is left out
+    /// when the keys of the value are enumerated if it is not there yet
+    pub fn set_non_enumerable_field(&self, field: &str, val: Value) -> Value {
+        if self.has_own_property(field) {
+            return self.set_field_slice(field, val);
+        }
+        self.set_prop_slice(
+            field,
+            Property::new()
+                .value(val.clone())
+                .writable(true)
+                .enumerable(false)
+                .configurable(true),
+        );
+        val
+    }
+
     /// Set the private field in the value
     pub fn set_internal_slot(&self, field: &str, val: Value) -> Value {
-        if let ValueData::Object(ref obj) = *self {
-            obj.borrow_mut()
-                .internal_slots
-                .insert(field.to_string(), val.clone());
+        match *self {
+            ValueData::Object(ref obj) => {
+                obj.borrow_mut()
+                    .internal_slots
+                    .insert(field.to_string(), val.clone());
+            }
+            ValueData::Function(ref func) => {
+                match *func.borrow_mut().deref_mut() {
+                    Function::NativeFunc(ref mut f) => f
+                        .object
+                        .internal_slots
+                        .insert(field.to_string(), val.clone()),
+                    Function::RegularFunc(ref mut f) => f
+                        .object
+                        .internal_slots
+                        .insert(field.to_string(), val.clone()),
+                };
+            }
+            _ => (),
         }
         val
     }
@@ -465,7 +547,7 @@ impl ValueData {
     }
 
     /// Set the property in the value
-    pub fn set_prop_slice<'t>(&self, field: &'t str, prop: Property) -> Property {
+    pub fn set_prop_slice(&self, field: &str, prop: Property) -> Property {
         self.set_prop(field.to_string(), prop)
     }
 
@@ -515,7 +597,10 @@ impl ValueData {
 
     pub fn to_json(&self) -> JSONValue {
         match *self {
-            ValueData::Null | ValueData::Undefined | ValueData::Function(_) => JSONValue::Null,
+            ValueData::Null
+            | ValueData::Undefined
+            | ValueData::Function(_)
+            | ValueData::ArrayBuffer(_) => JSONValue::Null,
             ValueData::Boolean(b) => JSONValue::Bool(b),
             ValueData::Object(ref obj) => {
                 let mut new_obj = Map::new();
@@ -542,8 +627,9 @@ impl ValueData {
             ValueData::Null => "null",
             ValueData::Undefined => "undefined",
             ValueData::Function(_) => "function",
+            ValueData::ArrayBuffer(_) => "object",
             ValueData::Object(ref o) => {
-                if o.deref().borrow().get_internal_slot("call").is_null() {
+                if o.borrow().get_internal_slot("call").is_null() {
                     "object"
                 } else {
                     "function"
@@ -553,12 +639,42 @@ impl ValueData {
     }
 }
 
-impl Default for ValueData {
-    fn default() -> Self {
-        ValueData::Undefined
+/// Store `val` in a data property, which keeps its attributes if it is already there or else is
+/// made writable, enumerable and configurable like any property added by assignment. Properties
+/// which are not writable are left as they are.
+/// <https://tc39.es/ecma262/#sec-ordinarysetwithowndescriptor>
+fn set_data_property(properties: &mut PropertyMap, field: String, val: Value) {
+    match properties.get_mut(&field) {
+        Some(prop) if prop.writable == Some(false) => (),
+        Some(prop) if prop.value.is_some() => prop.value = Some(val),
+        _ => {
+            properties.insert(
+                field,
+                Property::new()
+                    .value(val)
+                    .writable(true)
+                    .enumerable(true)
+                    .configurable(true),
+            );
+        }
     }
 }
 
+/// Get the `length` or an index property of the string `s`, counted in UTF-16 code units
+/// <https://tc39.es/ecma262/#sec-string-exotic-objects-getownproperty-p>
+fn string_prop(s: &str, field: &str) -> Option<Property> {
+    if field == "length" {
+        return Some(Property::default().value(to_value(s.encode_utf16().count() as i32)));
+    }
+    // Only canonical indices, so not "01" or "+1"
+    let index: usize = field.parse().ok()?;
+    if index.to_string() != field {
+        return None;
+    }
+    let unit = s.encode_utf16().nth(index)?;
+    Some(Property::default().value(to_value(String::from_utf16_lossy(&[unit]))))
+}
+
 impl Display for ValueData {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
@@ -573,15 +689,17 @@ impl Display for ValueData {
                     _ if v.is_nan() => "NaN".to_string(),
                     _ if v.is_infinite() && v.is_sign_negative() => "-Infinity".to_string(),
                     _ if v.is_infinite() => "Infinity".to_string(),
-                    _ => v.to_string(),
+                    _ => number_to_string(v),
                 }
             ),
             ValueData::Object(_) => write!(f, "{{}}"),
+            ValueData::ArrayBuffer(ref bytes) => write!(f, "{:?}", bytes.borrow()),
             ValueData::Integer(v) => write!(f, "{}", v),
             ValueData::Function(ref v) => match *v.borrow() {
                 Function::NativeFunc(_) => write!(f, "function() {{ [native code] }}"),
                 Function::RegularFunc(ref rf) => {
-                    write!(f, "function({}){}", rf.args.join(", "), rf.expr)
+                    let args: Vec<&str> = rf.args.iter().map(|arg| arg.as_str()).collect();
+                    write!(f, "function({}){}", args.join(", "), rf.expr)
                 }
             },
         }
@@ -648,31 +766,31 @@ impl Rem for ValueData {
 impl BitAnd for ValueData {
     type Output = Self;
     fn bitand(self, other: Self) -> Self {
-        ValueData::Integer(self.to_int() & other.to_int())
+        ValueData::Integer(self.to_int32() & other.to_int32())
     }
 }
 impl BitOr for ValueData {
     type Output = Self;
     fn bitor(self, other: Self) -> Self {
-        ValueData::Integer(self.to_int() | other.to_int())
+        ValueData::Integer(self.to_int32() | other.to_int32())
     }
 }
 impl BitXor for ValueData {
     type Output = Self;
     fn bitxor(self, other: Self) -> Self {
-        ValueData::Integer(self.to_int() ^ other.to_int())
+        ValueData::Integer(self.to_int32() ^ other.to_int32())
     }
 }
 impl Shl for ValueData {
     type Output = Self;
     fn shl(self, other: Self) -> Self {
-        ValueData::Integer(self.to_int() << other.to_int())
+        ValueData::Integer(self.to_int32().wrapping_shl(other.to_uint32()))
     }
 }
 impl Shr for ValueData {
     type Output = Self;
     fn shr(self, other: Self) -> Self {
-        ValueData::Integer(self.to_int() >> other.to_int())
+        ValueData::Integer(self.to_int32().wrapping_shr(other.to_uint32()))
     }
 }
 impl Not for ValueData {
@@ -719,9 +837,9 @@ impl FromValue for String {
     }
 }
 
-impl<'s> ToValue for &'s str {
+impl ToValue for &str {
     fn to_value(&self) -> Value {
-        Gc::new(ValueData::String(String::from_str(*self).unwrap()))
+        Gc::new(ValueData::String(String::from_str(self).unwrap()))
     }
 }
 
@@ -780,24 +898,48 @@ impl FromValue for bool {
     }
 }
 
-impl<'s, T: ToValue> ToValue for &'s [T] {
+/// Create an array of `values`. There is no realm to take `Array.prototype` from here, so the
+/// interpreter gives the array its prototype when it first looks up a property on it.
+fn array_from_values<'a, T: ToValue + 'a>(values: impl Iterator<Item = &'a T>) -> Value {
+    let mut arr = Object::default();
+    arr.kind = ObjectKind::Array;
+    let mut length: i32 = 0;
+    for (i, item) in values.enumerate() {
+        arr.properties
+            .insert(i.to_string(), Property::default().value(item.to_value()));
+        length += 1;
+    }
+    arr.properties.insert(
+        "length".to_string(),
+        Property::default()
+            .value(to_value(length))
+            .writable(true)
+            .enumerable(false)
+            .configurable(false),
+    );
+    to_value(arr)
+}
+
+impl<T: ToValue> ToValue for &[T] {
     fn to_value(&self) -> Value {
-        let mut arr = Object::default();
-        for (i, item) in self.iter().enumerate() {
-            arr.properties
-                .insert(i.to_string(), Property::default().value(item.to_value()));
-        }
-        to_value(arr)
+        array_from_values(self.iter())
     }
 }
 impl<T: ToValue> ToValue for Vec<T> {
     fn to_value(&self) -> Value {
-        let mut arr = Object::default();
-        for (i, item) in self.iter().enumerate() {
-            arr.properties
-                .insert(i.to_string(), Property::default().value(item.to_value()));
+        array_from_values(self.iter())
+    }
+}
+
+/// Creates an object with a property for each entry. Like arrays, the object has no prototype.
+impl<T: ToValue> ToValue for HashMap<String, T> {
+    fn to_value(&self) -> Value {
+        let mut obj = Object::default();
+        for (key, item) in self.iter() {
+            obj.properties
+                .insert(key.clone(), Property::default().value(item.to_value()));
         }
-        to_value(arr)
+        to_value(obj)
     }
 }
 
@@ -812,6 +954,29 @@ impl<T: FromValue> FromValue for Vec<T> {
     }
 }
 
+/// Reads the own enumerable data properties of an object. Accessor properties are skipped, as
+/// calling their getters needs an interpreter.
+impl<T: FromValue> FromValue for HashMap<String, T> {
+    fn from_value(v: Value) -> Result<Self, &'static str> {
+        let keys: Vec<String> = match *v {
+            ValueData::Object(ref obj) => obj
+                .borrow()
+                .properties
+                .iter()
+                .filter(|(_, prop)| prop.enumerable != Some(false) && prop.value.is_some())
+                .map(|(key, _)| key.clone())
+                .collect(),
+            _ => return Err("Value is not a valid object"),
+        };
+        keys.into_iter()
+            .map(|key| {
+                let value = from_value(v.get_field_slice(&key))?;
+                Ok((key, value))
+            })
+            .collect()
+    }
+}
+
 impl ToValue for Object {
     fn to_value(&self) -> Value {
         Gc::new(ValueData::Object(GcCell::new(self.clone())))
@@ -901,6 +1066,102 @@ pub fn to_value<A: ToValue>(v: A) -> Value {
     v.to_value()
 }
 
+/// Convert a finite number to the shortest string which reads back as the same number
+/// <https://tc39.es/ecma262/#sec-numeric-types-number-tostring>
+pub fn number_to_string(number: f64) -> String {
+    // -0 is shown as 0, it can only be told apart by operations like 1 / -0
+    if number == 0.0 {
+        return "0".to_string();
+    }
+    if number < 0.0 {
+        return format!("-{}", number_to_string(-number));
+    }
+
+    // Rust's exponent formatting gives the shortest digits which round trip, as `d.ddde-n`
+    let exponential = format!("{:e}", number);
+    let (mantissa, exponent) = exponential.split_at(exponential.find('e').unwrap());
+    let digits = mantissa.replace('.', "");
+    let k = digits.len() as i32;
+    // The decimal point goes after n digits
+    let n = i32::from_str(&exponent[1..]).unwrap() + 1;
+
+    if k <= n && n <= 21 {
+        // An integer, padded with zeros
+        format!("{}{}", digits, "0".repeat((n - k) as usize))
+    } else if 0 < n && n <= 21 {
+        let (integer, fraction) = digits.split_at(n as usize);
+        format!("{}.{}", integer, fraction)
+    } else if -6 < n && n <= 0 {
+        format!("0.{}{}", "0".repeat(-n as usize), digits)
+    } else {
+        let sign = if n - 1 < 0 { '-' } else { '+' };
+        let (first, rest) = digits.split_at(1);
+        if rest.is_empty() {
+            format!("{}e{}{}", first, sign, (n - 1).abs())
+        } else {
+            format!("{}.{}e{}{}", first, rest, sign, (n - 1).abs())
+        }
+    }
+}
+
+/// Whether `ch` is a WhiteSpace or LineTerminator code point, which are trimmed from strings
+/// <https://tc39.es/ecma262/#sec-white-space>
+pub fn is_js_whitespace(ch: char) -> bool {
+    match ch {
+        '\u{0009}' | '\u{000B}' | '\u{000C}' | '\u{0020}' | '\u{00A0}' | '\u{FEFF}' => true,
+        '\u{000A}' | '\u{000D}' | '\u{2028}' | '\u{2029}' => true,
+        // The other code points in the Space_Separator category
+        '\u{1680}' | '\u{2000}'..='\u{200A}' | '\u{202F}' | '\u{205F}' | '\u{3000}' => true,
+        _ => false,
+    }
+}
+
+/// Convert a string to a number following the StringNumericLiteral grammar
+/// <https://tc39.es/ecma262/#sec-tonumber-applied-to-the-string-type>
+pub fn string_to_number(string: &str) -> f64 {
+    let string = string.trim_matches(is_js_whitespace);
+    if string.is_empty() {
+        return 0.0;
+    }
+
+    let radix = match string.get(..2) {
+        Some("0x") | Some("0X") => Some(16),
+        Some("0o") | Some("0O") => Some(8),
+        Some("0b") | Some("0B") => Some(2),
+        _ => None,
+    };
+    if let Some(radix) = radix {
+        let digits = &string[2..];
+        if digits.is_empty() || !digits.chars().all(|ch| ch.is_digit(radix)) {
+            return f64::NAN;
+        }
+        return digits.chars().fold(0.0, |num, digit| {
+            num * f64::from(radix) + f64::from(digit.to_digit(radix).unwrap())
+        });
+    }
+
+    let (sign, unsigned) = match string.chars().next() {
+        Some('-') => (-1.0, &string[1..]),
+        Some('+') => (1.0, &string[1..]),
+        _ => (1.0, string),
+    };
+    if unsigned == "Infinity" {
+        return sign * f64::INFINITY;
+    }
+    // Rust also parses forms such as `inf` and `NaN` which Javascript doesn't accept
+    let is_decimal = unsigned.starts_with(|ch: char| ch.is_ascii_digit() || ch == '.')
+        && unsigned.chars().all(|ch| {
+            ch.is_ascii_digit() || ch == '.' || ch == 'e' || ch == 'E' || ch == '+' || ch == '-'
+        });
+    if !is_decimal {
+        return f64::NAN;
+    }
+    match f64::from_str(unsigned) {
+        Ok(num) => sign * num,
+        Err(_) => f64::NAN,
+    }
+}
+
 /// The internal comparison abstract operation SameValue(x, y),
 /// where x and y are ECMAScript language values, produces true or false.
 /// Such a comparison is performed as follows:
@@ -948,22 +1209,113 @@ pub fn same_value_non_number(x: &Value, y: &Value) -> bool {
     }
 }
 
+/// The internal comparison abstract operation SameValueZero(x, y), which differs from SameValue
+/// only in treating `+0` and `-0` as equal while still considering `NaN` equal to itself.
+///
+/// https://tc39.es/ecma262/#sec-samevaluezero
+pub fn same_value_zero(x: &Value, y: &Value) -> bool {
+    if x.get_type() != y.get_type() {
+        return false;
+    }
+
+    if x.get_type() == "number" {
+        let native_x = x.to_num();
+        let native_y = y.to_num();
+        return (native_x.is_nan() && native_y.is_nan()) || native_x == native_y;
+    }
+
+    strict_equals(x, y)
+}
+
+/// The Strict Equality Comparison `x === y`, where objects and functions are compared by identity
+///
+/// https://tc39.es/ecma262/#sec-strict-equality-comparison
+pub fn strict_equals(x: &Value, y: &Value) -> bool {
+    if x.get_type() != y.get_type() {
+        return false;
+    }
+
+    match x.get_type() {
+        "number" => x.to_num() == y.to_num(),
+        "object" | "function" => same_value(x, y, true),
+        _ => same_value_non_number(x, y),
+    }
+}
+
+/// The Abstract Equality Comparison `x == y`, which converts values of different types before comparing them
+///
+/// https://tc39.es/ecma262/#sec-abstract-equality-comparison
+pub fn abstract_equals(x: &Value, y: &Value, ctx: &mut Interpreter) -> Result<bool, Value> {
+    Ok(match (x.get_type(), y.get_type()) {
+        (x_type, y_type) if x_type == y_type => strict_equals(x, y),
+        ("null", "undefined") | ("undefined", "null") => true,
+        ("number", "string") => x.to_num() == y.to_num(),
+        ("string", "number") => x.to_num() == y.to_num(),
+        ("boolean", _) => abstract_equals(&to_value(x.to_num()), y, ctx)?,
+        (_, "boolean") => abstract_equals(x, &to_value(y.to_num()), ctx)?,
+        ("number", "object")
+        | ("string", "object")
+        | ("number", "function")
+        | ("string", "function") => {
+            let y_primitive = ctx.to_primitive(y, None)?;
+            !y_primitive.is_object()
+                && !y_primitive.is_function()
+                && abstract_equals(x, &y_primitive, ctx)?
+        }
+        ("object", "number")
+        | ("object", "string")
+        | ("function", "number")
+        | ("function", "string") => {
+            let x_primitive = ctx.to_primitive(x, None)?;
+            !x_primitive.is_object()
+                && !x_primitive.is_function()
+                && abstract_equals(&x_primitive, y, ctx)?
+        }
+        _ => false,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        exec::{Executor, Interpreter},
+        forward, forward_val,
+        realm::Realm,
+    };
+
+    #[test]
+    fn check_to_int32() {
+        assert_eq!(
+            ValueData::Number(2_147_483_648.0).to_int32(),
+            -2_147_483_648
+        );
+        assert_eq!(
+            ValueData::Number(-2_147_483_649.0).to_int32(),
+            2_147_483_647
+        );
+        assert_eq!(ValueData::Number(4_294_967_296.5).to_int32(), 0);
+        assert_eq!(ValueData::Number(-3.7).to_int32(), -3);
+        assert_eq!(ValueData::Number(f64::NAN).to_int32(), 0);
+        assert_eq!(ValueData::Number(f64::INFINITY).to_uint32(), 0);
+        assert_eq!(ValueData::Number(-1.0).to_uint32(), 4_294_967_295);
+        assert_eq!(ValueData::Integer(-1).to_uint32(), 4_294_967_295);
+        assert_eq!(ValueData::String(String::from("8")).to_int32(), 8);
+        assert_eq!(ValueData::Boolean(true).to_int32(), 1);
+    }
 
     #[test]
     fn check_is_object() {
         let val = ValueData::new_obj(None);
-        assert_eq!(val.is_object(), true);
+        assert!(val.is_object());
     }
 
     #[test]
     fn check_string_to_value() {
         let s = String::from("Hello");
         let v = s.to_value();
-        assert_eq!(v.is_string(), true);
-        assert_eq!(v.is_null(), false);
+        assert!(v.is_string());
+        assert!(!v.is_null());
     }
 
     #[test]
@@ -984,18 +1336,101 @@ mod tests {
 
     #[test]
     fn check_integer_is_true() {
-        assert_eq!(1.to_value().is_true(), true);
-        assert_eq!(0.to_value().is_true(), false);
-        assert_eq!((-1).to_value().is_true(), true);
+        assert!(1.to_value().is_true());
+        assert!(!0.to_value().is_true());
+        assert!((-1).to_value().is_true());
     }
 
     #[test]
     fn check_number_is_true() {
-        assert_eq!(1.0.to_value().is_true(), true);
-        assert_eq!(0.1.to_value().is_true(), true);
-        assert_eq!(0.0.to_value().is_true(), false);
-        assert_eq!((-0.0).to_value().is_true(), false);
-        assert_eq!((-1.0).to_value().is_true(), true);
-        assert_eq!(NAN.to_value().is_true(), false);
+        assert!(1.0.to_value().is_true());
+        assert!(0.1.to_value().is_true());
+        assert!(!0.0.to_value().is_true());
+        assert!(!(-0.0).to_value().is_true());
+        assert!((-1.0).to_value().is_true());
+        assert!(!f64::NAN.to_value().is_true());
+    }
+
+    #[test]
+    fn check_collections_from_value() {
+        let realm = Realm::create();
+        let mut engine: Interpreter = Executor::new(realm);
+        let init = r#"
+        const numbers = [1, 2, 3];
+        const words = { a: 'x', b: 'y' };
+        Object.defineProperty(words, 'hidden', { value: 'z', enumerable: false });
+        "#;
+        forward(&mut engine, init);
+
+        let numbers = forward_val(&mut engine, "numbers").unwrap();
+        assert_eq!(from_value::<Vec<i32>>(numbers), Ok(vec![1, 2, 3]));
+
+        let words = forward_val(&mut engine, "words").unwrap();
+        let words: HashMap<String, String> = from_value(words).unwrap();
+        assert_eq!(words.len(), 2);
+        assert_eq!(words["a"], "x");
+        assert_eq!(words["b"], "y");
+
+        assert!(from_value::<HashMap<String, i32>>(to_value(1)).is_err());
+    }
+
+    #[test]
+    fn check_collections_to_value() {
+        let realm = Realm::create();
+        let mut engine: Interpreter = Executor::new(realm);
+        let global = engine.get_realm().global_obj.clone();
+        global.set_field_slice("numbers", to_value(vec![1, 2, 3]));
+        global.set_field_slice("letters", to_value(&["a", "b"][..]));
+        let mut words = HashMap::new();
+        words.insert("greeting".to_string(), "hello");
+        global.set_field_slice("words", to_value(words));
+
+        assert_eq!(forward(&mut engine, "numbers.length"), "3");
+        assert_eq!(forward(&mut engine, "numbers[1]"), "2");
+        assert_eq!(forward(&mut engine, "Array.isArray(numbers)"), "true");
+        assert_eq!(forward(&mut engine, "numbers.join('-')"), "1-2-3");
+        assert_eq!(forward(&mut engine, "letters.length"), "2");
+        assert_eq!(forward(&mut engine, "letters.indexOf('b')"), "1");
+        assert_eq!(forward(&mut engine, "words.greeting"), "hello");
+        assert_eq!(forward(&mut engine, "typeof words"), "object");
+    }
+
+    #[test]
+    fn check_number_to_string() {
+        let cases = [
+            (0.0, "0"),
+            (-0.0, "0"),
+            (123.0, "123"),
+            (-1.5, "-1.5"),
+            (0.1 + 0.2, "0.30000000000000004"),
+            (1e21, "1e+21"),
+            (1.5e21, "1.5e+21"),
+            (1e20, "100000000000000000000"),
+            (123_456_789.012_345_67, "123456789.01234567"),
+            (0.000_001, "0.000001"),
+            (1e-7, "1e-7"),
+            (-1.25e-7, "-1.25e-7"),
+            (f64::MAX, "1.7976931348623157e+308"),
+            (5e-324, "5e-324"),
+        ];
+        for (number, expected) in cases.iter() {
+            assert_eq!(number_to_string(*number), *expected);
+        }
+    }
+
+    #[test]
+    fn check_number_formatting() {
+        let realm = Realm::create();
+        let mut engine: Interpreter = Executor::new(realm);
+
+        assert_eq!(
+            forward(&mut engine, "String(0.1 + 0.2) === '0.30000000000000004'"),
+            "true"
+        );
+        assert_eq!(forward(&mut engine, "String(1e21) === '1e+21'"), "true");
+        assert_eq!(forward(&mut engine, "String(123) === '123'"), "true");
+        assert_eq!(forward(&mut engine, "String(1.0)"), "1");
+        assert_eq!(forward(&mut engine, "'x' + 1e-7"), "x1e-7");
+        assert_eq!(forward(&mut engine, "(2.5e25).toString()"), "2.5e+25");
     }
 }
@@ -0,0 +1,295 @@
+//! The `WeakRef` and `FinalizationRegistry` objects
+//!
+//! **These are strong-reference stubs.** The `gc` crate used by the interpreter has no weak
+//! pointers, so a `WeakRef` holds a strong reference that keeps its target alive for as long
+//! as the `WeakRef` itself is reachable: `deref()` never returns `undefined`. Likewise a
+//! `FinalizationRegistry` never observes a target being collected, so its cleanup callback is
+//! never invoked. Registrations are still tracked so that `unregister` behaves as specified.
+//! <https://tc39.es/ecma262/#sec-managing-memory>
+use crate::{
+    exec::Interpreter,
+    js::{
+        error,
+        function::make_builtin_fn,
+        object::{Object, ObjectKind, PROTOTYPE},
+        value::{from_value, same_value, to_value, ResultValue, Value, ValueData},
+    },
+};
+use gc::Gc;
+
+/// Create a new `WeakRef` - [[Construct]]
+///
+/// The target is stored as a strong reference, so it is never collected while the `WeakRef`
+/// is alive.
+/// <https://tc39.es/ecma262/#sec-weak-ref-target>
+pub fn make_weak_ref(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let target = match args.first() {
+        Some(target) if target.is_object() || target.is_function() => target.clone(),
+        _ => {
+            return Err(error::new_error(
+                "TypeError",
+                "WeakRef: target must be an object",
+                ctx,
+            ))
+        }
+    };
+    this.set_internal_slot("WeakRefTarget", target);
+    Ok(this.clone())
+}
+
+/// Calling `WeakRef` without `new` is a TypeError - [[Call]]
+pub fn call_weak_ref(_: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    Err(error::new_error(
+        "TypeError",
+        "Constructor WeakRef requires 'new'",
+        ctx,
+    ))
+}
+
+/// WeakRef.prototype.deref ( )
+///
+/// Returns the target of the `WeakRef`. The target is strongly held, so this never returns
+/// `undefined`.
+/// <https://tc39.es/ecma262/#sec-weak-ref.prototype.deref>
+pub fn deref(this: &Value, _: &[Value], _: &mut Interpreter) -> ResultValue {
+    Ok(this.get_internal_slot("WeakRefTarget"))
+}
+
+/// Create a new `FinalizationRegistry` - [[Construct]]
+///
+/// The cleanup callback must be callable, but it is not kept: collection of registered targets
+/// is never observed, so cleanup never runs.
+/// <https://tc39.es/ecma262/#sec-finalization-registry-cleanup-callback>
+pub fn make_finalization_registry(
+    this: &Value,
+    args: &[Value],
+    ctx: &mut Interpreter,
+) -> ResultValue {
+    match args.first() {
+        Some(cleanup) if cleanup.is_function() => (),
+        _ => {
+            return Err(error::new_error(
+                "TypeError",
+                "FinalizationRegistry: cleanup must be callable",
+                ctx,
+            ))
+        }
+    }
+
+    let cells = ValueData::new_obj(None);
+    cells.set_field_slice("length", to_value(0_i32));
+    this.set_internal_slot("Cells", cells);
+    Ok(this.clone())
+}
+
+/// Calling `FinalizationRegistry` without `new` is a TypeError - [[Call]]
+pub fn call_finalization_registry(_: &Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    Err(error::new_error(
+        "TypeError",
+        "Constructor FinalizationRegistry requires 'new'",
+        ctx,
+    ))
+}
+
+/// FinalizationRegistry.prototype.register ( target, heldValue [ , unregisterToken ] )
+///
+/// Records the registration so that `unregister` can remove it. Cleanup never runs, so
+/// `heldValue` is never passed to a callback. The target itself is not stored, so the registry
+/// never keeps it alive.
+/// <https://tc39.es/ecma262/#sec-finalization-registry.prototype.register>
+pub fn register(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let undefined = Gc::new(ValueData::Undefined);
+    let target = args.first().cloned().unwrap_or_else(|| undefined.clone());
+    let held_value = args.get(1).cloned().unwrap_or_else(|| undefined.clone());
+    let unregister_token = args.get(2).cloned().unwrap_or_else(|| undefined.clone());
+
+    if !target.is_object() && !target.is_function() {
+        return Err(error::new_error(
+            "TypeError",
+            "FinalizationRegistry.register: target must be an object",
+            ctx,
+        ));
+    }
+    if same_value(&target, &held_value, true) {
+        return Err(error::new_error(
+            "TypeError",
+            "FinalizationRegistry.register: target and holdings must not be same",
+            ctx,
+        ));
+    }
+    if !unregister_token.is_undefined()
+        && !unregister_token.is_object()
+        && !unregister_token.is_function()
+    {
+        return Err(error::new_error(
+            "TypeError",
+            "FinalizationRegistry.register: unregisterToken must be an object",
+            ctx,
+        ));
+    }
+
+    let cell = ValueData::new_obj(None);
+    cell.set_field_slice("heldValue", held_value);
+    cell.set_field_slice("unregisterToken", unregister_token);
+
+    let cells = this.get_internal_slot("Cells");
+    let length: i32 = from_value(cells.get_field_slice("length")).unwrap();
+    cells.set_field(length.to_string(), cell);
+    cells.set_field_slice("length", to_value(length + 1));
+    Ok(undefined)
+}
+
+/// FinalizationRegistry.prototype.unregister ( unregisterToken )
+///
+/// Removes every registration made with `unregisterToken`, returning whether any were removed.
+/// <https://tc39.es/ecma262/#sec-finalization-registry.prototype.unregister>
+pub fn unregister(this: &Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let unregister_token = match args.first() {
+        Some(token) if token.is_object() || token.is_function() => token.clone(),
+        _ => {
+            return Err(error::new_error(
+                "TypeError",
+                "FinalizationRegistry.unregister: unregisterToken must be an object",
+                ctx,
+            ))
+        }
+    };
+
+    let cells = this.get_internal_slot("Cells");
+    let length: i32 = from_value(cells.get_field_slice("length")).unwrap();
+    let mut kept: Vec<Value> = Vec::new();
+    for n in 0..length {
+        let cell = cells.get_field(&n.to_string());
+        if !same_value(
+            &cell.get_field_slice("unregisterToken"),
+            &unregister_token,
+            true,
+        ) {
+            kept.push(cell);
+        }
+        cells.remove_prop(&n.to_string());
+    }
+
+    for (n, cell) in kept.iter().enumerate() {
+        cells.set_field(n.to_string(), cell.clone());
+    }
+    cells.set_field_slice("length", to_value(kept.len() as i32));
+    Ok(to_value(kept.len() as i32 != length))
+}
+
+/// Create a new `WeakRef` object
+pub fn create_weak_ref_constructor(global: &Value) -> Value {
+    let mut weak_ref_constructor = Object::default();
+    weak_ref_constructor.kind = ObjectKind::Function;
+    weak_ref_constructor.set_internal_method("construct", make_weak_ref);
+    weak_ref_constructor.set_internal_method("call", call_weak_ref);
+
+    // Create prototype
+    let proto = ValueData::new_obj(Some(global));
+    proto.set_field_slice("deref", make_builtin_fn(deref, global));
+
+    let weak_ref = to_value(weak_ref_constructor);
+    proto.set_field_slice("constructor", weak_ref.clone());
+    weak_ref.set_field_slice(PROTOTYPE, proto);
+    weak_ref
+}
+
+/// Create a new `FinalizationRegistry` object
+pub fn create_finalization_registry_constructor(global: &Value) -> Value {
+    let mut registry_constructor = Object::default();
+    registry_constructor.kind = ObjectKind::Function;
+    registry_constructor.set_internal_method("construct", make_finalization_registry);
+    registry_constructor.set_internal_method("call", call_finalization_registry);
+
+    // Create prototype
+    let proto = ValueData::new_obj(Some(global));
+    proto.set_field_slice("register", make_builtin_fn(register, global));
+    proto.set_field_slice("unregister", make_builtin_fn(unregister, global));
+
+    let registry = to_value(registry_constructor);
+    proto.set_field_slice("constructor", registry.clone());
+    registry.set_field_slice(PROTOTYPE, proto);
+    registry
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::exec::Executor;
+    use crate::realm::Realm;
+    use crate::{forward, forward_val, js::value::same_value};
+
+    #[test]
+    fn deref_returns_live_target() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const target = { a: 1 };
+        const weak = new WeakRef(target);
+        "#;
+        forward(&mut engine, init);
+
+        let target = forward_val(&mut engine, "target").unwrap();
+        let derefed = forward_val(&mut engine, "weak.deref()").unwrap();
+        assert!(same_value(&target, &derefed, true));
+        assert_eq!(forward(&mut engine, "weak.deref().a"), "1");
+    }
+
+    #[test]
+    fn unregister_removes_registrations() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+        let init = r#"
+        const registry = new FinalizationRegistry(function (held) {});
+        const target = {};
+        const token = {};
+        registry.register(target, "held", token);
+        "#;
+        forward(&mut engine, init);
+
+        assert_eq!(forward(&mut engine, "registry.unregister(token)"), "true");
+        assert_eq!(forward(&mut engine, "registry.unregister(token)"), "false");
+    }
+
+    #[test]
+    fn invalid_arguments_throw_type_errors() {
+        let realm = Realm::create();
+        let mut engine = Executor::new(realm);
+
+        assert_eq!(
+            forward(&mut engine, "WeakRef({})"),
+            "Error: TypeError: Constructor WeakRef requires 'new'"
+        );
+        assert_eq!(
+            forward(
+                &mut engine,
+                "try { new WeakRef(1) } catch (e) { e instanceof TypeError }"
+            ),
+            "true"
+        );
+        assert_eq!(
+            forward(
+                &mut engine,
+                "try { new FinalizationRegistry(1) } catch (e) { e.message }"
+            ),
+            "FinalizationRegistry: cleanup must be callable"
+        );
+        forward(
+            &mut engine,
+            "const registry = new FinalizationRegistry(function () {});",
+        );
+        assert_eq!(
+            forward(
+                &mut engine,
+                "try { registry.register(1, 2) } catch (e) { e.name }"
+            ),
+            "TypeError"
+        );
+        assert_eq!(
+            forward(
+                &mut engine,
+                "try { registry.unregister('token') } catch (e) { e instanceof TypeError }"
+            ),
+            "true"
+        );
+    }
+}
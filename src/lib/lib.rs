@@ -43,26 +43,57 @@ use crate::{
     exec::{Executor, Interpreter},
     js::value::ResultValue,
     realm::Realm,
-    syntax::{ast::expr::Expr, lexer::Lexer, parser::Parser},
+    syntax::{
+        ast::expr::Expr,
+        lexer::Lexer,
+        parser::{ParseError, Parser},
+    },
 };
 use wasm_bindgen::prelude::*;
 
-fn parser_expr(src: &str) -> Expr {
+/// A script which has been lexed and parsed, so it can be run many times without doing that again
+#[derive(Debug, Clone)]
+pub struct Script {
+    expr: Expr,
+}
+
+impl Script {
+    /// The parsed body of the script
+    pub fn expr(&self) -> &Expr {
+        &self.expr
+    }
+}
+
+/// Lex and parse the source code of a script, ready to be run with `Interpreter::run_compiled`
+pub fn compile(src: &str) -> Result<Script, ParseError> {
+    let mut lexer = Lexer::new(src);
+    lexer.lex()?;
+    let expr = Parser::new(lexer.tokens).parse_all()?;
+    Ok(Script { expr })
+}
+
+fn parser_expr(src: &str) -> Script {
     let mut lexer = Lexer::new(src);
     lexer.lex().expect("lexing failed");
     let tokens = lexer.tokens;
-    Parser::new(tokens).parse_all().expect("parsing failed")
+    let expr = Parser::new(tokens).parse_all().expect("parsing failed");
+    Script { expr }
 }
 
 /// Execute the code using an existing Interpreter
 /// The str is consumed and the state of the Interpreter is changed
 pub fn forward(engine: &mut Interpreter, src: &str) -> String {
     // Setup executor
-    let expr = parser_expr(src);
-    let result = engine.run(&expr);
+    let script = parser_expr(src);
+    let result = engine.run_compiled(&script);
     match result {
         Ok(v) => v.to_string(),
-        Err(v) => format!("{}: {}", "Error", v.to_string()),
+        // Error objects are shown by their name and message
+        Err(ref v) if v.is_error() => match engine.value_to_rust_string(v) {
+            Ok(message) => format!("{}: {}", "Error", message),
+            Err(thrown) => format!("{}: {}", "Error", thrown),
+        },
+        Err(v) => format!("{}: {}", "Error", v),
     }
 }
 
@@ -72,8 +103,8 @@ pub fn forward(engine: &mut Interpreter, src: &str) -> String {
 /// If the interpreter fails parsing an error value is returned instead (error object)
 pub fn forward_val(engine: &mut Interpreter, src: &str) -> ResultValue {
     // Setup executor
-    let expr = parser_expr(src);
-    engine.run(&expr)
+    let script = parser_expr(src);
+    engine.run_compiled(&script)
 }
 
 /// Create a clean Interpreter and execute the code
@@ -95,7 +126,7 @@ extern "C" {
 
 #[wasm_bindgen]
 pub fn evaluate(src: &str) -> String {
-    let mut lexer = Lexer::new(&src);
+    let mut lexer = Lexer::new(src);
     match lexer.lex() {
         Ok(_v) => (),
         Err(v) => log(&v.to_string()),
@@ -104,24 +135,59 @@ pub fn evaluate(src: &str) -> String {
     let tokens = lexer.tokens;
 
     // Setup executor
-    let expr: Expr;
-
-    match Parser::new(tokens).parse_all() {
-        Ok(v) => {
-            expr = v;
-        }
+    let script = match Parser::new(tokens).parse_all() {
+        Ok(expr) => Script { expr },
         Err(_v) => {
             log("parsing fail");
             return String::from("parsing failed");
         }
-    }
+    };
     // Create new Realm
     let realm = Realm::create();
     let mut engine: Interpreter = Executor::new(realm);
-    let result = engine.run(&expr);
+    let result = engine.run_compiled(&script);
     log("test2");
     match result {
         Ok(v) => v.to_string(),
-        Err(v) => format!("{}: {}", "error", v.to_string()),
+        Err(v) => format!("{}: {}", "error", v),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compile_once_run_many_times() {
+        let script = compile(
+            r#"
+            function fib(n) {
+                if (n <= 1) return 1;
+                return fib(n - 1) + fib(n - 2);
+            }
+            fib(12);
+            "#,
+        )
+        .expect("failed to compile");
+
+        let realm = Realm::create();
+        let mut engine: Interpreter = Executor::new(realm);
+        for _ in 0..20 {
+            let result = engine.run_compiled(&script).expect("failed to run");
+            assert_eq!(result.to_string(), "233");
+        }
+
+        // The same script can be shared between interpreters
+        let mut other: Interpreter = Executor::new(Realm::create());
+        assert_eq!(other.run_compiled(&script).unwrap().to_string(), "233");
+    }
+
+    #[test]
+    fn compile_errors() {
+        match compile("0x") {
+            Err(ParseError::Lexer(_)) => (),
+            result => panic!("expected a lexer error, got {:?}", result),
+        }
+        assert!(compile("var = 1;").is_err());
     }
 }
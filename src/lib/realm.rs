@@ -5,18 +5,24 @@
 //!A realm is represented in this implementation as a Realm struct with the fields specified from the spec
 use crate::{
     environment::{
-        declarative_environment_record::DeclarativeEnvironmentRecord,
+        declarative_environment_record::{DeclarativeBindings, DeclarativeEnvironmentRecord},
         global_environment_record::GlobalEnvironmentRecord,
         lexical_environment::LexicalEnvironment,
         object_environment_record::ObjectEnvironmentRecord,
     },
     js::{
-        array, boolean, console, function, json, math, object, regexp, string,
+        array, boolean, console, date, error,
+        function::{self, Function},
+        json, map, math, number, object, promise,
+        property::PropertyMap,
+        regexp, set, string, typed_array,
         value::{Value, ValueData},
+        weak_ref,
     },
+    syntax::ast::sym::Interner,
 };
 use gc::{Gc, GcCell};
-use std::collections::{hash_map::HashMap, hash_set::HashSet};
+use std::collections::hash_set::HashSet;
 
 /// Representation of a Realm.   
 /// In the specification these are called Realm Records.
@@ -25,6 +31,8 @@ pub struct Realm {
     pub global_obj: Value,
     pub global_env: Gc<GcCell<Box<GlobalEnvironmentRecord>>>,
     pub environment: LexicalEnvironment,
+    /// The names bound by the interpreter itself, such as `this` and `arguments`
+    pub interner: Interner,
 }
 
 impl Realm {
@@ -39,6 +47,7 @@ impl Realm {
             global_obj: global.clone(),
             global_env,
             environment: LexicalEnvironment::new(global),
+            interner: Interner::default(),
         };
 
         // Add new builtIns to Realm
@@ -52,16 +61,70 @@ impl Realm {
     fn create_instrinsics(&self) {
         let global = &self.global_obj;
         // Create intrinsics, add global objects here
+        // Every built-in function inherits from `Function.prototype`, so it comes first
+        function::init(global);
         object::init(global);
         console::init(global);
+        error::init(global);
         math::init(global);
-        function::init(global);
         json::init(global);
+        number::init(global);
+
+        // The global object refers to itself, so that scripts can reach it by name
+        // <https://tc39.es/ecma262/#sec-globalthis>
+        global.set_field_slice("globalThis", global.clone());
 
         global.set_field_slice("String", string::create_constructor(global));
         global.set_field_slice("RegExp", regexp::create_constructor(global));
         global.set_field_slice("Array", array::create_constructor(global));
         global.set_field_slice("Boolean", boolean::create_constructor(global));
+        global.set_field_slice("Number", number::create_constructor(global));
+        global.set_field_slice("Date", date::create_constructor(global));
+        global.set_field_slice("Map", map::create_constructor(global));
+        global.set_field_slice("Set", set::create_constructor(global));
+        global.set_field_slice("Promise", promise::create_constructor(global));
+        global.set_field_slice(
+            "ArrayBuffer",
+            typed_array::create_array_buffer_constructor(global),
+        );
+        for (name, constructor) in typed_array::create_typed_array_constructors(global) {
+            global.set_field_slice(name, constructor);
+        }
+        global.set_field_slice("WeakRef", weak_ref::create_weak_ref_constructor(global));
+        global.set_field_slice(
+            "FinalizationRegistry",
+            weak_ref::create_finalization_registry_constructor(global),
+        );
+
+        // Built-in properties are not enumerable, so only those added by scripts are listed by
+        // `Object.keys` and `for-in`
+        // <https://tc39.es/ecma262/#sec-ecmascript-standard-built-in-objects>
+        hide_properties(global, &mut HashSet::new());
+    }
+}
+
+/// Make the own properties of `value`, and of every object reachable through them, non-enumerable
+fn hide_properties(value: &Value, seen: &mut HashSet<*const ValueData>) {
+    if !seen.insert(&**value) {
+        return;
+    }
+    let mut reachable = Vec::new();
+    let mut hide = |properties: &mut PropertyMap| {
+        for prop in properties.values_mut() {
+            prop.enumerable = Some(false);
+            reachable.extend(prop.value.iter().chain(&prop.get).chain(&prop.set).cloned());
+        }
+    };
+    match **value {
+        ValueData::Object(ref obj) => hide(&mut obj.borrow_mut().properties),
+        ValueData::Function(ref func) => match *func.borrow_mut() {
+            Function::NativeFunc(ref mut func) => hide(&mut func.object.properties),
+            Function::RegularFunc(ref mut func) => hide(&mut func.object.properties),
+        },
+        _ => (),
+    }
+    for value in reachable {
+        hide_properties(&value, seen);
     }
 }
 
@@ -73,16 +136,16 @@ fn new_global_environment(
     let obj_rec = Box::new(ObjectEnvironmentRecord {
         bindings: global,
         outer_env: None,
-        /// Object Environment Records created for with statements (13.11)
-        /// can provide their binding object as an implicit this value for use in function calls.
-        /// The capability is controlled by a withEnvironment Boolean value that is associated
-        /// with each object Environment Record. By default, the value of withEnvironment is false
-        /// for any object Environment Record.
+        // Object Environment Records created for with statements (13.11)
+        // can provide their binding object as an implicit this value for use in function calls.
+        // The capability is controlled by a withEnvironment Boolean value that is associated
+        // with each object Environment Record. By default, the value of withEnvironment is false
+        // for any object Environment Record.
         with_environment: false,
     });
 
     let dcl_rec = Box::new(DeclarativeEnvironmentRecord {
-        env_rec: HashMap::new(),
+        env_rec: DeclarativeBindings::default(),
         outer_env: None,
     });
 
@@ -1,23 +1,55 @@
-use crate::syntax::ast::{
-    constant::Const,
-    op::{BinOp, Operator, UnaryOp},
+use crate::{
+    environment::lexical_environment::BindingCache,
+    syntax::ast::{
+        constant::Const,
+        op::{BinOp, Operator, UnaryOp},
+        pos::Position,
+        sym::Sym,
+    },
 };
+use gc::Gc;
 use gc_derive::{Finalize, Trace};
-use std::{
-    collections::btree_map::BTreeMap,
-    fmt::{Display, Formatter, Result},
-};
+use std::fmt::{Display, Formatter, Result};
 
-#[derive(Clone, Trace, Finalize, Debug, PartialEq)]
+#[derive(Clone, Trace, Finalize, Debug)]
 pub struct Expr {
     /// The expression definition
     pub def: ExprDef,
+    /// Where the expression was found in the source, used to locate runtime errors
+    pub pos: Option<Position>,
+    /// Where the variable read by a `Local` expression was found the last time it ran
+    pub(crate) binding: BindingCache,
+    /// Whether the expression was wrapped in parentheses, which keeps the parser from regrouping
+    /// the operators inside it with the ones around it
+    pub(crate) parenthesized: bool,
 }
 
 impl Expr {
-    /// Create a new expression with a starting and ending position
+    /// Create a new expression with no known position
     pub fn new(def: ExprDef) -> Self {
-        Self { def }
+        Self {
+            def,
+            pos: None,
+            binding: BindingCache::default(),
+            parenthesized: false,
+        }
+    }
+
+    /// Create a new expression found at `pos` in the source
+    pub fn new_at(def: ExprDef, pos: Option<Position>) -> Self {
+        Self {
+            def,
+            pos,
+            binding: BindingCache::default(),
+            parenthesized: false,
+        }
+    }
+}
+
+/// Expressions are equal if they do the same thing, wherever they were written
+impl PartialEq for Expr {
+    fn eq(&self, other: &Self) -> bool {
+        self.def == other.def
     }
 }
 
@@ -43,7 +75,11 @@ pub enum ExprDef {
     /// Run several expressions from top-to-bottom
     Block(Vec<Expr>),
     /// Load a reference to a value
-    Local(String),
+    Local(Sym),
+    /// The `this` value of the running function, or the global object outside of functions
+    ThisExpr,
+    /// The constructor a function was called with through `new`, or undefined
+    NewTargetExpr,
     /// Gets the constant field of a value
     GetConstField(Box<Expr>, String),
     /// Gets the field of a value
@@ -52,22 +88,43 @@ pub enum ExprDef {
     Call(Box<Expr>, Vec<Expr>),
     /// Repeatedly run an expression while the conditional expression resolves to true
     WhileLoop(Box<Expr>, Box<Expr>),
+    /// Run an expression once, then repeatedly while the conditional expression resolves to true
+    DoWhileLoop(Box<Expr>, Box<Expr>),
+    /// Run an expression with the properties of an object in scope
+    WithExpr(Box<Expr>, Box<Expr>),
+    /// Run an expression for each enumerable property key of an object, assigning the key to the
+    /// name, which may be declared by the loop
+    ForInLoop(Option<DeclarationKind>, String, Box<Expr>, Box<Expr>),
     /// Check if a conditional expression is true and run an expression if it is and another expression if it isn't
     If(Box<Expr>, Box<Expr>, Option<Box<Expr>>),
     /// Run blocks whose cases match the expression
     Switch(Box<Expr>, Vec<(Expr, Vec<Expr>)>, Option<Box<Expr>>),
-    /// Create an object out of the binary tree given
-    ObjectDecl(Box<BTreeMap<String, Expr>>),
+    /// Create an object out of the property definitions given
+    ObjectDecl(Vec<PropertyDefinition>),
     /// Create an array with items inside
     ArrayDecl(Vec<Expr>),
     /// Create a function with the given name, arguments, and expression
-    FunctionDecl(Option<String>, Vec<String>, Box<Expr>),
+    ///
+    /// The body is shared with every function made from it rather than copied
+    FunctionDecl(Option<String>, Vec<String>, Gc<Expr>),
     /// Create an arrow function with the given arguments and expression
-    ArrowFunctionDecl(Vec<String>, Box<Expr>),
+    ArrowFunctionDecl(Vec<String>, Gc<Expr>),
     /// Return the expression from a function
     Return(Option<Box<Expr>>),
+    /// Leave the innermost loop or `switch`
+    Break,
+    /// Go on to the next iteration of the innermost loop
+    Continue,
     /// Throw a value
     Throw(Box<Expr>),
+    /// Run a block, then the catch block if it threw, binding the thrown value to the optional
+    /// name, and finally the finally block whatever happened
+    Try(
+        Box<Expr>,
+        Option<String>,
+        Option<Box<Expr>>,
+        Option<Box<Expr>>,
+    ),
     /// Assign an expression to a value
     Assign(Box<Expr>, Box<Expr>),
     /// {
@@ -78,18 +135,20 @@ pub enum ExprDef {
     LetDecl(Vec<(String, Option<Expr>)>),
     /// Return a string representing the type of the given expression
     TypeOf(Box<Expr>),
+    /// A template literal, made of its text and the substitutions between the pieces of text
+    TemplateExpr(Vec<String>, Vec<Expr>),
 }
 
 impl Operator for ExprDef {
     fn get_assoc(&self) -> bool {
-        match *self {
+        !matches!(
+            *self,
             ExprDef::Construct(_, _)
-            | ExprDef::UnaryOp(_, _)
-            | ExprDef::TypeOf(_)
-            | ExprDef::If(_, _, _)
-            | ExprDef::Assign(_, _) => false,
-            _ => true,
-        }
+                | ExprDef::UnaryOp(_, _)
+                | ExprDef::TypeOf(_)
+                | ExprDef::If(_, _, _)
+                | ExprDef::Assign(_, _)
+        )
     }
     fn get_precedence(&self) -> u64 {
         match self {
@@ -101,6 +160,7 @@ impl Operator for ExprDef {
             | ExprDef::UnaryOp(UnaryOp::DecrementPre, _) => 3,
             ExprDef::UnaryOp(UnaryOp::Not, _)
             | ExprDef::UnaryOp(UnaryOp::Minus, _)
+            | ExprDef::UnaryOp(UnaryOp::Void, _)
             | ExprDef::TypeOf(_) => 4,
             ExprDef::BinOp(op, _, _) => op.get_precedence(),
             ExprDef::If(_, _, _) => 15,
@@ -123,6 +183,8 @@ impl Display for ExprDef {
                 write!(f, "}}")
             }
             ExprDef::Local(ref s) => write!(f, "{}", s),
+            ExprDef::ThisExpr => write!(f, "this"),
+            ExprDef::NewTargetExpr => write!(f, "new.target"),
             ExprDef::GetConstField(ref ex, ref field) => write!(f, "{}.{}", ex, field),
             ExprDef::GetField(ref ex, ref field) => write!(f, "{}[{}]", ex, field),
             ExprDef::Call(ref ex, ref args) => {
@@ -144,6 +206,12 @@ impl Display for ExprDef {
                 f.write_str(")")
             }
             ExprDef::WhileLoop(ref cond, ref expr) => write!(f, "while({}) {}", cond, expr),
+            ExprDef::DoWhileLoop(ref expr, ref cond) => write!(f, "do {} while({})", expr, cond),
+            ExprDef::WithExpr(ref object, ref expr) => write!(f, "with({}) {}", object, expr),
+            ExprDef::ForInLoop(ref kind, ref name, ref object, ref expr) => match *kind {
+                Some(ref kind) => write!(f, "for({} {} in {}) {}", kind, name, object, expr),
+                None => write!(f, "for({} in {}) {}", name, object, expr),
+            },
             ExprDef::If(ref cond, ref expr, None) => write!(f, "if({}) {}", cond, expr),
             ExprDef::If(ref cond, ref expr, Some(ref else_e)) => {
                 write!(f, "if({}) {} else {}", cond, expr, else_e)
@@ -168,10 +236,10 @@ impl Display for ExprDef {
                 Display::fmt(def, f)?;
                 f.write_str("}")
             }
-            ExprDef::ObjectDecl(ref map) => {
+            ExprDef::ObjectDecl(ref properties) => {
                 f.write_str("{")?;
-                for (key, value) in map.iter() {
-                    f.write_fmt(format_args!("{}: {},", key, value))?;
+                for property in properties.iter() {
+                    f.write_fmt(format_args!("{},", property))?;
                 }
                 f.write_str("}")
             }
@@ -191,7 +259,22 @@ impl Display for ExprDef {
             ExprDef::UnaryOp(ref op, ref a) => write!(f, "{}{}", op, a),
             ExprDef::Return(Some(ref ex)) => write!(f, "return {}", ex),
             ExprDef::Return(None) => write!(f, "return"),
+            ExprDef::Break => write!(f, "break"),
+            ExprDef::Continue => write!(f, "continue"),
             ExprDef::Throw(ref ex) => write!(f, "throw {}", ex),
+            ExprDef::Try(ref block, ref param, ref catch, ref finally) => {
+                write!(f, "try {}", block)?;
+                if let Some(ref catch) = *catch {
+                    match *param {
+                        Some(ref param) => write!(f, " catch ({}) {}", param, catch)?,
+                        None => write!(f, " catch {}", catch)?,
+                    }
+                }
+                if let Some(ref finally) = *finally {
+                    write!(f, " finally {}", finally)?;
+                }
+                Ok(())
+            }
             ExprDef::Assign(ref ref_e, ref val) => write!(f, "{} = {}", ref_e, val),
             ExprDef::VarDecl(ref vars) | ExprDef::LetDecl(ref vars) => {
                 if let ExprDef::VarDecl(_) = *self {
@@ -215,6 +298,104 @@ impl Display for ExprDef {
                 Ok(())
             }
             ExprDef::TypeOf(ref e) => write!(f, "typeof {}", e),
+            ExprDef::TemplateExpr(ref quasis, ref exprs) => {
+                write!(f, "`{}", quasis[0])?;
+                for (expr, quasi) in exprs.iter().zip(quasis.iter().skip(1)) {
+                    write!(f, "${{{}}}{}", expr, quasi)?;
+                }
+                write!(f, "`")
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+/// A member of an object literal
+/// <https://tc39.es/ecma262/#prod-PropertyDefinition>
+pub enum PropertyDefinition {
+    /// `key: value` - A data property
+    Property(PropertyName, Expr),
+    /// `key() {}`, `get key() {}` or `set key(value) {}` - A method or accessor, holding the
+    /// function declaration
+    MethodDefinition(MethodDefinitionKind, PropertyName, Expr),
+    /// `...source` - Copies the own properties of an object
+    SpreadObject(Expr),
+}
+
+impl Display for PropertyDefinition {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match *self {
+            PropertyDefinition::Property(ref key, ref value) => write!(f, "{}: {}", key, value),
+            PropertyDefinition::MethodDefinition(ref kind, ref key, ref method) => {
+                match method.def {
+                    ExprDef::FunctionDecl(_, ref args, ref body) => {
+                        write!(f, "{}{}({}){}", kind, key, args.join(", "), body)
+                    }
+                    _ => write!(f, "{}{}: {}", kind, key, method),
+                }
+            }
+            PropertyDefinition::SpreadObject(ref source) => write!(f, "...{}", source),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+/// The key of a member of an object literal
+/// <https://tc39.es/ecma262/#prod-PropertyName>
+pub enum PropertyName {
+    /// `key`, `"key"` or `1` - A name known when parsing
+    Literal(String),
+    /// `[expr]` - A name computed by converting the result of the expression to a string
+    Computed(Expr),
+}
+
+impl Display for PropertyName {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match *self {
+            PropertyName::Literal(ref name) => write!(f, "{}", name),
+            PropertyName::Computed(ref expr) => write!(f, "[{}]", expr),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+/// The kind of method defined in an object literal
+pub enum MethodDefinitionKind {
+    /// `key() {}` - A method stored as a data property
+    Ordinary,
+    /// `get key() {}` - A getter
+    Get,
+    /// `set key(value) {}` - A setter
+    Set,
+}
+
+impl Display for MethodDefinitionKind {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match *self {
+            MethodDefinitionKind::Ordinary => Ok(()),
+            MethodDefinitionKind::Get => write!(f, "get "),
+            MethodDefinitionKind::Set => write!(f, "set "),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+/// The keyword a name is declared with
+pub enum DeclarationKind {
+    /// `var`
+    Var,
+    /// `let`
+    Let,
+    /// `const`
+    Const,
+}
+
+impl Display for DeclarationKind {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match *self {
+            DeclarationKind::Var => write!(f, "var"),
+            DeclarationKind::Let => write!(f, "let"),
+            DeclarationKind::Const => write!(f, "const"),
         }
     }
 }
@@ -4,4 +4,5 @@ pub mod keyword;
 pub mod op;
 pub mod pos;
 pub mod punc;
+pub mod sym;
 pub mod token;
@@ -61,6 +61,10 @@ pub enum UnaryOp {
     Plus,
     /// `!a` - get the opposite of the boolean value
     Not,
+    /// `~a` - bitwise-not of the value
+    BitNot,
+    /// `void a` - evaluate the value and give undefined
+    Void,
 }
 
 impl Display for UnaryOp {
@@ -74,6 +78,8 @@ impl Display for UnaryOp {
                 UnaryOp::Plus => "+",
                 UnaryOp::Minus => "-",
                 UnaryOp::Not => "!",
+                UnaryOp::BitNot => "~",
+                UnaryOp::Void => "void ",
             }
         )
     }
@@ -92,6 +98,8 @@ pub enum BitOp {
     Shl,
     /// `a >> b` - Bit-shift rightrights
     Shr,
+    /// `a >>> b` - Zero-fill bit-shift rightwards
+    UShr,
 }
 
 impl Display for BitOp {
@@ -105,6 +113,7 @@ impl Display for BitOp {
                 BitOp::Xor => "^",
                 BitOp::Shl => "<<",
                 BitOp::Shr => ">>",
+                BitOp::UShr => ">>>",
             }
         )
     }
@@ -129,6 +138,8 @@ pub enum CompOp {
     LessThan,
     /// `a <= b` - If `a` is less than or equal to `b`
     LessThanOrEqual,
+    /// `a instanceof b` - If `a` inherits from the prototype of the constructor `b`
+    InstanceOf,
 }
 
 impl Display for CompOp {
@@ -145,6 +156,7 @@ impl Display for CompOp {
                 CompOp::GreaterThanOrEqual => ">=",
                 CompOp::LessThan => "<",
                 CompOp::LessThanOrEqual => "<=",
+                CompOp::InstanceOf => "instanceof",
             }
         )
     }
@@ -189,17 +201,18 @@ pub enum BinOp {
 
 impl Operator for BinOp {
     fn get_assoc(&self) -> bool {
-        true
+        !matches!(*self, BinOp::Assign(_))
     }
     fn get_precedence(&self) -> u64 {
         match *self {
             BinOp::Num(NumOp::Mul) | BinOp::Num(NumOp::Div) | BinOp::Num(NumOp::Mod) => 5,
             BinOp::Num(NumOp::Add) | BinOp::Num(NumOp::Sub) => 6,
-            BinOp::Bit(BitOp::Shl) | BinOp::Bit(BitOp::Shr) => 7,
+            BinOp::Bit(BitOp::Shl) | BinOp::Bit(BitOp::Shr) | BinOp::Bit(BitOp::UShr) => 7,
             BinOp::Comp(CompOp::LessThan)
             | BinOp::Comp(CompOp::LessThanOrEqual)
             | BinOp::Comp(CompOp::GreaterThan)
-            | BinOp::Comp(CompOp::GreaterThanOrEqual) => 8,
+            | BinOp::Comp(CompOp::GreaterThanOrEqual)
+            | BinOp::Comp(CompOp::InstanceOf) => 8,
             BinOp::Comp(CompOp::Equal)
             | BinOp::Comp(CompOp::NotEqual)
             | BinOp::Comp(CompOp::StrictEqual)
@@ -253,6 +266,8 @@ pub enum AssignOp {
     Shl,
     /// `a >>= b` - Right shift assign
     Shr,
+    /// `a >>>= b` - Zero-fill right shift assign
+    UShr,
 }
 
 impl Display for AssignOp {
@@ -271,6 +286,7 @@ impl Display for AssignOp {
                 AssignOp::Xor => "^=",
                 AssignOp::Shl => "<<=",
                 AssignOp::Shr => ">>=",
+                AssignOp::UShr => ">>>=",
             }
         )
     }
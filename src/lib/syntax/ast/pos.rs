@@ -1,3 +1,5 @@
+use gc::{unsafe_empty_trace, Finalize, Trace};
+
 #[derive(Clone, Copy, PartialEq, Debug)]
 /// A position in the Javascript source code
 /// Stores both the column number and the line number
@@ -27,3 +29,9 @@ impl Position {
         }
     }
 }
+
+impl Finalize for Position {}
+
+unsafe impl Trace for Position {
+    unsafe_empty_trace!();
+}
@@ -0,0 +1,135 @@
+//! Interned identifier names.
+//!
+//! Identifiers are interned when they are parsed, so every use of a name in a script shares one
+//! allocation, and looking up a variable compares pointers and precomputed hashes rather than
+//! hashing the whole name. Interners belong to the parser or realm which made them, so the names
+//! are freed along with their last user.
+
+use gc::{unsafe_empty_trace, Finalize, Trace};
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    fmt::{self, Debug, Display},
+    hash::{Hash, Hasher},
+    rc::Rc,
+};
+
+/// An identifier name with its hash
+struct SymData {
+    hash: u64,
+    name: Box<str>,
+}
+
+/// A handle to an identifier name. Handles from the same interner are equal exactly when they
+/// point to the same name, while handles from different interners fall back to comparing names.
+#[derive(Clone)]
+pub struct Sym(Rc<SymData>);
+
+impl Sym {
+    /// Make a handle for `name` which is not shared with the handles of an interner
+    pub fn new(name: &str) -> Self {
+        let mut hasher = DefaultHasher::new();
+        name.hash(&mut hasher);
+        Sym(Rc::new(SymData {
+            hash: hasher.finish(),
+            name: name.into(),
+        }))
+    }
+
+    /// The name this handle stands for
+    pub fn as_str(&self) -> &str {
+        &self.0.name
+    }
+}
+
+impl PartialEq for Sym {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+            || (self.0.hash == other.0.hash && self.0.name == other.0.name)
+    }
+}
+
+impl Eq for Sym {}
+
+impl Hash for Sym {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.0.hash);
+    }
+}
+
+impl From<&Sym> for Sym {
+    fn from(sym: &Sym) -> Self {
+        sym.clone()
+    }
+}
+
+impl From<&str> for Sym {
+    fn from(name: &str) -> Self {
+        Sym::new(name)
+    }
+}
+
+impl From<&String> for Sym {
+    fn from(name: &String) -> Self {
+        Sym::new(name)
+    }
+}
+
+impl From<String> for Sym {
+    fn from(name: String) -> Self {
+        Sym::new(&name)
+    }
+}
+
+impl Display for Sym {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Debug for Sym {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.as_str())
+    }
+}
+
+impl Finalize for Sym {}
+
+unsafe impl Trace for Sym {
+    unsafe_empty_trace!();
+}
+
+/// Hands out one shared handle for each name
+#[derive(Debug, Default)]
+pub struct Interner {
+    symbols: HashMap<Box<str>, Sym>,
+}
+
+impl Interner {
+    /// Get the handle for `name`, interning it if this is the first time it has been seen
+    pub fn intern(&mut self, name: &str) -> Sym {
+        if let Some(sym) = self.symbols.get(name) {
+            return sym.clone();
+        }
+        let sym = Sym::new(name);
+        self.symbols.insert(name.into(), sym.clone());
+        sym
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interning() {
+        let mut interner = Interner::default();
+        let a = interner.intern("interned_a");
+        let b = interner.intern("interned_b");
+        assert!(Rc::ptr_eq(&a.0, &interner.intern("interned_a").0));
+        assert_eq!(a, Sym::from("interned_a"));
+        assert_eq!(b, Sym::from(String::from("interned_b")));
+        assert_ne!(a, b);
+        assert_eq!(a.as_str(), "interned_a");
+        assert_eq!(b.to_string(), "interned_b");
+    }
+}
@@ -58,6 +58,14 @@ pub enum TokenData {
     Punctuator(Punctuator),
     /// A string literal
     StringLiteral(String),
+    /// A template literal without substitutions, like `` `text` ``
+    NoSubstitutionTemplate(String),
+    /// The text of a template literal up to its first substitution, like `` `text${ ``
+    TemplateHead(String),
+    /// The text of a template literal between two substitutions, like `` }text${ ``
+    TemplateMiddle(String),
+    /// The text of a template literal after its last substitution, like `` }text` ``
+    TemplateTail(String),
     /// A regular expression, consisting of body and flags
     RegularExpressionLiteral(String, String),
     /// A comment
@@ -75,6 +83,10 @@ impl Display for TokenData {
             TokenData::NumericLiteral(ref num) => write!(f, "{}", num),
             TokenData::Punctuator(ref punc) => write!(f, "{}", punc),
             TokenData::StringLiteral(ref lit) => write!(f, "{}", lit),
+            TokenData::NoSubstitutionTemplate(ref text) => write!(f, "`{}`", text),
+            TokenData::TemplateHead(ref text) => write!(f, "`{}${{", text),
+            TokenData::TemplateMiddle(ref text) => write!(f, "}}{}${{", text),
+            TokenData::TemplateTail(ref text) => write!(f, "}}{}`", text),
             TokenData::RegularExpressionLiteral(ref body, ref flags) => {
                 write!(f, "/{}/{}", body, flags)
             }
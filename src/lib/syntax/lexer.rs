@@ -3,6 +3,7 @@
 //! The Lexer splits its input source code into a sequence of input elements called tokens, represented by the [Token](../ast/token/struct.Token.html) structure.
 //! It also removes whitespace and comments and attaches them to the next token.
 use crate::syntax::ast::{
+    keyword::Keyword,
     punc::Punctuator,
     token::{Token, TokenData},
 };
@@ -107,6 +108,8 @@ pub struct Lexer<'a> {
     column_number: u64,
     // The full string
     buffer: Peekable<Chars<'a>>,
+    // The number of braces opened in each template substitution being lexed, innermost last
+    template_braces: Vec<u32>,
 }
 
 impl<'a> Lexer<'a> {
@@ -115,7 +118,7 @@ impl<'a> Lexer<'a> {
     /// # Arguments
     ///
     /// * `buffer` - A string slice that holds the source code.
-    /// The buffer needs to have a lifetime as long as the Lexer instance itself
+    ///   The buffer needs to have a lifetime as long as the Lexer instance itself
     ///
     /// # Example
     ///
@@ -129,6 +132,7 @@ impl<'a> Lexer<'a> {
             line_number: 1,
             column_number: 0,
             buffer: buffer.chars().peekable(),
+            template_braces: Vec::new(),
         }
     }
     /// Push tokens onto the token queue
@@ -142,6 +146,32 @@ impl<'a> Lexer<'a> {
         self.push_token(TokenData::Punctuator(punc));
     }
 
+    /// Whether the last token ends an operand, so a `/` after it divides rather than starting a
+    /// regular expression literal
+    fn follows_operand(&self) -> bool {
+        let last = self
+            .tokens
+            .iter()
+            .rev()
+            .find(|tk| !matches!(tk.data, TokenData::Comment(_)));
+        matches!(
+            last.map(|tk| &tk.data),
+            Some(TokenData::Identifier(_))
+                | Some(TokenData::NumericLiteral(_))
+                | Some(TokenData::StringLiteral(_))
+                | Some(TokenData::BooleanLiteral(_))
+                | Some(TokenData::NullLiteral)
+                | Some(TokenData::RegularExpressionLiteral(_, _))
+                | Some(TokenData::NoSubstitutionTemplate(_))
+                | Some(TokenData::TemplateTail(_))
+                | Some(TokenData::Keyword(Keyword::This))
+                | Some(TokenData::Punctuator(Punctuator::CloseParen))
+                | Some(TokenData::Punctuator(Punctuator::CloseBracket))
+                | Some(TokenData::Punctuator(Punctuator::Inc))
+                | Some(TokenData::Punctuator(Punctuator::Dec))
+        )
+    }
+
     /// next fetches the next token and return it, or a LexerError if there are no more.
     fn next(&mut self) -> Result<char, LexerError> {
         match self.buffer.next() {
@@ -196,6 +226,230 @@ impl<'a> Lexer<'a> {
         result
     }
 
+    /// Read the character an escape sequence in a string or template literal stands for.
+    /// The backslash and the `escape` character after it have already been consumed.
+    fn read_escape(&mut self, escape: char) -> Result<char, LexerError> {
+        Ok(match escape {
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'b' => '\x08',
+            'f' => '\x0c',
+            '0' => '\0',
+            'x' => {
+                let mut nums = String::with_capacity(2);
+                for _ in 0_u8..2 {
+                    nums.push(self.next()?);
+                }
+                self.column_number += 2;
+                let as_num = u64::from_str_radix(&nums, 16).unwrap_or_default();
+                match from_u32(as_num as u32) {
+                    Some(v) => v,
+                    None => panic!(
+                        "{}:{}: {} is not a valid unicode scalar value",
+                        self.line_number, self.column_number, as_num
+                    ),
+                }
+            }
+            'u' => {
+                // There are 2 types of codepoints. Surragate codepoints and unicode codepoints.
+                // UTF-16 could be surrogate codepoints, "\uXXXX\uXXXX" which make up a single unicode codepoint.
+                // We will need to loop to make sure we catch all UTF-16 codepoints
+                // Example Test: https://github.com/tc39/test262/blob/ee3715ee56744ccc8aeb22a921f442e98090b3c1/implementation-contributed/v8/mjsunit/es6/unicode-escapes.js#L39-L44
+
+                // Support \u{X..X} (Unicode Codepoint)
+                if self.next_is('{') {
+                    let s = self.take_char_while(char::is_alphanumeric).unwrap();
+
+                    // We know this is a single unicode codepoint, convert to u32
+                    let as_num = u32::from_str_radix(&s, 16).unwrap_or_default();
+                    let c = from_u32(as_num).expect("Invalid Unicode escape sequence");
+
+                    self.next()?; // '}'
+                    self.column_number += s.len() as u64 + 3;
+                    c
+                } else {
+                    let mut codepoints: Vec<u16> = vec![];
+                    loop {
+                        // Collect each character after \u e.g \uD83D will give "D83D"
+                        let s = self.take_char_while(char::is_alphanumeric).unwrap();
+
+                        // Convert to u16
+                        let as_num = u16::from_str_radix(&s, 16).unwrap_or_default();
+
+                        codepoints.push(as_num);
+                        self.column_number += s.len() as u64 + 2;
+
+                        // Check for another UTF-16 codepoint
+                        if self.next_is('\\') && self.next_is('u') {
+                            continue;
+                        }
+                        break;
+                    }
+
+                    // codepoints length should either be 1 (unicode codepoint) or 2 (surrogate codepoint).
+                    // Rust's decode_utf16 will deal with it regardless
+                    decode_utf16(codepoints.iter().cloned())
+                        .next()
+                        .unwrap()
+                        .unwrap()
+                }
+            }
+            '\'' | '"' | '\\' | '`' | '$' | '{' | '}' => escape,
+            ch => panic!(
+                "{}:{}: Invalid escape `{}`",
+                self.line_number, self.column_number, ch
+            ),
+        })
+    }
+
+    /// Read the text of a template literal up to the next substitution or the closing backtick.
+    /// `head` is whether the text follows the opening backtick rather than a substitution.
+    /// <https://tc39.es/ecma262/#sec-template-literal-lexical-components>
+    fn read_template(&mut self, head: bool) -> Result<(), LexerError> {
+        let mut buf = String::new();
+        loop {
+            let ch = match self.buffer.next() {
+                Some(ch) => ch,
+                None => return Err(LexerError::new("Unterminated template literal")),
+            };
+            self.column_number += 1;
+            match ch {
+                '`' => {
+                    self.push_token(if head {
+                        TokenData::NoSubstitutionTemplate(buf)
+                    } else {
+                        TokenData::TemplateTail(buf)
+                    });
+                    return Ok(());
+                }
+                '$' if self.next_is('{') => {
+                    self.template_braces.push(0);
+                    self.push_token(if head {
+                        TokenData::TemplateHead(buf)
+                    } else {
+                        TokenData::TemplateMiddle(buf)
+                    });
+                    return Ok(());
+                }
+                '\\' => {
+                    let escape = self.next()?;
+                    if escape != '\n' {
+                        buf.push(self.read_escape(escape)?);
+                    }
+                }
+                // Line terminators are kept, with CRLF normalised to LF
+                '\r' | '\n' => {
+                    if ch == '\r' {
+                        self.next_is('\n');
+                    }
+                    buf.push('\n');
+                    self.line_number += 1;
+                    self.column_number = 0;
+                }
+                _ => buf.push(ch),
+            }
+        }
+    }
+
+    /// Read the digits of the given radix which follow, which may be separated by single underscores
+    fn read_digits(&mut self, buf: &mut String, radix: u32) -> Result<(), LexerError> {
+        let mut after_digit = buf.chars().last().is_some_and(|ch| ch.is_digit(radix));
+        while let Some(ch) = self.preview_next() {
+            if ch.is_digit(radix) {
+                buf.push(self.next()?);
+                after_digit = true;
+            } else if ch == '_' {
+                self.next()?;
+                self.column_number += 1;
+                if !after_digit || !self.preview_next().is_some_and(|ch| ch.is_digit(radix)) {
+                    return Err(LexerError::new(
+                        "Numeric separators are only allowed between digits",
+                    ));
+                }
+                after_digit = false;
+            } else {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Read a numeric literal starting with `first`, which is a digit or a `.` followed by a digit
+    /// <https://tc39.es/ecma262/#sec-literals-numeric-literals>
+    fn read_number(&mut self, first: char) -> Result<f64, LexerError> {
+        let mut buf = first.to_string();
+        let radix = match self.preview_next() {
+            Some('x') | Some('X') if first == '0' => Some(16),
+            Some('o') | Some('O') if first == '0' => Some(8),
+            Some('b') | Some('B') if first == '0' => Some(2),
+            _ => None,
+        };
+        let num = if let Some(radix) = radix {
+            // 0x, 0o and 0b literals are integers in the given radix
+            buf.push(self.next()?);
+            let mut digits = String::new();
+            self.read_digits(&mut digits, radix)?;
+            if digits.is_empty() {
+                return Err(LexerError::new(&format!(
+                    "Expected digits after the {} prefix of a numeric literal",
+                    buf
+                )));
+            }
+            buf.push_str(&digits);
+            digits.chars().fold(0.0, |num, digit| {
+                num * f64::from(radix) + f64::from(digit.to_digit(radix).unwrap())
+            })
+        } else if first == '0' && self.preview_next().is_some_and(|ch| ch.is_ascii_digit()) {
+            // Legacy octal literals like 0777, which are decimal if they contain an 8 or a 9
+            let digits = self.take_char_while(|ch| ch.is_ascii_digit())?;
+            buf.push_str(&digits);
+            if digits.chars().all(|ch| ch.is_digit(8)) {
+                digits.chars().fold(0.0, |num, digit| {
+                    num * 8.0 + f64::from(digit.to_digit(8).unwrap())
+                })
+            } else {
+                f64::from_str(&digits).unwrap()
+            }
+        } else {
+            if first == '.' {
+                self.read_digits(&mut buf, 10)?;
+            } else {
+                self.read_digits(&mut buf, 10)?;
+                if self.next_is('.') {
+                    buf.push('.');
+                    self.read_digits(&mut buf, 10)?;
+                }
+            }
+            if let Some('e') | Some('E') = self.preview_next() {
+                buf.push(self.next()?);
+                if let Some('+') | Some('-') = self.preview_next() {
+                    buf.push(self.next()?);
+                }
+                let mut exponent = String::new();
+                self.read_digits(&mut exponent, 10)?;
+                if exponent.is_empty() {
+                    return Err(LexerError::new(
+                        "Expected digits in the exponent of a numeric literal",
+                    ));
+                }
+                buf.push_str(&exponent);
+            }
+            f64::from_str(&buf).unwrap()
+        };
+        // A literal can't run straight into an identifier or another number, as in 3in or 1.2.3
+        match self.preview_next() {
+            Some(ch) if ch.is_alphanumeric() || ch == '$' || ch == '_' => Err(LexerError::new(
+                &format!("Unexpected '{}' after the numeric literal {}", ch, buf),
+            )),
+            _ => {
+                // The first character has already been counted
+                self.column_number += (buf.len() - 1) as u64;
+                Ok(num)
+            }
+        }
+    }
+
     pub fn lex(&mut self) -> Result<(), LexerError> {
         loop {
             // Check if we've reached the end
@@ -218,93 +472,7 @@ impl<'a> Lexer<'a> {
                             '\\' => {
                                 let escape = self.next()?;
                                 if escape != '\n' {
-                                    let escaped_ch = match escape {
-                                        'n' => '\n',
-                                        'r' => '\r',
-                                        't' => '\t',
-                                        'b' => '\x08',
-                                        'f' => '\x0c',
-                                        '0' => '\0',
-                                        'x' => {
-                                            let mut nums = String::with_capacity(2);
-                                            for _ in 0_u8..2 {
-                                                nums.push(self.next()?);
-                                            }
-                                            self.column_number += 2;
-                                            let as_num = match u64::from_str_radix(&nums, 16) {
-                                                Ok(v) => v,
-                                                Err(_) => 0,
-                                            };
-                                            match from_u32(as_num as u32) {
-                                                Some(v) => v,
-                                                None => panic!(
-                                                    "{}:{}: {} is not a valid unicode scalar value",
-                                                    self.line_number, self.column_number, as_num
-                                                ),
-                                            }
-                                        }
-                                        'u' => {
-                                            // There are 2 types of codepoints. Surragate codepoints and unicode codepoints.
-                                            // UTF-16 could be surrogate codepoints, "\uXXXX\uXXXX" which make up a single unicode codepoint.
-                                            // We will need to loop to make sure we catch all UTF-16 codepoints
-                                            // Example Test: https://github.com/tc39/test262/blob/ee3715ee56744ccc8aeb22a921f442e98090b3c1/implementation-contributed/v8/mjsunit/es6/unicode-escapes.js#L39-L44
-
-                                            // Support \u{X..X} (Unicode Codepoint)
-                                            if self.next_is('{') {
-                                                let s = self
-                                                    .take_char_while(char::is_alphanumeric)
-                                                    .unwrap();
-
-                                                // We know this is a single unicode codepoint, convert to u32
-                                                let as_num = match u32::from_str_radix(&s, 16) {
-                                                    Ok(v) => v,
-                                                    Err(_) => 0,
-                                                };
-                                                let c = from_u32(as_num)
-                                                    .expect("Invalid Unicode escape sequence");
-
-                                                self.next()?; // '}'
-                                                self.column_number += s.len() as u64 + 3;
-                                                c
-                                            } else {
-                                                let mut codepoints: Vec<u16> = vec![];
-                                                loop {
-                                                    // Collect each character after \u e.g \uD83D will give "D83D"
-                                                    let s = self
-                                                        .take_char_while(char::is_alphanumeric)
-                                                        .unwrap();
-
-                                                    // Convert to u16
-                                                    let as_num = match u16::from_str_radix(&s, 16) {
-                                                        Ok(v) => v,
-                                                        Err(_) => 0,
-                                                    };
-
-                                                    codepoints.push(as_num);
-                                                    self.column_number += s.len() as u64 + 2;
-
-                                                    // Check for another UTF-16 codepoint
-                                                    if self.next_is('\\') && self.next_is('u') {
-                                                        continue;
-                                                    }
-                                                    break;
-                                                }
-
-                                                // codepoints length should either be 1 (unicode codepoint) or 2 (surrogate codepoint).
-                                                // Rust's decode_utf16 will deal with it regardless
-                                                decode_utf16(codepoints.iter().cloned())
-                                                    .next()
-                                                    .unwrap()
-                                                    .unwrap()
-                                            }
-                                        }
-                                        '\'' | '"' | '\\' => escape,
-                                        ch => panic!(
-                                            "{}:{}: Invalid escape `{}`",
-                                            self.line_number, self.column_number, ch
-                                        ),
-                                    };
-                                    buf.push(escaped_ch);
+                                    buf.push(self.read_escape(escape)?);
                                 }
                             }
                             next_ch => buf.push(next_ch),
@@ -317,88 +485,14 @@ impl<'a> Lexer<'a> {
                     // to compensate for the incrementing at the top
                     self.column_number += str_length + 1;
                 }
-                '0' => {
-                    let mut buf = String::new();
-                    let num = if self.next_is('x') {
-                        while let Some(ch) = self.preview_next() {
-                            if ch.is_digit(16) {
-                                buf.push(self.next()?);
-                            } else {
-                                break;
-                            }
-                        }
-                        u64::from_str_radix(&buf, 16).unwrap()
-                    } else if self.next_is('b') {
-                        while let Some(ch) = self.preview_next() {
-                            if ch.is_digit(2) {
-                                buf.push(self.next()?);
-                            } else {
-                                break;
-                            }
-                        }
-                        u64::from_str_radix(&buf, 2).unwrap()
-                    } else {
-                        let mut gone_decimal = false;
-                        loop {
-                            let next_ch = self.preview_next().unwrap_or('_');
-                            match next_ch {
-                                next_ch if next_ch.is_digit(8) => {
-                                    buf.push(next_ch);
-                                    self.next()?;
-                                }
-                                'O' | 'o' => {
-                                    self.next()?;
-                                }
-                                '8' | '9' | '.' => {
-                                    gone_decimal = true;
-                                    buf.push(next_ch);
-                                    self.next()?;
-                                }
-                                _ => break,
-                            }
-                        }
-                        if gone_decimal {
-                            u64::from_str(&buf).unwrap()
-                        } else if buf.is_empty() {
-                            0
-                        } else {
-                            u64::from_str_radix(&buf, 8).unwrap()
-                        }
-                    };
-                    self.push_token(TokenData::NumericLiteral(num as f64))
-                }
-                _ if ch.is_digit(10) => {
-                    let mut buf = ch.to_string();
-                    'digitloop: while let Some(ch) = self.preview_next() {
-                        match ch {
-                            '.' => loop {
-                                buf.push(self.next()?);
-
-                                let ch = match self.preview_next() {
-                                    Some(ch) => ch,
-                                    None => break,
-                                };
-
-                                if !ch.is_digit(10) {
-                                    break 'digitloop;
-                                }
-                            },
-                            'e' | '+' | '-' => {
-                                buf.push(self.next()?);
-                            }
-                            _ if ch.is_digit(10) => {
-                                buf.push(self.next()?);
-                            }
-                            _ => break,
-                        }
-                    }
-                    // TODO make this a bit more safe -------------------------------VVVV
-                    self.push_token(TokenData::NumericLiteral(f64::from_str(&buf).unwrap()))
+                _ if ch.is_ascii_digit() => {
+                    let num = self.read_number(ch)?;
+                    self.push_token(TokenData::NumericLiteral(num))
                 }
                 _ if ch.is_alphabetic() || ch == '$' || ch == '_' => {
                     let mut buf = ch.to_string();
                     while let Some(ch) = self.preview_next() {
-                        if ch.is_alphabetic() || ch.is_digit(10) || ch == '_' {
+                        if ch.is_alphabetic() || ch.is_ascii_digit() || ch == '_' {
                             buf.push(self.next()?);
                         } else {
                             break;
@@ -423,6 +517,10 @@ impl<'a> Lexer<'a> {
                 }
                 ';' => self.push_punc(Punctuator::Semicolon),
                 ':' => self.push_punc(Punctuator::Colon),
+                '.' if self.preview_next().is_some_and(|ch| ch.is_ascii_digit()) => {
+                    let num = self.read_number(ch)?;
+                    self.push_token(TokenData::NumericLiteral(num))
+                }
                 '.' => {
                     // . or ...
                     if self.next_is('.') {
@@ -438,8 +536,25 @@ impl<'a> Lexer<'a> {
                 '(' => self.push_punc(Punctuator::OpenParen),
                 ')' => self.push_punc(Punctuator::CloseParen),
                 ',' => self.push_punc(Punctuator::Comma),
-                '{' => self.push_punc(Punctuator::OpenBlock),
-                '}' => self.push_punc(Punctuator::CloseBlock),
+                '`' => self.read_template(true)?,
+                '{' => {
+                    if let Some(braces) = self.template_braces.last_mut() {
+                        *braces += 1;
+                    }
+                    self.push_punc(Punctuator::OpenBlock)
+                }
+                '}' => match self.template_braces.last_mut() {
+                    // The brace closes a template substitution
+                    Some(0) => {
+                        self.template_braces.pop();
+                        self.read_template(false)?
+                    }
+                    Some(braces) => {
+                        *braces -= 1;
+                        self.push_punc(Punctuator::CloseBlock)
+                    }
+                    None => self.push_punc(Punctuator::CloseBlock),
+                },
                 '[' => self.push_punc(Punctuator::OpenBracket),
                 ']' => self.push_punc(Punctuator::CloseBracket),
                 '?' => self.push_punc(Punctuator::Question),
@@ -477,26 +592,28 @@ impl<'a> Lexer<'a> {
                                 // first, try to parse a regex literal
                                 let mut body = String::new();
                                 let mut regex = false;
-                                loop {
-                                    match self.buffer.next() {
-                                        // end of body
-                                        Some('/') => {
-                                            regex = true;
-                                            break;
-                                        }
-                                        // newline/eof not allowed in regex literal
-                                        Some('\n') | Some('\r') | Some('\u{2028}')
-                                        | Some('\u{2029}') | None => break,
-                                        // escape sequence
-                                        Some('\\') => {
-                                            body.push('\\');
-                                            match self.next()? {
-                                                // newline not allowed in regex literal
-                                                '\n' | '\r' | '\u{2028}' | '\u{2029}' => break,
-                                                ch => body.push(ch),
+                                if !self.follows_operand() {
+                                    loop {
+                                        match self.buffer.next() {
+                                            // end of body
+                                            Some('/') => {
+                                                regex = true;
+                                                break;
                                             }
+                                            // newline/eof not allowed in regex literal
+                                            Some('\n') | Some('\r') | Some('\u{2028}')
+                                            | Some('\u{2029}') | None => break,
+                                            // escape sequence
+                                            Some('\\') => {
+                                                body.push('\\');
+                                                match self.next()? {
+                                                    // newline not allowed in regex literal
+                                                    '\n' | '\r' | '\u{2028}' | '\u{2029}' => break,
+                                                    ch => body.push(ch),
+                                                }
+                                            }
+                                            Some(ch) => body.push(ch),
                                         }
-                                        Some(ch) => body.push(ch),
                                     }
                                 }
                                 if regex {
@@ -585,7 +702,6 @@ impl<'a> Lexer<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::syntax::ast::keyword::Keyword;
 
     #[test]
     fn check_single_line_comment() {
@@ -948,6 +1064,41 @@ mod tests {
         assert_eq!(lexer.tokens[11].data, TokenData::NumericLiteral(999.0));
     }
 
+    #[test]
+    fn numbers_with_radix_exponent_and_separators() {
+        let mut lexer =
+            Lexer::new("0xFF 0XaB 0b1010 0B11 0o777 1e3 1.5e-2 2E+2 .5 1_000_000 0xff_ff 1.2_5");
+        lexer.lex().expect("failed to lex");
+        let expected = [
+            255.0,
+            171.0,
+            10.0,
+            3.0,
+            511.0,
+            1000.0,
+            0.015,
+            200.0,
+            0.5,
+            1_000_000.0,
+            65535.0,
+            1.25,
+        ];
+        for (token, num) in lexer.tokens.iter().zip(expected.iter()) {
+            assert_eq!(token.data, TokenData::NumericLiteral(*num));
+        }
+        assert_eq!(lexer.tokens.len(), expected.len());
+    }
+
+    #[test]
+    fn malformed_numbers() {
+        for source in &[
+            "0x", "0b2", "0o", "1e", "1e+", "1__0", "1_", "0x_1", "1._5", "3in", "0b1a",
+        ] {
+            let mut lexer = Lexer::new(source);
+            assert!(lexer.lex().is_err(), "{} should not lex", source);
+        }
+    }
+
     #[test]
     fn test_single_number_without_semicolon() {
         let mut lexer = Lexer::new("1");
@@ -981,4 +1132,66 @@ mod tests {
             TokenData::RegularExpressionLiteral("\\/[^\\/]*\\/*".to_string(), "gmi".to_string())
         );
     }
+
+    #[test]
+    fn test_division_after_operand() {
+        let mut lexer = Lexer::new("a / b / c; (1) / 2 /g; x = /b/");
+        lexer.lex().expect("failed to lex");
+        assert_eq!(lexer.tokens[1].data, TokenData::Punctuator(Punctuator::Div));
+        assert_eq!(lexer.tokens[3].data, TokenData::Punctuator(Punctuator::Div));
+        assert_eq!(lexer.tokens[9].data, TokenData::Punctuator(Punctuator::Div));
+        assert_eq!(
+            lexer.tokens[11].data,
+            TokenData::Punctuator(Punctuator::Div)
+        );
+        assert_eq!(
+            lexer.tokens[16].data,
+            TokenData::RegularExpressionLiteral("b".to_string(), "".to_string())
+        );
+    }
+
+    #[test]
+    fn test_template_literal() {
+        let mut lexer = Lexer::new("`a\\`b`");
+        lexer.lex().expect("failed to lex");
+        assert_eq!(
+            lexer.tokens[0].data,
+            TokenData::NoSubstitutionTemplate("a`b".to_string())
+        );
+
+        let mut lexer = Lexer::new("`x${ {a: 1}.a }y${`z${b}`}`");
+        lexer.lex().expect("failed to lex");
+        assert_eq!(
+            lexer.tokens[0].data,
+            TokenData::TemplateHead("x".to_string())
+        );
+        assert_eq!(
+            lexer.tokens[1].data,
+            TokenData::Punctuator(Punctuator::OpenBlock)
+        );
+        assert_eq!(
+            lexer.tokens[5].data,
+            TokenData::Punctuator(Punctuator::CloseBlock)
+        );
+        assert_eq!(
+            lexer.tokens[8].data,
+            TokenData::TemplateMiddle("y".to_string())
+        );
+        assert_eq!(
+            lexer.tokens[9].data,
+            TokenData::TemplateHead("z".to_string())
+        );
+        assert_eq!(
+            lexer.tokens[10].data,
+            TokenData::Identifier("b".to_string())
+        );
+        assert_eq!(
+            lexer.tokens[11].data,
+            TokenData::TemplateTail("".to_string())
+        );
+        assert_eq!(
+            lexer.tokens[12].data,
+            TokenData::TemplateTail("".to_string())
+        );
+    }
 }
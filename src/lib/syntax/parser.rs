@@ -1,19 +1,25 @@
 use crate::syntax::ast::constant::Const;
-use crate::syntax::ast::expr::{Expr, ExprDef};
+use crate::syntax::ast::expr::{
+    DeclarationKind, Expr, ExprDef, MethodDefinitionKind, PropertyDefinition, PropertyName,
+};
 use crate::syntax::ast::keyword::Keyword;
 use crate::syntax::ast::op::{AssignOp, BinOp, BitOp, CompOp, LogOp, NumOp, Operator, UnaryOp};
 use crate::syntax::ast::punc::Punctuator;
+use crate::syntax::ast::sym::Interner;
 use crate::syntax::ast::token::{Token, TokenData};
-use std::collections::btree_map::BTreeMap;
+use crate::syntax::lexer::LexerError;
+use gc::Gc;
+use std::mem;
 
 macro_rules! mk (
     ($this:expr, $def:expr) => {
         {
-            Expr::new($def)
+            let pos = $this.tokens.get($this.pos.wrapping_sub(1)).map(|tk| tk.pos);
+            Expr::new_at($def, pos)
         }
     };
     ($this:expr, $def:expr, $first:expr) => {
-        Expr::new($def)
+        Expr::new_at($def, Some($first.pos))
     };
 );
 
@@ -23,11 +29,19 @@ pub enum ParseError {
     /// When it expected a certain kind of token, but got another as part of something
     Expected(Vec<TokenData>, Token, &'static str),
     /// When it expected a certain expression, but got another
-    ExpectedExpr(&'static str, Expr),
+    ExpectedExpr(&'static str, Box<Expr>),
     /// When it didn't expect this keyword
     UnexpectedKeyword(Keyword),
     /// When there is an abrupt end to the parsing
     AbruptEnd,
+    /// When the source code couldn't be split into tokens
+    Lexer(LexerError),
+}
+
+impl From<LexerError> for ParseError {
+    fn from(err: LexerError) -> Self {
+        ParseError::Lexer(err)
+    }
 }
 
 pub type ParseResult = Result<Expr, ParseError>;
@@ -38,19 +52,25 @@ pub struct Parser {
     tokens: Vec<Token>,
     /// The current position within the tokens
     pos: usize,
+    /// The identifier names of the script
+    interner: Interner,
 }
 
 impl Parser {
     /// Create a new parser, using `tokens` as input
     pub fn new(tokens: Vec<Token>) -> Self {
-        Self { tokens, pos: 0 }
+        Self {
+            tokens,
+            pos: 0,
+            interner: Interner::default(),
+        }
     }
 
     /// Parse all expressions in the token array
     pub fn parse_all(&mut self) -> ParseResult {
         let mut exprs = Vec::new();
         while self.pos < self.tokens.len() {
-            let result = self.parse()?;
+            let result = self.parse_statement()?;
             exprs.push(result);
         }
 
@@ -59,6 +79,32 @@ impl Parser {
         Ok(Expr::new(ExprDef::Block(exprs)))
     }
 
+    /// Returns true if a line terminator separates the token at `pos` from the one before it
+    fn newline_before(&self, pos: usize) -> bool {
+        match (self.tokens.get(pos.wrapping_sub(1)), self.tokens.get(pos)) {
+            (Some(prev), Some(next)) => next.pos.line_number > prev.pos.line_number,
+            _ => false,
+        }
+    }
+
+    /// Returns true if a statement ends before the token at `pos`, either explicitly or by
+    /// automatic semicolon insertion
+    /// <https://tc39.es/ecma262/#sec-rules-of-automatic-semicolon-insertion>
+    fn at_statement_end(&self, pos: usize) -> bool {
+        match self.tokens.get(pos) {
+            Some(Token {
+                data: TokenData::Punctuator(Punctuator::Semicolon),
+                ..
+            })
+            | Some(Token {
+                data: TokenData::Punctuator(Punctuator::CloseBlock),
+                ..
+            })
+            | None => true,
+            Some(_) => self.newline_before(pos),
+        }
+    }
+
     fn get_token(&self, pos: usize) -> Result<Token, ParseError> {
         if pos < self.tokens.len() {
             Ok(self.tokens.get(pos).expect("failed getting token").clone())
@@ -70,9 +116,83 @@ impl Parser {
     fn parse_struct(&mut self, keyword: Keyword) -> ParseResult {
         match keyword {
             Keyword::Throw => {
+                // A line terminator after `throw` cannot end the statement, as it needs a value
+                if self.newline_before(self.pos) {
+                    return Err(ParseError::Expected(
+                        Vec::new(),
+                        self.get_token(self.pos)?,
+                        "throw statement",
+                    ));
+                }
                 let thrown = self.parse()?;
                 Ok(mk!(self, ExprDef::Throw(Box::new(thrown))))
             }
+            // Labels are not supported, so the statement ends straight after the keyword
+            Keyword::Break | Keyword::Continue => {
+                if !self.at_statement_end(self.pos) {
+                    return Err(ParseError::Expected(
+                        vec![TokenData::Punctuator(Punctuator::Semicolon)],
+                        self.get_token(self.pos)?,
+                        "break or continue statement",
+                    ));
+                }
+                if self.next_is(TokenData::Punctuator(Punctuator::Semicolon)) {
+                    self.pos += 1;
+                }
+                Ok(mk!(
+                    self,
+                    match keyword {
+                        Keyword::Break => ExprDef::Break,
+                        _ => ExprDef::Continue,
+                    }
+                ))
+            }
+            Keyword::Try => {
+                let block = self.parse_block("try block")?;
+                let mut param = None;
+                let mut catch = None;
+                if self.next_is(TokenData::Keyword(Keyword::Catch)) {
+                    self.pos += 1;
+                    // The binding of the thrown value is optional
+                    if self.next_is(TokenData::Punctuator(Punctuator::OpenParen)) {
+                        self.pos += 1;
+                        let tk = self.get_token(self.pos)?;
+                        match tk.data {
+                            TokenData::Identifier(ref name) => param = Some(name.clone()),
+                            _ => {
+                                return Err(ParseError::Expected(
+                                    vec![TokenData::Identifier("identifier".to_string())],
+                                    tk,
+                                    "catch parameter",
+                                ))
+                            }
+                        }
+                        self.pos += 1;
+                        self.expect_punc(Punctuator::CloseParen, "catch parameter")?;
+                    }
+                    catch = Some(Box::new(self.parse_block("catch block")?));
+                }
+                let finally = if self.next_is(TokenData::Keyword(Keyword::Finally)) {
+                    self.pos += 1;
+                    Some(Box::new(self.parse_block("finally block")?))
+                } else {
+                    None
+                };
+                if catch.is_none() && finally.is_none() {
+                    return Err(ParseError::Expected(
+                        vec![
+                            TokenData::Keyword(Keyword::Catch),
+                            TokenData::Keyword(Keyword::Finally),
+                        ],
+                        self.get_token(self.pos)?,
+                        "try statement",
+                    ));
+                }
+                Ok(mk!(
+                    self,
+                    ExprDef::Try(Box::new(block), param, catch, finally)
+                ))
+            }
             // vars, lets and consts are similar in parsing structure, we can group them together
             Keyword::Var | Keyword::Let => {
                 let mut vars = Vec::new();
@@ -180,16 +300,25 @@ impl Parser {
                 self,
                 ExprDef::Return(Some(Box::new(self.parse()?.clone())))
             )),
+            Keyword::New if self.next_is(TokenData::Punctuator(Punctuator::Dot)) => {
+                self.pos += 1;
+                self.expect(TokenData::Identifier("target".to_string()), "new.target")?;
+                Ok(mk!(self, ExprDef::NewTargetExpr))
+            }
             Keyword::New => {
-                let call = self.parse()?;
-                match call.def {
-                    ExprDef::Call(ref func, ref args) => {
-                        Ok(mk!(self, ExprDef::Construct(func.clone(), args.clone())))
-                    }
-                    _ => Err(ParseError::ExpectedExpr("constructor", call)),
-                }
+                // Operators after the arguments apply to the constructed object
+                let call = self.parse_unary_operand()?;
+                self.construct(call)
             }
-            Keyword::TypeOf => Ok(mk!(self, ExprDef::TypeOf(Box::new(self.parse()?)))),
+            Keyword::This => Ok(mk!(self, ExprDef::ThisExpr)),
+            Keyword::TypeOf => Ok(mk!(
+                self,
+                ExprDef::TypeOf(Box::new(self.parse_unary_operand()?))
+            )),
+            Keyword::Void => Ok(mk!(
+                self,
+                ExprDef::UnaryOp(UnaryOp::Void, Box::new(self.parse_unary_operand()?))
+            )),
             Keyword::If => {
                 self.expect_punc(Punctuator::OpenParen, "if block")?;
                 let cond = self.parse()?;
@@ -220,6 +349,60 @@ impl Parser {
                     ExprDef::WhileLoop(Box::new(cond), Box::new(expr))
                 ))
             }
+            // Only `for-in` loops are parsed so far
+            Keyword::For => {
+                self.expect_punc(Punctuator::OpenParen, "for-in loop")?;
+                let kind = match self.get_token(self.pos)?.data {
+                    TokenData::Keyword(Keyword::Var) => Some(DeclarationKind::Var),
+                    TokenData::Keyword(Keyword::Let) => Some(DeclarationKind::Let),
+                    TokenData::Keyword(Keyword::Const) => Some(DeclarationKind::Const),
+                    _ => None,
+                };
+                if kind.is_some() {
+                    self.pos += 1;
+                }
+                let tk = self.get_token(self.pos)?;
+                let name = match tk.data {
+                    TokenData::Identifier(ref name) => name.clone(),
+                    _ => {
+                        return Err(ParseError::Expected(
+                            vec![TokenData::Identifier("identifier".to_string())],
+                            tk,
+                            "for-in binding",
+                        ))
+                    }
+                };
+                self.pos += 1;
+                self.expect(TokenData::Keyword(Keyword::In), "for-in loop")?;
+                let object = self.parse()?;
+                self.expect_punc(Punctuator::CloseParen, "for-in loop")?;
+                let expr = self.parse()?;
+                Ok(mk!(
+                    self,
+                    ExprDef::ForInLoop(kind, name, Box::new(object), Box::new(expr))
+                ))
+            }
+            Keyword::With => {
+                self.expect_punc(Punctuator::OpenParen, "with object")?;
+                let object = self.parse()?;
+                self.expect_punc(Punctuator::CloseParen, "with object")?;
+                let expr = self.parse()?;
+                Ok(mk!(
+                    self,
+                    ExprDef::WithExpr(Box::new(object), Box::new(expr))
+                ))
+            }
+            Keyword::Do => {
+                let expr = self.parse()?;
+                self.expect(TokenData::Keyword(Keyword::While), "do-while loop")?;
+                self.expect_punc(Punctuator::OpenParen, "do-while condition")?;
+                let cond = self.parse()?;
+                self.expect_punc(Punctuator::CloseParen, "do-while condition")?;
+                Ok(mk!(
+                    self,
+                    ExprDef::DoWhileLoop(Box::new(expr), Box::new(cond))
+                ))
+            }
             Keyword::Switch => {
                 self.expect_punc(Punctuator::OpenParen, "switch value")?;
                 let value = self.parse();
@@ -240,7 +423,7 @@ impl Parser {
                                     TokenData::Keyword(Keyword::Case)
                                     | TokenData::Keyword(Keyword::Default)
                                     | TokenData::Punctuator(Punctuator::CloseBlock) => break,
-                                    _ => block.push(self.parse()?),
+                                    _ => block.push(self.parse_statement()?),
                                 }
                             }
                             cases.push((cond.unwrap(), block));
@@ -253,12 +436,16 @@ impl Parser {
                                     TokenData::Keyword(Keyword::Case)
                                     | TokenData::Keyword(Keyword::Default)
                                     | TokenData::Punctuator(Punctuator::CloseBlock) => break,
-                                    _ => block.push(self.parse()?),
+                                    _ => block.push(self.parse_statement()?),
                                 }
                             }
                             default = Some(mk!(self, ExprDef::Block(block)));
                         }
-                        TokenData::Punctuator(Punctuator::CloseBlock) => break,
+                        TokenData::Punctuator(Punctuator::CloseBlock) => {
+                            // Leave the closing brace to be checked below
+                            self.pos -= 1;
+                            break;
+                        }
                         _ => {
                             return Err(ParseError::Expected(
                                 vec![
@@ -275,14 +462,7 @@ impl Parser {
                 self.expect_punc(Punctuator::CloseBlock, "switch block")?;
                 Ok(mk!(
                     self,
-                    ExprDef::Switch(
-                        Box::new(value.unwrap()),
-                        cases,
-                        match default {
-                            Some(v) => Some(Box::new(v)),
-                            None => None,
-                        }
-                    )
+                    ExprDef::Switch(Box::new(value.unwrap()), cases, default.map(Box::new))
                 ))
             }
             Keyword::Function => {
@@ -302,40 +482,51 @@ impl Parser {
                         ))
                     }
                 };
-                // Now we have the function identifier we should have an open paren for arguments ( )
-                self.expect_punc(Punctuator::OpenParen, "function")?;
-                let mut args: Vec<String> = Vec::new();
-                let mut tk = self.get_token(self.pos)?;
-                while tk.data != TokenData::Punctuator(Punctuator::CloseParen) {
-                    match tk.data {
-                        TokenData::Identifier(ref id) => args.push(id.clone()),
-                        _ => {
-                            return Err(ParseError::Expected(
-                                vec![TokenData::Identifier("identifier".to_string())],
-                                tk.clone(),
-                                "function arguments",
-                            ))
-                        }
-                    }
-                    self.pos += 1;
-                    if self.get_token(self.pos)?.data == TokenData::Punctuator(Punctuator::Comma) {
-                        self.pos += 1;
-                    }
-                    tk = self.get_token(self.pos)?;
-                }
-                self.pos += 1;
-                let block = self.parse()?;
-                Ok(mk!(
-                    self,
-                    ExprDef::FunctionDecl(name, args, Box::new(block))
-                ))
+                self.parse_function(name)
             }
             _ => Err(ParseError::UnexpectedKeyword(keyword)),
         }
     }
 
+    /// Parse a statement of a script, block or switch case. A function declaration is a whole
+    /// statement, so nothing after it is taken as operating on the function.
+    fn parse_statement(&mut self) -> ParseResult {
+        if let (Ok(TokenData::Keyword(Keyword::Function)), Ok(TokenData::Identifier(_))) = (
+            self.get_token(self.pos).map(|tk| tk.data),
+            self.get_token(self.pos + 1).map(|tk| tk.data),
+        ) {
+            return self.parse_primary();
+        }
+        self.parse()
+    }
+
     /// Parse a single expression
     pub fn parse(&mut self) -> ParseResult {
+        let expr = self.parse_primary()?;
+        // A `return` ending its statement has nothing following it
+        if let ExprDef::Return(None) = expr.def {
+            return Ok(expr);
+        }
+        if self.pos >= self.tokens.len() {
+            Ok(expr)
+        } else {
+            self.parse_next(expr)
+        }
+    }
+
+    /// Parse the operand of a prefix operator. This takes in member accesses and calls, but
+    /// leaves any binary operator after them to apply to the result of the prefix operator.
+    fn parse_unary_operand(&mut self) -> ParseResult {
+        let expr = self.parse_primary()?;
+        if self.pos >= self.tokens.len() {
+            Ok(expr)
+        } else {
+            self.parse_next_ops(expr, true)
+        }
+    }
+
+    /// Parse an expression up to the operators which may follow it
+    fn parse_primary(&mut self) -> ParseResult {
         if self.pos > self.tokens.len() {
             return Err(ParseError::AbruptEnd);
         }
@@ -353,14 +544,27 @@ impl Parser {
             TokenData::NumericLiteral(num) => mk!(self, ExprDef::Const(Const::Num(num))),
             TokenData::NullLiteral => mk!(self, ExprDef::Const(Const::Null)),
             TokenData::StringLiteral(text) => mk!(self, ExprDef::Const(Const::String(text))),
+            TokenData::NoSubstitutionTemplate(text) => {
+                mk!(self, ExprDef::TemplateExpr(vec![text], Vec::new()))
+            }
+            TokenData::TemplateHead(text) => self.parse_template(text)?,
             TokenData::BooleanLiteral(val) => mk!(self, ExprDef::Const(Const::Bool(val))),
             TokenData::Identifier(ref s) if s == "undefined" => {
                 mk!(self, ExprDef::Const(Const::Undefined))
             }
-            TokenData::Identifier(s) => mk!(self, ExprDef::Local(s)),
+            TokenData::Identifier(s) => mk!(self, ExprDef::Local(self.interner.intern(&s))),
+            TokenData::Keyword(Keyword::Return) if self.at_statement_end(self.pos) => {
+                // `return` has no value when the statement ends straight after it
+                if self.get_token(self.pos).ok().map(|tk| tk.data)
+                    == Some(TokenData::Punctuator(Punctuator::Semicolon))
+                {
+                    self.pos += 1;
+                }
+                mk!(self, ExprDef::Return(None))
+            }
             TokenData::Keyword(keyword) => self.parse_struct(keyword)?,
             TokenData::RegularExpressionLiteral(body, flags) => Expr::new(ExprDef::Construct(
-                Box::new(Expr::new(ExprDef::Local("RegExp".to_string()))),
+                Box::new(Expr::new(ExprDef::Local(self.interner.intern("RegExp")))),
                 vec![
                     Expr::new(ExprDef::Const(Const::String(body))),
                     Expr::new(ExprDef::Const(Const::String(flags))),
@@ -376,7 +580,7 @@ impl Parser {
                         let expr = self.parse()?;
                         mk!(
                             self,
-                            ExprDef::ArrowFunctionDecl(Vec::new(), Box::new(expr)),
+                            ExprDef::ArrowFunctionDecl(Vec::new(), Gc::new(expr)),
                             token
                         )
                     }
@@ -385,12 +589,16 @@ impl Parser {
                         let next_tok = self.get_token(self.pos)?;
                         self.pos += 1;
                         match next_tok.data {
-                            TokenData::Punctuator(Punctuator::CloseParen) => next,
+                            TokenData::Punctuator(Punctuator::CloseParen) => {
+                                let mut next = next;
+                                next.parenthesized = true;
+                                next
+                            }
                             TokenData::Punctuator(Punctuator::Comma) => {
                                 // at this point it's probably gonna be an arrow function
                                 let mut args = vec![
                                     match next.def {
-                                        ExprDef::Local(ref name) => (*name).clone(),
+                                        ExprDef::Local(ref name) => name.to_string(),
                                         _ => "".to_string(),
                                     },
                                     match self.get_token(self.pos)?.data {
@@ -440,11 +648,7 @@ impl Parser {
                                     "arrow function",
                                 )?;
                                 let expr = self.parse()?;
-                                mk!(
-                                    self,
-                                    ExprDef::ArrowFunctionDecl(args, Box::new(expr)),
-                                    token
-                                )
+                                mk!(self, ExprDef::ArrowFunctionDecl(args, Gc::new(expr)), token)
                             }
                             _ => {
                                 return Err(ParseError::Expected(
@@ -500,41 +704,37 @@ impl Parser {
                     == TokenData::Punctuator(Punctuator::CloseBlock) =>
             {
                 self.pos += 1;
-                mk!(self, ExprDef::ObjectDecl(Box::new(BTreeMap::new())), token)
+                mk!(self, ExprDef::ObjectDecl(Vec::new()), token)
             }
-            TokenData::Punctuator(Punctuator::OpenBlock)
-                if self.get_token(self.pos + 1)?.data
-                    == TokenData::Punctuator(Punctuator::Colon) =>
-            {
-                let mut map = Box::new(BTreeMap::new());
-                while self.get_token(self.pos - 1)?.data == TokenData::Punctuator(Punctuator::Comma)
-                    || map.len() == 0
-                {
+            TokenData::Punctuator(Punctuator::OpenBlock) if self.is_object_literal() => {
+                let mut properties = Vec::new();
+                loop {
+                    properties.push(self.parse_property_definition()?);
                     let tk = self.get_token(self.pos)?;
-                    let name = match tk.data {
-                        TokenData::Identifier(ref id) => id.clone(),
-                        TokenData::StringLiteral(ref str) => str.clone(),
+                    self.pos += 1;
+                    match tk.data {
+                        TokenData::Punctuator(Punctuator::Comma)
+                            if self.get_token(self.pos)?.data
+                                == TokenData::Punctuator(Punctuator::CloseBlock) =>
+                        {
+                            self.pos += 1;
+                            break;
+                        }
+                        TokenData::Punctuator(Punctuator::Comma) => (),
+                        TokenData::Punctuator(Punctuator::CloseBlock) => break,
                         _ => {
                             return Err(ParseError::Expected(
                                 vec![
-                                    TokenData::Identifier("identifier".to_string()),
-                                    TokenData::StringLiteral("string".to_string()),
+                                    TokenData::Punctuator(Punctuator::Comma),
+                                    TokenData::Punctuator(Punctuator::CloseBlock),
                                 ],
                                 tk,
                                 "object declaration",
                             ))
                         }
-                    };
-                    self.pos += 1;
-                    self.expect(
-                        TokenData::Punctuator(Punctuator::Colon),
-                        "object declaration",
-                    )?;
-                    let value = self.parse()?;
-                    map.insert(name, value);
-                    self.pos += 1;
+                    }
                 }
-                mk!(self, ExprDef::ObjectDecl(map), token)
+                mk!(self, ExprDef::ObjectDecl(properties), token)
             }
             TokenData::Punctuator(Punctuator::OpenBlock) => {
                 let mut exprs = Vec::new();
@@ -544,7 +744,7 @@ impl Parser {
                     {
                         break;
                     } else {
-                        exprs.push(self.parse()?);
+                        exprs.push(self.parse_statement()?);
                     }
                 }
                 self.pos += 1;
@@ -552,45 +752,67 @@ impl Parser {
             }
             TokenData::Punctuator(Punctuator::Sub) => mk!(
                 self,
-                ExprDef::UnaryOp(UnaryOp::Minus, Box::new(self.parse()?))
+                ExprDef::UnaryOp(UnaryOp::Minus, Box::new(self.parse_unary_operand()?))
             ),
             TokenData::Punctuator(Punctuator::Add) => mk!(
                 self,
-                ExprDef::UnaryOp(UnaryOp::Plus, Box::new(self.parse()?))
+                ExprDef::UnaryOp(UnaryOp::Plus, Box::new(self.parse_unary_operand()?))
             ),
             TokenData::Punctuator(Punctuator::Not) => mk!(
                 self,
-                ExprDef::UnaryOp(UnaryOp::Not, Box::new(self.parse()?))
+                ExprDef::UnaryOp(UnaryOp::Not, Box::new(self.parse_unary_operand()?))
+            ),
+            TokenData::Punctuator(Punctuator::Neg) => mk!(
+                self,
+                ExprDef::UnaryOp(UnaryOp::BitNot, Box::new(self.parse_unary_operand()?))
             ),
             TokenData::Punctuator(Punctuator::Inc) => mk!(
                 self,
-                ExprDef::UnaryOp(UnaryOp::IncrementPre, Box::new(self.parse()?))
+                ExprDef::UnaryOp(UnaryOp::IncrementPre, Box::new(self.parse_unary_operand()?))
             ),
             TokenData::Punctuator(Punctuator::Dec) => mk!(
                 self,
-                ExprDef::UnaryOp(UnaryOp::DecrementPre, Box::new(self.parse()?))
+                ExprDef::UnaryOp(UnaryOp::DecrementPre, Box::new(self.parse_unary_operand()?))
             ),
             _ => return Err(ParseError::Expected(Vec::new(), token.clone(), "script")),
         };
-        if self.pos >= self.tokens.len() {
-            Ok(expr)
-        } else {
-            self.parse_next(expr)
-        }
+        Ok(expr)
     }
 
     fn parse_next(&mut self, expr: Expr) -> ParseResult {
+        self.parse_next_ops(expr, false)
+    }
+
+    /// Parse the operators following `expr`, or only member accesses and calls if
+    /// `member_only` is set
+    fn parse_next_ops(&mut self, expr: Expr, member_only: bool) -> ParseResult {
+        // A statement such as a declaration which took in the semicolon ending it is complete
+        if self.pos > 0
+            && self.tokens[self.pos - 1].data == TokenData::Punctuator(Punctuator::Semicolon)
+        {
+            return Ok(expr);
+        }
         let next = self.get_token(self.pos)?;
+        if member_only {
+            match next.data {
+                TokenData::Punctuator(Punctuator::Dot)
+                | TokenData::Punctuator(Punctuator::OpenBracket)
+                | TokenData::Punctuator(Punctuator::OpenParen) => (),
+                _ => return Ok(expr),
+            }
+        }
         let mut carry_on = true;
         let mut result = expr.clone();
         match next.data {
             TokenData::Punctuator(Punctuator::Dot) => {
                 self.pos += 1;
                 let tk = self.get_token(self.pos)?;
-                match tk.data {
-                    TokenData::Identifier(ref s) => {
-                        result = mk!(self, ExprDef::GetConstField(Box::new(expr), s.to_string()))
-                    }
+                // Reserved words such as `delete` and `catch` are property names here
+                let field = match tk.data {
+                    TokenData::Identifier(ref s) => s.to_string(),
+                    TokenData::Keyword(ref keyword) => keyword.to_string(),
+                    TokenData::BooleanLiteral(value) => value.to_string(),
+                    TokenData::NullLiteral => "null".to_string(),
                     _ => {
                         return Err(ParseError::Expected(
                             vec![TokenData::Identifier("identifier".to_string())],
@@ -598,7 +820,8 @@ impl Parser {
                             "field access",
                         ))
                     }
-                }
+                };
+                result = mk!(self, ExprDef::GetConstField(Box::new(expr), field));
                 self.pos += 1;
             }
             TokenData::Punctuator(Punctuator::OpenParen) => {
@@ -654,8 +877,20 @@ impl Parser {
                 )?;
                 result = mk!(self, ExprDef::GetField(Box::new(expr), Box::new(index)));
             }
-            TokenData::Punctuator(Punctuator::Semicolon) | TokenData::Comment(_) => {
+            // A semicolon ends the statement, so nothing after it applies to `expr`
+            TokenData::Punctuator(Punctuator::Semicolon) => {
                 self.pos += 1;
+                carry_on = false
+            }
+            TokenData::Comment(_) => {
+                self.pos += 1;
+            }
+            // Postfix operators must be on the same line as their operand, otherwise they
+            // begin the next statement
+            TokenData::Punctuator(Punctuator::Inc) | TokenData::Punctuator(Punctuator::Dec)
+                if self.newline_before(self.pos) =>
+            {
+                carry_on = false
             }
             TokenData::Punctuator(Punctuator::Assign) => {
                 self.pos += 1;
@@ -686,6 +921,9 @@ impl Parser {
             TokenData::Punctuator(Punctuator::AssignRightSh) => {
                 result = self.binop(BinOp::Assign(AssignOp::Shr), expr)?
             }
+            TokenData::Punctuator(Punctuator::AssignURightSh) => {
+                result = self.binop(BinOp::Assign(AssignOp::UShr), expr)?
+            }
             TokenData::Punctuator(Punctuator::AssignLeftSh) => {
                 result = self.binop(BinOp::Assign(AssignOp::Shl), expr)?
             }
@@ -696,11 +934,11 @@ impl Parser {
                 self.pos += 1;
                 let mut args = Vec::with_capacity(1);
                 match result.def {
-                    ExprDef::Local(ref name) => args.push((*name).clone()),
-                    _ => return Err(ParseError::ExpectedExpr("identifier", result)),
+                    ExprDef::Local(ref name) => args.push(name.to_string()),
+                    _ => return Err(ParseError::ExpectedExpr("identifier", Box::new(result))),
                 }
                 let next = self.parse()?;
-                result = mk!(self, ExprDef::ArrowFunctionDecl(args, Box::new(next)));
+                result = mk!(self, ExprDef::ArrowFunctionDecl(args, Gc::new(next)));
             }
             TokenData::Punctuator(Punctuator::Add) => {
                 result = self.binop(BinOp::Num(NumOp::Add), expr)?
@@ -738,6 +976,9 @@ impl Parser {
             TokenData::Punctuator(Punctuator::RightSh) => {
                 result = self.binop(BinOp::Bit(BitOp::Shr), expr)?
             }
+            TokenData::Punctuator(Punctuator::URightSh) => {
+                result = self.binop(BinOp::Bit(BitOp::UShr), expr)?
+            }
             TokenData::Punctuator(Punctuator::Eq) => {
                 result = self.binop(BinOp::Comp(CompOp::Equal), expr)?
             }
@@ -753,6 +994,9 @@ impl Parser {
             TokenData::Punctuator(Punctuator::LessThan) => {
                 result = self.binop(BinOp::Comp(CompOp::LessThan), expr)?
             }
+            TokenData::Keyword(Keyword::InstanceOf) => {
+                result = self.binop(BinOp::Comp(CompOp::InstanceOf), expr)?
+            }
             TokenData::Punctuator(Punctuator::LessThanOrEq) => {
                 result = self.binop(BinOp::Comp(CompOp::LessThanOrEqual), expr)?
             }
@@ -763,54 +1007,313 @@ impl Parser {
                 result = self.binop(BinOp::Comp(CompOp::GreaterThanOrEqual), expr)?
             }
             TokenData::Punctuator(Punctuator::Inc) => {
+                self.pos += 1;
                 result = mk!(
                     self,
-                    ExprDef::UnaryOp(UnaryOp::IncrementPost, Box::new(self.parse()?))
+                    ExprDef::UnaryOp(UnaryOp::IncrementPost, Box::new(expr))
                 )
             }
             TokenData::Punctuator(Punctuator::Dec) => {
+                self.pos += 1;
                 result = mk!(
                     self,
-                    ExprDef::UnaryOp(UnaryOp::DecrementPost, Box::new(self.parse()?))
+                    ExprDef::UnaryOp(UnaryOp::DecrementPost, Box::new(expr))
                 )
             }
             _ => carry_on = false,
         };
         if carry_on && self.pos < self.tokens.len() {
-            self.parse_next(result)
+            self.parse_next_ops(result, member_only)
         } else {
             Ok(result)
         }
     }
 
+    /// Turn the first call in a chain of member accesses and calls following `new` into the
+    /// construction of an object, so that `new Foo().bar()` calls `bar` on the new object
+    fn construct(&self, mut expr: Expr) -> ParseResult {
+        // The parts are taken from the node rather than moved out of it, as it implements `Drop`
+        let def = match expr.def {
+            ExprDef::Call(ref mut func, ref mut args) => {
+                let func = Self::take_expr(func);
+                let args = mem::take(args);
+                if Self::contains_call(&func) {
+                    ExprDef::Call(Box::new(self.construct(func)?), args)
+                } else {
+                    ExprDef::Construct(Box::new(func), args)
+                }
+            }
+            ExprDef::GetConstField(ref mut obj, ref mut field) => ExprDef::GetConstField(
+                Box::new(self.construct(Self::take_expr(obj))?),
+                mem::take(field),
+            ),
+            ExprDef::GetField(ref mut obj, ref mut field) => ExprDef::GetField(
+                Box::new(self.construct(Self::take_expr(obj))?),
+                Box::new(Self::take_expr(field)),
+            ),
+            _ => return Err(ParseError::ExpectedExpr("constructor", Box::new(expr))),
+        };
+        Ok(Expr::new_at(def, expr.pos))
+    }
+
+    /// Move the expression out of `expr`, leaving `undefined` in its place
+    fn take_expr(expr: &mut Expr) -> Expr {
+        mem::replace(expr, Expr::new(ExprDef::Const(Const::Undefined)))
+    }
+
+    /// Returns true if `expr` is a call, or a member access of one
+    fn contains_call(expr: &Expr) -> bool {
+        match expr.def {
+            ExprDef::Call(_, _) => true,
+            ExprDef::GetConstField(ref obj, _) | ExprDef::GetField(ref obj, _) => {
+                Self::contains_call(obj)
+            }
+            _ => false,
+        }
+    }
+
+    /// Parse the substitutions and text of a template literal whose head is `head`
+    fn parse_template(&mut self, head: String) -> ParseResult {
+        let mut quasis = vec![head];
+        let mut exprs = Vec::new();
+        loop {
+            exprs.push(self.parse()?);
+            let token = self.get_token(self.pos)?;
+            self.pos += 1;
+            match token.data {
+                TokenData::TemplateMiddle(text) => quasis.push(text),
+                TokenData::TemplateTail(text) => {
+                    quasis.push(text);
+                    break;
+                }
+                _ => {
+                    return Err(ParseError::Expected(
+                        vec![TokenData::TemplateTail("}`".to_string())],
+                        token,
+                        "template literal",
+                    ))
+                }
+            }
+        }
+        Ok(mk!(self, ExprDef::TemplateExpr(quasis, exprs)))
+    }
+
     fn binop(&mut self, op: BinOp, orig: Expr) -> Result<Expr, ParseError> {
-        let (precedence, assoc) = op.get_precedence_and_assoc();
         self.pos += 1;
         let next = self.parse()?;
-        Ok(match next.def {
-            ExprDef::BinOp(ref op2, ref a, ref b) => {
+        Ok(self.regroup(op, orig, next))
+    }
+
+    /// Join `orig` to the expression parsed after `op`, which has taken in every operator to its
+    /// right. The operators there which bind less tightly than `op` are moved above it, so `op`
+    /// only takes the leftmost operand they share.
+    fn regroup(&self, op: BinOp, orig: Expr, next: Expr) -> Expr {
+        let (precedence, assoc) = op.get_precedence_and_assoc();
+        match next.def {
+            ExprDef::BinOp(ref op2, ref a, ref b) if !next.parenthesized => {
                 let other_precedence = op2.get_precedence();
-                if precedence < other_precedence || (precedence == other_precedence && !assoc) {
-                    mk!(
-                        self,
-                        ExprDef::BinOp(
-                            op2.clone(),
-                            b.clone(),
-                            Box::new(mk!(
-                                self,
-                                ExprDef::BinOp(op.clone(), Box::new(orig), a.clone())
-                            ))
-                        )
-                    )
-                } else {
-                    mk!(
-                        self,
-                        ExprDef::BinOp(op, Box::new(orig), Box::new(next.clone()))
-                    )
+                if precedence < other_precedence || (precedence == other_precedence && assoc) {
+                    let left = self.regroup(op, orig, (**a).clone());
+                    return Expr::new_at(
+                        ExprDef::BinOp(op2.clone(), Box::new(left), b.clone()),
+                        next.pos,
+                    );
+                }
+            }
+            // The conditional operator binds less tightly than any operator but assignment, so
+            // `op` only joins the condition
+            ExprDef::If(ref cond, ref if_e, Some(ref else_e))
+                if !next.parenthesized && !matches!(op, BinOp::Assign(_)) =>
+            {
+                let cond = self.regroup(op, orig, (**cond).clone());
+                return Expr::new_at(
+                    ExprDef::If(Box::new(cond), if_e.clone(), Some(else_e.clone())),
+                    next.pos,
+                );
+            }
+            _ => (),
+        }
+        mk!(self, ExprDef::BinOp(op, Box::new(orig), Box::new(next)))
+    }
+
+    /// Parse the parameter list and body of a function, starting at the open paren
+    fn parse_function(&mut self, name: Option<String>) -> ParseResult {
+        // Now we have the function identifier we should have an open paren for arguments ( )
+        self.expect_punc(Punctuator::OpenParen, "function")?;
+        let mut args: Vec<String> = Vec::new();
+        let mut tk = self.get_token(self.pos)?;
+        while tk.data != TokenData::Punctuator(Punctuator::CloseParen) {
+            match tk.data {
+                TokenData::Identifier(ref id) => args.push(id.clone()),
+                _ => {
+                    return Err(ParseError::Expected(
+                        vec![TokenData::Identifier("identifier".to_string())],
+                        tk.clone(),
+                        "function arguments",
+                    ))
+                }
+            }
+            self.pos += 1;
+            if self.get_token(self.pos)?.data == TokenData::Punctuator(Punctuator::Comma) {
+                self.pos += 1;
+            }
+            tk = self.get_token(self.pos)?;
+        }
+        self.pos += 1;
+        // The body is always a block, even when it looks like an object literal
+        let block = self.parse_block("function body")?;
+        Ok(mk!(self, ExprDef::FunctionDecl(name, args, Gc::new(block))))
+    }
+
+    /// Parse a block between braces, which is never an object literal
+    fn parse_block(&mut self, routine: &'static str) -> ParseResult {
+        self.expect_punc(Punctuator::OpenBlock, routine)?;
+        let mut exprs = Vec::new();
+        while self.get_token(self.pos)?.data != TokenData::Punctuator(Punctuator::CloseBlock) {
+            exprs.push(self.parse_statement()?);
+        }
+        self.pos += 1;
+        Ok(mk!(self, ExprDef::Block(exprs)))
+    }
+
+    /// Returns true if the next token is `tk`
+    fn next_is(&self, tk: TokenData) -> bool {
+        self.tokens.get(self.pos).map(|next| &next.data) == Some(&tk)
+    }
+
+    /// Returns true if the tokens after an open brace begin an object literal rather than a block
+    fn is_object_literal(&self) -> bool {
+        let data_at = |offset| self.get_token(self.pos + offset).map(|tk| tk.data);
+        if let Ok(TokenData::Punctuator(Punctuator::Spread)) = data_at(0) {
+            return true;
+        }
+        // Skip the first property name, noting whether it follows `get` or `set`
+        let (accessor, after) = match data_at(0) {
+            Ok(TokenData::Identifier(ref kind)) if kind == "get" || kind == "set" => {
+                match self.skip_property_name(1) {
+                    Some(offset) => (true, offset),
+                    None => (false, 1),
+                }
+            }
+            _ => match self.skip_property_name(0) {
+                Some(offset) => (false, offset),
+                None => return false,
+            },
+        };
+        match data_at(after) {
+            Ok(TokenData::Punctuator(Punctuator::Colon)) => !accessor,
+            // Accessors and methods are followed by their parameters and body, which
+            // tells a method apart from a call at the start of a block
+            Ok(TokenData::Punctuator(Punctuator::OpenParen)) => {
+                match self.skip_balanced(after, Punctuator::OpenParen, Punctuator::CloseParen) {
+                    Some(offset) => {
+                        accessor
+                            || matches!(
+                                data_at(offset),
+                                Ok(TokenData::Punctuator(Punctuator::OpenBlock))
+                            )
+                    }
+                    None => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the offset after the property name starting `offset` tokens past the current position
+    fn skip_property_name(&self, offset: usize) -> Option<usize> {
+        match self.get_token(self.pos + offset).ok()?.data {
+            TokenData::Identifier(_)
+            | TokenData::StringLiteral(_)
+            | TokenData::NumericLiteral(_) => Some(offset + 1),
+            TokenData::Punctuator(Punctuator::OpenBracket) => {
+                self.skip_balanced(offset, Punctuator::OpenBracket, Punctuator::CloseBracket)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the offset after the `close` punctuator matching the `open` one at `offset`
+    fn skip_balanced(&self, offset: usize, open: Punctuator, close: Punctuator) -> Option<usize> {
+        let mut depth = 0;
+        let mut offset = offset;
+        loop {
+            match self.get_token(self.pos + offset).ok()?.data {
+                TokenData::Punctuator(ref p) if *p == open => depth += 1,
+                TokenData::Punctuator(ref p) if *p == close => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(offset + 1);
+                    }
+                }
+                _ => (),
+            }
+            offset += 1;
+        }
+    }
+
+    /// Parse the name of a property in an object literal
+    fn parse_property_name(&mut self) -> Result<PropertyName, ParseError> {
+        let tk = self.get_token(self.pos)?;
+        self.pos += 1;
+        match tk.data {
+            TokenData::Identifier(ref id) => Ok(PropertyName::Literal(id.clone())),
+            TokenData::StringLiteral(ref str) => Ok(PropertyName::Literal(str.clone())),
+            TokenData::NumericLiteral(num) => Ok(PropertyName::Literal(num.to_string())),
+            TokenData::Punctuator(Punctuator::OpenBracket) => {
+                let expr = self.parse()?;
+                self.expect_punc(Punctuator::CloseBracket, "computed property name")?;
+                Ok(PropertyName::Computed(expr))
+            }
+            _ => Err(ParseError::Expected(
+                vec![
+                    TokenData::Identifier("identifier".to_string()),
+                    TokenData::StringLiteral("string".to_string()),
+                ],
+                tk,
+                "object declaration",
+            )),
+        }
+    }
+
+    /// Parse a single member of an object literal
+    fn parse_property_definition(&mut self) -> Result<PropertyDefinition, ParseError> {
+        if self.get_token(self.pos)?.data == TokenData::Punctuator(Punctuator::Spread) {
+            self.pos += 1;
+            return Ok(PropertyDefinition::SpreadObject(self.parse()?));
+        }
+        let kind = match self.get_token(self.pos)?.data {
+            TokenData::Identifier(ref id) if id == "get" => Some(MethodDefinitionKind::Get),
+            TokenData::Identifier(ref id) if id == "set" => Some(MethodDefinitionKind::Set),
+            _ => None,
+        };
+        if let Some(kind) = kind {
+            // `get` and `set` are only accessors when followed by a property name
+            match self.get_token(self.pos + 1)?.data {
+                TokenData::Identifier(_)
+                | TokenData::StringLiteral(_)
+                | TokenData::NumericLiteral(_)
+                | TokenData::Punctuator(Punctuator::OpenBracket) => {
+                    self.pos += 1;
+                    let name = self.parse_property_name()?;
+                    let method = self.parse_function(None)?;
+                    return Ok(PropertyDefinition::MethodDefinition(kind, name, method));
                 }
+                _ => (),
             }
-            _ => mk!(self, ExprDef::BinOp(op, Box::new(orig), Box::new(next))),
-        })
+        }
+        let name = self.parse_property_name()?;
+        if self.get_token(self.pos)?.data == TokenData::Punctuator(Punctuator::OpenParen) {
+            let method = self.parse_function(None)?;
+            return Ok(PropertyDefinition::MethodDefinition(
+                MethodDefinitionKind::Ordinary,
+                name,
+                method,
+            ));
+        }
+        self.expect_punc(Punctuator::Colon, "object declaration")?;
+        let value = self.parse()?;
+        Ok(PropertyDefinition::Property(name, value))
     }
 
     /// Returns an error if the next symbol is not `tk`
@@ -835,7 +1338,10 @@ impl Parser {
 mod tests {
     use super::*;
     use crate::syntax::{
-        ast::expr::{Expr, ExprDef},
+        ast::{
+            expr::{Expr, ExprDef},
+            sym::Sym,
+        },
         lexer::Lexer,
     };
 
@@ -875,6 +1381,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn check_template() {
+        check_parser(
+            "`a${b}c`",
+            &[Expr::new(ExprDef::TemplateExpr(
+                vec![String::from("a"), String::from("c")],
+                vec![Expr::new(ExprDef::Local(Sym::from("b")))],
+            ))],
+        );
+        check_invalid("`a${b c}`");
+    }
+
     #[test]
     fn check_array() {
         use crate::syntax::ast::constant::Const;
@@ -1088,15 +1606,15 @@ mod tests {
             "a + b",
             &[create_bin_op(
                 BinOp::Num(NumOp::Add),
-                Expr::new(ExprDef::Local(String::from("a"))),
-                Expr::new(ExprDef::Local(String::from("b"))),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("b"))),
             )],
         );
         check_parser(
             "a+1",
             &[create_bin_op(
                 BinOp::Num(NumOp::Add),
-                Expr::new(ExprDef::Local(String::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
                 Expr::new(ExprDef::Const(Const::Num(1.0))),
             )],
         );
@@ -1104,15 +1622,15 @@ mod tests {
             "a - b",
             &[create_bin_op(
                 BinOp::Num(NumOp::Sub),
-                Expr::new(ExprDef::Local(String::from("a"))),
-                Expr::new(ExprDef::Local(String::from("b"))),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("b"))),
             )],
         );
         check_parser(
             "a-1",
             &[create_bin_op(
                 BinOp::Num(NumOp::Sub),
-                Expr::new(ExprDef::Local(String::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
                 Expr::new(ExprDef::Const(Const::Num(1.0))),
             )],
         );
@@ -1120,15 +1638,15 @@ mod tests {
             "a / b",
             &[create_bin_op(
                 BinOp::Num(NumOp::Div),
-                Expr::new(ExprDef::Local(String::from("a"))),
-                Expr::new(ExprDef::Local(String::from("b"))),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("b"))),
             )],
         );
         check_parser(
             "a/2",
             &[create_bin_op(
                 BinOp::Num(NumOp::Div),
-                Expr::new(ExprDef::Local(String::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
                 Expr::new(ExprDef::Const(Const::Num(2.0))),
             )],
         );
@@ -1136,15 +1654,15 @@ mod tests {
             "a * b",
             &[create_bin_op(
                 BinOp::Num(NumOp::Mul),
-                Expr::new(ExprDef::Local(String::from("a"))),
-                Expr::new(ExprDef::Local(String::from("b"))),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("b"))),
             )],
         );
         check_parser(
             "a*2",
             &[create_bin_op(
                 BinOp::Num(NumOp::Mul),
-                Expr::new(ExprDef::Local(String::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
                 Expr::new(ExprDef::Const(Const::Num(2.0))),
             )],
         );
@@ -1152,15 +1670,15 @@ mod tests {
             "a % b",
             &[create_bin_op(
                 BinOp::Num(NumOp::Mod),
-                Expr::new(ExprDef::Local(String::from("a"))),
-                Expr::new(ExprDef::Local(String::from("b"))),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("b"))),
             )],
         );
         check_parser(
             "a%2",
             &[create_bin_op(
                 BinOp::Num(NumOp::Mod),
-                Expr::new(ExprDef::Local(String::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
                 Expr::new(ExprDef::Const(Const::Num(2.0))),
             )],
         );
@@ -1170,21 +1688,20 @@ mod tests {
             "a + d*(b-3)+1",
             &[create_bin_op(
                 BinOp::Num(NumOp::Add),
-                Expr::new(ExprDef::Local(String::from("a"))),
                 create_bin_op(
                     BinOp::Num(NumOp::Add),
-                    // FIXME: shouldn't the last addition be on the right?
-                    Expr::new(ExprDef::Const(Const::Num(1.0))),
+                    Expr::new(ExprDef::Local(Sym::from("a"))),
                     create_bin_op(
                         BinOp::Num(NumOp::Mul),
-                        Expr::new(ExprDef::Local(String::from("d"))),
+                        Expr::new(ExprDef::Local(Sym::from("d"))),
                         create_bin_op(
                             BinOp::Num(NumOp::Sub),
-                            Expr::new(ExprDef::Local(String::from("b"))),
+                            Expr::new(ExprDef::Local(Sym::from("b"))),
                             Expr::new(ExprDef::Const(Const::Num(3.0))),
                         ),
                     ),
                 ),
+                Expr::new(ExprDef::Const(Const::Num(1.0))),
             )],
         );
 
@@ -1193,16 +1710,16 @@ mod tests {
             "a & b",
             &[create_bin_op(
                 BinOp::Bit(BitOp::And),
-                Expr::new(ExprDef::Local(String::from("a"))),
-                Expr::new(ExprDef::Local(String::from("b"))),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("b"))),
             )],
         );
         check_parser(
             "a&b",
             &[create_bin_op(
                 BinOp::Bit(BitOp::And),
-                Expr::new(ExprDef::Local(String::from("a"))),
-                Expr::new(ExprDef::Local(String::from("b"))),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("b"))),
             )],
         );
 
@@ -1210,16 +1727,16 @@ mod tests {
             "a | b",
             &[create_bin_op(
                 BinOp::Bit(BitOp::Or),
-                Expr::new(ExprDef::Local(String::from("a"))),
-                Expr::new(ExprDef::Local(String::from("b"))),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("b"))),
             )],
         );
         check_parser(
             "a|b",
             &[create_bin_op(
                 BinOp::Bit(BitOp::Or),
-                Expr::new(ExprDef::Local(String::from("a"))),
-                Expr::new(ExprDef::Local(String::from("b"))),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("b"))),
             )],
         );
 
@@ -1227,16 +1744,16 @@ mod tests {
             "a ^ b",
             &[create_bin_op(
                 BinOp::Bit(BitOp::Xor),
-                Expr::new(ExprDef::Local(String::from("a"))),
-                Expr::new(ExprDef::Local(String::from("b"))),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("b"))),
             )],
         );
         check_parser(
             "a^b",
             &[create_bin_op(
                 BinOp::Bit(BitOp::Xor),
-                Expr::new(ExprDef::Local(String::from("a"))),
-                Expr::new(ExprDef::Local(String::from("b"))),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("b"))),
             )],
         );
 
@@ -1244,16 +1761,16 @@ mod tests {
             "a << b",
             &[create_bin_op(
                 BinOp::Bit(BitOp::Shl),
-                Expr::new(ExprDef::Local(String::from("a"))),
-                Expr::new(ExprDef::Local(String::from("b"))),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("b"))),
             )],
         );
         check_parser(
             "a<<b",
             &[create_bin_op(
                 BinOp::Bit(BitOp::Shl),
-                Expr::new(ExprDef::Local(String::from("a"))),
-                Expr::new(ExprDef::Local(String::from("b"))),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("b"))),
             )],
         );
 
@@ -1261,16 +1778,33 @@ mod tests {
             "a >> b",
             &[create_bin_op(
                 BinOp::Bit(BitOp::Shr),
-                Expr::new(ExprDef::Local(String::from("a"))),
-                Expr::new(ExprDef::Local(String::from("b"))),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("b"))),
             )],
         );
         check_parser(
             "a>>b",
             &[create_bin_op(
                 BinOp::Bit(BitOp::Shr),
-                Expr::new(ExprDef::Local(String::from("a"))),
-                Expr::new(ExprDef::Local(String::from("b"))),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("b"))),
+            )],
+        );
+
+        check_parser(
+            "a >>> b",
+            &[create_bin_op(
+                BinOp::Bit(BitOp::UShr),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("b"))),
+            )],
+        );
+        check_parser(
+            "a>>>b",
+            &[create_bin_op(
+                BinOp::Bit(BitOp::UShr),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("b"))),
             )],
         );
 
@@ -1279,87 +1813,95 @@ mod tests {
             "a += b",
             &[create_bin_op(
                 BinOp::Assign(AssignOp::Add),
-                Expr::new(ExprDef::Local(String::from("a"))),
-                Expr::new(ExprDef::Local(String::from("b"))),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("b"))),
             )],
         );
         check_parser(
             "a -= b",
             &[create_bin_op(
                 BinOp::Assign(AssignOp::Sub),
-                Expr::new(ExprDef::Local(String::from("a"))),
-                Expr::new(ExprDef::Local(String::from("b"))),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("b"))),
             )],
         );
         check_parser(
             "a *= b",
             &[create_bin_op(
                 BinOp::Assign(AssignOp::Mul),
-                Expr::new(ExprDef::Local(String::from("a"))),
-                Expr::new(ExprDef::Local(String::from("b"))),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("b"))),
             )],
         );
         check_parser(
             "a /= b",
             &[create_bin_op(
                 BinOp::Assign(AssignOp::Div),
-                Expr::new(ExprDef::Local(String::from("a"))),
-                Expr::new(ExprDef::Local(String::from("b"))),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("b"))),
             )],
         );
         check_parser(
             "a %= b",
             &[create_bin_op(
                 BinOp::Assign(AssignOp::Mod),
-                Expr::new(ExprDef::Local(String::from("a"))),
-                Expr::new(ExprDef::Local(String::from("b"))),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("b"))),
             )],
         );
         check_parser(
             "a &= b",
             &[create_bin_op(
                 BinOp::Assign(AssignOp::And),
-                Expr::new(ExprDef::Local(String::from("a"))),
-                Expr::new(ExprDef::Local(String::from("b"))),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("b"))),
             )],
         );
         check_parser(
             "a |= b",
             &[create_bin_op(
                 BinOp::Assign(AssignOp::Or),
-                Expr::new(ExprDef::Local(String::from("a"))),
-                Expr::new(ExprDef::Local(String::from("b"))),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("b"))),
             )],
         );
         check_parser(
             "a ^= b",
             &[create_bin_op(
                 BinOp::Assign(AssignOp::Xor),
-                Expr::new(ExprDef::Local(String::from("a"))),
-                Expr::new(ExprDef::Local(String::from("b"))),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("b"))),
             )],
         );
         check_parser(
             "a <<= b",
             &[create_bin_op(
                 BinOp::Assign(AssignOp::Shl),
-                Expr::new(ExprDef::Local(String::from("a"))),
-                Expr::new(ExprDef::Local(String::from("b"))),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("b"))),
             )],
         );
         check_parser(
             "a >>= b",
             &[create_bin_op(
                 BinOp::Assign(AssignOp::Shr),
-                Expr::new(ExprDef::Local(String::from("a"))),
-                Expr::new(ExprDef::Local(String::from("b"))),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("b"))),
+            )],
+        );
+        check_parser(
+            "a >>>= b",
+            &[create_bin_op(
+                BinOp::Assign(AssignOp::UShr),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("b"))),
             )],
         );
         check_parser(
             "a %= 10 / 2",
             &[create_bin_op(
                 BinOp::Assign(AssignOp::Mod),
-                Expr::new(ExprDef::Local(String::from("a"))),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
                 create_bin_op(
                     BinOp::Num(NumOp::Div),
                     Expr::new(ExprDef::Const(Const::Num(10.0))),
@@ -1367,5 +1909,367 @@ mod tests {
                 ),
             )],
         );
+
+        // The conditional operator takes the whole binary expression before it as its condition,
+        // but an assignment takes the whole conditional expression after it
+        let local = |name: &str| Box::new(Expr::new(ExprDef::Local(Sym::from(name))));
+        check_parser(
+            "a < b ? c : d",
+            &[Expr::new(ExprDef::If(
+                Box::new(create_bin_op(
+                    BinOp::Comp(CompOp::LessThan),
+                    *local("a"),
+                    *local("b"),
+                )),
+                local("c"),
+                Some(local("d")),
+            ))],
+        );
+        check_parser(
+            "a += b ? c : d",
+            &[create_bin_op(
+                BinOp::Assign(AssignOp::Add),
+                *local("a"),
+                Expr::new(ExprDef::If(local("b"), local("c"), Some(local("d")))),
+            )],
+        );
+    }
+
+    #[test]
+    fn check_automatic_semicolon_insertion() {
+        use crate::syntax::ast::constant::Const;
+
+        check_parser(
+            "a = 1\nb = 2",
+            &[
+                Expr::new(ExprDef::Assign(
+                    Box::new(Expr::new(ExprDef::Local(Sym::from("a")))),
+                    Box::new(Expr::new(ExprDef::Const(Const::Num(1.0)))),
+                )),
+                Expr::new(ExprDef::Assign(
+                    Box::new(Expr::new(ExprDef::Local(Sym::from("b")))),
+                    Box::new(Expr::new(ExprDef::Const(Const::Num(2.0)))),
+                )),
+            ],
+        );
+        check_parser(
+            "return\na",
+            &[
+                Expr::new(ExprDef::Return(None)),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
+            ],
+        );
+        check_parser(
+            "{ return }",
+            &[Expr::new(ExprDef::Block(vec![Expr::new(ExprDef::Return(
+                None,
+            ))]))],
+        );
+        check_parser("return;", &[Expr::new(ExprDef::Return(None))]);
+        check_parser(
+            "return a",
+            &[Expr::new(ExprDef::Return(Some(Box::new(Expr::new(
+                ExprDef::Local(Sym::from("a")),
+            )))))],
+        );
+        check_invalid("throw\na");
+
+        // An explicit semicolon ends the statement before an operator, an index or a call
+        let local = |name: &str| Expr::new(ExprDef::Local(Sym::from(name)));
+        check_parser(
+            "a; -1",
+            &[
+                local("a"),
+                Expr::new(ExprDef::UnaryOp(
+                    UnaryOp::Minus,
+                    Box::new(Expr::new(ExprDef::Const(Const::Num(1.0)))),
+                )),
+            ],
+        );
+        check_parser(
+            "a; [1]",
+            &[
+                local("a"),
+                Expr::new(ExprDef::ArrayDecl(vec![Expr::new(ExprDef::Const(
+                    Const::Num(1.0),
+                ))])),
+            ],
+        );
+        check_parser("a; (b)", &[local("a"), local("b")]);
+    }
+
+    #[test]
+    fn check_break_and_continue() {
+        check_parser(
+            "while (a) { break; continue }",
+            &[Expr::new(ExprDef::WhileLoop(
+                Box::new(Expr::new(ExprDef::Local(Sym::from("a")))),
+                Box::new(Expr::new(ExprDef::Block(vec![
+                    Expr::new(ExprDef::Break),
+                    Expr::new(ExprDef::Continue),
+                ]))),
+            ))],
+        );
+        check_parser(
+            "break\na",
+            &[
+                Expr::new(ExprDef::Break),
+                Expr::new(ExprDef::Local(Sym::from("a"))),
+            ],
+        );
+        // Labels are not supported
+        check_invalid("break a");
+
+        check_parser(
+            "switch (a) { case b: break; default: c }",
+            &[Expr::new(ExprDef::Switch(
+                Box::new(Expr::new(ExprDef::Local(Sym::from("a")))),
+                vec![(
+                    Expr::new(ExprDef::Local(Sym::from("b"))),
+                    vec![Expr::new(ExprDef::Break)],
+                )],
+                Some(Box::new(Expr::new(ExprDef::Block(vec![Expr::new(
+                    ExprDef::Local(Sym::from("c")),
+                )])))),
+            ))],
+        );
+        check_invalid("switch (a) { case b: break;");
+    }
+
+    #[test]
+    fn check_try() {
+        let block = |name: &str| {
+            Box::new(Expr::new(ExprDef::Block(vec![Expr::new(ExprDef::Local(
+                Sym::from(name),
+            ))])))
+        };
+
+        check_parser(
+            "try { a } catch (e) { b }",
+            &[Expr::new(ExprDef::Try(
+                block("a"),
+                Some(String::from("e")),
+                Some(block("b")),
+                None,
+            ))],
+        );
+        check_parser(
+            "try { a } catch { b }",
+            &[Expr::new(ExprDef::Try(
+                block("a"),
+                None,
+                Some(block("b")),
+                None,
+            ))],
+        );
+        check_parser(
+            "try { a } finally { c }",
+            &[Expr::new(ExprDef::Try(
+                block("a"),
+                None,
+                None,
+                Some(block("c")),
+            ))],
+        );
+        check_parser(
+            "try { a } catch { b } finally { c }",
+            &[Expr::new(ExprDef::Try(
+                block("a"),
+                None,
+                Some(block("b")),
+                Some(block("c")),
+            ))],
+        );
+        check_invalid("try { a }");
+        check_invalid("try { a } catch () { b }");
+    }
+
+    #[test]
+    fn check_with() {
+        check_parser(
+            "with (a) { b }",
+            &[Expr::new(ExprDef::WithExpr(
+                Box::new(Expr::new(ExprDef::Local(Sym::from("a")))),
+                Box::new(Expr::new(ExprDef::Block(vec![Expr::new(ExprDef::Local(
+                    Sym::from("b"),
+                ))]))),
+            ))],
+        );
+        check_invalid("with a { b }");
+    }
+
+    #[test]
+    fn check_for_in() {
+        let local = |name| Box::new(Expr::new(ExprDef::Local(Sym::from(name))));
+        check_parser(
+            "for (let key in obj) { key }",
+            &[Expr::new(ExprDef::ForInLoop(
+                Some(DeclarationKind::Let),
+                String::from("key"),
+                local("obj"),
+                Box::new(Expr::new(ExprDef::Block(vec![*local("key")]))),
+            ))],
+        );
+        check_parser(
+            "for (key in obj) key",
+            &[Expr::new(ExprDef::ForInLoop(
+                None,
+                String::from("key"),
+                local("obj"),
+                local("key"),
+            ))],
+        );
+        check_invalid("for (let key of obj) { key }");
+    }
+
+    #[test]
+    fn check_prefix_operators() {
+        use crate::syntax::ast::constant::Const;
+
+        // Binary operators after the operand apply to the result of the prefix operator
+        check_parser(
+            "void 1 === a",
+            &[Expr::new(ExprDef::BinOp(
+                BinOp::Comp(CompOp::StrictEqual),
+                Box::new(Expr::new(ExprDef::UnaryOp(
+                    UnaryOp::Void,
+                    Box::new(Expr::new(ExprDef::Const(Const::Num(1.0)))),
+                ))),
+                Box::new(Expr::new(ExprDef::Local(Sym::from("a")))),
+            ))],
+        );
+        check_parser(
+            "!a && b",
+            &[Expr::new(ExprDef::BinOp(
+                BinOp::Log(LogOp::And),
+                Box::new(Expr::new(ExprDef::UnaryOp(
+                    UnaryOp::Not,
+                    Box::new(Expr::new(ExprDef::Local(Sym::from("a")))),
+                ))),
+                Box::new(Expr::new(ExprDef::Local(Sym::from("b")))),
+            ))],
+        );
+        check_parser(
+            "~a | b",
+            &[Expr::new(ExprDef::BinOp(
+                BinOp::Bit(BitOp::Or),
+                Box::new(Expr::new(ExprDef::UnaryOp(
+                    UnaryOp::BitNot,
+                    Box::new(Expr::new(ExprDef::Local(Sym::from("a")))),
+                ))),
+                Box::new(Expr::new(ExprDef::Local(Sym::from("b")))),
+            ))],
+        );
+        // Postfix operators apply to the expression before them
+        check_parser(
+            "a++",
+            &[Expr::new(ExprDef::UnaryOp(
+                UnaryOp::IncrementPost,
+                Box::new(Expr::new(ExprDef::Local(Sym::from("a")))),
+            ))],
+        );
+        // Member accesses and calls are part of the operand
+        check_parser(
+            "-a.b(c)",
+            &[Expr::new(ExprDef::UnaryOp(
+                UnaryOp::Minus,
+                Box::new(Expr::new(ExprDef::Call(
+                    Box::new(Expr::new(ExprDef::GetConstField(
+                        Box::new(Expr::new(ExprDef::Local(Sym::from("a")))),
+                        String::from("b"),
+                    ))),
+                    vec![Expr::new(ExprDef::Local(Sym::from("c")))],
+                ))),
+            ))],
+        );
+        check_parser(
+            "typeof (a + b)",
+            &[Expr::new(ExprDef::TypeOf(Box::new(Expr::new(
+                ExprDef::BinOp(
+                    BinOp::Num(NumOp::Add),
+                    Box::new(Expr::new(ExprDef::Local(Sym::from("a")))),
+                    Box::new(Expr::new(ExprDef::Local(Sym::from("b")))),
+                ),
+            ))))],
+        );
+    }
+
+    #[test]
+    fn check_construct() {
+        let local = |name: &str| Box::new(Expr::new(ExprDef::Local(Sym::from(name))));
+
+        check_parser(
+            "new a(b)",
+            &[Expr::new(ExprDef::Construct(
+                local("a"),
+                vec![Expr::new(ExprDef::Local(Sym::from("b")))],
+            ))],
+        );
+        // Operators after the arguments apply to the new object
+        check_parser(
+            "new a() instanceof b",
+            &[Expr::new(ExprDef::BinOp(
+                BinOp::Comp(CompOp::InstanceOf),
+                Box::new(Expr::new(ExprDef::Construct(local("a"), vec![]))),
+                local("b"),
+            ))],
+        );
+        check_parser(
+            "new a.b().c()",
+            &[Expr::new(ExprDef::Call(
+                Box::new(Expr::new(ExprDef::GetConstField(
+                    Box::new(Expr::new(ExprDef::Construct(
+                        Box::new(Expr::new(ExprDef::GetConstField(
+                            local("a"),
+                            "b".to_string(),
+                        ))),
+                        vec![],
+                    ))),
+                    "c".to_string(),
+                ))),
+                vec![],
+            ))],
+        );
+        check_invalid("new a");
+        check_parser("new.target", &[Expr::new(ExprDef::NewTargetExpr)]);
+        check_invalid("new.foo");
+    }
+
+    #[test]
+    fn check_this() {
+        let this = || Box::new(Expr::new(ExprDef::ThisExpr));
+
+        check_parser("this", &[Expr::new(ExprDef::ThisExpr)]);
+        check_parser(
+            "this.a",
+            &[Expr::new(ExprDef::GetConstField(this(), "a".to_string()))],
+        );
+        check_parser(
+            "this.a = 1",
+            &[Expr::new(ExprDef::Assign(
+                Box::new(Expr::new(ExprDef::GetConstField(this(), "a".to_string()))),
+                Box::new(Expr::new(ExprDef::Const(Const::Num(1.0)))),
+            ))],
+        );
+    }
+
+    #[test]
+    fn check_reserved_word_fields() {
+        let map = || Box::new(Expr::new(ExprDef::Local(Sym::from("map"))));
+
+        check_parser(
+            "map.delete",
+            &[Expr::new(ExprDef::GetConstField(
+                map(),
+                "delete".to_string(),
+            ))],
+        );
+        check_parser(
+            "map.catch; map.null",
+            &[
+                Expr::new(ExprDef::GetConstField(map(), "catch".to_string())),
+                Expr::new(ExprDef::GetConstField(map(), "null".to_string())),
+            ],
+        );
     }
 }